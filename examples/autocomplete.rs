@@ -0,0 +1,30 @@
+use dialoguer::{theme::ColorfulTheme, Autocomplete};
+
+fn main() {
+    let hosts = [
+        "github.com",
+        "gitlab.com",
+        "gitea.io",
+        "docs.rs",
+        "crates.io",
+        "rust-lang.org",
+    ];
+
+    let host = Autocomplete::with_theme(&ColorfulTheme::default())
+        .with_prompt("Host")
+        .suggest_with(|input| {
+            if input.is_empty() {
+                return vec![];
+            }
+
+            hosts
+                .iter()
+                .filter(|host| host.contains(input))
+                .map(|host| host.to_string())
+                .collect()
+        })
+        .interact()
+        .unwrap();
+
+    println!("Connecting to {}", host);
+}
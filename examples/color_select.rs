@@ -0,0 +1,23 @@
+use dialoguer::{theme::ColorfulTheme, ColorSelect, Palette, Rgb};
+
+fn main() {
+    let color = ColorSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Label color")
+        .palette(Palette::Ansi256)
+        .default(Rgb::new(255, 0, 0))
+        .interact()
+        .unwrap();
+
+    println!("Picked {}", color);
+
+    let accent = ColorSelect::new()
+        .with_prompt("Accent color")
+        .palette(Palette::TrueColor)
+        .interact_opt()
+        .unwrap();
+
+    match accent {
+        Some(accent) => println!("Accent is {}", accent),
+        None => println!("Keeping the default accent"),
+    }
+}
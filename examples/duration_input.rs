@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use dialoguer::{theme::ColorfulTheme, DurationInput};
+
+fn main() {
+    let timeout = DurationInput::with_theme(&ColorfulTheme::default())
+        .with_prompt("Request timeout")
+        .default(Duration::from_secs(90))
+        .interact()
+        .unwrap();
+
+    println!("Timing out after {:?}", timeout);
+
+    let retention = DurationInput::new()
+        .with_prompt("Keep logs for (e.g. 2w, 36h)")
+        .interact_opt()
+        .unwrap();
+
+    match retention {
+        Some(retention) => println!("Keeping logs for {} hours", retention.as_secs() / 3600),
+        None => println!("Keeping logs forever"),
+    }
+}
@@ -0,0 +1,15 @@
+use dialoguer::{theme::ColorfulTheme, EditList};
+
+fn main() {
+    let hosts = EditList::with_theme(&ColorfulTheme::default())
+        .with_prompt("Allowed hosts (a: add, e: edit, d: delete, K/J: move)")
+        .entry_prompt("Host")
+        .items(&["localhost", "127.0.0.1", "example.com"])
+        .interact_opt()
+        .unwrap();
+
+    match hosts {
+        Some(hosts) => println!("Allowing {} hosts: {}", hosts.len(), hosts.join(", ")),
+        None => println!("Keeping the current allowlist"),
+    }
+}
@@ -0,0 +1,32 @@
+use dialoguer::{theme::ColorfulTheme, GridSelect};
+
+fn main() {
+    let months = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let month = GridSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Billing month")
+        .items(&months)
+        .columns(4)
+        .interact()
+        .unwrap();
+
+    println!("Billing starts in {}", months[month]);
+
+    let emoji = [
+        "😀", "😂", "😍", "🤔", "😎", "😭", "👍", "👎", "🎉", "🔥", "🚀", "💯",
+    ];
+
+    let reaction = GridSelect::new()
+        .with_prompt("Reaction")
+        .items(&emoji)
+        .columns(6)
+        .interact_opt()
+        .unwrap();
+
+    match reaction {
+        Some(idx) => println!("Reacted with {}", emoji[idx]),
+        None => println!("No reaction"),
+    }
+}
@@ -0,0 +1,18 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use dialoguer::{theme::ColorfulTheme, IpInput};
+
+fn main() {
+    let gateway: Ipv4Addr = IpInput::with_theme(&ColorfulTheme::default())
+        .with_prompt("Gateway")
+        .default(Ipv4Addr::new(192, 168, 0, 1))
+        .interact()
+        .unwrap();
+
+    let dns: Option<IpAddr> = IpInput::with_theme(&ColorfulTheme::default())
+        .with_prompt("DNS server")
+        .interact_opt()
+        .unwrap();
+
+    println!("Gateway {}, DNS {:?}", gateway, dns);
+}
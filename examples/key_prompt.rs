@@ -0,0 +1,22 @@
+use dialoguer::{console::Key, theme::ColorfulTheme, KeyPrompt};
+
+fn main() {
+    KeyPrompt::new()
+        .with_prompt("Press any key to continue")
+        .interact()
+        .unwrap();
+
+    let key = KeyPrompt::with_theme(&ColorfulTheme::default())
+        .with_prompt("Overwrite config.toml?")
+        .allowed_keys(vec![Key::Char('y'), Key::Char('n'), Key::Char('a')])
+        .report(true)
+        .interact_opt()
+        .unwrap();
+
+    match key {
+        Some(Key::Char('y')) => println!("Overwriting config.toml"),
+        Some(Key::Char('a')) => println!("Overwriting all files"),
+        Some(_) => println!("Keeping config.toml"),
+        None => println!("Aborted"),
+    }
+}
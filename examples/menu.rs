@@ -0,0 +1,28 @@
+use dialoguer::{theme::ColorfulTheme, Menu, MenuItem};
+
+fn main() {
+    let path = Menu::with_theme(&ColorfulTheme::default())
+        .with_prompt("Main menu")
+        .item(MenuItem::new("New game"))
+        .item(MenuItem::new("Load game"))
+        .item(
+            MenuItem::new("Settings")
+                .child(
+                    MenuItem::new("Audio")
+                        .child(MenuItem::new("Mute"))
+                        .child(MenuItem::new("Volume")),
+                )
+                .child(
+                    MenuItem::new("Video")
+                        .children(vec![MenuItem::new("Fullscreen"), MenuItem::new("Windowed")]),
+                ),
+        )
+        .item(MenuItem::new("Quit"))
+        .interact_opt()
+        .unwrap();
+
+    match path {
+        Some(path) => println!("Chose entry at {:?}", path),
+        None => println!("Left the menu"),
+    }
+}
@@ -0,0 +1,23 @@
+use dialoguer::{theme::ColorfulTheme, NumberInput};
+
+fn main() {
+    let port: u16 = NumberInput::with_theme(&ColorfulTheme::default())
+        .with_prompt("Port")
+        .default(8080)
+        .min(1024)
+        .interact()
+        .unwrap();
+
+    println!("Listening on port {}", port);
+
+    let ratio: f64 = NumberInput::with_theme(&ColorfulTheme::default())
+        .with_prompt("Compression ratio")
+        .default(0.5)
+        .min(0.0)
+        .max(1.0)
+        .step(0.25)
+        .interact()
+        .unwrap();
+
+    println!("Compressing with ratio {}", ratio);
+}
@@ -0,0 +1,23 @@
+use dialoguer::{theme::ColorfulTheme, PathSelect};
+
+fn main() {
+    let file = PathSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Pick a Rust source file")
+        .extension("rs")
+        .interact()
+        .unwrap();
+
+    println!("Picked file {}", file.display());
+
+    let dir = PathSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Pick an output directory")
+        .select_files(false)
+        .select_dirs(true)
+        .interact_opt()
+        .unwrap();
+
+    match dir {
+        Some(dir) => println!("Writing to {}", dir.display()),
+        None => println!("Nothing picked"),
+    }
+}
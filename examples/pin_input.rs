@@ -0,0 +1,21 @@
+use dialoguer::{theme::ColorfulTheme, PinInput};
+
+fn main() {
+    let code = PinInput::with_theme(&ColorfulTheme::default())
+        .with_prompt("Authentication code")
+        .interact()
+        .unwrap();
+
+    println!("Verifying code {}", code);
+
+    let pin = PinInput::with_theme(&ColorfulTheme::default())
+        .with_prompt("Card PIN")
+        .length(4)
+        .mask(true)
+        .interact_opt()
+        .unwrap();
+
+    if pin.is_some() {
+        println!("PIN accepted");
+    }
+}
@@ -0,0 +1,22 @@
+use dialoguer::{theme::ColorfulTheme, RangeSelect};
+
+fn main() {
+    let commits = &[
+        "3f2a1b0 Update dependencies",
+        "9c8d7e6 Fix paging on small terminals",
+        "5a4b3c2 Add range select prompt",
+        "1e2f3a4 Document theme methods",
+        "7b6c5d4 Bump version",
+    ];
+
+    let (start, end) = RangeSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Pick the commits to squash (space anchors the range)")
+        .items(&commits[..])
+        .interact()
+        .unwrap();
+
+    println!("Squashing {} commits:", end - start + 1);
+    for commit in &commits[start..=end] {
+        println!("  {}", commit);
+    }
+}
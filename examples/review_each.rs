@@ -0,0 +1,23 @@
+use dialoguer::{theme::ColorfulTheme, ReviewDecision, ReviewEach};
+
+fn main() {
+    let hunks = vec![
+        ("src/lib.rs", "-use std::io;\n+use std::{fmt, io};"),
+        ("src/theme.rs", "+/// Formats an item.\n fn format_item("),
+        ("README.md", "-Version 0.9\n+Version 0.10"),
+    ];
+
+    let reviewed = ReviewEach::with_theme(&ColorfulTheme::default())
+        .with_prompt("Stage this hunk?")
+        .allow_edit(true)
+        .interact(hunks, |(file, diff)| format!("--- {}\n{}", file, diff))
+        .unwrap();
+
+    for ((file, _), decision) in reviewed {
+        match decision {
+            ReviewDecision::Yes => println!("Staging hunk in {}", file),
+            ReviewDecision::No => println!("Skipping hunk in {}", file),
+            ReviewDecision::Edit => println!("Editing hunk in {}", file),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use dialoguer::{theme::ColorfulTheme, Slider};
+
+fn main() {
+    let volume: u8 = Slider::with_theme(&ColorfulTheme::default())
+        .with_prompt("Volume")
+        .min(0)
+        .max(100)
+        .default(50)
+        .step(5)
+        .interact()
+        .unwrap();
+
+    println!("Volume set to {}%", volume);
+
+    let quality: f32 = Slider::new()
+        .with_prompt("Quality")
+        .min(0.0)
+        .max(1.0)
+        .step(0.125)
+        .width(16)
+        .interact()
+        .unwrap();
+
+    println!("Encoding with quality {}", quality);
+}
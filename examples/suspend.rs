@@ -0,0 +1,50 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use console::Term;
+use dialoguer::{set_suspend_hook, theme::ColorfulTheme, Confirm};
+
+fn main() {
+    // A stand-in for a progress bar drawing from a background thread.
+    let visible = Arc::new(AtomicBool::new(true));
+    let (hide, show) = (visible.clone(), visible.clone());
+
+    set_suspend_hook((
+        move || {
+            hide.store(false, Ordering::SeqCst);
+            Term::stderr().clear_line().unwrap();
+        },
+        move || show.store(true, Ordering::SeqCst),
+    ));
+
+    let ticker = visible.clone();
+    thread::spawn(move || {
+        for tick in 0.. {
+            if ticker.load(Ordering::SeqCst) {
+                let term = Term::stderr();
+                term.clear_line().unwrap();
+                term.write_str(&format!("working {}", ".".repeat(tick % 4)))
+                    .unwrap();
+            }
+            thread::sleep(Duration::from_millis(200));
+        }
+    });
+
+    thread::sleep(Duration::from_secs(1));
+
+    let proceed = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Continue with the deployment?")
+        .interact()
+        .unwrap();
+
+    thread::sleep(Duration::from_secs(1));
+    Term::stderr().clear_line().unwrap();
+
+    println!("{}", if proceed { "Deployed" } else { "Aborted" });
+}
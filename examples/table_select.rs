@@ -0,0 +1,24 @@
+use dialoguer::{theme::ColorfulTheme, TableSelect};
+
+fn main() {
+    let vms = [
+        ["web-1", "eu-west-1", "running"],
+        ["web-2", "eu-west-1", "running"],
+        ["db-primary", "us-east-2", "stopped"],
+        [
+            "batch-worker-with-a-long-name",
+            "ap-southeast-1",
+            "provisioning",
+        ],
+    ];
+
+    let selection = TableSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Pick a VM")
+        .header(&["Name", "Region", "Status"])
+        .rows(&vms)
+        .default(0)
+        .interact()
+        .unwrap();
+
+    println!("Connecting to {}", vms[selection][0]);
+}
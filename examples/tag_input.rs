@@ -0,0 +1,12 @@
+use dialoguer::{theme::ColorfulTheme, TagInput};
+
+fn main() {
+    let labels = TagInput::with_theme(&ColorfulTheme::default())
+        .with_prompt("Labels")
+        .defaults(&["triage"])
+        .suggestions(&["bug", "enhancement", "documentation", "good first issue"])
+        .interact()
+        .unwrap();
+
+    println!("Labels: {:?}", labels);
+}
@@ -0,0 +1,26 @@
+use dialoguer::{theme::ColorfulTheme, Toggle};
+
+fn main() {
+    let https = Toggle::with_theme(&ColorfulTheme::default())
+        .with_prompt("Protocol")
+        .disabled_label("HTTP")
+        .enabled_label("HTTPS")
+        .default(true)
+        .interact()
+        .unwrap();
+
+    println!("Using {}", if https { "https" } else { "http" });
+
+    let verbose = Toggle::new()
+        .with_prompt("Logging")
+        .disabled_label("Quiet")
+        .enabled_label("Verbose")
+        .interact_opt()
+        .unwrap();
+
+    match verbose {
+        Some(true) => println!("Logging verbosely"),
+        Some(false) => println!("Logging quietly"),
+        None => println!("Keeping the current logging setting"),
+    }
+}
@@ -0,0 +1,25 @@
+use dialoguer::{theme::ColorfulTheme, TransferSelect};
+
+fn main() {
+    let songs = &[
+        "Bohemian Rhapsody",
+        "Hotel California",
+        "Stairway to Heaven",
+        "Imagine",
+        "Smells Like Teen Spirit",
+        "Billie Jean",
+    ];
+
+    let playlist = TransferSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Build a playlist (tab switches panes, enter moves songs, c confirms)")
+        .titles("Library", "Playlist")
+        .items(&songs[..])
+        .defaults(&[3])
+        .interact()
+        .unwrap();
+
+    println!("Your playlist:");
+    for (position, idx) in playlist.iter().enumerate() {
+        println!("  {}. {}", position + 1, songs[*idx]);
+    }
+}
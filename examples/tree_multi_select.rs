@@ -0,0 +1,24 @@
+use dialoguer::{theme::ColorfulTheme, TreeMultiSelect, TreeNode};
+
+fn main() {
+    let tree = vec![
+        TreeNode::new("src")
+            .child(TreeNode::new("lib.rs").checked(true))
+            .child(
+                TreeNode::new("prompts")
+                    .child(TreeNode::new("select.rs"))
+                    .child(TreeNode::new("input.rs")),
+            )
+            .expanded(true),
+        TreeNode::new("examples").child(TreeNode::new("select.rs")),
+        TreeNode::new("Cargo.toml"),
+    ];
+
+    let paths = TreeMultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Files to include")
+        .items(tree)
+        .interact()
+        .unwrap();
+
+    println!("Including {:?}", paths);
+}
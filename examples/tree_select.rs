@@ -0,0 +1,42 @@
+use dialoguer::{theme::ColorfulTheme, TreeNode, TreeSelect};
+
+fn main() {
+    let path = TreeSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Pick a category")
+        .item(
+            TreeNode::new("Fruit")
+                .child(TreeNode::new("Apple"))
+                .child(
+                    TreeNode::new("Citrus")
+                        .child(TreeNode::new("Lemon"))
+                        .child(TreeNode::new("Orange")),
+                )
+                .expanded(true),
+        )
+        .item(
+            TreeNode::new("Vegetables")
+                .child(TreeNode::new("Carrot"))
+                .child(TreeNode::new("Potato")),
+        )
+        .interact()
+        .unwrap();
+
+    println!("Picked node at {:?}", path);
+
+    let path = TreeSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Pick a department (any level)")
+        .allow_branch_selection(true)
+        .item(
+            TreeNode::new("Engineering")
+                .child(TreeNode::new("Backend"))
+                .child(TreeNode::new("Frontend")),
+        )
+        .item(TreeNode::new("Sales"))
+        .interact_opt()
+        .unwrap();
+
+    match path {
+        Some(path) => println!("Picked node at {:?}", path),
+        None => println!("Nothing picked"),
+    }
+}
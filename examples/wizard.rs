@@ -15,10 +15,9 @@ struct Config {
 }
 
 fn init_config() -> Result<Option<Config>, Box<dyn Error>> {
-    let theme = ColorfulTheme {
-        values_style: Style::new().yellow().dim(),
-        ..ColorfulTheme::default()
-    };
+    let theme = ColorfulTheme::builder()
+        .values_style(Style::new().yellow().dim())
+        .build();
     println!("Welcome to the setup wizard");
 
     if !Confirm::with_theme(&theme)
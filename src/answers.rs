@@ -0,0 +1,182 @@
+//! Records the answers to prompts and replays them to preseed later sessions.
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    sync::{Mutex, MutexGuard},
+};
+
+use once_cell::sync::Lazy;
+
+#[derive(Default)]
+struct State {
+    recording: Option<File>,
+    // The answers left to replay for each prompt text, in the order they were recorded.
+    replay: HashMap<String, VecDeque<String>>,
+}
+
+static STATE: Lazy<Mutex<State>> = Lazy::new(Default::default);
+
+fn lock() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Records the answers to the following prompts to the file at `path`, replacing it.
+///
+/// Every answer is written as soon as it is given, so the answers of an interrupted session
+/// are kept. The format is described in [`replay_answers`].
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{record_answers, Confirm};
+///
+/// record_answers("answers.txt")?;
+///
+/// let proceed = Confirm::new().with_prompt("Continue?").interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn record_answers<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let file = File::create(path)?;
+    lock().recording = Some(file);
+    Ok(())
+}
+
+/// Stops recording the answers started with [`record_answers`].
+pub fn stop_recording_answers() {
+    lock().recording = None;
+}
+
+/// Answers the following prompts with the answers in the file at `path`, like `debconf`
+/// preseeding.
+///
+/// The answers for a prompt text are used in order, prompts without an answer left are
+/// asked interactively. A replayed answer is reported like a given one, and fails the
+/// prompt with an [`InvalidInput`](io::ErrorKind::InvalidInput) error if it doesn't fit.
+///
+/// Every line holds the prompt text and the answer separated by a tab. Backslashes, tabs
+/// and newlines are escaped as `\\`, `\t` and `\n`. Empty lines and lines starting with
+/// `#` are skipped. The answers are `yes` or `no` for [`Confirm`](crate::Confirm), the text
+/// or number (counting from one) of an item for [`Select`](crate::Select), a comma
+/// separated list of those for [`MultiSelect`](crate::MultiSelect) and the text for
+/// [`Input`](crate::Input). Selects with an [`ItemProvider`](crate::ItemProvider) and
+/// redacted inputs are always asked.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{replay_answers, Confirm};
+///
+/// // answers.txt contains "Continue?\tyes".
+/// replay_answers("answers.txt")?;
+///
+/// let proceed = Confirm::new().with_prompt("Continue?").interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn replay_answers<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let replay = parse(BufReader::new(File::open(path)?))?;
+    lock().replay = replay;
+    Ok(())
+}
+
+/// Takes the next answer to replay for the prompt with the text `prompt`.
+pub(crate) fn replayed(prompt: &str) -> Option<String> {
+    lock()
+        .replay
+        .get_mut(prompt)
+        .and_then(|answers| answers.pop_front())
+}
+
+/// Records the `answer` to the prompt with the text `prompt`, if recording.
+pub(crate) fn record(prompt: &str, answer: &str) -> io::Result<()> {
+    match lock().recording {
+        Some(ref mut file) => {
+            writeln!(file, "{}\t{}", escape(prompt), escape(answer))?;
+            file.flush()
+        }
+        None => Ok(()),
+    }
+}
+
+fn parse<R: BufRead>(reader: R) -> io::Result<HashMap<String, VecDeque<String>>> {
+    let mut replay: HashMap<String, VecDeque<String>> = HashMap::new();
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (prompt, answer) = match line.find('\t') {
+            Some(tab) => (&line[..tab], &line[tab + 1..]),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Missing tab after the prompt on line {}", idx + 1),
+                ))
+            }
+        };
+
+        replay
+            .entry(unescape(prompt))
+            .or_default()
+            .push_back(unescape(answer));
+    }
+
+    Ok(replay)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        let text = "a\tb\\nc\nd";
+        assert_eq!(escape(text), "a\\tb\\\\nc\\nd");
+        assert_eq!(unescape(&escape(text)), text);
+    }
+
+    #[test]
+    fn test_parse() {
+        let file = "# preseeded\n\nContinue?\tyes\nName\tAda\\tL\nContinue?\tno\n";
+        let replay = parse(file.as_bytes()).unwrap();
+
+        assert_eq!(replay["Continue?"], ["yes", "no"]);
+        assert_eq!(replay["Name"], ["Ada\tL"]);
+
+        assert!(parse("Continue? yes".as_bytes()).is_err());
+    }
+}
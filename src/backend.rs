@@ -0,0 +1,1431 @@
+//! Abstracts the terminal prompts interact on.
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use console::{Key, Term};
+use once_cell::sync::Lazy;
+
+/// What a terminal can render beyond plain text, see [`Backend::capabilities`].
+///
+/// The default supports everything, add restrictions to it for terminals which can't.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use dialoguer::Capabilities;
+///
+/// let mut capabilities = Capabilities::default();
+/// capabilities.hide_cursor = false;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// The cursor can be moved and rows cleared. Without it frames are reprinted below
+    /// the previous ones instead of replacing them.
+    pub cursor_movement: bool,
+    /// The cursor can be hidden while prompts render.
+    pub hide_cursor: bool,
+    /// Colors and text styles are rendered.
+    pub colors: bool,
+    /// OSC 8 hyperlinks are rendered. Without it only the text of links is shown.
+    pub hyperlinks: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Capabilities {
+            cursor_movement: true,
+            hide_cursor: true,
+            colors: true,
+            hyperlinks: true,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Returns the capabilities of a plain text terminal, which prints escape codes
+    /// literally.
+    pub fn dumb() -> Self {
+        Capabilities {
+            cursor_movement: false,
+            hide_cursor: false,
+            colors: false,
+            hyperlinks: false,
+        }
+    }
+
+    /// Returns the capabilities of the terminal type `term`, the value of `TERM`.
+    ///
+    /// Unset, `dumb` and VT52 terminals are plain text, and terminals up to the VT100 can't
+    /// hide the cursor. Neither those nor the Linux console render hyperlinks.
+    pub fn from_term_name(term: Option<&str>) -> Self {
+        match term.unwrap_or("") {
+            "" | "dumb" | "vt52" => Capabilities::dumb(),
+            "vt100" | "vt102" | "ansi" => Capabilities {
+                hide_cursor: false,
+                hyperlinks: false,
+                ..Capabilities::default()
+            },
+            "linux" => Capabilities {
+                hyperlinks: false,
+                ..Capabilities::default()
+            },
+            _ => Capabilities::default(),
+        }
+    }
+}
+
+/// The terminal a prompt reads keys from and renders to.
+///
+/// [`Term`] implements this, and with the `crossterm` feature
+/// [`CrosstermBackend`](crate::CrosstermBackend) does too, for applications built on crossterm
+/// which shouldn't pull in a second terminal stack with its own raw mode handling. Every
+/// `interact_on` method takes a backend.
+///
+/// Only reading, writing and the size are required, the cursor movements and clearing
+/// methods default to ANSI escape codes written with [`write_str`](Self::write_str), which
+/// are left out where [`capabilities`](Self::capabilities) rule them out.
+pub trait Backend {
+    /// Reads a single key without echoing it.
+    ///
+    /// Ctrl-C fails with an [`Interrupted`](io::ErrorKind::Interrupted) error, see
+    /// [`interrupted`], instead of raising `SIGINT`, so prompts restore the terminal before
+    /// the error reaches the application.
+    ///
+    /// A resize of the terminal while waiting may end the read early with [`Key::Unknown`],
+    /// which prompts ignore apart from rendering again at the new size.
+    fn read_key(&self) -> io::Result<Key>;
+
+    /// Reads a line of input, echoing it, starting out with `initial` which can be edited.
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String>;
+
+    /// Reads a line of input without echoing it.
+    fn read_secure_line(&self) -> io::Result<String>;
+
+    /// Writes `s` without a newline.
+    fn write_str(&self, s: &str) -> io::Result<()>;
+
+    /// Flushes the output written so far.
+    fn flush(&self) -> io::Result<()>;
+
+    /// Returns the size of the terminal in rows and columns.
+    fn size(&self) -> (u16, u16);
+
+    /// Reads a line of input, echoing it.
+    fn read_line(&self) -> io::Result<String> {
+        self.read_line_initial_text("")
+    }
+
+    /// Returns whether another key was typed already, so [`read_key`](Self::read_key)
+    /// returns without waiting.
+    ///
+    /// Prompts use it to apply keys held down and repeating faster than frames render at
+    /// once, rather than rendering a frame for each of them. The default is false.
+    fn key_pending(&self) -> bool {
+        false
+    }
+
+    /// Waits up to `timeout` for a key, returning whether [`read_key`](Self::read_key)
+    /// then returns without blocking.
+    ///
+    /// Prompts waiting for a key this way can give up in between, like when they are
+    /// [cancelled](crate::CancellationToken). Backends which can't wait return true right
+    /// away, reading the key blocks then. Ending the wait early with false, like when the
+    /// terminal is resized, is fine. The default is true.
+    fn poll_key(&self, timeout: Duration) -> io::Result<bool> {
+        let _ = timeout;
+        Ok(true)
+    }
+
+    /// Writes `s` followed by a newline.
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.write_str(&format!("{}\n", s))
+    }
+
+    /// Returns whether a user is attending the terminal, rather than it being redirected.
+    fn is_attended(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the terminal renders colors.
+    fn colors_supported(&self) -> bool {
+        true
+    }
+
+    /// Returns what the terminal can render, probed when a prompt starts.
+    ///
+    /// The default supports everything, with colors as
+    /// [`colors_supported`](Self::colors_supported) reports them.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            colors: self.colors_supported(),
+            ..Capabilities::default()
+        }
+    }
+
+    /// Moves the cursor up by `n` rows.
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        if n > 0 && self.capabilities().cursor_movement {
+            self.write_str(&format!("\x1b[{}A", n))?;
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor down by `n` rows.
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        if n > 0 && self.capabilities().cursor_movement {
+            self.write_str(&format!("\x1b[{}B", n))?;
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor left by `n` columns.
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        if n > 0 && self.capabilities().cursor_movement {
+            self.write_str(&format!("\x1b[{}D", n))?;
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor right by `n` columns.
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        if n > 0 && self.capabilities().cursor_movement {
+            self.write_str(&format!("\x1b[{}C", n))?;
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor left by `n` columns and clears the rest of the row.
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        if n > 0 && self.capabilities().cursor_movement {
+            self.write_str(&format!("\x1b[{}D\x1b[0K", n))?;
+        }
+        Ok(())
+    }
+
+    /// Clears the row of the cursor and moves the cursor to its start.
+    fn clear_line(&self) -> io::Result<()> {
+        if !self.capabilities().cursor_movement {
+            return Ok(());
+        }
+        self.write_str("\r\x1b[2K")
+    }
+
+    /// Clears the `n` rows above the cursor and moves the cursor to the first of them.
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        self.move_cursor_up(n)?;
+        for _ in 0..n {
+            self.clear_line()?;
+            self.move_cursor_down(1)?;
+        }
+        self.move_cursor_up(n)
+    }
+
+    /// Clears everything from the row of the cursor down.
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        if !self.capabilities().cursor_movement {
+            return Ok(());
+        }
+        self.write_str("\r\x1b[0J")
+    }
+
+    /// Inserts `n` blank rows at the row of the cursor, pushing the rows below it down and
+    /// the last ones off the screen.
+    fn insert_lines(&self, n: usize) -> io::Result<()> {
+        if n > 0 && self.capabilities().cursor_movement {
+            self.write_str(&format!("\x1b[{}L", n))?;
+        }
+        Ok(())
+    }
+
+    /// Hides the cursor.
+    fn hide_cursor(&self) -> io::Result<()> {
+        if !self.capabilities().hide_cursor {
+            return Ok(());
+        }
+        self.write_str("\x1b[?25l")
+    }
+
+    /// Shows the cursor.
+    fn show_cursor(&self) -> io::Result<()> {
+        if !self.capabilities().hide_cursor {
+            return Ok(());
+        }
+        self.write_str("\x1b[?25h")
+    }
+
+    /// Switches to the alternate screen buffer and moves the cursor to its top left corner.
+    fn enter_alternate_screen(&self) -> io::Result<()> {
+        if !self.capabilities().cursor_movement {
+            return Ok(());
+        }
+        self.write_str("\x1b[?1049h\x1b[H")
+    }
+
+    /// Switches back to the primary screen buffer, restoring its contents and the cursor.
+    fn leave_alternate_screen(&self) -> io::Result<()> {
+        if !self.capabilities().cursor_movement {
+            return Ok(());
+        }
+        self.write_str("\x1b[?1049l")
+    }
+
+    /// Called when a prompt starts, before it reads keys. The default does nothing.
+    fn begin_prompt(&self) {}
+
+    /// Called when a prompt ends, also when it fails or panics, so backends restore the
+    /// terminal mode they changed for the keys read. The default does nothing.
+    fn end_prompt(&self) {}
+}
+
+/// Ends the prompt on a backend once dropped, see [`Backend::end_prompt`].
+///
+/// Renderers hold one for the whole prompt, so errors returned with `?` anywhere in it
+/// restore the terminal as well.
+pub(crate) struct PromptGuard<'a> {
+    term: &'a dyn Backend,
+}
+
+impl<'a> PromptGuard<'a> {
+    pub fn new(term: &'a dyn Backend) -> Self {
+        term.begin_prompt();
+        PromptGuard { term }
+    }
+}
+
+impl Drop for PromptGuard<'_> {
+    fn drop(&mut self) {
+        self.term.end_prompt();
+    }
+}
+
+static DEFAULT_TERM: Lazy<Mutex<Option<Arc<dyn Backend + Send + Sync>>>> =
+    Lazy::new(Default::default);
+
+/// Sets the terminal the `interact` methods of all prompts render on, instead of stderr.
+///
+/// This configures once where prompts go, for instance to stdout, when it isn't piped, or to
+/// a [`CrosstermBackend`](crate::CrosstermBackend). The `interact_on` methods still render on
+/// the terminal they are given.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{console::Term, set_default_term, Confirm};
+///
+/// set_default_term(Term::stdout());
+///
+/// let proceed = Confirm::new().with_prompt("Continue?").interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_default_term<B: Backend + Send + Sync + 'static>(term: B) {
+    *DEFAULT_TERM.lock().unwrap_or_else(|err| err.into_inner()) = Some(Arc::new(term));
+}
+
+/// Returns the terminal set with [`set_default_term`], stderr by default.
+pub(crate) fn default_term() -> Arc<dyn Backend + Send + Sync> {
+    match *DEFAULT_TERM.lock().unwrap_or_else(|err| err.into_inner()) {
+        Some(ref term) => term.clone(),
+        None => Arc::new(Term::stderr()),
+    }
+}
+
+static STRICT_INLINE: AtomicBool = AtomicBool::new(false);
+
+/// Makes prompts count the rows they clear conservatively, so they never erase output
+/// rendered before them.
+///
+/// Prompts only clear the lines they rendered themselves, counting the rows the lines wrap
+/// into at the current terminal width. After a resize, terminals which don't rewrap their
+/// contents keep the rows the lines were drawn in, so this count can exceed them. In
+/// strict mode the smaller of both counts is cleared, which may leave remnants of a prompt
+/// on terminals which rewrap, but never touches the lines above it.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{set_strict_inline, Select};
+///
+/// set_strict_inline(true);
+///
+/// let selection = Select::new().items(&["Yes", "No"]).interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_strict_inline(enabled: bool) {
+    STRICT_INLINE.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether prompts count the rows they clear conservatively.
+pub(crate) fn strict_inline() -> bool {
+    STRICT_INLINE.load(Ordering::Relaxed)
+}
+
+/// Keeps the terminal on the alternate screen until left or dropped.
+pub(crate) struct AlternateScreen<'a> {
+    term: &'a dyn Backend,
+    active: bool,
+}
+
+impl<'a> AlternateScreen<'a> {
+    pub fn enter(term: &'a dyn Backend) -> io::Result<Self> {
+        term.enter_alternate_screen()?;
+        Ok(AlternateScreen { term, active: true })
+    }
+
+    pub fn leave(mut self) -> io::Result<()> {
+        self.active = false;
+        self.term.leave_alternate_screen()?;
+        self.term.flush()
+    }
+}
+
+impl Drop for AlternateScreen<'_> {
+    fn drop(&mut self) {
+        if self.active {
+            let _ = self.term.leave_alternate_screen();
+            let _ = self.term.flush();
+        }
+    }
+}
+
+/// Returns the error prompts fail with when the user presses Ctrl-C.
+///
+/// Its kind is [`Interrupted`](io::ErrorKind::Interrupted), which tells it apart from other
+/// errors.
+pub fn interrupted() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, "Interrupted by Ctrl-C")
+}
+
+/// Interrupts blocking reads when the terminal is resized, until dropped.
+///
+/// The handler is shared by all watches and only installed if `SIGWINCH` has its default
+/// disposition, so handlers of the application are left alone. It is installed without
+/// `SA_RESTART`, which makes waiting for a key fail with `EINTR`.
+/// Renderers keep a watch for the whole prompt, so the handler isn't installed again for
+/// every key.
+#[cfg(unix)]
+pub(crate) struct ResizeWatch {
+    handler: Option<crate::signal::HandlerGuard>,
+    resizes: usize,
+}
+
+#[cfg(unix)]
+static RESIZES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+#[cfg(unix)]
+static RESIZE_HANDLER: crate::signal::SharedHandler =
+    crate::signal::SharedHandler::new(libc::SIGWINCH, on_resize);
+
+#[cfg(unix)]
+extern "C" fn on_resize(_: std::os::raw::c_int) {
+    RESIZES.fetch_add(1, Ordering::SeqCst);
+}
+
+#[cfg(unix)]
+impl ResizeWatch {
+    pub fn start() -> Self {
+        ResizeWatch {
+            handler: RESIZE_HANDLER.acquire(),
+            resizes: RESIZES.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Returns whether the terminal was resized since the watch started.
+    fn resized(&self) -> bool {
+        self.handler.is_some() && RESIZES.load(Ordering::SeqCst) != self.resizes
+    }
+}
+
+// The capabilities of the terminal `TERM` names, Windows consoles support everything.
+#[cfg(unix)]
+static TERM_CAPABILITIES: Lazy<Capabilities> =
+    Lazy::new(|| Capabilities::from_term_name(std::env::var("TERM").ok().as_deref()));
+
+#[cfg(not(unix))]
+static TERM_CAPABILITIES: Lazy<Capabilities> = Lazy::new(Capabilities::default);
+
+// Whether the last text written to a `Term` ended a line.
+static AT_LINE_START: AtomicBool = AtomicBool::new(true);
+
+/// Reads a line from stdin, edited and echoed by the line discipline of the terminal rather
+/// than with escape codes.
+fn read_cooked_line() -> io::Result<String> {
+    use std::io::BufRead;
+
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "stdin closed"));
+    }
+    AT_LINE_START.store(true, Ordering::Relaxed);
+    Ok(line.trim_end_matches(&['\r', '\n'][..]).to_string())
+}
+
+/// Starts a new line for the next frame, the way terminals which can't clear rows render
+/// frames below the previous ones.
+fn reprint(term: &Term) -> io::Result<()> {
+    if AT_LINE_START.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    Backend::write_line(term, "")
+}
+
+impl Backend for Term {
+    #[cfg(unix)]
+    fn read_key(&self) -> io::Result<Key> {
+        use crate::job_control;
+
+        let watch = ResizeWatch::start();
+        let _stop_watch = job_control::StopWatch::start();
+
+        // Signals interrupt waiting for the key, console only reads it once it arrived, so
+        // its Ctrl-C is always the key.
+        let read = || {
+            if self.is_term() {
+                crate::escape::wait_key()?;
+            }
+            Term::read_key_raw(self)
+        };
+
+        loop {
+            if job_control::take_stop() {
+                job_control::suspend(self)?;
+                return Ok(Key::Unknown);
+            }
+
+            let waiting = job_control::Waiting::start();
+            let key = crate::legacy::read_key(|| crate::escape::read_key(read));
+            drop(waiting);
+
+            match key {
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+                    if job_control::stop_pending() {
+                        continue;
+                    }
+                    if watch.resized() {
+                        return Ok(Key::Unknown);
+                    }
+                }
+                Ok(Key::CtrlC) => return Err(interrupted()),
+                Ok(Key::Char(job_control::SUSPEND_KEY)) => {
+                    job_control::suspend(self)?;
+                    return Ok(Key::Unknown);
+                }
+                key => return key,
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn read_key(&self) -> io::Result<Key> {
+        match crate::legacy::read_key(|| crate::escape::read_key(|| Term::read_key_raw(self)))? {
+            Key::CtrlC => Err(interrupted()),
+            key => Ok(key),
+        }
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        Term::read_line_initial_text(self, initial)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        Term::read_secure_line(self)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        if !s.is_empty() {
+            AT_LINE_START.store(s.ends_with('\n'), Ordering::Relaxed);
+        }
+        Term::write_str(self, s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Term::flush(self)
+    }
+
+    fn key_pending(&self) -> bool {
+        crate::legacy::key_pending() || (self.is_term() && crate::escape::key_pending())
+    }
+
+    fn poll_key(&self, timeout: Duration) -> io::Result<bool> {
+        if crate::legacy::key_pending() || !self.is_term() {
+            return Ok(true);
+        }
+        #[cfg(unix)]
+        {
+            // Reading the key then suspends for a stop left to the prompt.
+            let _waiting = crate::job_control::Waiting::start();
+            Ok(crate::escape::poll_key(timeout)? || crate::job_control::stop_pending())
+        }
+        #[cfg(not(unix))]
+        crate::escape::poll_key(timeout)
+    }
+
+    fn size(&self) -> (u16, u16) {
+        crate::deterministic::deterministic_size().unwrap_or_else(|| Term::size(self))
+    }
+
+    fn read_line(&self) -> io::Result<String> {
+        if !TERM_CAPABILITIES.cursor_movement && self.is_term() {
+            return read_cooked_line();
+        }
+        Term::read_line(self)
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        AT_LINE_START.store(true, Ordering::Relaxed);
+        Term::write_line(self, s)
+    }
+
+    fn is_attended(&self) -> bool {
+        self.features().is_attended()
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.features().colors_supported()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        let colors = self.features().colors_supported();
+        Capabilities {
+            colors,
+            // Output which doesn't get styles, like pipes, doesn't get links either.
+            hyperlinks: TERM_CAPABILITIES.hyperlinks && colors && !crate::is_legacy_console(),
+            ..*TERM_CAPABILITIES
+        }
+    }
+
+    // Console also supports the legacy Windows console, which has no escape codes.
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        if !TERM_CAPABILITIES.cursor_movement {
+            return Ok(());
+        }
+        Term::move_cursor_up(self, n)
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        if !TERM_CAPABILITIES.cursor_movement {
+            return Ok(());
+        }
+        Term::move_cursor_down(self, n)
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        if !TERM_CAPABILITIES.cursor_movement {
+            return Ok(());
+        }
+        Term::move_cursor_left(self, n)
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        if !TERM_CAPABILITIES.cursor_movement {
+            return Ok(());
+        }
+        Term::move_cursor_right(self, n)
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        if !TERM_CAPABILITIES.cursor_movement {
+            return Ok(());
+        }
+        Term::clear_chars(self, n)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        if !TERM_CAPABILITIES.cursor_movement {
+            return reprint(self);
+        }
+        Term::clear_line(self)
+    }
+
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        if !TERM_CAPABILITIES.cursor_movement {
+            return reprint(self);
+        }
+        Term::clear_last_lines(self, n)
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        if !TERM_CAPABILITIES.cursor_movement {
+            return reprint(self);
+        }
+        Term::clear_to_end_of_screen(self)
+    }
+
+    fn insert_lines(&self, n: usize) -> io::Result<()> {
+        if n > 0 && TERM_CAPABILITIES.cursor_movement {
+            Term::write_str(self, &format!("\x1b[{}L", n))?;
+        }
+        Ok(())
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        if !TERM_CAPABILITIES.hide_cursor {
+            return Ok(());
+        }
+        Term::hide_cursor(self)
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        if !TERM_CAPABILITIES.hide_cursor {
+            return Ok(());
+        }
+        Term::show_cursor(self)
+    }
+}
+
+/// The most repeats of a key applied at once, so a frame still renders now and then while
+/// keys keep arriving.
+const MAX_REPEATS: usize = 32;
+
+/// Reads keys for prompts navigating lists, merging a navigation key held down into one
+/// read with the number of times it repeated.
+///
+/// Keys repeating faster than frames render pile up on slow and remote terminals, with the
+/// cursor lagging behind and every frame sent although only the last one is seen. Handling
+/// the repeats before rendering again catches up at once.
+#[derive(Default)]
+pub(crate) struct RepeatedKeys {
+    // A different key read while counting repeats.
+    next: Option<Key>,
+}
+
+impl RepeatedKeys {
+    /// Returns whether a key was read already and is returned by the next read.
+    pub fn pending(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// Reads the next key and how many times in a row it was typed.
+    pub fn read(&mut self, term: &dyn Backend) -> io::Result<(Key, usize)> {
+        let key = match self.next.take() {
+            Some(key) => key,
+            None => term.read_key()?,
+        };
+        // Whether repeats are pending depends on the timing of the keys.
+        if !is_navigation(&key) || crate::deterministic::is_deterministic() {
+            return Ok((key, 1));
+        }
+
+        let mut repeats = 1;
+        while repeats < MAX_REPEATS && term.key_pending() {
+            let next = term.read_key()?;
+            if next != key {
+                self.next = Some(next);
+                break;
+            }
+            repeats += 1;
+        }
+
+        Ok((key, repeats))
+    }
+}
+
+/// Returns whether `key` moves through a list, the keys commonly held down.
+fn is_navigation(key: &Key) -> bool {
+    matches!(
+        key,
+        Key::ArrowUp
+            | Key::ArrowDown
+            | Key::ArrowLeft
+            | Key::ArrowRight
+            | Key::PageUp
+            | Key::PageDown
+            | Key::Tab
+            | Key::BackTab
+    )
+}
+
+/// Runs `interact` on the default terminal with keys read from `keys` instead, for the
+/// `interact_with_keys` methods of prompts.
+pub(crate) fn with_keys<T, K, F>(keys: K, interact: F) -> io::Result<T>
+where
+    K: IntoIterator<Item = Key>,
+    F: FnOnce(&dyn Backend) -> io::Result<T>,
+{
+    let term = default_term();
+    interact(&ScriptedKeys {
+        term: &*term,
+        keys: std::cell::RefCell::new(keys.into_iter()),
+    })
+}
+
+/// A backend reading keys from an iterator and rendering to another backend.
+struct ScriptedKeys<'a, I> {
+    term: &'a dyn Backend,
+    keys: std::cell::RefCell<I>,
+}
+
+impl<I: Iterator<Item = Key>> Backend for ScriptedKeys<'_, I> {
+    fn read_key(&self) -> io::Result<Key> {
+        match self.keys.borrow_mut().next() {
+            Some(Key::CtrlC) => Err(interrupted()),
+            Some(key) => Ok(key),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the prompt needs more keys than given",
+            )),
+        }
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        read_line_keys(self, initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        read_line_keys(self, "", false)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.term.write_str(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.term.flush()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        self.term.size()
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.term.write_line(s)
+    }
+
+    // The keys are given, so prompts don't fall back to reading lines from stdin.
+    fn is_attended(&self) -> bool {
+        true
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.term.colors_supported()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.term.capabilities()
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_up(n)
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_down(n)
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_left(n)
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_right(n)
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        self.term.clear_chars(n)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        self.term.clear_line()
+    }
+
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        self.term.clear_last_lines(n)
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        self.term.clear_to_end_of_screen()
+    }
+
+    fn insert_lines(&self, n: usize) -> io::Result<()> {
+        self.term.insert_lines(n)
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        self.term.hide_cursor()
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        self.term.show_cursor()
+    }
+
+    fn enter_alternate_screen(&self) -> io::Result<()> {
+        self.term.enter_alternate_screen()
+    }
+
+    fn leave_alternate_screen(&self) -> io::Result<()> {
+        self.term.leave_alternate_screen()
+    }
+
+    fn begin_prompt(&self) {
+        self.term.begin_prompt()
+    }
+
+    fn end_prompt(&self) {
+        self.term.end_prompt()
+    }
+}
+
+/// Reads a line key by key for backends without a line discipline, starting out with
+/// `initial`, and echoes it if `echo` is set.
+pub(crate) fn read_line_keys<B: Backend + ?Sized>(
+    term: &B,
+    initial: &str,
+    echo: bool,
+) -> io::Result<String> {
+    let mut line = initial.to_string();
+    if echo {
+        term.write_str(&line)?;
+        term.flush()?;
+    }
+
+    loop {
+        match term.read_key()? {
+            Key::Enter => break,
+            Key::Backspace => {
+                if let Some(c) = line.pop() {
+                    if echo {
+                        term.clear_chars(crate::char_width(c))?;
+                    }
+                }
+            }
+            Key::Char(c) if !c.is_control() => {
+                line.push(c);
+                if echo {
+                    term.write_str(c.encode_utf8(&mut [0; 4]))?;
+                }
+            }
+            _ => continue,
+        }
+
+        if echo {
+            term.flush()?;
+        }
+    }
+
+    term.write_line("")?;
+    Ok(line)
+}
+
+/// A [`Backend`] on top of crossterm, writing to stderr or stdout.
+///
+/// Raw mode is enabled while reading keys and restored to how it was found once the prompt
+/// flushes its next frame, so it coexists with applications which manage raw mode
+/// themselves. Keys typed in between, like ones held down, are read without leaving it.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Confirm, CrosstermBackend};
+///
+/// let proceed = Confirm::new()
+///     .with_prompt("Do you want to continue?")
+///     .interact_on(&CrosstermBackend::stderr())?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "crossterm")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrosstermBackend {
+    stdout: bool,
+}
+
+#[cfg(feature = "crossterm")]
+impl CrosstermBackend {
+    /// Creates a backend writing to stderr.
+    pub fn stderr() -> Self {
+        CrosstermBackend { stdout: false }
+    }
+
+    /// Creates a backend writing to stdout.
+    pub fn stdout() -> Self {
+        CrosstermBackend { stdout: true }
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl Default for CrosstermBackend {
+    fn default() -> Self {
+        CrosstermBackend::stderr()
+    }
+}
+
+/// Enables raw mode until dropped, unless it was enabled already.
+#[cfg(feature = "crossterm")]
+struct RawMode {
+    was_enabled: bool,
+}
+
+#[cfg(feature = "crossterm")]
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        let was_enabled = crossterm::terminal::is_raw_mode_enabled()?;
+        if !was_enabled {
+            crossterm::terminal::enable_raw_mode()?;
+        }
+        Ok(RawMode { was_enabled })
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        if !self.was_enabled {
+            let _ = crossterm::terminal::disable_raw_mode();
+        }
+    }
+}
+
+/// Raw mode held by reads and polls, kept for the keys typed after them until the output is
+/// flushed, or while lines are read until the last of them ends.
+#[cfg(feature = "crossterm")]
+struct HeldRawMode {
+    raw_mode: Option<RawMode>,
+    lines: usize,
+    prompts: usize,
+}
+
+#[cfg(feature = "crossterm")]
+static HELD_RAW_MODE: Mutex<HeldRawMode> = Mutex::new(HeldRawMode {
+    raw_mode: None,
+    lines: 0,
+    prompts: 0,
+});
+
+#[cfg(feature = "crossterm")]
+impl HeldRawMode {
+    fn lock() -> std::sync::MutexGuard<'static, HeldRawMode> {
+        HELD_RAW_MODE.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Enables raw mode unless it's held already.
+    fn hold() -> io::Result<()> {
+        let mut held = HeldRawMode::lock();
+        if held.raw_mode.is_none() {
+            held.raw_mode = Some(RawMode::enable()?);
+        }
+        Ok(())
+    }
+
+    /// Restores the mode raw mode was enabled from, unless a line is being read.
+    fn release() {
+        let mut held = HeldRawMode::lock();
+        if held.lines == 0 {
+            held.raw_mode = None;
+        }
+    }
+
+    /// Restores the mode raw mode was enabled from, also while a line is being read.
+    #[cfg(unix)]
+    fn restore() {
+        HeldRawMode::lock().raw_mode = None;
+    }
+}
+
+/// Keeps raw mode held across the keys of a line until dropped.
+#[cfg(feature = "crossterm")]
+struct ReadingLine;
+
+#[cfg(feature = "crossterm")]
+impl ReadingLine {
+    fn start() -> Self {
+        HeldRawMode::lock().lines += 1;
+        ReadingLine
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl Drop for ReadingLine {
+    fn drop(&mut self) {
+        let mut held = HeldRawMode::lock();
+        held.lines -= 1;
+        if held.lines == 0 {
+            held.raw_mode = None;
+        }
+    }
+}
+
+/// Reads key events until one translates to a key, returning Ctrl-C as [`Key::CtrlC`].
+#[cfg(feature = "crossterm")]
+fn read_event_key() -> io::Result<Key> {
+    loop {
+        match crossterm::event::read()? {
+            crossterm::event::Event::Key(event) => {
+                if let Some(key) = key_from_event(event) {
+                    return Ok(key);
+                }
+            }
+            crossterm::event::Event::Resize(..) => return Ok(Key::Unknown),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "crossterm")]
+fn key_from_event(event: crossterm::event::KeyEvent) -> Option<Key> {
+    use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
+
+    if event.kind == KeyEventKind::Release {
+        return None;
+    }
+
+    let key = match event.code {
+        KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => Key::CtrlC,
+        KeyCode::Char(c)
+            if event.modifiers.contains(KeyModifiers::CONTROL) && c.is_ascii_alphabetic() =>
+        {
+            // Matches the control characters console reads for other shortcuts.
+            Key::Char(((c.to_ascii_lowercase() as u8) & 0x1f) as char)
+        }
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Escape,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::BackTab => Key::BackTab,
+        KeyCode::Left => Key::ArrowLeft,
+        KeyCode::Right => Key::ArrowRight,
+        KeyCode::Up => Key::ArrowUp,
+        KeyCode::Down => Key::ArrowDown,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::Insert => Key::Insert,
+        KeyCode::Delete => Key::Del,
+        _ => Key::Unknown,
+    };
+
+    // Shift+arrows are passed on as the sequences terminals send for them.
+    match key {
+        Key::ArrowLeft | Key::ArrowRight | Key::ArrowUp | Key::ArrowDown
+            if event.modifiers.contains(KeyModifiers::SHIFT) =>
+        {
+            Some(crate::escape::shift_arrow(&key))
+        }
+        key => Some(key),
+    }
+}
+
+/// How often crossterm backends look for stops while waiting for a key.
+#[cfg(all(feature = "crossterm", unix))]
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[cfg(feature = "crossterm")]
+impl Backend for CrosstermBackend {
+    fn read_key(&self) -> io::Result<Key> {
+        HeldRawMode::hold()?;
+
+        #[cfg(unix)]
+        {
+            use crate::job_control;
+
+            let _stop_watch = job_control::StopWatch::start();
+            // Crossterm doesn't wake up for signals, so look for stops left to the prompt
+            // in between.
+            let waiting = job_control::Waiting::start();
+            loop {
+                if job_control::take_stop() {
+                    drop(waiting);
+                    HeldRawMode::restore();
+                    job_control::suspend(self)?;
+                    return Ok(Key::Unknown);
+                }
+                match crossterm::event::poll(STOP_POLL_INTERVAL) {
+                    Ok(true) => break,
+                    Ok(false) => {}
+                    Err(err) => {
+                        HeldRawMode::release();
+                        return Err(err);
+                    }
+                }
+            }
+        }
+
+        match read_event_key() {
+            Ok(Key::CtrlC) => {
+                HeldRawMode::release();
+                Err(interrupted())
+            }
+            #[cfg(unix)]
+            Ok(Key::Char(crate::job_control::SUSPEND_KEY)) => {
+                HeldRawMode::restore();
+                crate::job_control::suspend(self)?;
+                Ok(Key::Unknown)
+            }
+            Ok(key) => Ok(key),
+            Err(err) => {
+                HeldRawMode::release();
+                Err(err)
+            }
+        }
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        let _line = ReadingLine::start();
+        read_line_keys(self, initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        let _line = ReadingLine::start();
+        read_line_keys(self, "", false)
+    }
+
+    fn key_pending(&self) -> bool {
+        HeldRawMode::hold().is_ok()
+            && crossterm::event::poll(Duration::from_millis(0)).unwrap_or(false)
+    }
+
+    fn poll_key(&self, timeout: Duration) -> io::Result<bool> {
+        HeldRawMode::hold()?;
+        #[cfg(unix)]
+        {
+            // Reading the key then suspends for a stop left to the prompt.
+            let _waiting = crate::job_control::Waiting::start();
+            let deadline = std::time::Instant::now() + timeout;
+            loop {
+                if crate::job_control::stop_pending() {
+                    return Ok(true);
+                }
+                let left = deadline.saturating_duration_since(std::time::Instant::now());
+                if crossterm::event::poll(left.min(STOP_POLL_INTERVAL))? {
+                    return Ok(true);
+                }
+                if left <= STOP_POLL_INTERVAL {
+                    return Ok(false);
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        crossterm::event::poll(timeout)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        use std::io::Write;
+
+        if self.stdout {
+            io::stdout().write_all(s.as_bytes())
+        } else {
+            io::stderr().write_all(s.as_bytes())
+        }
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        use std::io::Write;
+
+        let result = if self.stdout {
+            io::stdout().flush()
+        } else {
+            io::stderr().flush()
+        };
+        // Prompts flush once they rendered the keys read, and when they end.
+        HeldRawMode::release();
+        result
+    }
+
+    fn size(&self) -> (u16, u16) {
+        if let Some(size) = crate::deterministic::deterministic_size() {
+            return size;
+        }
+        match crossterm::terminal::size() {
+            Ok((cols, rows)) if cols > 0 && rows > 0 => (rows, cols),
+            _ => (24, 79),
+        }
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        // Raw mode may be enabled by the application, where a newline doesn't return.
+        self.write_str(&format!("{}\r\n", s))
+    }
+
+    fn begin_prompt(&self) {
+        HeldRawMode::lock().prompts += 1;
+    }
+
+    fn end_prompt(&self) {
+        let mut held = HeldRawMode::lock();
+        held.prompts -= 1;
+        // Nested prompts keep raw mode until the last of them ends.
+        if held.prompts == 0 && held.lines == 0 {
+            held.raw_mode = None;
+        }
+    }
+
+    fn is_attended(&self) -> bool {
+        use crossterm::tty::IsTty;
+
+        if self.stdout {
+            io::stdout().is_tty()
+        } else {
+            io::stderr().is_tty()
+        }
+    }
+
+    fn colors_supported(&self) -> bool {
+        if self.stdout {
+            console::colors_enabled()
+        } else {
+            console::colors_enabled_stderr()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Select, StreamBackend};
+
+    #[test]
+    fn test_scripted_keys() {
+        let screen = crate::Headless::new(24, 80);
+        let term = ScriptedKeys {
+            term: &screen,
+            keys: std::cell::RefCell::new(vec![Key::ArrowDown, Key::Enter].into_iter()),
+        };
+        let mut select = Select::new();
+        select.items(&["a", "b"]).default(0);
+        assert_eq!(select.interact_on(&term).unwrap(), 1);
+
+        let err = select.interact_on(&term).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_repeated_keys() {
+        let term = StreamBackend::new(&b"\x1b[B\x1b[B\x1b[Bx\x1b[A"[..], Vec::new());
+        let mut keys = RepeatedKeys::default();
+        assert_eq!(keys.read(&term).unwrap(), (Key::ArrowDown, 3));
+        assert_eq!(keys.read(&term).unwrap(), (Key::Char('x'), 1));
+        assert_eq!(keys.read(&term).unwrap(), (Key::ArrowUp, 1));
+    }
+
+    #[test]
+    fn test_repeats_render_once() {
+        let term = StreamBackend::new(&b"\x1b[B\x1b[B\x1b[B\r"[..], Vec::new());
+        let sel = Select::new()
+            .items(&["a", "b", "c", "d"])
+            .default(0)
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(sel, 3);
+
+        // The first frame and the one after the repeats.
+        let output = String::from_utf8(term.into_inner().1).unwrap();
+        assert_eq!(
+            output.matches("  d").count() + output.matches("> d").count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_read_line_keys() {
+        let term = StreamBackend::new(&b"ab\x7fc\x01\x1b[D\r"[..], Vec::new());
+        assert_eq!(read_line_keys(&term, "x", true).unwrap(), "xac");
+        let output = String::from_utf8(term.into_inner().1).unwrap();
+        assert!(output.starts_with("xab"));
+        assert!(output.ends_with("c\r\n"));
+
+        let term = StreamBackend::new(&b"pw\r"[..], Vec::new());
+        assert_eq!(read_line_keys(&term, "", false).unwrap(), "pw");
+        assert_eq!(term.into_inner().1, b"\r\n");
+
+        let term = StreamBackend::new(&b"ab"[..], Vec::new());
+        let err = read_line_keys(&term, "", true).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn test_crossterm_keys() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+        let key = |code, modifiers| key_from_event(KeyEvent::new(code, modifiers));
+        assert_eq!(
+            key(KeyCode::Char('a'), KeyModifiers::NONE),
+            Some(Key::Char('a'))
+        );
+        assert_eq!(
+            key(KeyCode::Char('A'), KeyModifiers::SHIFT),
+            Some(Key::Char('A'))
+        );
+        assert_eq!(
+            key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            Some(Key::CtrlC)
+        );
+        assert_eq!(
+            key(KeyCode::Char('Z'), KeyModifiers::CONTROL),
+            Some(Key::Char('\x1a'))
+        );
+        assert_eq!(key(KeyCode::Enter, KeyModifiers::NONE), Some(Key::Enter));
+        assert_eq!(
+            key(KeyCode::BackTab, KeyModifiers::SHIFT),
+            Some(Key::BackTab)
+        );
+        assert_eq!(key(KeyCode::Delete, KeyModifiers::NONE), Some(Key::Del));
+        assert_eq!(key(KeyCode::Left, KeyModifiers::NONE), Some(Key::ArrowLeft));
+        assert_eq!(
+            key(KeyCode::Left, KeyModifiers::SHIFT),
+            Some(crate::escape::shift_arrow(&Key::ArrowLeft))
+        );
+        assert_eq!(key(KeyCode::F(1), KeyModifiers::NONE), Some(Key::Unknown));
+
+        let mut release = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        assert_eq!(key_from_event(release), None);
+        release.kind = KeyEventKind::Repeat;
+        assert_eq!(key_from_event(release), Some(Key::Char('a')));
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn test_prompt_error_restores_raw_mode() {
+        // Raw mode enabled before the prompt, so dropping it leaves the terminal alone.
+        fn read_held(term: &CrosstermBackend) -> io::Result<()> {
+            let _prompt = PromptGuard::new(term);
+            HeldRawMode::lock().raw_mode = Some(RawMode { was_enabled: true });
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "failed before flushing",
+            ))?;
+            term.flush()
+        }
+
+        let term = CrosstermBackend::stderr();
+        let outer = PromptGuard::new(&term);
+        assert!(read_held(&term).is_err());
+        // The outer prompt still holds it.
+        assert!(HeldRawMode::lock().raw_mode.is_some());
+        drop(outer);
+        assert!(HeldRawMode::lock().raw_mode.is_none());
+
+        assert!(read_held(&term).is_err());
+        assert!(HeldRawMode::lock().raw_mode.is_none());
+    }
+
+    #[test]
+    fn test_capabilities_from_term_name() {
+        assert_eq!(Capabilities::from_term_name(None), Capabilities::dumb());
+        assert_eq!(
+            Capabilities::from_term_name(Some("dumb")),
+            Capabilities::dumb()
+        );
+        assert!(!Capabilities::from_term_name(Some("vt100")).hide_cursor);
+        assert!(Capabilities::from_term_name(Some("vt100")).cursor_movement);
+        assert!(!Capabilities::from_term_name(Some("linux")).hyperlinks);
+        assert!(Capabilities::from_term_name(Some("linux")).cursor_movement);
+        assert_eq!(
+            Capabilities::from_term_name(Some("xterm-256color")),
+            Capabilities::default()
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Detects whether the terminal has a light or a dark background.
+use std::env;
+
+use crate::theme::Background;
+
+/// Reads the background from `COLORFGBG`, which holds `fg;bg` or `fg;default;bg` with
+/// numbers of the 16 color palette.
+fn from_colorfgbg(val: &str) -> Option<Background> {
+    match val.rsplit(';').next()?.parse::<u8>().ok()? {
+        0..=6 | 8 => Some(Background::Dark),
+        7 | 9..=15 => Some(Background::Light),
+        _ => None,
+    }
+}
+
+/// Reads the background from an OSC 11 reply like `\x1b]11;rgb:ffff/ffff/ffff\x1b\\`.
+///
+/// Every channel has one to four hex digits.
+fn from_osc11_reply(reply: &str) -> Option<Background> {
+    let start = reply.find("rgb:")? + 4;
+    let color = reply[start..].split(['\x07', '\x1b']).next()?;
+
+    let mut channels = vec![];
+    for channel in color.split('/') {
+        if channel.is_empty() || channel.len() > 4 {
+            return None;
+        }
+        let max = (1u32 << (4 * channel.len())) - 1;
+        channels.push(u32::from_str_radix(channel, 16).ok()? as f64 / max as f64);
+    }
+    if channels.len() != 3 {
+        return None;
+    }
+
+    let luminance = 0.2126 * channels[0] + 0.7152 * channels[1] + 0.0722 * channels[2];
+    Some(if luminance > 0.5 {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+/// Asks the terminal for its background color and waits briefly for the reply.
+#[cfg(unix)]
+fn query() -> Option<Background> {
+    use std::{
+        fs::{File, OpenOptions},
+        io::{Read, Write},
+        os::unix::io::AsRawFd,
+    };
+
+    fn read_reply(tty: &mut File) -> Option<String> {
+        tty.write_all(b"\x1b]11;?\x1b\\").ok()?;
+        tty.flush().ok()?;
+
+        let mut reply = vec![];
+        let mut buf = [0; 64];
+        while !reply.ends_with(b"\x07") && !reply.ends_with(b"\x1b\\") && reply.len() < 256 {
+            let mut poll = libc::pollfd {
+                fd: tty.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // Terminals without OSC 11 support never reply.
+            if unsafe { libc::poll(&mut poll, 1, 100) } <= 0 {
+                return None;
+            }
+            match tty.read(&mut buf).ok()? {
+                0 => return None,
+                len => reply.extend_from_slice(&buf[..len]),
+            }
+        }
+
+        Some(String::from_utf8_lossy(&reply).into_owned())
+    }
+
+    // Terminals without cursor movement print escape codes literally.
+    let term = console::Term::stderr();
+    if !term.features().is_attended() || !crate::Backend::capabilities(&term).cursor_movement {
+        return None;
+    }
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let fd = tty.as_raw_fd();
+
+    // The reply must neither be echoed nor wait for a newline.
+    let mut original = unsafe { std::mem::zeroed::<libc::termios>() };
+    if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+        return None;
+    }
+    let mut raw = original;
+    unsafe { libc::cfmakeraw(&mut raw) };
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+        return None;
+    }
+
+    let reply = read_reply(&mut tty);
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+
+    from_osc11_reply(&reply?)
+}
+
+#[cfg(not(unix))]
+fn query() -> Option<Background> {
+    None
+}
+
+/// Reads the background from the `COLORFGBG` variable set by some terminals.
+pub(crate) fn from_env() -> Option<Background> {
+    env::var("COLORFGBG")
+        .ok()
+        .and_then(|val| from_colorfgbg(&val))
+}
+
+/// Detects the background, see [`Background::detect`].
+pub(crate) fn detect() -> Option<Background> {
+    // The terminal may reply too late, or not at all.
+    if crate::deterministic::is_deterministic() {
+        return from_env();
+    }
+    from_env().or_else(query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colorfgbg() {
+        assert_eq!(from_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(from_colorfgbg("0;default;15"), Some(Background::Light));
+        assert_eq!(from_colorfgbg("default"), None);
+    }
+
+    #[test]
+    fn test_osc11_reply() {
+        assert_eq!(
+            from_osc11_reply("\x1b]11;rgb:ffff/ffff/ffff\x1b\\"),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            from_osc11_reply("\x1b]11;rgb:1e/1e/2e\x07"),
+            Some(Background::Dark)
+        );
+        assert_eq!(from_osc11_reply("\x1b]11;rgb:ff/ff\x07"), None);
+    }
+}
@@ -0,0 +1,268 @@
+//! Aborts prompts waiting for keys from other threads.
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use console::Key;
+
+use crate::{backend::read_line_keys, Backend, Capabilities};
+
+/// How long a cancellable prompt waits for a key before checking its token again.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A handle aborting the prompts of the backends [bound](Self::bind) to it.
+///
+/// The token is cheap to clone, all clones cancel the same prompts. Once cancelled, a
+/// prompt waiting for a key fails with [`cancelled`] and restores the terminal like it does
+/// on Ctrl-C, and so do all prompts started on the bound backends afterwards.
+///
+/// Waiting is only interrupted on backends implementing
+/// [`Backend::poll_key`](crate::Backend::poll_key), like a [`Term`](console::Term) on Unix
+/// or the crossterm backend. On other backends the prompt notices the cancellation with the
+/// next key.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::{thread, time::Duration};
+///
+/// use console::Term;
+/// use dialoguer::{CancellationToken, Confirm};
+///
+/// let token = CancellationToken::new();
+/// let canceller = token.clone();
+/// thread::spawn(move || {
+///     // The device asked about was unplugged.
+///     thread::sleep(Duration::from_secs(5));
+///     canceller.cancel();
+/// });
+///
+/// let term = token.bind(Term::stderr());
+/// match Confirm::new().with_prompt("Format the device?").interact_on(&term) {
+///     Ok(format) => println!("Format: {}", format),
+///     Err(_) if token.is_cancelled() => println!("The device is gone"),
+///     Err(err) => return Err(err.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a token which isn't cancelled yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the prompts of the backends bound to the token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns a backend running prompts on `term` until the token is cancelled.
+    pub fn bind<B: Backend>(&self, term: B) -> Cancellable<B> {
+        Cancellable {
+            term,
+            token: self.clone(),
+        }
+    }
+}
+
+/// Returns the error prompts fail with when they are cancelled.
+///
+/// Its kind is [`Interrupted`](io::ErrorKind::Interrupted) like the one of [`interrupted`](
+/// crate::interrupted), check [`CancellationToken::is_cancelled`] to tell them apart.
+pub fn cancelled() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, "Cancelled")
+}
+
+/// A [`Backend`] whose prompts are aborted by a [`CancellationToken`], created with
+/// [`CancellationToken::bind`].
+#[derive(Debug)]
+pub struct Cancellable<B> {
+    term: B,
+    token: CancellationToken,
+}
+
+impl<B> Cancellable<B> {
+    /// Returns the backend the prompts run on.
+    pub fn into_inner(self) -> B {
+        self.term
+    }
+}
+
+impl<B: Backend> Backend for Cancellable<B> {
+    fn read_key(&self) -> io::Result<Key> {
+        let size = self.term.size();
+        loop {
+            if self.token.is_cancelled() {
+                return Err(cancelled());
+            }
+            if self.term.poll_key(POLL_INTERVAL)? {
+                break;
+            }
+            if self.term.size() != size {
+                return Ok(Key::Unknown);
+            }
+        }
+
+        let key = self.term.read_key()?;
+        if self.token.is_cancelled() {
+            return Err(cancelled());
+        }
+        Ok(key)
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        read_line_keys(self, initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        read_line_keys(self, "", false)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.term.write_str(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.term.flush()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        self.term.size()
+    }
+
+    fn key_pending(&self) -> bool {
+        self.term.key_pending()
+    }
+
+    fn poll_key(&self, timeout: Duration) -> io::Result<bool> {
+        if self.token.is_cancelled() {
+            return Ok(true);
+        }
+        self.term.poll_key(timeout)
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.term.write_line(s)
+    }
+
+    fn is_attended(&self) -> bool {
+        self.term.is_attended()
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.term.colors_supported()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.term.capabilities()
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_up(n)
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_down(n)
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_left(n)
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_right(n)
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        self.term.clear_chars(n)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        self.term.clear_line()
+    }
+
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        self.term.clear_last_lines(n)
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        self.term.clear_to_end_of_screen()
+    }
+
+    fn insert_lines(&self, n: usize) -> io::Result<()> {
+        self.term.insert_lines(n)
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        self.term.hide_cursor()
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        self.term.show_cursor()
+    }
+
+    fn enter_alternate_screen(&self) -> io::Result<()> {
+        self.term.enter_alternate_screen()
+    }
+
+    fn leave_alternate_screen(&self) -> io::Result<()> {
+        self.term.leave_alternate_screen()
+    }
+
+    fn begin_prompt(&self) {
+        self.term.begin_prompt()
+    }
+
+    fn end_prompt(&self) {
+        self.term.end_prompt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Headless, Input, Select};
+
+    #[test]
+    fn test_cancelled_before_key() {
+        let token = CancellationToken::new();
+        let term = token.bind(Headless::new(10, 40).keys(vec![Key::ArrowDown, Key::Enter]));
+        token.cancel();
+
+        let err = Select::new()
+            .items(&["a", "b"])
+            .interact_on(&term)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_not_cancelled() {
+        let token = CancellationToken::new();
+        let term = token.bind(Headless::new(10, 40).keys("hi\n".chars().map(|c| match c {
+            '\n' => Key::Enter,
+            c => Key::Char(c),
+        })));
+
+        let name: String = Input::new().interact_text_on(&term).unwrap();
+        assert_eq!(name, "hi");
+    }
+}
@@ -1,4 +1,23 @@
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
+
 /// Trait for completion handling.
 pub trait Completion {
     fn get(&self, input: &str) -> Option<String>;
 }
+
+/// The future of a completion returned by [`AsyncCompletion::get`].
+#[cfg(feature = "async")]
+pub type CompletionFuture<'a> = Pin<Box<dyn Future<Output = Option<String>> + 'a>>;
+
+/// Trait for completion handling with completions supplied by a future, like from an
+/// LSP-like backend or an HTTP API.
+///
+/// See [`Input::completion_with_async`](crate::Input::completion_with_async).
+#[cfg(feature = "async")]
+pub trait AsyncCompletion {
+    /// Returns the future of the completion of `input`.
+    ///
+    /// The future can't borrow `input`, copy it into the future instead.
+    fn get(&self, input: &str) -> CompletionFuture<'_>;
+}
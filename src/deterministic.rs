@@ -0,0 +1,103 @@
+//! Renders byte-identical output regardless of the machine and the timing of keys.
+use std::{
+    env,
+    sync::{Mutex, MutexGuard},
+};
+
+use once_cell::sync::Lazy;
+
+/// The environment variable enabling [`set_deterministic`] with a size like `80x24`, in
+/// columns by rows.
+const DETERMINISTIC_VAR: &str = "DIALOGUER_DETERMINISTIC";
+
+// Rows and columns.
+type Size = (u16, u16);
+
+// The size set with `set_deterministic`, or `None` after `clear_deterministic`.
+static FORCED: Lazy<Mutex<Option<Option<Size>>>> = Lazy::new(Default::default);
+
+static FROM_ENV: Lazy<Option<Size>> = Lazy::new(|| {
+    env::var(DETERMINISTIC_VAR)
+        .ok()
+        .and_then(|val| parse_size(&val))
+});
+
+fn lock() -> MutexGuard<'static, Option<Option<Size>>> {
+    FORCED.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Parses a size in columns by rows like `80x24` into rows and columns.
+fn parse_size(val: &str) -> Option<(u16, u16)> {
+    let mut parts = val.trim().splitn(2, ['x', 'X']);
+    let cols = parts.next()?.trim().parse().ok()?;
+    let rows = parts.next()?.trim().parse().ok()?;
+    if rows == 0 || cols == 0 {
+        return None;
+    }
+    Some((rows, cols))
+}
+
+/// Makes prompts render the same bytes on every machine and run, for tests comparing
+/// output in CI.
+///
+/// Terminals report `rows` and `columns` as their size, and everything depending on the
+/// timing of keys is switched off:
+///
+/// * Navigation keys typed ahead are handled one frame at a time instead of catching up
+///   at once.
+/// * A lone `Esc` is recognized right away, ignoring the
+///   [escape timeout](crate::set_escape_timeout).
+/// * The terminal isn't asked for its background color, see
+///   [`Background::detect`](crate::theme::Background::detect), which then only consults
+///   `COLORFGBG`.
+/// * The visual [bell](crate::theme::Theme::bell) flashes without pausing.
+///
+/// Setting the `DIALOGUER_DETERMINISTIC` environment variable to a size like `80x24`, in
+/// columns by rows, enables the mode too.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{set_deterministic, Select};
+///
+/// set_deterministic(24, 80);
+///
+/// let selection = Select::new().items(&["Yes", "No"]).interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_deterministic(rows: u16, columns: u16) {
+    *lock() = Some(Some((rows.max(1), columns.max(1))));
+}
+
+/// Switches off the mode enabled with [`set_deterministic`], even if the
+/// `DIALOGUER_DETERMINISTIC` environment variable enables it.
+pub fn clear_deterministic() {
+    *lock() = Some(None);
+}
+
+/// Returns the size in rows and columns terminals report in deterministic mode, or `None`
+/// if the mode is off.
+pub(crate) fn deterministic_size() -> Option<(u16, u16)> {
+    lock().unwrap_or(*FROM_ENV)
+}
+
+/// Returns whether prompts render deterministically, see [`set_deterministic`].
+pub(crate) fn is_deterministic() -> bool {
+    deterministic_size().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("80x24"), Some((24, 80)));
+        assert_eq!(parse_size(" 120 X 40 "), Some((40, 120)));
+        assert_eq!(parse_size("80"), None);
+        assert_eq!(parse_size("0x24"), None);
+        assert_eq!(parse_size("1"), None);
+    }
+}
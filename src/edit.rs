@@ -103,11 +103,14 @@ impl Editor {
         let cmd = iterator.next().unwrap();
         let args: Vec<&str> = iterator.collect();
 
-        let rv = process::Command::new(cmd)
-            .args(args)
-            .arg(f.path())
-            .spawn()?
-            .wait()?;
+        let rv = {
+            let _pause = crate::pause_renderer();
+            process::Command::new(cmd)
+                .args(args)
+                .arg(f.path())
+                .spawn()?
+                .wait()?
+        };
 
         if rv.success() && self.require_save && ts >= fs::metadata(f.path())?.modified()? {
             return Ok(None);
@@ -0,0 +1,464 @@
+//! Tells a lone `Esc` apart from escape sequences arriving slowly, and looks for keys typed
+//! ahead.
+use std::{
+    env, io,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+use console::Key;
+use once_cell::sync::Lazy;
+
+/// The environment variable holding the escape timeout in milliseconds, see
+/// [`set_escape_timeout`].
+const ESCAPE_TIMEOUT_VAR: &str = "DIALOGUER_ESCAPE_TIMEOUT";
+
+#[derive(Default)]
+struct State {
+    forced: Option<Duration>,
+    // A key read after an `Esc` which didn't start an escape sequence.
+    pending: Option<Key>,
+}
+
+static STATE: Lazy<Mutex<State>> = Lazy::new(Default::default);
+
+static FROM_ENV: Lazy<Duration> = Lazy::new(|| {
+    env::var(ESCAPE_TIMEOUT_VAR)
+        .ok()
+        .and_then(|val| val.trim().parse().ok())
+        .map(Duration::from_millis)
+        .unwrap_or_default()
+});
+
+fn lock() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Sets how long to wait for the rest of an escape sequence after an `Esc` read from a
+/// [`Term`](console::Term).
+///
+/// Keys like the arrows send escape sequences starting with the byte of `Esc`. Over slow
+/// connections like SSH links the rest of a sequence can arrive late, which reads as `Esc`
+/// cancelling the prompt followed by stray characters. Within the timeout the rest still
+/// counts as part of the sequence, at the cost of a lone `Esc` taking that long to be
+/// recognized.
+///
+/// The default is no wait, or the milliseconds in the `DIALOGUER_ESCAPE_TIMEOUT`
+/// environment variable, so users on high latency links can tune it themselves. It only
+/// applies on Unix, Windows consoles report keys as a whole.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
+///
+/// use dialoguer::{set_escape_timeout, Select};
+///
+/// set_escape_timeout(Duration::from_millis(100));
+///
+/// let selection = Select::new().items(&["Yes", "No"]).interact_opt()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_escape_timeout(timeout: Duration) {
+    lock().forced = Some(timeout);
+}
+
+/// Returns how long to wait for the rest of an escape sequence, see
+/// [`set_escape_timeout`].
+pub fn escape_timeout() -> Duration {
+    lock().forced.unwrap_or_else(|| *FROM_ENV)
+}
+
+/// Returns the bytes following `Esc` in a key `read` returned for an escape sequence cut
+/// short, or `None` for complete keys.
+fn cut_sequence(key: &Key) -> Option<Vec<u8>> {
+    match key {
+        Key::Escape => Some(vec![]),
+        Key::UnknownEscSeq(chars) => match chars.as_slice() {
+            ['['] | ['O'] => Some(vec![chars[0] as u8]),
+            ['[', digit] if digit.is_ascii_digit() => Some(vec![b'[', *digit as u8]),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the arrow key of a Shift+arrow sequence, like `Esc [ 1 ; 2 D` for Shift+Left.
+///
+/// Keys don't carry modifiers, so backends pass these on as unknown escape sequences.
+pub(crate) fn shifted_arrow(key: &Key) -> Option<Key> {
+    match key {
+        Key::UnknownEscSeq(chars) => match chars.as_slice() {
+            ['[', '1', ';', '2', 'A'] => Some(Key::ArrowUp),
+            ['[', '1', ';', '2', 'B'] => Some(Key::ArrowDown),
+            ['[', '1', ';', '2', 'C'] => Some(Key::ArrowRight),
+            ['[', '1', ';', '2', 'D'] => Some(Key::ArrowLeft),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the sequence of `arrow` with Shift, see [`shifted_arrow`].
+#[cfg(any(feature = "crossterm", test))]
+pub(crate) fn shift_arrow(arrow: &Key) -> Key {
+    let last = match arrow {
+        Key::ArrowUp => 'A',
+        Key::ArrowDown => 'B',
+        Key::ArrowRight => 'C',
+        _ => 'D',
+    };
+    Key::UnknownEscSeq(vec!['[', '1', ';', '2', last])
+}
+
+/// Reads the rest of a key with modifiers, which console stops reading after `Esc [ 1 ;`
+/// and returns the modifier and the final character of as keys of their own.
+fn complete_modified<F: FnMut() -> io::Result<Key>>(key: Key, read: &mut F) -> io::Result<Key> {
+    let mut chars = match key {
+        Key::UnknownEscSeq(chars) if chars == ['[', '1', ';'] => chars,
+        key => return Ok(key),
+    };
+
+    loop {
+        match read()? {
+            Key::Char(c) if c.is_ascii_digit() => chars.push(c),
+            Key::Char(c) if c.is_ascii_alphabetic() || c == '~' => {
+                chars.push(c);
+                return Ok(Key::UnknownEscSeq(chars));
+            }
+            other => {
+                lock().pending = Some(other);
+                return Ok(Key::UnknownEscSeq(chars));
+            }
+        }
+    }
+}
+
+/// Reads a key with `read`, waiting for the rest of escape sequences it returns cut short.
+pub(crate) fn read_key<F: FnMut() -> io::Result<Key>>(mut read: F) -> io::Result<Key> {
+    if let Some(key) = lock().pending.take() {
+        return Ok(key);
+    }
+
+    let key = read()?;
+    let key = complete_modified(key, &mut read)?;
+    let timeout = escape_timeout();
+    match cut_sequence(&key) {
+        Some(read_so_far)
+            if timeout > Duration::from_millis(0) && !crate::deterministic::is_deterministic() =>
+        {
+            complete(key, read_so_far, timeout)
+        }
+        _ => Ok(key),
+    }
+}
+
+/// Returns whether a key can be read from the terminal without waiting.
+pub(crate) fn key_pending() -> bool {
+    lock().pending.is_some() || tty_pending()
+}
+
+/// Waits up to `timeout` for a key on the terminal, see
+/// [`Backend::poll_key`](crate::Backend::poll_key).
+#[cfg(unix)]
+pub(crate) fn poll_key(timeout: Duration) -> io::Result<bool> {
+    if lock().pending.is_some() {
+        return Ok(true);
+    }
+    let tty = match tty::RawTty::open() {
+        Ok(tty) => tty,
+        Err(_) => return Ok(true),
+    };
+    match tty.wait(Some(timeout)) {
+        Err(err) if err.kind() == io::ErrorKind::Interrupted => Ok(false),
+        result => result,
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn poll_key(_timeout: Duration) -> io::Result<bool> {
+    Ok(true)
+}
+
+/// Waits until a key can be read from the terminal, failing with an
+/// [`Interrupted`](io::ErrorKind::Interrupted) error if a signal arrives first, or a stop
+/// is left to the prompt.
+///
+/// Console reports reads interrupted by signals like Ctrl-C, so its reads only start once
+/// this returned and don't block.
+#[cfg(unix)]
+pub(crate) fn wait_key() -> io::Result<()> {
+    if lock().pending.is_some() {
+        return Ok(());
+    }
+    match tty::RawTty::open() {
+        Ok(tty) => tty.wait(None).map(drop),
+        Err(_) => Ok(()),
+    }
+}
+
+#[cfg(unix)]
+fn tty_pending() -> bool {
+    // Typed keys only become readable in raw mode, the terminal holds them back for a
+    // whole line otherwise.
+    tty::RawTty::open()
+        .and_then(|tty| tty.poll(Duration::from_millis(0)))
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn tty_pending() -> bool {
+    false
+}
+
+/// Completes the escape sequence `key` from the bytes arriving within `timeout`, returning
+/// `key` itself if they don't.
+#[cfg(unix)]
+fn complete(key: Key, read_so_far: Vec<u8>, timeout: Duration) -> io::Result<Key> {
+    let tty = match tty::RawTty::open() {
+        Ok(tty) => tty,
+        Err(_) => return Ok(key),
+    };
+
+    let mut read_so_far = std::iter::once(b'\x1b').chain(read_so_far);
+    let mut pending = None;
+    let completed = crate::stream::parse_key(
+        || match read_so_far.next() {
+            Some(byte) => Ok(byte),
+            None => tty.read_byte(timeout),
+        },
+        |byte| pending = Some(byte),
+    );
+
+    let completed = match completed {
+        Ok(completed) => completed,
+        Err(err) if err.kind() == io::ErrorKind::TimedOut => return Ok(key),
+        Err(err) => return Err(err),
+    };
+
+    // A byte after a lone `Esc` starts the next key, which is read in full right away.
+    if let Some(byte) = pending {
+        let mut first = Some(byte);
+        let next = crate::stream::parse_key(
+            || match first.take() {
+                Some(byte) => Ok(byte),
+                None => tty.read_byte(timeout),
+            },
+            |_| {},
+        );
+        lock().pending = match next {
+            Ok(next) => Some(next),
+            Err(err) if err.kind() == io::ErrorKind::TimedOut && byte == b'\x1b' => {
+                Some(Key::Escape)
+            }
+            Err(_) => None,
+        };
+    }
+
+    Ok(completed)
+}
+
+#[cfg(not(unix))]
+fn complete(key: Key, _read_so_far: Vec<u8>, _timeout: Duration) -> io::Result<Key> {
+    Ok(key)
+}
+
+#[cfg(unix)]
+mod tty {
+    use std::{
+        fs::{File, OpenOptions},
+        io::{self, Read},
+        os::unix::io::{AsRawFd, RawFd},
+        time::Duration,
+    };
+
+    /// The controlling terminal in raw mode, restored when dropped.
+    pub(super) struct RawTty {
+        file: File,
+        original: libc::termios,
+    }
+
+    impl RawTty {
+        pub fn open() -> io::Result<Self> {
+            let file = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+            let fd = file.as_raw_fd();
+
+            let mut original = unsafe { std::mem::zeroed::<libc::termios>() };
+            if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut raw = original;
+            unsafe { libc::cfmakeraw(&mut raw) };
+            if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(RawTty { file, original })
+        }
+
+        /// Returns whether a byte arrives within `timeout`.
+        pub fn poll(&self, timeout: Duration) -> io::Result<bool> {
+            let mut poll = libc::pollfd {
+                fd: self.file.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let millis = timeout.as_millis().min(i32::MAX as u128) as i32;
+            match unsafe { libc::poll(&mut poll, 1, millis) } {
+                -1 => Err(io::Error::last_os_error()),
+                ready => Ok(ready > 0),
+            }
+        }
+
+        /// Returns whether a byte arrives within `timeout`, or waits for one without it,
+        /// also waking up for stops left to the prompt.
+        pub fn wait(&self, timeout: Option<Duration>) -> io::Result<bool> {
+            wait(
+                self.file.as_raw_fd(),
+                crate::job_control::wake_fd(),
+                timeout,
+            )
+        }
+
+        /// Reads a byte arriving within `timeout`, failing with a
+        /// [`TimedOut`](io::ErrorKind::TimedOut) error otherwise.
+        pub fn read_byte(&self, timeout: Duration) -> io::Result<u8> {
+            if !self.poll(timeout)? {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "no escape sequence",
+                ));
+            }
+
+            let mut buf = [0];
+            match (&self.file).read(&mut buf)? {
+                0 => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "tty closed")),
+                _ => Ok(buf[0]),
+            }
+        }
+    }
+
+    /// Waits up to `timeout`, or without one, returning whether `fd` is readable then.
+    ///
+    /// Fails with an [`Interrupted`](io::ErrorKind::Interrupted) error if a signal arrives
+    /// first, or if `wake` becomes readable, which signal handlers write to.
+    pub(super) fn wait(
+        fd: RawFd,
+        wake: Option<RawFd>,
+        timeout: Option<Duration>,
+    ) -> io::Result<bool> {
+        let mut polls = [fd, wake.unwrap_or(-1)].map(|fd| libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        });
+        let millis = timeout.map_or(-1, |timeout| {
+            timeout.as_millis().min(i32::MAX as u128) as i32
+        });
+        loop {
+            match unsafe { libc::poll(polls.as_mut_ptr(), 2, millis) } {
+                -1 => {
+                    let err = io::Error::last_os_error();
+                    if err.raw_os_error() != Some(libc::EAGAIN) {
+                        return Err(err);
+                    }
+                }
+                _ if polls[1].revents != 0 => {
+                    crate::job_control::drain_wake();
+                    return Err(io::ErrorKind::Interrupted.into());
+                }
+                ready => return Ok(ready > 0),
+            }
+        }
+    }
+
+    impl Drop for RawTty {
+        fn drop(&mut self) {
+            unsafe { libc::tcsetattr(self.file.as_raw_fd(), libc::TCSANOW, &self.original) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cut_sequence() {
+        assert_eq!(cut_sequence(&Key::Escape), Some(vec![]));
+        assert_eq!(
+            cut_sequence(&Key::UnknownEscSeq(vec!['['])),
+            Some(vec![b'['])
+        );
+        assert_eq!(
+            cut_sequence(&Key::UnknownEscSeq(vec!['[', '5'])),
+            Some(vec![b'[', b'5'])
+        );
+        // Alt combinations are complete.
+        assert_eq!(cut_sequence(&Key::UnknownEscSeq(vec!['x'])), None);
+        assert_eq!(cut_sequence(&Key::ArrowUp), None);
+    }
+
+    #[test]
+    fn test_complete_modified() {
+        let mut rest = vec![Key::Char('2'), Key::Char('D')].into_iter();
+        let key = complete_modified(Key::UnknownEscSeq(vec!['[', '1', ';']), &mut || {
+            Ok(rest.next().unwrap())
+        })
+        .unwrap();
+        assert_eq!(shifted_arrow(&key), Some(Key::ArrowLeft));
+        assert_eq!(key, shift_arrow(&Key::ArrowLeft));
+
+        assert_eq!(shifted_arrow(&Key::ArrowLeft), None);
+        assert_eq!(
+            shifted_arrow(&Key::UnknownEscSeq(vec!['[', '1', ';', '5', 'D'])),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wait_interrupted() {
+        use std::{os::unix::thread::JoinHandleExt, sync::mpsc, thread, time::Duration};
+
+        use crate::signal::SharedHandler;
+
+        extern "C" fn ignore(_: libc::c_int) {}
+
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let [read, write] = fds;
+
+        static URGENT_HANDLER: SharedHandler = SharedHandler::new(libc::SIGURG, ignore);
+        let _handler = URGENT_HANDLER.acquire().unwrap();
+
+        let (started, waiting) = mpsc::channel();
+        let waiter = thread::spawn(move || {
+            started.send(()).unwrap();
+            tty::wait(read, None, None)
+        });
+        waiting.recv().unwrap();
+        // A signal arriving before the waiter polls is missed, so keep sending them.
+        while !waiter.is_finished() {
+            unsafe { libc::pthread_kill(waiter.as_pthread_t(), libc::SIGURG) };
+            thread::sleep(Duration::from_millis(10));
+        }
+        let err = waiter.join().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+
+        assert_eq!(unsafe { libc::write(write, b"x".as_ptr().cast(), 1) }, 1);
+        assert!(tty::wait(read, None, None).unwrap());
+        // The stop handler wakes up waits through another pipe.
+        let mut idle = [0; 2];
+        assert_eq!(unsafe { libc::pipe(idle.as_mut_ptr()) }, 0);
+        assert!(!tty::wait(idle[0], None, Some(Duration::from_millis(0))).unwrap());
+        let err = tty::wait(idle[0], Some(read), None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+
+        for fd in fds.iter().chain(&idle) {
+            unsafe { libc::close(*fd) };
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Reports what happens in prompts to the application.
+//!
+//! An observer registered with [`set_event_observer`] learns about every frame, key and
+//! answer of the prompts, so an application can drive side effects like a preview pane of
+//! the active item, logging or sounds without reimplementing the prompts.
+use std::{
+    io,
+    sync::{mpsc, Arc, Mutex},
+    time::Duration,
+};
+
+use console::Key;
+use once_cell::sync::Lazy;
+
+use crate::{Backend, Capabilities};
+
+/// Something which happened in a prompt, see [`set_event_observer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PromptEvent {
+    /// The prompt flushed what it rendered to the terminal.
+    Rendered,
+    /// The prompt read the key. Keys of lines read as a whole, like passwords, aren't
+    /// reported.
+    KeyPressed(Key),
+    /// The cursor of a list prompt moved to the item at the index, also reported for the
+    /// item it starts out on.
+    SelectionChanged(usize),
+    /// The prompt was answered.
+    Submitted,
+    /// The prompt was quit without an answer, or interrupted with Ctrl-C.
+    Cancelled,
+}
+
+/// Trait for components following the events of prompts.
+///
+/// It is implemented for closures taking a [`PromptEvent`].
+pub trait PromptObserver: Send + Sync {
+    /// Invoked on the thread running the prompt when `event` happened.
+    fn on_event(&self, event: &PromptEvent);
+}
+
+impl<F> PromptObserver for F
+where
+    F: Fn(&PromptEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &PromptEvent) {
+        self(event)
+    }
+}
+
+static OBSERVER: Lazy<Mutex<Option<Arc<dyn PromptObserver>>>> = Lazy::new(Default::default);
+
+fn observer() -> Option<Arc<dyn PromptObserver>> {
+    OBSERVER
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+}
+
+/// Registers the observer the prompts [`Confirm`](crate::Confirm),
+/// [`Select`](crate::Select), [`MultiSelect`](crate::MultiSelect), [`Sort`](crate::Sort),
+/// [`FuzzySelect`](crate::FuzzySelect), [`Input`](crate::Input) and
+/// [`Password`](crate::Password) report their [events](PromptEvent) to.
+///
+/// Replaces a previously registered observer.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{set_event_observer, PromptEvent, Select};
+///
+/// let hosts = ["alpha", "beta", "gamma"];
+/// set_event_observer(move |event: &PromptEvent| {
+///     if let PromptEvent::SelectionChanged(idx) = *event {
+///         eprintln!("\x1b]0;{}\x07", hosts[idx]);
+///     }
+/// });
+///
+/// let host = Select::new().items(&hosts).interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_event_observer<O: PromptObserver + 'static>(observer: O) {
+    *OBSERVER.lock().unwrap_or_else(|err| err.into_inner()) = Some(Arc::new(observer));
+}
+
+/// Removes the registered event observer.
+pub fn clear_event_observer() {
+    *OBSERVER.lock().unwrap_or_else(|err| err.into_inner()) = None;
+}
+
+/// Registers an observer sending the events of prompts to the returned receiver, for
+/// applications handling them on another thread.
+///
+/// Replaces a previously registered observer. Events are dropped once the receiver is.
+pub fn event_stream() -> mpsc::Receiver<PromptEvent> {
+    let (sender, receiver) = mpsc::channel();
+    let sender = Mutex::new(sender);
+    set_event_observer(move |event: &PromptEvent| {
+        let _ = sender
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .send(event.clone());
+    });
+    receiver
+}
+
+/// Reports `event` to the registered observer.
+pub(crate) fn emit(event: PromptEvent) {
+    if let Some(observer) = observer() {
+        observer.on_event(&event);
+    }
+}
+
+/// Reports a [`SelectionChanged`](PromptEvent::SelectionChanged) event if the cursor moved
+/// from `previous` to another item.
+pub(crate) fn selection_changed(previous: Option<usize>, current: Option<usize>) {
+    match current {
+        Some(sel) if current != previous => emit(PromptEvent::SelectionChanged(sel)),
+        _ => {}
+    }
+}
+
+/// Runs `ask` on `term`, reporting its frames, keys and answer to the registered
+/// observer.
+pub(crate) fn observe<T>(
+    term: &dyn Backend,
+    mut ask: impl FnMut(&dyn Backend) -> io::Result<T>,
+) -> io::Result<T> {
+    observe_opt(term, |term| ask(term).map(Some))
+        .map(|value| value.expect("prompts without quitting answer"))
+}
+
+/// Like [`observe`] for prompts returning `None` when quit.
+pub(crate) fn observe_opt<T>(
+    term: &dyn Backend,
+    ask: impl FnOnce(&dyn Backend) -> io::Result<Option<T>>,
+) -> io::Result<Option<T>> {
+    if observer().is_none() {
+        return ask(term);
+    }
+
+    let rv = ask(&Observed { term });
+    match rv {
+        Ok(Some(_)) => emit(PromptEvent::Submitted),
+        Ok(None) => emit(PromptEvent::Cancelled),
+        Err(ref err) if err.kind() == io::ErrorKind::Interrupted => emit(PromptEvent::Cancelled),
+        Err(_) => {}
+    }
+    rv
+}
+
+/// A backend reporting the keys read and frames flushed to the registered observer.
+struct Observed<'a> {
+    term: &'a dyn Backend,
+}
+
+impl Backend for Observed<'_> {
+    fn read_key(&self) -> io::Result<Key> {
+        let key = self.term.read_key()?;
+        emit(PromptEvent::KeyPressed(key.clone()));
+        Ok(key)
+    }
+
+    // Lines are read by the terminal itself, so their keys aren't reported.
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        self.term.read_line_initial_text(initial)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        self.term.read_secure_line()
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.term.write_str(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.term.flush()?;
+        emit(PromptEvent::Rendered);
+        Ok(())
+    }
+
+    fn size(&self) -> (u16, u16) {
+        self.term.size()
+    }
+
+    fn key_pending(&self) -> bool {
+        self.term.key_pending()
+    }
+
+    fn poll_key(&self, timeout: Duration) -> io::Result<bool> {
+        self.term.poll_key(timeout)
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.term.write_line(s)
+    }
+
+    fn is_attended(&self) -> bool {
+        self.term.is_attended()
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.term.colors_supported()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.term.capabilities()
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_up(n)
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_down(n)
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_left(n)
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_right(n)
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        self.term.clear_chars(n)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        self.term.clear_line()
+    }
+
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        self.term.clear_last_lines(n)
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        self.term.clear_to_end_of_screen()
+    }
+
+    fn insert_lines(&self, n: usize) -> io::Result<()> {
+        self.term.insert_lines(n)
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        self.term.hide_cursor()
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        self.term.show_cursor()
+    }
+
+    fn enter_alternate_screen(&self) -> io::Result<()> {
+        self.term.enter_alternate_screen()
+    }
+
+    fn leave_alternate_screen(&self) -> io::Result<()> {
+        self.term.leave_alternate_screen()
+    }
+
+    fn begin_prompt(&self) {
+        self.term.begin_prompt()
+    }
+
+    fn end_prompt(&self) {
+        self.term.end_prompt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Headless, Select};
+
+    use std::thread;
+
+    #[test]
+    fn test_select_events() {
+        // Other tests run prompts on their own threads at the same time.
+        let thread = thread::current().id();
+        let events = Arc::new(Mutex::new(vec![]));
+        let recorded = events.clone();
+        set_event_observer(move |event: &PromptEvent| {
+            if thread::current().id() == thread {
+                recorded.lock().unwrap().push(event.clone());
+            }
+        });
+
+        let term = Headless::new(10, 40).keys(vec![Key::ArrowDown, Key::Enter]);
+        let sel = Select::new()
+            .items(&["a", "b"])
+            .default(0)
+            .interact_on(&term)
+            .unwrap();
+        clear_event_observer();
+        assert_eq!(sel, 1);
+
+        let events = events.lock().unwrap();
+        let significant: Vec<_> = events
+            .iter()
+            .filter(|event| **event != PromptEvent::Rendered)
+            .cloned()
+            .collect();
+        // 'Enter' is read right after 'Down' to check whether 'Down' is held.
+        assert_eq!(
+            significant,
+            vec![
+                PromptEvent::SelectionChanged(0),
+                PromptEvent::KeyPressed(Key::ArrowDown),
+                PromptEvent::KeyPressed(Key::Enter),
+                PromptEvent::SelectionChanged(1),
+                PromptEvent::Submitted,
+            ]
+        );
+        assert!(events.contains(&PromptEvent::Rendered));
+    }
+}
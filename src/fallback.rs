@@ -0,0 +1,332 @@
+//! Answers prompts without interacting with the terminal.
+use std::{
+    cell::Cell,
+    env,
+    io::{self, BufRead},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::Backend;
+
+static NON_TTY_FALLBACK: AtomicBool = AtomicBool::new(false);
+static DEFAULTS_MODE: AtomicBool = AtomicBool::new(false);
+
+/// The environment variable which enables [`set_defaults_mode`] when set to anything but
+/// `0` or nothing.
+const DEFAULTS_MODE_VAR: &str = "DIALOGUER_DEFAULTS";
+
+thread_local! {
+    // Set while a prompt which timed out resolves to its default, see `with_defaults`.
+    static DEFAULTS_SCOPE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Makes prompts read their answers as plain lines from stdin when stdin or the terminal
+/// they render to isn't a TTY.
+///
+/// Otherwise prompts wait for keys which never come, or return empty answers. With the
+/// fallback a prompt prints its question and reads one line:
+///
+/// * [`Confirm`](crate::Confirm) reads `y`, `yes`, `n` or `no`.
+/// * [`Select`](crate::Select) lists the numbered items and reads a number or the text of
+///   an item.
+/// * [`MultiSelect`](crate::MultiSelect) does the same and reads a comma separated list.
+/// * [`Input`](crate::Input) reads the text as is.
+/// * [`Password`](crate::Password) reads the password, and its confirmation on the next
+///   line.
+///
+/// Other prompts can't be answered with a line and fail with [`not_a_tty`] instead of
+/// waiting. An empty line picks the default. Answers which are invalid or missing because stdin
+/// ended fail the prompt with an error instead of asking again. Only stdin is checked on
+/// Unix, other platforms only check the terminal.
+///
+/// Terminals which can't move the cursor, like those with `TERM=dumb` in Emacs shells, get
+/// these prompts as lines too, without enabling the fallback. There invalid answers are
+/// asked for again, as a user types them.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{set_non_tty_fallback, Confirm};
+///
+/// // Lets `mytool < answers.txt` and CI jobs answer the prompts.
+/// set_non_tty_fallback(true);
+///
+/// let proceed = Confirm::new().with_prompt("Continue?").interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_non_tty_fallback(enabled: bool) {
+    NON_TTY_FALLBACK.store(enabled, Ordering::Relaxed);
+}
+
+/// Makes every prompt resolve to its default right away, without rendering anything but
+/// the report of the answer.
+///
+/// This honors `--yes` or `--non-interactive` flags without branching around every prompt.
+/// Prompts which always start out with an answer resolve to it, like the items checked up
+/// front in lists, the original order in [`Sort`](crate::Sort) or the initial text of an
+/// input without a default. Prompts without a default, like passwords, fail with an
+/// [`InvalidInput`](io::ErrorKind::InvalidInput) error. Setting the `DIALOGUER_DEFAULTS` environment variable to anything but `0`
+/// enables the mode too.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{set_defaults_mode, Confirm};
+///
+/// set_defaults_mode(std::env::args().any(|arg| arg == "--yes"));
+///
+/// let proceed = Confirm::new()
+///     .with_prompt("Continue?")
+///     .default(true)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_defaults_mode(enabled: bool) {
+    DEFAULTS_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether prompts resolve to their defaults without interaction.
+pub(crate) fn defaults_mode() -> bool {
+    DEFAULTS_SCOPE.with(Cell::get)
+        || DEFAULTS_MODE.load(Ordering::Relaxed)
+        || env::var_os(DEFAULTS_MODE_VAR).is_some_and(|val| !val.is_empty() && val != "0")
+}
+
+/// Runs `ask` with the prompts on the current thread resolving to their defaults, as in
+/// [`defaults_mode`].
+pub(crate) fn with_defaults<T>(ask: impl FnOnce() -> T) -> T {
+    struct ScopeGuard(bool);
+
+    impl Drop for ScopeGuard {
+        fn drop(&mut self) {
+            DEFAULTS_SCOPE.with(|scope| scope.set(self.0));
+        }
+    }
+
+    let _guard = ScopeGuard(DEFAULTS_SCOPE.with(|scope| scope.replace(true)));
+    ask()
+}
+
+/// Returns the error for a prompt without a default in [`defaults_mode`].
+pub(crate) fn no_default(prompt: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("No default to accept for the prompt {:?}", prompt),
+    )
+}
+
+/// Returns the error prompts which can't read their answer as a line fail with when the
+/// [non-TTY fallback](set_non_tty_fallback) is active.
+///
+/// Its kind is [`Unsupported`](io::ErrorKind::Unsupported), which tells it apart from
+/// other errors.
+pub fn not_a_tty() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Prompt needs a TTY and can't be answered from stdin",
+    )
+}
+
+/// Returns whether prompts on `term` read their answers as lines, from stdin or from `term`
+/// in [line mode](line_mode).
+pub(crate) fn is_active(term: &dyn Backend) -> bool {
+    line_mode(term) || without_tty(term)
+}
+
+/// Fails with [`not_a_tty`] if the non-TTY fallback is active for `term`, for prompts which
+/// can't read their answer as a line.
+pub(crate) fn require_tty(term: &dyn Backend) -> io::Result<()> {
+    if without_tty(term) {
+        return Err(not_a_tty());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+thread_local! {
+    // Tests run in parallel, so they can't enable the fallback for all of them.
+    static TEST_WITHOUT_TTY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns whether the non-TTY fallback is enabled, and stdin or `term` isn't a TTY.
+fn without_tty(term: &dyn Backend) -> bool {
+    #[cfg(test)]
+    if TEST_WITHOUT_TTY.with(Cell::get) {
+        return true;
+    }
+    NON_TTY_FALLBACK.load(Ordering::Relaxed) && (!term.is_attended() || !stdin_is_tty())
+}
+
+/// Runs `ask` as if the non-TTY fallback were enabled without a TTY.
+#[cfg(test)]
+pub(crate) fn test_without_tty<T>(ask: impl FnOnce() -> T) -> T {
+    TEST_WITHOUT_TTY.with(|scope| scope.set(true));
+    let rv = ask();
+    TEST_WITHOUT_TTY.with(|scope| scope.set(false));
+    rv
+}
+
+/// Returns whether `term` is attended but can't move the cursor, like with `TERM=dumb` in
+/// an Emacs shell, so prompts ask for lines typed into it instead of reading keys.
+fn line_mode(term: &dyn Backend) -> bool {
+    term.is_attended() && !term.capabilities().cursor_movement
+}
+
+/// Asks for an answer with `ask` until it is valid in [line mode](line_mode), where a user
+/// types the answers, passing it the error of the previous one. Otherwise invalid answers
+/// fail the prompt.
+pub(crate) fn ask_lines<T, F>(term: &dyn Backend, mut ask: F) -> io::Result<T>
+where
+    F: FnMut(Option<&str>) -> io::Result<T>,
+{
+    let mut error = None;
+    loop {
+        match ask(error.as_deref()) {
+            Err(err) if err.kind() == io::ErrorKind::InvalidInput && line_mode(term) => {
+                error = Some(err.to_string());
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn stdin_is_tty() -> bool {
+    unsafe { libc::isatty(libc::STDIN_FILENO) == 1 }
+}
+
+#[cfg(not(unix))]
+fn stdin_is_tty() -> bool {
+    true
+}
+
+/// Reads the answer to the question rendered last.
+///
+/// With `echo` the answer is written to `term`, since input which isn't typed doesn't show
+/// up otherwise.
+pub(crate) fn read_answer(term: &dyn Backend, echo: bool) -> io::Result<String> {
+    term.flush()?;
+
+    // The terminal echoes what is typed into it.
+    if line_mode(term) {
+        return if echo {
+            term.read_line()
+        } else {
+            term.read_secure_line()
+        };
+    }
+
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        term.write_line("")?;
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "No answer left on stdin",
+        ));
+    }
+
+    let answer = line.trim_end_matches(&['\r', '\n'][..]).to_string();
+    term.write_line(if echo { &answer } else { "" })?;
+    term.flush()?;
+
+    Ok(answer)
+}
+
+/// Returns the error for an answer which isn't one of the `expected` ones.
+pub(crate) fn invalid_answer(answer: &str, expected: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Invalid answer {:?}, expected {}", answer, expected),
+    )
+}
+
+/// Parses a yes or no answer, an empty one picks `default`.
+pub(crate) fn parse_confirm(answer: &str, default: Option<bool>) -> Option<bool> {
+    match answer.trim().to_lowercase().as_str() {
+        "" => default,
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses the number of an item, counting from one as listed, or its text into its index.
+pub(crate) fn parse_choice(answer: &str, items: &[String]) -> Option<usize> {
+    let answer = answer.trim();
+
+    match answer.parse::<usize>() {
+        Ok(number) if number >= 1 && number <= items.len() => Some(number - 1),
+        _ => items.iter().position(|item| item == answer),
+    }
+}
+
+/// Parses a comma separated list of choices, see [`parse_choice`].
+pub(crate) fn parse_choices(answer: &str, items: &[String]) -> Option<Vec<usize>> {
+    answer
+        .split(',')
+        .filter(|choice| !choice.trim().is_empty())
+        .map(|choice| parse_choice(choice, items))
+        .collect()
+}
+
+/// Returns the answer [`parse_choice`] parses into `idx`, the text of the item unless it
+/// could be mistaken for a number or a list.
+pub(crate) fn choice_answer(idx: usize, items: &[String]) -> String {
+    let item = &items[idx];
+
+    if item.contains(',') || item.trim() != item || item.parse::<usize>().is_ok() {
+        (idx + 1).to_string()
+    } else {
+        item.clone()
+    }
+}
+
+/// Returns the answer [`parse_choices`] parses into `indices`.
+pub(crate) fn choices_answer(indices: &[usize], items: &[String]) -> String {
+    let answers: Vec<String> = indices
+        .iter()
+        .map(|&idx| choice_answer(idx, items))
+        .collect();
+    answers.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_confirm() {
+        assert_eq!(parse_confirm("Yes", None), Some(true));
+        assert_eq!(parse_confirm(" n ", Some(true)), Some(false));
+        assert_eq!(parse_confirm("", Some(true)), Some(true));
+        assert_eq!(parse_confirm("", None), None);
+        assert_eq!(parse_confirm("maybe", Some(true)), None);
+    }
+
+    #[test]
+    fn test_parse_choices() {
+        let items = vec!["red".to_string(), "green".to_string(), "3".to_string()];
+
+        assert_eq!(parse_choice("2", &items), Some(1));
+        assert_eq!(parse_choice("green", &items), Some(1));
+        assert_eq!(parse_choice("3", &items), Some(2));
+        assert_eq!(parse_choice("4", &items), None);
+        assert_eq!(parse_choice("0", &items), None);
+
+        assert_eq!(parse_choices("1, green", &items), Some(vec![0, 1]));
+        assert_eq!(parse_choices("", &items), Some(vec![]));
+        assert_eq!(parse_choices("1,blue", &items), None);
+
+        assert_eq!(choice_answer(1, &items), "green");
+        assert_eq!(choice_answer(2, &items), "3");
+        assert_eq!(choices_answer(&[0, 2], &items), "red, 3");
+
+        let items = vec!["2".to_string(), "a, b".to_string()];
+        assert_eq!(choices_answer(&[0, 1], &items), "1, 2");
+        assert_eq!(parse_choices("1, 2", &items), Some(vec![0, 1]));
+    }
+}
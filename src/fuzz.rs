@@ -0,0 +1,365 @@
+//! Entry points feeding arbitrary bytes to prompts, for hunting panics with `cargo fuzz`.
+//!
+//! Every function decodes the first bytes of `data` into the terminal size and the
+//! configuration of a prompt, and the rest into keys, then runs the prompt on a
+//! [`Headless`] terminal until it is answered or the keys run out. Errors are expected and
+//! ignored, only panics are findings.
+//!
+//! ```rust,ignore
+//! // fuzz/fuzz_targets/select.rs
+//! #![no_main]
+//!
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| dialoguer::fuzz::select(data));
+//! ```
+use std::io;
+
+use console::Key;
+
+use crate::{
+    state::{
+        AutocompleteState, ConfirmState, InputState, MultiSelectState, NumberInputState,
+        SelectState, SliderState, SortState, TransferSelectState, TreeMultiSelectState,
+    },
+    theme::SimpleTheme,
+    Backend, Confirm, Headless, Input, MultiSelect, Select, Sort, StreamBackend, TreeNode,
+};
+
+/// The keys a byte below their number stands for, other bytes are typed as characters.
+const KEYS: [Key; 20] = [
+    Key::Enter,
+    Key::Escape,
+    Key::Backspace,
+    Key::Del,
+    Key::Tab,
+    Key::BackTab,
+    Key::ArrowUp,
+    Key::ArrowDown,
+    Key::ArrowLeft,
+    Key::ArrowRight,
+    Key::Home,
+    Key::End,
+    Key::PageUp,
+    Key::PageDown,
+    Key::Insert,
+    Key::Unknown,
+    Key::Char(' '),
+    Key::Char('q'),
+    Key::Char('j'),
+    Key::Char('k'),
+];
+
+/// Characters of different widths typed for bytes beyond ASCII.
+const WIDE_CHARS: [char; 4] = ['ä', '日', '🦀', '\u{301}'];
+
+/// Reads the configuration and keys from the bytes of a fuzz input.
+struct Data<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Data<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Data { bytes }
+    }
+
+    fn byte(&mut self) -> u8 {
+        match self.bytes.split_first() {
+            Some((&byte, rest)) => {
+                self.bytes = rest;
+                byte
+            }
+            None => 0,
+        }
+    }
+
+    fn flag(&mut self) -> bool {
+        self.byte() & 1 == 1
+    }
+
+    /// Returns a number below `max`, or `!0` for the byte `255` to hit unset indices.
+    fn index(&mut self, max: usize) -> usize {
+        match self.byte() {
+            255 => !0,
+            byte => byte as usize % max.max(1),
+        }
+    }
+
+    /// Returns `len` items whose widths vary with the input.
+    fn items(&mut self, len: usize) -> Vec<String> {
+        (0..len)
+            .map(|idx| {
+                let wide = WIDE_CHARS[idx % WIDE_CHARS.len()];
+                let repeat = self.byte() as usize % 40;
+                format!("{} {}", idx, wide.to_string().repeat(repeat))
+            })
+            .collect()
+    }
+
+    /// Returns a headless terminal sized by the input.
+    fn term(&mut self) -> Headless {
+        let rows = self.byte() as u16 % 30;
+        let cols = self.byte() as u16 % 100;
+        Headless::new(rows, cols).colors(self.flag())
+    }
+
+    /// Returns the rest of the input as keys.
+    fn keys(self) -> Vec<Key> {
+        self.bytes.iter().map(|&byte| key(byte)).collect()
+    }
+}
+
+fn key(byte: u8) -> Key {
+    match byte {
+        byte if (byte as usize) < KEYS.len() => KEYS[byte as usize].clone(),
+        byte if byte.is_ascii_graphic() => Key::Char(byte as char),
+        byte => Key::Char(WIDE_CHARS[byte as usize % WIDE_CHARS.len()]),
+    }
+}
+
+/// Runs a [`Select`] with fuzzed items, paging and options.
+pub fn select(data: &[u8]) {
+    let mut data = Data::new(data);
+    let term = data.term();
+    let len = data.byte() as usize % 64;
+    let items = data.items(len);
+
+    let mut select = Select::with_theme(&SimpleTheme);
+    select
+        .items(&items)
+        .default(data.index(len + 2))
+        .clear(data.flag())
+        .numbered(data.flag())
+        .horizontal_scroll(data.flag())
+        .scrolling(data.flag())
+        .max_rows(data.byte() as usize % 20);
+    if data.flag() {
+        select.item_disabled("disabled");
+    }
+    if data.flag() {
+        select.with_prompt("Pick").with_hint("hint");
+    }
+    if data.flag() {
+        let total = data.byte() as usize;
+        select.item_provider(move |start: usize, count: usize| {
+            Ok((start..(start + count).min(total))
+                .map(|idx| idx.to_string())
+                .collect())
+        });
+    }
+
+    let keys = data.keys();
+    let _ = term.keys(keys).render(|term| select.interact_on_opt(term));
+}
+
+/// Runs a [`MultiSelect`] with fuzzed items, limits and options.
+pub fn multi_select(data: &[u8]) {
+    let mut data = Data::new(data);
+    let term = data.term();
+    let len = data.byte() as usize % 64;
+    let items = data.items(len);
+    let defaults: Vec<bool> = (0..len).map(|_| data.flag()).collect();
+
+    let mut select = MultiSelect::with_theme(&SimpleTheme);
+    select
+        .items(&items)
+        .defaults(&defaults)
+        .clear(data.flag())
+        .numbered(data.flag())
+        .horizontal_scroll(data.flag())
+        .scrolling(data.flag())
+        .max_rows(data.byte() as usize % 20);
+    if data.flag() {
+        select.item_disabled("disabled");
+    }
+    if data.flag() {
+        select.min_selections(data.byte() as usize % 8);
+    }
+    if data.flag() {
+        select.max_selections(data.byte() as usize % 8);
+    }
+
+    let keys = data.keys();
+    let _ = term.keys(keys).render(|term| select.interact_on_opt(term));
+}
+
+/// Runs a [`Sort`] with fuzzed items.
+pub fn sort(data: &[u8]) {
+    let mut data = Data::new(data);
+    let term = data.term();
+    let len = data.byte() as usize % 64;
+    let items = data.items(len);
+
+    let mut sort = Sort::with_theme(&SimpleTheme);
+    sort.items(&items)
+        .clear(data.flag())
+        .scrolling(data.flag())
+        .max_rows(data.byte() as usize % 20);
+
+    let keys = data.keys();
+    let _ = term.keys(keys).render(|term| sort.interact_on_opt(term));
+}
+
+/// Runs a `FuzzySelect` with fuzzed items and search terms.
+#[cfg(feature = "fuzzy-select")]
+pub fn fuzzy_select(data: &[u8]) {
+    let mut data = Data::new(data);
+    let term = data.term();
+    let len = data.byte() as usize % 64;
+    let items = data.items(len);
+
+    let mut select = crate::FuzzySelect::with_theme(&SimpleTheme);
+    select
+        .items(&items)
+        .default(data.index(len + 2))
+        .highlight_matches(data.flag());
+
+    let keys = data.keys();
+    let _ = term.keys(keys).render(|term| select.interact_on_opt(term));
+}
+
+/// Runs a [`Confirm`] with fuzzed options.
+pub fn confirm(data: &[u8]) {
+    let mut data = Data::new(data);
+    let term = data.term();
+
+    let mut confirm = Confirm::with_theme(&SimpleTheme);
+    confirm
+        .with_prompt("Continue?")
+        .wait_for_newline(data.flag())
+        .show_default(data.flag());
+    if data.flag() {
+        confirm.default(data.flag());
+    }
+
+    let keys = data.keys();
+    let _ = term.keys(keys).render(|term| confirm.interact_on_opt(term));
+}
+
+/// Runs an [`Input`] editing fuzzed text.
+pub fn input(data: &[u8]) {
+    let mut data = Data::new(data);
+    let term = data.term();
+    let initial_len = data.byte() as usize % 8;
+    let initial: String = data.items(initial_len).concat();
+
+    let mut input = Input::<String>::with_theme(&SimpleTheme);
+    input
+        .with_prompt("Name")
+        .with_initial_text(initial)
+        .allow_empty(data.flag());
+
+    let keys = data.keys();
+    let _ = term.keys(keys).render(|term| input.interact_text_on(term));
+}
+
+/// Feeds fuzzed keys to the state machines of the [`state`](crate::state) module.
+pub fn states(data: &[u8]) {
+    let mut data = Data::new(data);
+    let len = data.byte() as usize % 64;
+    let page_size = data.byte() as usize % 20;
+    let paged = data.flag();
+    let disabled: Vec<bool> = (0..len).map(|_| data.flag()).collect();
+
+    let mut select = SelectState::new(len)
+        .default(Some(data.index(len + 2)))
+        .disabled(disabled.clone())
+        .allow_quit(data.flag())
+        .numbered(data.flag())
+        .horizontal_scroll(data.flag());
+    select.set_page(page_size, paged);
+
+    let mut multi_select = MultiSelectState::new(len)
+        .disabled(disabled)
+        .min_selections(Some(data.byte() as usize % 8))
+        .max_selections(Some(data.byte() as usize % 8))
+        .numbered(data.flag());
+    multi_select.set_page(page_size, paged);
+
+    let mut confirm = ConfirmState::new()
+        .wait_for_newline(data.flag())
+        .allow_quit(data.flag());
+    if data.flag() {
+        confirm = confirm.default(data.flag(), data.flag());
+    }
+
+    let mut sort = SortState::new(len).allow_quit(data.flag());
+    let mut input = InputState::new();
+    let bound = |byte: u8| Some(i32::from(byte as i8));
+    let mut number_input = NumberInputState::new(i32::from(data.byte() as i8))
+        .bounds(bound(data.byte()), bound(data.byte()))
+        .step(i32::from(data.byte()));
+    let min = i32::from(data.byte() as i8);
+    let mut slider = SliderState::new(min, min + i32::from(data.byte()))
+        .default(i32::from(data.byte() as i8))
+        .step(i32::from(data.byte()));
+    let mut transfer_select = TransferSelectState::new(len).defaults(&[data.index(len + 2)]);
+    let mut tree_multi_select = TreeMultiSelectState::new(vec![
+        TreeNode::new("a")
+            .child(TreeNode::new("a1").checked(data.flag()))
+            .child(TreeNode::new("a2").child(TreeNode::new("a2x")))
+            .expanded(data.flag()),
+        TreeNode::new("b"),
+    ]);
+    let mut autocomplete = AutocompleteState::new(String::new());
+
+    for key in data.keys() {
+        select.handle_key(key.clone());
+        multi_select.handle_key(key.clone());
+        confirm.handle_key(key.clone());
+        sort.handle_key(key.clone());
+        input.handle_key(key.clone());
+        number_input.handle_key(key.clone());
+        slider.handle_key(key.clone());
+        transfer_select.handle_key(key.clone());
+        tree_multi_select.handle_key(key.clone());
+        autocomplete.handle_key(key);
+        if let Some(query) = autocomplete.query() {
+            let suggestions = vec![format!("{}a", query), format!("{}b", query)];
+            autocomplete.set_suggestions(suggestions);
+        }
+    }
+}
+
+/// Decodes fuzzed bytes into keys as a [`StreamBackend`] reading them from a remote
+/// terminal does.
+pub fn stream_keys(data: &[u8]) {
+    let term = StreamBackend::new(data, Vec::new());
+    loop {
+        match term.read_key() {
+            Err(err) if err.kind() != io::ErrorKind::Interrupted => break,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_points() {
+        let inputs: [&[u8]; 4] = [
+            &[],
+            &[10, 40, 0, 3, 1, 2, 3, 255, 7, 7, 6, 0],
+            &[5, 20, 1, 0, 16, 16, 9, 9, 12, 13, 0, 255, 0],
+            &[24, 80, 0, 2, 30, 30, 1, 1, 1, 1, 1, 1, 1, 255, 1, 2, 3, 0],
+        ];
+        for data in inputs.iter() {
+            select(data);
+            multi_select(data);
+            sort(data);
+            confirm(data);
+            input(data);
+            states(data);
+            stream_keys(data);
+            #[cfg(feature = "fuzzy-select")]
+            fuzzy_select(data);
+        }
+    }
+
+    #[cfg(feature = "fuzzy-select")]
+    #[test]
+    fn test_fuzzy_select_enter_without_selection() {
+        // Three items, no default and 'Enter' right away.
+        fuzzy_select(&[10, 40, 0, 3, 1, 1, 1, 255, 0, 0]);
+    }
+}
@@ -0,0 +1,295 @@
+//! Controls prompts from other threads while they wait for the operator.
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::Duration,
+};
+
+use console::Key;
+
+use crate::{backend::read_line_keys, cancelled, Backend, Capabilities};
+
+/// How long a prompt with a handle waits for a key before checking the handle again.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Default)]
+struct Shared {
+    cancelled: AtomicBool,
+    redraws: AtomicUsize,
+    prompt: Mutex<Option<String>>,
+}
+
+/// A handle controlling a prompt from other threads, split off with `into_parts` of
+/// [`Select`](crate::Select), [`MultiSelect`](crate::MultiSelect) or
+/// [`Confirm`](crate::Confirm).
+///
+/// The handle is cheap to clone and can be sent to other threads, like the ones of a
+/// long-lived daemon noticing that a question became moot or needs other words. It
+/// controls the prompt every time it is interacted with.
+///
+/// Waiting is only interrupted on backends implementing
+/// [`Backend::poll_key`](crate::Backend::poll_key). On other backends the prompt notices
+/// the requests with the next key.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::{thread, time::Duration};
+///
+/// use dialoguer::Select;
+///
+/// let mut select = Select::new();
+/// select
+///     .with_prompt("Restart the worker?")
+///     .items(&["Now", "Later"]);
+/// let (select, handle) = select.into_parts();
+///
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_secs(60));
+///     handle.set_prompt("Restart the worker? (waiting for a minute already)");
+/// });
+///
+/// let when = select.interact()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PromptHandle {
+    shared: Arc<Shared>,
+}
+
+impl PromptHandle {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders the prompt again, like after output of other threads messed it up.
+    pub fn redraw(&self) {
+        self.shared.redraws.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Aborts the prompt, which fails with [`cancelled`] and restores the terminal like it
+    /// does on Ctrl-C. So do later interactions.
+    pub fn cancel(&self) {
+        self.shared.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether the prompt was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.shared.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Replaces the text of the prompt, rendering it again.
+    pub fn set_prompt<S: Into<String>>(&self, prompt: S) {
+        *self.lock_prompt() = Some(prompt.into());
+        self.redraw();
+    }
+
+    fn lock_prompt(&self) -> MutexGuard<'_, Option<String>> {
+        self.shared
+            .prompt
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Returns the prompt text set through `handle`, if any.
+pub(crate) fn prompt(handle: Option<&PromptHandle>) -> Option<String> {
+    handle.and_then(|handle| handle.lock_prompt().clone())
+}
+
+/// Replaces `prompt` with the text set through `handle`, returning whether it changed.
+pub(crate) fn update_prompt(handle: Option<&PromptHandle>, prompt: &mut Option<String>) -> bool {
+    match self::prompt(handle) {
+        Some(text) if prompt.as_ref() != Some(&text) => {
+            *prompt = Some(text);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Runs `ask` on `term`, until the prompt is cancelled through `handle`.
+///
+/// Reading a key returns [`Key::Unknown`] once a redraw was requested, which prompts
+/// render again on.
+pub(crate) fn ask<T>(
+    term: &dyn Backend,
+    handle: Option<&PromptHandle>,
+    ask: impl FnOnce(&dyn Backend) -> io::Result<T>,
+) -> io::Result<T> {
+    match handle {
+        Some(handle) => {
+            if handle.is_cancelled() {
+                return Err(cancelled());
+            }
+            ask(&Handled { term, handle })
+        }
+        None => ask(term),
+    }
+}
+
+/// A backend controlled by a [`PromptHandle`].
+struct Handled<'a> {
+    term: &'a dyn Backend,
+    handle: &'a PromptHandle,
+}
+
+impl Backend for Handled<'_> {
+    fn read_key(&self) -> io::Result<Key> {
+        let size = self.term.size();
+        let redraws = self.handle.shared.redraws.load(Ordering::SeqCst);
+        loop {
+            if self.handle.is_cancelled() {
+                return Err(cancelled());
+            }
+            if self.handle.shared.redraws.load(Ordering::SeqCst) != redraws {
+                return Ok(Key::Unknown);
+            }
+            if self.term.poll_key(POLL_INTERVAL)? {
+                break;
+            }
+            if self.term.size() != size {
+                return Ok(Key::Unknown);
+            }
+        }
+
+        let key = self.term.read_key()?;
+        if self.handle.is_cancelled() {
+            return Err(cancelled());
+        }
+        Ok(key)
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        read_line_keys(self, initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        read_line_keys(self, "", false)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.term.write_str(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.term.flush()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        self.term.size()
+    }
+
+    fn key_pending(&self) -> bool {
+        self.term.key_pending()
+    }
+
+    fn poll_key(&self, timeout: Duration) -> io::Result<bool> {
+        self.term.poll_key(timeout)
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.term.write_line(s)
+    }
+
+    fn is_attended(&self) -> bool {
+        self.term.is_attended()
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.term.colors_supported()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.term.capabilities()
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_up(n)
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_down(n)
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_left(n)
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_right(n)
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        self.term.clear_chars(n)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        self.term.clear_line()
+    }
+
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        self.term.clear_last_lines(n)
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        self.term.clear_to_end_of_screen()
+    }
+
+    fn insert_lines(&self, n: usize) -> io::Result<()> {
+        self.term.insert_lines(n)
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        self.term.hide_cursor()
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        self.term.show_cursor()
+    }
+
+    fn enter_alternate_screen(&self) -> io::Result<()> {
+        self.term.enter_alternate_screen()
+    }
+
+    fn leave_alternate_screen(&self) -> io::Result<()> {
+        self.term.leave_alternate_screen()
+    }
+
+    fn begin_prompt(&self) {
+        self.term.begin_prompt()
+    }
+
+    fn end_prompt(&self) {
+        self.term.end_prompt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Confirm, Headless};
+
+    #[test]
+    fn test_set_prompt_and_cancel() {
+        let mut confirm = Confirm::new();
+        confirm.with_prompt("Restart?").default(true);
+        let (confirm, handle) = confirm.into_parts();
+
+        handle.set_prompt("Restart now?");
+        let term = Headless::new(10, 40).keys(vec![Key::Char('y')]);
+        assert!(confirm.interact_on(&term).unwrap());
+        assert!(term.screen().contains("Restart now?"));
+
+        handle.cancel();
+        let term = Headless::new(10, 40).keys(vec![Key::Char('y')]);
+        let err = confirm.interact_on(&term).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert!(handle.is_cancelled());
+    }
+}
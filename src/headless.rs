@@ -0,0 +1,483 @@
+//! Renders prompts into strings without a terminal.
+use std::{
+    collections::VecDeque,
+    io, mem,
+    sync::{Mutex, MutexGuard},
+};
+
+use console::Key;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    backend::{read_line_keys, Backend},
+    width::{clusters, escape_parts},
+};
+
+/// A [`Backend`] rendering into an in-memory screen, for showing what a prompt looks like
+/// without a live terminal.
+///
+/// Prompts run on it as on any terminal: the keys given up front are fed to them, and once
+/// they run out [`render`](Self::render) returns the frame the prompt waits with. This
+/// makes it possible to show a prompt in any state, like in a web frontend, a recorder or
+/// generated documentation, with exactly the output of the theme and the terminal size.
+///
+/// The screen follows a terminal which wraps long lines and scrolls. Styles are kept as
+/// ANSI codes in front of the text they apply to unless colors are turned off with
+/// [`colors`](Self::colors). The styles of [`console`] only emit codes while its colors are
+/// enabled, which they aren't when stderr isn't a terminal, so enable them with
+/// [`console::set_colors_enabled_stderr`] for colored frames in that case.
+///
+/// ## Example usage
+///
+/// ```rust
+/// # fn test() -> std::io::Result<()> {
+/// use dialoguer::{console::Key, theme::SimpleTheme, Headless, Select};
+///
+/// let frame = Headless::new(24, 40)
+///     .colors(false)
+///     .keys(vec![Key::ArrowDown])
+///     .render(|term| {
+///         Select::with_theme(&SimpleTheme)
+///             .with_prompt("Pick a color")
+///             .items(&["Red", "Green", "Blue"])
+///             .default(0)
+///             .interact_on(term)
+///     })?;
+///
+/// assert_eq!(frame, "Pick a color:\n  Red\n> Green\n  Blue");
+/// # Ok(())
+/// # }
+/// # test().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Headless {
+    rows: u16,
+    cols: u16,
+    colors: bool,
+    keys: Mutex<VecDeque<Key>>,
+    screen: Mutex<Screen>,
+    // The screen at the moment the keys ran out.
+    frame: Mutex<Option<String>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+impl Headless {
+    /// Creates a headless terminal of `rows` rows and `cols` columns.
+    pub fn new(rows: u16, cols: u16) -> Self {
+        Headless {
+            rows: rows.max(1),
+            cols: cols.max(1),
+            colors: true,
+            keys: Mutex::new(VecDeque::new()),
+            screen: Mutex::new(Screen::default()),
+            frame: Mutex::new(None),
+        }
+    }
+
+    /// Sets whether colors are rendered.
+    ///
+    /// The default is true, the frame then holds the ANSI codes of the styles.
+    pub fn colors(mut self, val: bool) -> Self {
+        self.colors = val;
+        self
+    }
+
+    /// Adds keys fed to the prompt before its frame is taken.
+    pub fn keys<I: IntoIterator<Item = Key>>(self, keys: I) -> Self {
+        lock(&self.keys).extend(keys);
+        self
+    }
+
+    /// Runs `interact` and returns the frame the prompt waits with once the keys run out.
+    ///
+    /// If the prompt completes before that, the screen it leaves behind is returned instead.
+    /// Trailing blanks are trimmed from every line and blank lines from the end.
+    pub fn render<T, F>(&self, interact: F) -> io::Result<String>
+    where
+        F: FnOnce(&Self) -> io::Result<T>,
+    {
+        let result = interact(self);
+        if let Some(frame) = lock(&self.frame).take() {
+            return Ok(frame);
+        }
+
+        result.map(|_| self.screen())
+    }
+
+    /// Returns the current contents of the screen.
+    pub fn screen(&self) -> String {
+        lock(&self.screen).contents()
+    }
+
+    fn with_screen<F: FnOnce(&mut Screen, usize, usize)>(&self, f: F) -> io::Result<()> {
+        f(
+            &mut lock(&self.screen),
+            self.rows as usize,
+            self.cols as usize,
+        );
+        Ok(())
+    }
+}
+
+impl Backend for Headless {
+    fn read_key(&self) -> io::Result<Key> {
+        if let Some(key) = lock(&self.keys).pop_front() {
+            return Ok(key);
+        }
+
+        *lock(&self.frame) = Some(self.screen());
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "no more keys for the headless terminal",
+        ))
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        read_line_keys(self, initial, true)
+    }
+
+    fn key_pending(&self) -> bool {
+        !lock(&self.keys).is_empty()
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        read_line_keys(self, "", false)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.with_screen(|screen, _, cols| screen.write(s, cols))
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> (u16, u16) {
+        (self.rows, self.cols)
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.colors
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        self.with_screen(|screen, rows, _| {
+            let top = screen.lines.len().saturating_sub(rows);
+            screen.row = screen.row.saturating_sub(n).max(top);
+        })
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        self.with_screen(|screen, rows, _| {
+            let bottom = screen.lines.len().max(rows) - 1;
+            screen.row = (screen.row + n).min(bottom.max(screen.row));
+        })
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        self.with_screen(|screen, _, _| screen.col = screen.col.saturating_sub(n))
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        self.with_screen(|screen, _, cols| screen.col = (screen.col + n).min(cols - 1))
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        self.with_screen(|screen, _, _| {
+            screen.col = screen.col.saturating_sub(n);
+            let col = screen.col;
+            screen.line().truncate(col);
+        })
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        self.with_screen(|screen, _, _| {
+            screen.col = 0;
+            *screen.line() = Line::default();
+        })
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        self.with_screen(|screen, _, _| {
+            screen.col = 0;
+            let row = screen.row;
+            screen.lines.truncate(row);
+        })
+    }
+
+    fn insert_lines(&self, n: usize) -> io::Result<()> {
+        self.with_screen(|screen, _, _| {
+            let row = screen.row.min(screen.lines.len());
+            for _ in 0..n {
+                screen.lines.insert(row, Line::default());
+            }
+        })
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn enter_alternate_screen(&self) -> io::Result<()> {
+        self.with_screen(|screen, _, _| {
+            let primary = mem::take(screen);
+            screen.primary = Some(Box::new(primary));
+        })
+    }
+
+    fn leave_alternate_screen(&self) -> io::Result<()> {
+        self.with_screen(|screen, _, _| {
+            if let Some(primary) = screen.primary.take() {
+                *screen = *primary;
+            }
+        })
+    }
+}
+
+/// A cell of the screen, a char with the zero-width chars following it and the ANSI codes
+/// in front of it. The second column of a wide char is a cell without text.
+#[derive(Clone, Debug, Default)]
+struct Cell {
+    codes: String,
+    text: String,
+}
+
+#[derive(Debug, Default)]
+struct Line {
+    cells: Vec<Cell>,
+    // ANSI codes written after the last cell, like a reset.
+    trailing_codes: String,
+}
+
+impl Line {
+    fn truncate(&mut self, col: usize) {
+        self.cells.truncate(col);
+        self.trailing_codes.clear();
+    }
+
+    fn contents(&self) -> String {
+        let mut contents = String::new();
+        for cell in &self.cells {
+            contents.push_str(&cell.codes);
+            contents.push_str(&cell.text);
+        }
+        let len = contents.trim_end_matches(' ').len();
+        contents.truncate(len);
+        contents.push_str(&self.trailing_codes);
+        contents
+    }
+}
+
+#[derive(Debug, Default)]
+struct Screen {
+    lines: Vec<Line>,
+    row: usize,
+    col: usize,
+    // ANSI codes waiting for the next char written.
+    codes: String,
+    // The primary screen while the alternate one is shown.
+    primary: Option<Box<Screen>>,
+}
+
+impl Screen {
+    fn line(&mut self) -> &mut Line {
+        if self.lines.len() <= self.row {
+            self.lines.resize_with(self.row + 1, Line::default);
+        }
+        &mut self.lines[self.row]
+    }
+
+    fn write(&mut self, s: &str, cols: usize) {
+        for (part, is_ansi) in escape_parts(s) {
+            if is_ansi {
+                // Only styles are kept, other codes like the visual bell or links don't show.
+                if part.starts_with("\x1b[") && part.ends_with('m') {
+                    self.codes.push_str(part);
+                }
+                continue;
+            }
+
+            for (_, cluster) in clusters(part) {
+                match cluster.chars().next() {
+                    Some('\n') => {
+                        self.flush_codes();
+                        self.row += 1;
+                        self.col = 0;
+                    }
+                    Some('\r') => self.col = 0,
+                    Some(c) if c.is_control() => {}
+                    _ => self.put(cluster, cluster.width(), cols),
+                }
+            }
+        }
+
+        self.flush_codes();
+    }
+
+    fn flush_codes(&mut self) {
+        if !self.codes.is_empty() {
+            let codes = mem::take(&mut self.codes);
+            self.line().trailing_codes.push_str(&codes);
+        }
+    }
+
+    fn put(&mut self, text: &str, width: usize, cols: usize) {
+        if width == 0 {
+            // Zero width clusters like lone combining marks join the cell before them.
+            let col = self.col;
+            if let Some(cell) = col
+                .checked_sub(1)
+                .and_then(|col| self.line().cells.get_mut(col))
+            {
+                cell.text.push_str(text);
+            }
+            return;
+        }
+
+        if self.col + width > cols && self.col > 0 {
+            self.flush_codes();
+            self.row += 1;
+            self.col = 0;
+        }
+
+        let codes = mem::take(&mut self.codes);
+        let col = self.col;
+        let line = self.line();
+        if line.cells.len() < col + width {
+            let blank = Cell {
+                codes: String::new(),
+                text: " ".to_string(),
+            };
+            line.cells.resize(col + width, blank);
+        }
+
+        line.cells[col] = Cell {
+            codes,
+            text: text.to_string(),
+        };
+        for cell in &mut line.cells[col + 1..col + width] {
+            *cell = Cell::default();
+        }
+        self.col += width;
+    }
+
+    fn contents(&self) -> String {
+        let mut lines: Vec<String> = self.lines.iter().map(Line::contents).collect();
+        while lines
+            .last()
+            .is_some_and(|line| console::strip_ansi_codes(line).is_empty())
+        {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{theme::SimpleTheme, Confirm, Input, MultiSelect, Select};
+
+    fn headless() -> Headless {
+        Headless::new(24, 20).colors(false)
+    }
+
+    #[test]
+    fn test_screen_wraps_and_overwrites() {
+        let term = headless();
+        term.write_line("first line").unwrap();
+        term.write_str("a very long line which wraps").unwrap();
+        assert_eq!(term.screen(), "first line\na very long line whi\nch wraps");
+
+        term.clear_line().unwrap();
+        term.move_cursor_up(1).unwrap();
+        term.clear_line().unwrap();
+        term.write_str("日本x").unwrap();
+        assert_eq!(term.screen(), "first line\n日本x");
+    }
+
+    #[test]
+    fn test_screen_zero_width_clusters() {
+        let term = headless();
+        term.write_line("\u{301}a").unwrap();
+        term.write_str("日").unwrap();
+        term.write_str("\u{301}").unwrap();
+        assert_eq!(term.screen(), "a\n日\u{301}");
+    }
+
+    #[test]
+    fn test_render_waiting_frame() {
+        let frame = headless()
+            .keys(vec![Key::ArrowDown, Key::Char(' ')])
+            .render(|term| {
+                MultiSelect::with_theme(&SimpleTheme)
+                    .with_prompt("Pick")
+                    .items(&["a", "b"])
+                    .interact_on(term)
+            })
+            .unwrap();
+        assert_eq!(frame, "Pick:\n  [ ] a\n> [x] b");
+    }
+
+    #[test]
+    fn test_render_completed_prompt() {
+        let screen = headless()
+            .keys(vec![Key::Char('y')])
+            .render(|term| {
+                Confirm::with_theme(&SimpleTheme)
+                    .with_prompt("Ok?")
+                    .interact_on(term)
+            })
+            .unwrap();
+        assert_eq!(screen, "Ok? yes");
+
+        let screen = headless()
+            .keys("hi\r".chars().map(|c| match c {
+                '\r' => Key::Enter,
+                c => Key::Char(c),
+            }))
+            .render(|term| {
+                Input::<String>::with_theme(&SimpleTheme)
+                    .with_prompt("Name")
+                    .interact_text_on(term)
+            })
+            .unwrap();
+        assert_eq!(screen, "Name: hi");
+    }
+
+    struct Bold;
+
+    impl crate::theme::Theme for Bold {
+        fn format_select_prompt_item(
+            &self,
+            f: &mut dyn std::fmt::Write,
+            text: &str,
+            _active: bool,
+        ) -> std::fmt::Result {
+            write!(f, "\x1b[1m{}\x1b[0m", text)
+        }
+    }
+
+    #[test]
+    fn test_render_keeps_styles() {
+        let render = |colors| {
+            Headless::new(24, 20)
+                .colors(colors)
+                .render(|term| {
+                    Select::with_theme(&Bold)
+                        .items(&["a", "b"])
+                        .interact_on(term)
+                })
+                .unwrap()
+        };
+        assert_eq!(render(true), "\x1b[1ma\x1b[0m\n\x1b[1mb\x1b[0m");
+        assert_eq!(render(false), "a\nb");
+    }
+}
@@ -0,0 +1,236 @@
+//! Suspends prompts with Ctrl-Z and renders them from scratch once the process continues.
+//!
+//! Terminals in raw mode don't turn Ctrl-Z into `SIGTSTP`, so backends reading it as a key
+//! stop the process themselves with [`suspend`], after restoring the terminal. While a key
+//! is read a [`StopWatch`] also catches `SIGTSTP` sent from elsewhere: the handler can't
+//! restore the terminal of the prompt, so it leaves stopping to the backend waiting for
+//! the key, which [takes the stop](take_stop) and suspends just the same. Either way the
+//! frames rendered before are above the output of the shell afterwards, so renderers count
+//! the [`resumes`] to start over below it.
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(unix)]
+use std::{
+    io,
+    os::unix::io::RawFd,
+    sync::{
+        atomic::{AtomicBool, AtomicI32},
+        Once,
+    },
+};
+
+#[cfg(unix)]
+use crate::{
+    signal::{HandlerGuard, SharedHandler},
+    Backend,
+};
+
+/// The control character terminals in raw mode read for Ctrl-Z.
+#[cfg(unix)]
+pub(crate) const SUSPEND_KEY: char = '\x1a';
+
+static RESUMES: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns how often the process continued after being stopped within a prompt.
+pub(crate) fn resumes() -> usize {
+    RESUMES.load(Ordering::SeqCst)
+}
+
+/// Stops the process like Ctrl-Z in a shell, showing the cursor of `term` first.
+///
+/// Returns once the process continues. Nothing happens if the application ignores or
+/// handles `SIGTSTP` itself.
+#[cfg(unix)]
+pub(crate) fn suspend(term: &dyn Backend) -> io::Result<()> {
+    unsafe {
+        let mut current: libc::sigaction = std::mem::zeroed();
+        if libc::sigaction(libc::SIGTSTP, std::ptr::null(), &mut current) != 0 {
+            return Ok(());
+        }
+        if current.sa_sigaction != libc::SIG_DFL && !STOP_HANDLER.handles(&current) {
+            return Ok(());
+        }
+    }
+
+    term.show_cursor()?;
+    term.flush()?;
+    stop();
+    RESUMES.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Stops the process with the default action of `SIGTSTP`, returning once it continues.
+///
+/// Only makes async-signal-safe calls, so the handler can stop with it as well.
+#[cfg(unix)]
+fn stop() {
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        let mut handler: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = libc::SIG_DFL;
+        libc::sigaction(libc::SIGTSTP, &action, &mut handler);
+
+        // Within the handler the signal is blocked until it returns.
+        let mut mask: libc::sigset_t = std::mem::zeroed();
+        libc::sigemptyset(&mut mask);
+        libc::sigaddset(&mut mask, libc::SIGTSTP);
+        libc::sigprocmask(libc::SIG_UNBLOCK, &mask, std::ptr::null_mut());
+        libc::raise(libc::SIGTSTP);
+
+        libc::sigaction(libc::SIGTSTP, &handler, std::ptr::null_mut());
+    }
+}
+
+/// How many backends are waiting for a key, and take stops from the handler.
+#[cfg(unix)]
+static WAITING: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(unix)]
+static STOP_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// The ends of the pipe the handler wakes waiting backends through, -1 until created.
+#[cfg(unix)]
+static WAKE_READ: AtomicI32 = AtomicI32::new(-1);
+#[cfg(unix)]
+static WAKE_WRITE: AtomicI32 = AtomicI32::new(-1);
+
+/// Marks a backend as waiting for a key until dropped, so stops are left to it.
+#[cfg(unix)]
+pub(crate) struct Waiting(());
+
+#[cfg(unix)]
+impl Waiting {
+    pub fn start() -> Self {
+        WAITING.fetch_add(1, Ordering::SeqCst);
+        Waiting(())
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        WAITING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Returns whether a stop was left to a waiting backend, without taking it.
+#[cfg(unix)]
+pub(crate) fn stop_pending() -> bool {
+    STOP_PENDING.load(Ordering::SeqCst)
+}
+
+/// Takes a stop left to waiting backends, returning whether there was one to
+/// [`suspend`] for.
+#[cfg(unix)]
+pub(crate) fn take_stop() -> bool {
+    STOP_PENDING.swap(false, Ordering::SeqCst)
+}
+
+/// Returns the end of the pipe becoming readable when a stop is left to waiting backends,
+/// which they poll along with the terminal.
+///
+/// The signal may be delivered to any thread, so it doesn't necessarily interrupt the wait.
+#[cfg(unix)]
+pub(crate) fn wake_fd() -> Option<RawFd> {
+    match WAKE_READ.load(Ordering::SeqCst) {
+        -1 => None,
+        fd => Some(fd),
+    }
+}
+
+/// Empties the wake pipe after a waiting backend woke up.
+#[cfg(unix)]
+pub(crate) fn drain_wake() {
+    if let Some(fd) = wake_fd() {
+        let mut buf = [0u8; 16];
+        while unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) } > 0 {}
+    }
+}
+
+#[cfg(unix)]
+fn create_wake_pipe() {
+    static CREATE: Once = Once::new();
+    CREATE.call_once(|| {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return;
+        }
+        for fd in fds {
+            unsafe {
+                let flags = libc::fcntl(fd, libc::F_GETFL);
+                libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+                libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC);
+            }
+        }
+        WAKE_READ.store(fds[0], Ordering::SeqCst);
+        WAKE_WRITE.store(fds[1], Ordering::SeqCst);
+    });
+}
+
+/// Catches `SIGTSTP` while reading a key, until dropped.
+///
+/// Like the resize watch, the handler is shared by all watches and only installed if
+/// `SIGTSTP` has its default disposition. Renderers keep a watch for the whole prompt, so
+/// the handler isn't installed again for every key.
+#[cfg(unix)]
+pub(crate) struct StopWatch {
+    _handler: Option<HandlerGuard>,
+}
+
+#[cfg(unix)]
+static STOP_HANDLER: SharedHandler = SharedHandler::new(libc::SIGTSTP, on_stop);
+
+#[cfg(unix)]
+extern "C" fn on_stop(_: std::os::raw::c_int) {
+    if WAITING.load(Ordering::SeqCst) > 0 {
+        STOP_PENDING.store(true, Ordering::SeqCst);
+        let fd = WAKE_WRITE.load(Ordering::SeqCst);
+        if fd != -1 {
+            unsafe { libc::write(fd, b"\0".as_ptr() as *const libc::c_void, 1) };
+        }
+        return;
+    }
+
+    // Nothing reads a key, like while the prompt renders, so stop right away. The prompt
+    // starts over below the output of the shell once it renders again.
+    stop();
+    RESUMES.fetch_add(1, Ordering::SeqCst);
+}
+
+#[cfg(unix)]
+impl StopWatch {
+    pub fn start() -> Self {
+        create_wake_pipe();
+        StopWatch {
+            _handler: STOP_HANDLER.acquire(),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_left_to_waiting_backend() {
+        let _watch = StopWatch::start();
+        let before = resumes();
+
+        let waiting = Waiting::start();
+        on_stop(libc::SIGTSTP);
+        drop(waiting);
+
+        // Nothing stopped yet.
+        assert_eq!(resumes(), before);
+        assert!(stop_pending());
+        let mut byte = 0u8;
+        let fd = wake_fd().unwrap();
+        assert_eq!(
+            unsafe { libc::read(fd, &mut byte as *mut u8 as *mut libc::c_void, 1) },
+            1
+        );
+        drain_wake();
+
+        assert!(take_stop());
+        assert!(!take_stop());
+    }
+}
@@ -0,0 +1,102 @@
+//! Writes keys as text and reads them back.
+use console::Key;
+
+/// Returns the keys of `script`, where text is typed as it is and names in angle brackets
+/// stand for other keys, or a message naming what is wrong with it.
+pub(crate) fn parse_keys(script: &str) -> Result<Vec<Key>, String> {
+    let mut keys = vec![];
+    let mut rest = script;
+
+    while let Some(c) = rest.chars().next() {
+        if c != '<' {
+            keys.push(match c {
+                '\n' => Key::Enter,
+                '\t' => Key::Tab,
+                c => Key::Char(c),
+            });
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let end = match rest.find('>') {
+            Some(end) => end,
+            None => return Err(format!("missing `>` in key script {:?}", script)),
+        };
+        keys.push(match &rest[1..end] {
+            "enter" => Key::Enter,
+            "esc" => Key::Escape,
+            "tab" => Key::Tab,
+            "backtab" => Key::BackTab,
+            "backspace" => Key::Backspace,
+            "del" => Key::Del,
+            "insert" => Key::Insert,
+            "up" => Key::ArrowUp,
+            "down" => Key::ArrowDown,
+            "left" => Key::ArrowLeft,
+            "right" => Key::ArrowRight,
+            "home" => Key::Home,
+            "end" => Key::End,
+            "pageup" => Key::PageUp,
+            "pagedown" => Key::PageDown,
+            "ctrl-c" => Key::CtrlC,
+            "unknown" => Key::Unknown,
+            "lt" => Key::Char('<'),
+            name => return Err(format!("unknown key <{}> in key script", name)),
+        });
+        rest = &rest[end + 1..];
+    }
+
+    Ok(keys)
+}
+
+/// Returns the name of `key` in a script, see [`parse_keys`].
+///
+/// Keys without a name, like unknown escape sequences, are named `<unknown>`.
+pub(crate) fn key_name(key: &Key) -> String {
+    let name = match key {
+        Key::Char('<') => "lt",
+        Key::Char(c) => return c.to_string(),
+        Key::Enter => "enter",
+        Key::Escape => "esc",
+        Key::Tab => "tab",
+        Key::BackTab => "backtab",
+        Key::Backspace => "backspace",
+        Key::Del => "del",
+        Key::Insert => "insert",
+        Key::ArrowUp => "up",
+        Key::ArrowDown => "down",
+        Key::ArrowLeft => "left",
+        Key::ArrowRight => "right",
+        Key::Home => "home",
+        Key::End => "end",
+        Key::PageUp => "pageup",
+        Key::PageDown => "pagedown",
+        Key::CtrlC => "ctrl-c",
+        _ => "unknown",
+    };
+    format!("<{}>", name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(
+            parse_keys("a<lt><down><enter>").unwrap(),
+            [Key::Char('a'), Key::Char('<'), Key::ArrowDown, Key::Enter]
+        );
+        assert_eq!(parse_keys("ü\n").unwrap(), [Key::Char('ü'), Key::Enter]);
+        assert!(parse_keys("<up2>").is_err());
+        assert!(parse_keys("<up").is_err());
+    }
+
+    #[test]
+    fn test_key_name_round_trip() {
+        let script = "a<lt><enter><esc><tab><backtab><backspace><del><insert><up><down>\
+                      <left><right><home><end><pageup><pagedown><ctrl-c><unknown>";
+        let names: String = parse_keys(script).unwrap().iter().map(key_name).collect();
+        assert_eq!(names, script);
+    }
+}
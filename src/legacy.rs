@@ -0,0 +1,186 @@
+//! Keeps prompts usable on legacy Windows consoles.
+use std::{
+    env, io,
+    sync::{Mutex, MutexGuard},
+};
+
+use console::Key;
+use once_cell::sync::Lazy;
+
+/// The environment variable which forces [`set_legacy_console`] on when set to anything but
+/// `0`, or off when set to `0`.
+const LEGACY_CONSOLE_VAR: &str = "DIALOGUER_LEGACY_CONSOLE";
+
+#[derive(Default)]
+struct State {
+    forced: Option<bool>,
+    // A key read after a prefix character which didn't start a scan code.
+    pending: Option<Key>,
+}
+
+static STATE: Lazy<Mutex<State>> = Lazy::new(Default::default);
+
+static DETECTED: Lazy<bool> = Lazy::new(|| match env::var(LEGACY_CONSOLE_VAR) {
+    Ok(val) if !val.is_empty() => val != "0",
+    _ => detect(),
+});
+
+fn lock() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Forces the compatibility mode for legacy Windows consoles on or off.
+///
+/// Consoles predating Windows 10, like `conhost` with a legacy code page, can't render
+/// Unicode symbols and some terminals on top of them send the keys of the arrow block as
+/// DOS scan codes, a `0x00` or `0xE0` prefix followed by a letter. In the compatibility
+/// mode [`ColorfulTheme`](crate::theme::ColorfulTheme) defaults to
+/// [ASCII symbols](crate::theme::ColorfulThemeBuilder::ascii) and scan codes read from a
+/// [`Term`](console::Term) are translated to the keys they stand for.
+///
+/// The mode is detected on Windows when the console doesn't support escape codes. Setting
+/// the `DIALOGUER_LEGACY_CONSOLE` environment variable to `0` or anything else turns it
+/// off or on instead.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{set_legacy_console, theme::ColorfulTheme, Select};
+///
+/// set_legacy_console(true);
+///
+/// let selection = Select::with_theme(&ColorfulTheme::default())
+///     .items(&["Yes", "No"])
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_legacy_console(enabled: bool) {
+    lock().forced = Some(enabled);
+}
+
+/// Returns whether the compatibility mode for legacy Windows consoles is on.
+///
+/// See [`set_legacy_console`].
+pub fn is_legacy_console() -> bool {
+    lock().forced.unwrap_or_else(|| *DETECTED)
+}
+
+#[cfg(windows)]
+fn detect() -> bool {
+    let term = console::Term::stderr();
+    term.is_term() && !term.features().colors_supported()
+}
+
+#[cfg(not(windows))]
+fn detect() -> bool {
+    false
+}
+
+/// Returns the key of a DOS scan code following a `0x00` or `0xE0` prefix.
+fn scan_code_key(code: char) -> Option<Key> {
+    Some(match code {
+        'H' => Key::ArrowUp,
+        'P' => Key::ArrowDown,
+        'K' => Key::ArrowLeft,
+        'M' => Key::ArrowRight,
+        'G' => Key::Home,
+        'O' => Key::End,
+        'I' => Key::PageUp,
+        'Q' => Key::PageDown,
+        'R' => Key::Insert,
+        'S' => Key::Del,
+        _ => return None,
+    })
+}
+
+/// Returns whether `c` is a scan code prefix, also as `0xE0` decoded in common code pages.
+fn is_scan_code_prefix(c: char) -> bool {
+    // 'à' in Windows-1252 and Latin-1, 'α' in 437, 'Ó' in 850.
+    matches!(c, '\0' | '\u{e0}' | 'α' | 'Ó')
+}
+
+/// Returns whether a key read ahead while translating scan codes is waiting.
+pub(crate) fn key_pending() -> bool {
+    lock().pending.is_some()
+}
+
+/// Reads a key with `read`, translating scan codes when the compatibility mode is on.
+pub(crate) fn read_key<F: FnMut() -> io::Result<Key>>(mut read: F) -> io::Result<Key> {
+    if let Some(key) = lock().pending.take() {
+        return Ok(key);
+    }
+
+    let key = read()?;
+    if !is_legacy_console() {
+        return Ok(key);
+    }
+
+    translate(key, read, |key| lock().pending = Some(key))
+}
+
+fn translate<F, P>(key: Key, mut read: F, mut pend: P) -> io::Result<Key>
+where
+    F: FnMut() -> io::Result<Key>,
+    P: FnMut(Key),
+{
+    match key {
+        Key::Char(prefix) if is_scan_code_prefix(prefix) => {
+            let next = read()?;
+            if let Key::Char(code) = next {
+                if let Some(key) = scan_code_key(code) {
+                    return Ok(key);
+                }
+            }
+
+            // The prefix was typed itself, the next key is handed out on the following read.
+            pend(next);
+            Ok(key)
+        }
+        key => Ok(key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate_keys(keys: Vec<Key>) -> (Key, Option<Key>) {
+        let mut keys = keys.into_iter();
+        let first = keys.next().unwrap();
+        let mut pending = None;
+        let key = translate(
+            first,
+            || Ok(keys.next().unwrap()),
+            |key| pending = Some(key),
+        )
+        .unwrap();
+        (key, pending)
+    }
+
+    #[test]
+    fn test_translate_scan_codes() {
+        assert_eq!(
+            translate_keys(vec![Key::Char('\u{e0}'), Key::Char('H')]),
+            (Key::ArrowUp, None)
+        );
+        assert_eq!(
+            translate_keys(vec![Key::Char('α'), Key::Char('P')]),
+            (Key::ArrowDown, None)
+        );
+        assert_eq!(
+            translate_keys(vec![Key::Char('\0'), Key::Char('S')]),
+            (Key::Del, None)
+        );
+    }
+
+    #[test]
+    fn test_translate_plain_keys() {
+        assert_eq!(translate_keys(vec![Key::Char('x')]), (Key::Char('x'), None));
+        assert_eq!(
+            translate_keys(vec![Key::Char('à'), Key::Char('x')]),
+            (Key::Char('à'), Some(Key::Char('x')))
+        );
+    }
+}
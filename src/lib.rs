@@ -10,28 +10,105 @@
 //!
 //! # Crate Contents
 //!
-//! * Confirmation prompts
-//! * Input prompts (regular and password)
-//! * Input validation
-//! * Selections prompts (single and multi)
+//! * Confirmation and toggle prompts
+//! * Input prompts (regular, password, PIN codes, tags and autocomplete)
+//! * List editing prompt
+//! * Number, duration input and slider prompts
+//! * IP address and CIDR block input
+//! * Input validation, with ready-made validators (with the `validators` feature)
+//! * Selections prompts (single, multi and range)
+//! * Dual-list transfer prompt
+//! * Table, tree (single and multi) and grid select prompts
+//! * Color picker prompt
+//! * Nested menus
+//! * Wizards numbering a sequence of prompts
+//! * "Press any key" prompts
+//! * Reviewing a sequence of items one by one
+//! * File and directory browser
 //! * Fuzzy select prompt
 //! * Other kind of prompts
 //! * Editor launching
+//! * Suspending progress bars while prompts are active
+//! * Loading themes from TOML or JSON files
+//! * Adapting colors to light and dark terminal backgrounds
+//! * Pluggable terminal backends, including crossterm and arbitrary reader/writer pairs
+//! * Rendering prompts into strings without a terminal
+//! * Answering prompts from stdin lines without a terminal, or with their defaults
+//! * Recording answers and replaying them to preseed unattended runs
+//! * Testing prompt flows with scripted keys (with the `test` feature)
+//! * Recording transcripts of sessions and replaying them
+//! * Deterministic rendering for tests in CI
+//! * Awaiting prompts in tokio tasks (with the `tokio` feature)
+//! * Suggestions and completions supplied by futures (with the `async` feature)
+//! * Cancelling, redrawing and rewording prompts from other threads
+//! * Timeouts resolving prompts to their defaults
+//! * Observing the events of prompts, like keys and selection changes
+//! * Updating the items of list prompts while they are open
+//! * Driving the key handling of prompts from other event loops
 
 #![deny(clippy::all)]
 
+pub use answers::{record_answers, replay_answers, stop_recording_answers};
+#[cfg(feature = "crossterm")]
+pub use backend::CrosstermBackend;
+pub use backend::{interrupted, set_default_term, set_strict_inline, Backend, Capabilities};
+pub use cancel::{cancelled, Cancellable, CancellationToken};
 #[cfg(feature = "completion")]
 pub use completion::Completion;
+#[cfg(all(feature = "completion", feature = "async"))]
+pub use completion::{AsyncCompletion, CompletionFuture};
 pub use console;
+pub use deterministic::{clear_deterministic, set_deterministic};
 #[cfg(feature = "editor")]
 pub use edit::Editor;
+pub use escape::{escape_timeout, set_escape_timeout};
+pub use events::{
+    clear_event_observer, event_stream, set_event_observer, PromptEvent, PromptObserver,
+};
+pub use fallback::{not_a_tty, set_defaults_mode, set_non_tty_fallback};
+pub use handle::PromptHandle;
+pub use headless::Headless;
 #[cfg(feature = "history")]
 pub use history::History;
+pub use legacy::{is_legacy_console, set_legacy_console};
+pub use link::hyperlink;
 use paging::Paging;
 pub use prompts::{
-    confirm::Confirm, input::Input, multi_select::MultiSelect, select::Select, sort::Sort,
+    autocomplete::Autocomplete,
+    color_select::{ColorSelect, Palette, Rgb},
+    confirm::Confirm,
+    duration_input::DurationInput,
+    edit_list::EditList,
+    grid_select::GridSelect,
+    input::Input,
+    ip_input::{IpInput, IpValue},
+    key_prompt::KeyPrompt,
+    menu::{Menu, MenuItem},
+    multi_select::MultiSelect,
+    number_input::{NumberInput, Numeric},
+    path_select::PathSelect,
+    pin_input::PinInput,
+    range_select::RangeSelect,
+    review_each::{ReviewDecision, ReviewEach},
+    select::Select,
+    slider::Slider,
+    sort::Sort,
+    table_select::TableSelect,
+    tag_input::TagInput,
+    toggle::Toggle,
+    transfer_select::TransferSelect,
+    tree_multi_select::{CheckState, TreeMultiSelect},
+    tree_select::{TreeNode, TreeSelect},
 };
-pub use validate::Validator;
+pub use provider::ItemProvider;
+pub use stream::StreamBackend;
+pub use suspend::{clear_suspend_hook, pause_renderer, set_suspend_hook, PauseGuard, SuspendHook};
+pub use timeout::timed_out;
+pub use transcript::{Recorder, Replay};
+pub use updates::ItemUpdate;
+pub use validate::{TransformingValidator, Validator};
+pub use width::{char_width, text_width};
+pub use wizard::Wizard;
 
 #[cfg(feature = "fuzzy-select")]
 pub use prompts::fuzzy_select::FuzzySelect;
@@ -39,13 +116,51 @@ pub use prompts::fuzzy_select::FuzzySelect;
 #[cfg(feature = "password")]
 pub use prompts::password::Password;
 
+mod answers;
+mod backend;
+mod background;
+mod cancel;
 #[cfg(feature = "completion")]
 mod completion;
+mod deterministic;
 #[cfg(feature = "editor")]
 mod edit;
+mod escape;
+mod events;
+mod fallback;
+#[cfg(feature = "fuzz")]
+#[doc(hidden)]
+pub mod fuzz;
+mod handle;
+mod headless;
 #[cfg(feature = "history")]
 mod history;
+mod job_control;
+mod key_script;
+mod legacy;
+mod link;
+mod loading;
 mod paging;
 mod prompts;
+mod provider;
+#[cfg(unix)]
+mod signal;
+pub mod state;
+mod stream;
+mod suspend;
+#[cfg(feature = "tokio")]
+mod task;
+mod template;
+#[cfg(feature = "test")]
+pub mod test;
 pub mod theme;
+#[cfg(feature = "theme-file")]
+mod theme_file;
+mod timeout;
+mod transcript;
+mod updates;
 mod validate;
+#[cfg(feature = "validators")]
+pub mod validators;
+mod width;
+mod wizard;
@@ -0,0 +1,124 @@
+//! Renders terminal hyperlinks.
+use std::borrow::Cow;
+
+/// Returns `text` marked up as a link to `url`, which terminals supporting OSC 8
+/// hyperlinks let users open with a click, usually while holding Cmd or Ctrl.
+///
+/// Links can be put in prompts, items and any other text a prompt renders. On terminals
+/// without hyperlinks, as reported by [`Capabilities::hyperlinks`](crate::Capabilities),
+/// only `text` is rendered.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{hyperlink, Confirm};
+///
+/// let prompt = format!(
+///     "Merge {}?",
+///     hyperlink("#42", "https://github.com/console-rs/dialoguer/pull/42")
+/// );
+/// let merge = Confirm::new().with_prompt(prompt).interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+}
+
+/// Returns the length of the OSC sequence `text` starts with, if it does.
+fn osc_len(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix("\x1b]")?;
+    let end = rest.find(['\x07', '\x1b'])?;
+    let terminator = match &rest.as_bytes()[end..] {
+        [b'\x07', ..] => 1,
+        [b'\x1b', b'\\', ..] => 2,
+        _ => return None,
+    };
+    Some(2 + end + terminator)
+}
+
+/// Splits `text` into OSC sequences like hyperlinks and the text between them, telling
+/// them apart like [`console::AnsiCodeIterator`] does for other escape codes.
+pub(crate) fn split_osc(text: &str) -> Vec<(&str, bool)> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("\x1b]") {
+        match osc_len(&rest[start..]) {
+            Some(len) => {
+                if start > 0 {
+                    parts.push((&rest[..start], false));
+                }
+                parts.push((&rest[start..start + len], true));
+                rest = &rest[start + len..];
+            }
+            None => break,
+        }
+    }
+
+    if !rest.is_empty() {
+        parts.push((rest, false));
+    }
+
+    parts
+}
+
+/// Removes the OSC sequences from `text`, leaving the text of hyperlinks.
+pub(crate) fn strip_osc(text: &str) -> Cow<'_, str> {
+    if !text.contains("\x1b]") {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(
+        split_osc(text)
+            .into_iter()
+            .filter(|&(_, is_osc)| !is_osc)
+            .map(|(part, _)| part)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyperlink() {
+        assert_eq!(
+            hyperlink("docs", "https://docs.rs"),
+            "\x1b]8;;https://docs.rs\x1b\\docs\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_split_osc() {
+        let link = hyperlink("b", "u");
+        let text = format!("a{}c", link);
+        assert_eq!(
+            split_osc(&text),
+            [
+                ("a", false),
+                ("\x1b]8;;u\x1b\\", true),
+                ("b", false),
+                ("\x1b]8;;\x1b\\", true),
+                ("c", false)
+            ]
+        );
+        assert_eq!(
+            split_osc("\x1b]0;title\x07x"),
+            [("\x1b]0;title\x07", true), ("x", false)]
+        );
+        // Unterminated sequences are left as text.
+        assert_eq!(split_osc("\x1b]8;;u"), [("\x1b]8;;u", false)]);
+    }
+
+    #[test]
+    fn test_strip_osc() {
+        assert_eq!(
+            strip_osc(&hyperlink("\x1b[1mb\x1b[0m", "u")),
+            "\x1b[1mb\x1b[0m"
+        );
+        assert_eq!(strip_osc("plain"), "plain");
+    }
+}
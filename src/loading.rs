@@ -0,0 +1,125 @@
+//! Awaits the futures of suggestion and completion providers while prompts read keys.
+//!
+//! Prompts don't run in an executor, so they poll the futures themselves: whenever one
+//! was woken, and otherwise wait for keys for a [`SPIN_INTERVAL`] at a time, advancing
+//! the spinner rendered meanwhile.
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    time::Duration,
+};
+
+/// How long a prompt loading something waits for a key before advancing its spinner.
+pub(crate) const SPIN_INTERVAL: Duration = Duration::from_millis(80);
+
+/// A future which doesn't have to be `Send`, as providers return them.
+pub(crate) type LocalFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Records that a future can make progress.
+struct Woken(AtomicBool);
+
+impl Wake for Woken {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Something a prompt is loading between keys.
+///
+/// Dropping it discards the result, like when the input it was loaded for changed.
+pub(crate) struct Loading<'a, T> {
+    future: LocalFuture<'a, T>,
+    woken: Arc<Woken>,
+    frame: usize,
+}
+
+impl<'a, T> Loading<'a, T> {
+    pub fn new(future: impl Future<Output = T> + 'a) -> Self {
+        Loading {
+            future: Box::pin(future),
+            // Futures are polled once up front, ready ones don't show a spinner.
+            woken: Arc::new(Woken(AtomicBool::new(true))),
+            frame: 0,
+        }
+    }
+
+    /// Returns the frame of the spinner to render.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Advances the spinner.
+    pub fn tick(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+    }
+
+    /// Polls the future if it was woken since the last poll, returning its output once
+    /// it is ready.
+    pub fn poll(&mut self) -> Option<T> {
+        if !self.woken.0.swap(false, Ordering::SeqCst) {
+            return None;
+        }
+
+        let waker = Waker::from(self.woken.clone());
+        match self.future.as_mut().poll(&mut Context::from_waker(&waker)) {
+            Poll::Ready(output) => Some(output),
+            Poll::Pending => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{sync::Mutex, task::Waker};
+
+    /// A future resolving once its value was sent.
+    #[derive(Clone, Default)]
+    struct Later(Arc<Mutex<(Option<u32>, Option<Waker>)>>);
+
+    impl Later {
+        fn send(&self, value: u32) {
+            let mut state = self.0.lock().unwrap();
+            state.0 = Some(value);
+            if let Some(waker) = state.1.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    impl Future for Later {
+        type Output = u32;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+            let mut state = self.0.lock().unwrap();
+            match state.0.take() {
+                Some(value) => Poll::Ready(value),
+                None => {
+                    state.1 = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_polls_when_woken() {
+        assert_eq!(Loading::new(async { 1 }).poll(), Some(1));
+
+        let later = Later::default();
+        let mut loading = Loading::new(later.clone());
+        assert_eq!(loading.poll(), None);
+        // Not woken, so not polled again.
+        later.0.lock().unwrap().0 = Some(3);
+        assert_eq!(loading.poll(), None);
+
+        later.send(2);
+        assert_eq!(loading.poll(), Some(2));
+    }
+}
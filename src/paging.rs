@@ -1,6 +1,115 @@
-use std::io;
+use std::{io, ops::Range};
 
-use console::Term;
+use console::Key;
+
+use crate::Backend;
+
+/// The current page counting from 1, and the number of pages if known.
+pub type PagingInfo = (usize, Option<usize>);
+
+/// The number of items kept visible above and below the cursor when scrolling by default.
+const SCROLL_CONTEXT: usize = 2;
+
+/// Returns the first visible item after moving the cursor to `cursor_pos`, scrolling the
+/// window starting at `offset` as little as possible to keep `context` items around the
+/// cursor.
+///
+/// A context of half the capacity or more keeps the cursor centered.
+fn scroll_offset(
+    offset: usize,
+    cursor_pos: usize,
+    context: usize,
+    capacity: usize,
+    items_len: usize,
+) -> usize {
+    let context = context.min(capacity.saturating_sub(1) / 2);
+
+    let offset = if cursor_pos < offset + context {
+        cursor_pos.saturating_sub(context)
+    } else if cursor_pos + context >= offset + capacity {
+        cursor_pos + context + 1 - capacity
+    } else {
+        offset
+    };
+
+    offset.min(items_len.saturating_sub(capacity))
+}
+
+/// Returns the rows of the scrollbar thumb next to the `rows` visible items starting at
+/// `first`, out of `items_len` items.
+fn scrollbar_thumb(first: usize, rows: usize, items_len: usize) -> Range<usize> {
+    let len = (rows * rows / items_len.max(1)).max(1).min(rows);
+
+    let start = if first + rows >= items_len {
+        rows - len
+    } else {
+        (first * rows / items_len).min(rows - len)
+    };
+
+    start..start + len
+}
+
+/// Collects the digits typed to jump to an item of a numbered list.
+///
+/// The jump happens on 'Enter', or as soon as another digit can't make a valid number.
+#[derive(Default)]
+pub struct Jump {
+    digits: String,
+}
+
+impl Jump {
+    /// Returns the digits typed so far, if any.
+    pub fn digits(&self) -> Option<&str> {
+        if self.digits.is_empty() {
+            None
+        } else {
+            Some(&self.digits)
+        }
+    }
+
+    /// Handles `key`, returning whether it was consumed and the index of the item to jump to.
+    ///
+    /// Keys which aren't consumed discard the typed digits.
+    pub fn handle(&mut self, key: &Key, items_len: usize) -> (bool, Option<usize>) {
+        match key {
+            Key::Char('0') if self.digits.is_empty() => (true, None),
+            Key::Char(c) if c.is_ascii_digit() => {
+                self.digits.push(*c);
+                let number = self.number();
+                if number.saturating_mul(10) > items_len {
+                    (true, self.finish(items_len))
+                } else {
+                    (true, None)
+                }
+            }
+            Key::Backspace if !self.digits.is_empty() => {
+                self.digits.pop();
+                (true, None)
+            }
+            Key::Enter if !self.digits.is_empty() => (true, self.finish(items_len)),
+            Key::Escape if !self.digits.is_empty() => {
+                self.digits.clear();
+                (true, None)
+            }
+            _ => {
+                self.digits.clear();
+                (false, None)
+            }
+        }
+    }
+
+    fn number(&self) -> usize {
+        self.digits.parse().unwrap_or(usize::MAX)
+    }
+
+    /// Clears the digits, returning the index of the item they number.
+    fn finish(&mut self, items_len: usize) -> Option<usize> {
+        let number = self.number();
+        self.digits.clear();
+
+        number.min(items_len).checked_sub(1)
+    }
+}
 
 /// Creates a paging module
 ///
@@ -12,60 +121,179 @@ pub struct Paging<'a> {
     pub capacity: usize,
     pub active: bool,
     pub max_capacity: Option<usize>,
-    term: &'a Term,
+    pub max_rows: Option<usize>,
+    pub scrolling: bool,
+    pub scroll_context: usize,
+    term: &'a dyn Backend,
+    footer_rows: usize,
+    offset: usize,
+    rendered_info: Option<PagingInfo>,
     current_term_size: (u16, u16),
+    resumes: usize,
     items_len: usize,
+    complete: bool,
     activity_transition: bool,
 }
 
 impl<'a> Paging<'a> {
-    pub fn new(term: &'a Term, items_len: usize, max_capacity: Option<usize>) -> Paging<'a> {
-        let term_size = term.size();
-        // Subtract -2 because we need space to render the prompt, if paging is active
-        let capacity = max_capacity
-            .unwrap_or(std::usize::MAX)
-            .min(term_size.0 as usize)
-            // Safeguard in case term_size or max_length is 2 or less. Guarantees no unwanted wrapping behavior.
-            .max(3)
-            - 2;
-        let pages = (items_len as f64 / capacity as f64).ceil() as usize;
-
-        Paging {
-            pages,
+    pub fn new(
+        term: &'a dyn Backend,
+        items_len: usize,
+        max_capacity: Option<usize>,
+        footer_rows: usize,
+    ) -> Paging<'a> {
+        let mut paging = Paging {
+            pages: 0,
             current_page: 0,
-            capacity,
-            active: pages > 1,
+            capacity: 0,
+            active: false,
             term,
-            current_term_size: term_size,
+            footer_rows,
+            current_term_size: term.size(),
+            resumes: crate::job_control::resumes(),
             items_len,
+            complete: true,
             max_capacity,
+            max_rows: None,
+            scrolling: false,
+            scroll_context: SCROLL_CONTEXT,
+            offset: 0,
+            rendered_info: None,
             // Set transition initially to true to trigger prompt rendering for inactive paging on start
             activity_transition: true,
+        };
+        paging.update_capacity();
+        paging.active = paging.should_be_active();
+
+        paging
+    }
+
+    /// Limits the rows taken up by the prompt, including the prompt line and the footer, to
+    /// `max_rows` if that is less than the terminal height.
+    pub fn max_rows(mut self, max_rows: Option<usize>) -> Self {
+        self.max_rows = max_rows;
+        self.update_capacity();
+        self.active = self.should_be_active();
+        self
+    }
+
+    /// Sets the number of items before anything is rendered, `complete` being `false` while
+    /// more items can be loaded.
+    pub fn items_len(mut self, items_len: usize, complete: bool) -> Self {
+        self.set_items_len(items_len, complete);
+        self.active = self.should_be_active();
+        self
+    }
+
+    /// Updates the number of items after more were loaded, taking effect with the next
+    /// [`update`](Self::update).
+    pub fn set_items_len(&mut self, items_len: usize, complete: bool) {
+        self.items_len = items_len;
+        self.complete = complete;
+        self.update_capacity();
+    }
+
+    /// Pages while the items don't fit, or while not all of them are loaded.
+    fn should_be_active(&self) -> bool {
+        self.pages > 1 || !self.complete
+    }
+
+    fn paging_info(&self) -> PagingInfo {
+        (
+            self.current_page + 1,
+            if self.complete {
+                Some(self.pages)
+            } else {
+                None
+            },
+        )
+    }
+
+    /// Moves the visible items with the cursor instead of flipping whole pages.
+    pub fn scrolling(mut self, scrolling: bool) -> Self {
+        self.scrolling = scrolling;
+        self
+    }
+
+    /// Keeps `scroll_context` items visible above and below the cursor when scrolling,
+    /// instead of the default of two.
+    pub fn scroll_context(mut self, scroll_context: Option<usize>) -> Self {
+        self.scroll_context = scroll_context.unwrap_or(SCROLL_CONTEXT);
+        self
+    }
+
+    /// Returns the index of the first visible item.
+    pub fn first_item(&self) -> usize {
+        if self.scrolling {
+            self.offset
+        } else {
+            self.current_page * self.capacity
+        }
+    }
+
+    /// Returns the rows of the scrollbar thumb next to the visible items, if paging is active.
+    pub fn scrollbar(&self) -> Option<Range<usize>> {
+        let first = self.first_item();
+        let rows = self.capacity.min(self.items_len.saturating_sub(first));
+
+        if self.active && rows > 0 {
+            Some(scrollbar_thumb(first, rows, self.items_len))
+        } else {
+            None
         }
     }
 
+    /// Derives the capacity and the number of pages from the available rows.
+    fn update_capacity(&mut self) {
+        let rows = self
+            .max_rows
+            .unwrap_or(usize::MAX)
+            .min(self.current_term_size.0 as usize);
+
+        // Subtract -2 because we need space to render the prompt, if paging is active.
+        // Footer lines below the items only take away from the available rows.
+        self.capacity = self
+            .max_capacity
+            .unwrap_or(usize::MAX)
+            .min(rows.saturating_sub(self.footer_rows))
+            // Safeguard in case term_size or max_length is 2 or less. Guarantees no unwanted wrapping behavior.
+            .max(3)
+            - 2;
+        self.pages = (self.items_len as f64 / self.capacity as f64).ceil() as usize;
+    }
+
     /// Updates all internal based on the current terminal size and cursor position
+    ///
+    /// A resize since the last update recomputes the pages, which may turn paging on or off.
     pub fn update(&mut self, cursor_pos: usize) -> io::Result<()> {
         let new_term_size = self.term.size();
 
-        if self.current_term_size != new_term_size {
+        let resized = self.current_term_size != new_term_size;
+        if resized {
             self.current_term_size = new_term_size;
-            self.capacity = self
-                .max_capacity
-                .unwrap_or(std::usize::MAX)
-                .min(self.current_term_size.0 as usize)
-                .max(3)
-                - 2;
-            self.pages = (self.items_len as f64 / self.capacity as f64).ceil() as usize;
+            self.update_capacity();
+            self.current_page = self.current_page.min(self.pages.saturating_sub(1));
         }
 
-        if self.active == (self.pages > 1) {
-            self.activity_transition = false;
-        } else {
-            self.active = self.pages > 1;
-            self.activity_transition = true;
-            // Clear everything to prevent "ghost" lines in terminal when a resize happened
-            self.term.clear_last_lines(self.capacity)?;
+        // The prompt is rendered again after the process was stopped with Ctrl-Z, as it's
+        // rendered from scratch below the output of the shell.
+        let resumes = crate::job_control::resumes();
+        let resumed = self.resumes != resumes;
+        self.resumes = resumes;
+
+        // The prompt is rendered again when it gains or loses the page indicator, and after
+        // a resize as the terminal may have rewrapped it.
+        self.activity_transition = resized || resumed || self.active != self.should_be_active();
+        self.active = self.should_be_active();
+
+        if cursor_pos != !0 && self.scrolling {
+            self.offset = scroll_offset(
+                self.offset,
+                cursor_pos,
+                self.scroll_context,
+                self.capacity,
+                self.items_len,
+            );
         }
 
         if cursor_pos != !0
@@ -79,17 +307,27 @@ impl<'a> Paging<'a> {
     }
 
     /// Renders a prompt when the following conditions are met:
-    /// * Paging is active
+    /// * The page changed while paging is active
     /// * Transition of the paging activity happened (active -> inactive / inactive -> active)
+    /// * The terminal was resized
+    /// * A redraw was requested with [`redraw_prompt`](Self::redraw_prompt)
+    ///
+    /// Otherwise the prompt rendered before stays in place, so it doesn't flash while the
+    /// items below it are redrawn.
     pub fn render_prompt<F>(&mut self, mut render_prompt: F) -> io::Result<()>
     where
-        F: FnMut(Option<(usize, usize)>) -> io::Result<()>,
+        F: FnMut(Option<PagingInfo>) -> io::Result<()>,
     {
-        if self.active {
-            let paging_info = Some((self.current_page + 1, self.pages));
+        let paging_info = if self.active {
+            Some(self.paging_info())
+        } else {
+            None
+        };
+
+        if self.activity_transition || paging_info != self.rendered_info {
             render_prompt(paging_info)?;
-        } else if self.activity_transition {
-            render_prompt(None)?;
+            self.rendered_info = paging_info;
+            self.activity_transition = false;
         }
 
         self.term.flush()?;
@@ -97,25 +335,71 @@ impl<'a> Paging<'a> {
         Ok(())
     }
 
-    /// Navigates to the next page
-    pub fn next_page(&mut self) -> usize {
-        if self.current_page == self.pages - 1 {
-            self.current_page = 0;
-        } else {
-            self.current_page += 1;
+    /// Renders a footer below the items if paging is active.
+    pub fn render_footer<F>(&self, render_footer: F) -> io::Result<()>
+    where
+        F: FnOnce(PagingInfo) -> io::Result<()>,
+    {
+        if self.active {
+            render_footer(self.paging_info())?;
         }
 
-        self.current_page * self.capacity
+        Ok(())
     }
 
-    /// Navigates to the previous page
-    pub fn previous_page(&mut self) -> usize {
-        if self.current_page == 0 {
-            self.current_page = self.pages - 1;
-        } else {
-            self.current_page -= 1;
-        }
+    /// Renders the prompt again on the next call to [`render_prompt`](Self::render_prompt).
+    pub fn redraw_prompt(&mut self) {
+        self.activity_transition = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_offset() {
+        // Moving within the window keeps it in place.
+        assert_eq!(scroll_offset(0, 5, 2, 10, 100), 0);
+        // Moving down keeps two items visible below the cursor.
+        assert_eq!(scroll_offset(0, 8, 2, 10, 100), 1);
+        assert_eq!(scroll_offset(10, 8, 2, 10, 100), 6);
+        // The window doesn't move past the ends of the list.
+        assert_eq!(scroll_offset(0, 99, 2, 10, 100), 90);
+        assert_eq!(scroll_offset(90, 0, 2, 10, 100), 0);
+        assert_eq!(scroll_offset(0, 3, 2, 10, 5), 0);
+        // A large context keeps the cursor centered.
+        assert_eq!(scroll_offset(0, 8, 100, 9, 100), 4);
+        assert_eq!(scroll_offset(4, 7, 100, 9, 100), 3);
+    }
+
+    #[test]
+    fn test_scrollbar_thumb() {
+        assert_eq!(scrollbar_thumb(0, 10, 100), 0..1);
+        assert_eq!(scrollbar_thumb(50, 10, 100), 5..6);
+        assert_eq!(scrollbar_thumb(90, 10, 100), 9..10);
+        assert_eq!(scrollbar_thumb(0, 10, 20), 0..5);
+        assert_eq!(scrollbar_thumb(10, 10, 20), 5..10);
+        // The last page may show fewer rows, the thumb still ends with them.
+        assert_eq!(scrollbar_thumb(20, 4, 24), 3..4);
+    }
+
+    #[test]
+    fn test_jump() {
+        let mut jump = Jump::default();
+        assert_eq!(jump.handle(&Key::Char('5'), 100), (true, None));
+        assert_eq!(jump.digits(), Some("5"));
+        // A third digit would exceed 100 items, so the second one completes the number.
+        assert_eq!(jump.handle(&Key::Char('7'), 100), (true, Some(56)));
+        assert_eq!(jump.digits(), None);
+
+        assert_eq!(jump.handle(&Key::Char('1'), 100), (true, None));
+        assert_eq!(jump.handle(&Key::Enter, 100), (true, Some(0)));
+        assert_eq!(jump.handle(&Key::Enter, 100), (false, None));
 
-        self.current_page * self.capacity
+        assert_eq!(jump.handle(&Key::Char('9'), 5), (true, Some(4)));
+        assert_eq!(jump.handle(&Key::Char('2'), 100), (true, None));
+        assert_eq!(jump.handle(&Key::ArrowDown, 100), (false, None));
+        assert_eq!(jump.digits(), None);
     }
 }
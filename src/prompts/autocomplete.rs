@@ -0,0 +1,391 @@
+use std::{future, io, time::Duration};
+
+#[cfg(feature = "async")]
+use std::future::Future;
+
+use crate::{
+    backend::{default_term, with_keys},
+    fallback,
+    loading::{Loading, LocalFuture, SPIN_INTERVAL},
+    paging::Paging,
+    state::{AutocompleteState, Transition},
+    theme::{SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout, Backend,
+};
+
+use console::Key;
+
+// The futures don't borrow from the prompt, which keeps it covariant in its lifetime.
+type SuggestionCallback<'a> = Box<dyn Fn(&str) -> LocalFuture<'static, Vec<String>> + Send + 'a>;
+
+/// Renders a free text prompt with a live list of suggestions.
+///
+/// The suggestions for the typed text are provided by a callback and rendered below the
+/// input. 'Up'/'Down' highlight a suggestion and 'Tab' copies the highlighted (or the
+/// first) suggestion into the input. Unlike `FuzzySelect` the suggestions are optional:
+/// 'Enter' returns the typed text, or the highlighted suggestion if there is one.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::Autocomplete;
+///
+/// let hosts = ["github.com", "gitlab.com", "docs.rs", "crates.io"];
+/// let host = Autocomplete::new()
+///     .with_prompt("Host")
+///     .suggest_with(|input| {
+///         hosts
+///             .iter()
+///             .filter(|host| host.starts_with(input))
+///             .map(|host| host.to_string())
+///             .collect()
+///     })
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Autocomplete<'a> {
+    prompt: String,
+    report: bool,
+    clear: bool,
+    initial_text: String,
+    max_suggestions: usize,
+    suggest: Option<SuggestionCallback<'a>>,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for Autocomplete<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Autocomplete<'static> {
+    /// Creates an autocomplete prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl<'a> Autocomplete<'a> {
+    /// Sets the autocomplete prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the entered text after interaction.
+    ///
+    /// The default is to report the entered text.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Indicates whether the suggestions should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the suggestions.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Sets the text the input starts with.
+    pub fn with_initial_text<S: Into<String>>(&mut self, val: S) -> &mut Self {
+        self.initial_text = val.into();
+        self
+    }
+
+    /// Sets the maximum number of suggestions rendered below the input.
+    ///
+    /// The default is 5.
+    pub fn max_suggestions(&mut self, val: usize) -> &mut Self {
+        self.max_suggestions = val;
+        self
+    }
+
+    /// Registers the callback providing the suggestions for the typed text.
+    ///
+    /// The callback is invoked whenever the input changes.
+    pub fn suggest_with<F>(&mut self, suggest: F) -> &mut Self
+    where
+        F: Fn(&str) -> Vec<String> + Send + 'a,
+    {
+        self.suggest = Some(Box::new(move |input| {
+            Box::pin(future::ready(suggest(input))) as LocalFuture<'static, _>
+        }));
+        self
+    }
+
+    /// Registers the callback returning a future of the suggestions for the typed text,
+    /// for suggestions from services like a shell history or an HTTP API.
+    ///
+    /// The future is polled by the prompt between keys, with a spinner rendered until it
+    /// resolves. It is dropped once the input changes, so the suggestions shown are always
+    /// the ones for the current input. The future owns what it needs, like a copy of the
+    /// input. Futures relying on the reactor of a runtime, like
+    /// the IO of tokio, have to be spawned on it, returning the handle of the task.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Autocomplete;
+    ///
+    /// # async fn search_history(prefix: String) -> Vec<String> { vec![prefix] }
+    /// let command = Autocomplete::new()
+    ///     .with_prompt("Command")
+    ///     .suggest_with_async(|input| search_history(input.to_string()))
+    ///     .interact()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn suggest_with_async<F, Fut>(&mut self, suggest: F) -> &mut Self
+    where
+        F: Fn(&str) -> Fut + Send + 'a,
+        Fut: Future<Output = Vec<String>> + 'static,
+    {
+        self.suggest = Some(Box::new(move |input| {
+            Box::pin(suggest(input)) as LocalFuture<'static, _>
+        }));
+        self
+    }
+
+    /// Gives up waiting for the input after `timeout`, resolving to the
+    /// [initial text](Self::with_initial_text), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the entered text if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<String> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(text)` if user hit 'Enter' or `None` if user cancelled with 'Esc'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<String>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<String> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<String> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<String>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    /// Starts loading the suggestions for `input`, limited to the configured maximum.
+    fn suggestions(&self, input: &str) -> Loading<'static, Vec<String>> {
+        let max_suggestions = self.max_suggestions;
+        let suggestions = self.suggest.as_ref().map(|suggest| suggest(input));
+
+        Loading::new(async move {
+            match suggestions {
+                Some(suggestions) => {
+                    let mut suggestions = suggestions.await;
+                    suggestions.truncate(max_suggestions);
+                    suggestions
+                }
+                None => vec![],
+            }
+        })
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<String>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            if self.initial_text.is_empty() {
+                return Err(fallback::no_default(&self.prompt));
+            }
+            if self.report {
+                render.input_prompt_selection(&self.prompt, &self.initial_text)?;
+            }
+            term.flush()?;
+
+            return Ok(Some(self.initial_text.clone()));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut state = AutocompleteState::new(self.initial_text.clone()).allow_quit(allow_quit);
+        let mut loading = state.query().map(|query| self.suggestions(query));
+        // Paging adds two to the maximum for the prompt line and a spare row, as it does
+        // for `max_length`. The suggestions scroll if the terminal is too small for them.
+        let mut paging = Paging::new(term, 0, Some(self.max_suggestions + 2), 0)
+            .scrolling(true)
+            .scroll_context(Some(0));
+
+        render.hide_cursor()?;
+
+        loop {
+            if let Some(loaded) = loading.as_mut().and_then(Loading::poll) {
+                state.set_suggestions(loaded);
+                loading = None;
+                paging.set_items_len(state.suggestions().len(), true);
+                paging.update(0)?;
+            }
+
+            render.autocomplete_prompt(&self.prompt, state.text(), state.position())?;
+
+            match loading {
+                Some(ref loading) => render.loading(loading.frame())?,
+                None => {
+                    for (idx, suggestion) in state
+                        .suggestions()
+                        .iter()
+                        .enumerate()
+                        .skip(paging.first_item())
+                        .take(paging.capacity)
+                    {
+                        render
+                            .autocomplete_suggestion(suggestion, state.selection() == Some(idx))?;
+                    }
+                }
+            }
+
+            term.flush()?;
+
+            if let Some(ref mut loading) = loading {
+                if !term.poll_key(SPIN_INTERVAL)? {
+                    loading.tick();
+                    render.clear()?;
+                    continue;
+                }
+            }
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(input) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    if self.report {
+                        render.input_prompt_selection(&self.prompt, &input)?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(input));
+                }
+                _ => render.reject_key(&key)?,
+            }
+
+            if let Some(query) = state.query() {
+                // Suggestions still loading for the previous input are discarded.
+                loading = Some(self.suggestions(query));
+                paging.set_items_len(0, true);
+            }
+            paging.update(state.selection().unwrap_or(0))?;
+
+            render.clear()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Autocomplete<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<String>> {
+        crate::task::interact_async(std::mem::take(self), |autocomplete| autocomplete.interact())
+    }
+}
+
+impl<'a> Autocomplete<'a> {
+    /// Creates an autocomplete prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            clear: true,
+            initial_text: "".into(),
+            max_suggestions: 5,
+            suggest: None,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggestions_are_limited() {
+        let mut prompt = Autocomplete::new();
+        assert!(prompt.suggestions("a").poll().unwrap().is_empty());
+
+        prompt
+            .max_suggestions(2)
+            .suggest_with(|input| vec![input.to_string(); 3]);
+        assert_eq!(prompt.suggestions("a").poll().unwrap(), ["a", "a"]);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async_suggestions() {
+        let term = crate::Headless::new(10, 40).keys(vec![Key::Char('g'), Key::Tab, Key::Enter]);
+        let command = Autocomplete::new()
+            .suggest_with_async(|input| {
+                let input = input.to_string();
+                async move { vec![format!("{}it", input)] }
+            })
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(command, "git");
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err =
+            fallback::test_without_tty(|| Autocomplete::new().interact_on(&term)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
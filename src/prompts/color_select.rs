@@ -0,0 +1,540 @@
+use std::{env, fmt, io, str::FromStr, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{ColorSelectState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::{Color, Key, Style};
+
+/// A color in the RGB color space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Creates a color from its components.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Rgb { r, g, b }
+    }
+
+    /// Returns the index of the closest color of `palette` in the xterm palette.
+    ///
+    /// The 16 standard colors are only considered for [`Palette::Ansi16`], as terminals
+    /// customize them. Styles are limited to the 256 color palette, so
+    /// [`Palette::TrueColor`] approximates like [`Palette::Ansi256`].
+    pub fn closest(self, palette: Palette) -> u8 {
+        let candidates = match palette {
+            Palette::Ansi16 => 0..=15,
+            Palette::Ansi256 | Palette::TrueColor => 16..=255,
+        };
+        let distance = |other: Rgb| {
+            let delta = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            delta(self.r, other.r) + delta(self.g, other.g) + delta(self.b, other.b)
+        };
+
+        candidates
+            .min_by_key(|&idx| distance(ansi256(idx)))
+            .unwrap_or_default()
+    }
+
+    /// Sets the foreground of `style` to the closest color the terminal supports.
+    ///
+    /// This lets themes use brand colors, see [`Palette::detect`].
+    ///
+    /// ```rust
+    /// use dialoguer::{console::Style, theme::ColorfulTheme, Rgb};
+    ///
+    /// let orange = Rgb::new(255, 120, 0);
+    /// let theme = ColorfulTheme::builder()
+    ///     .prompt_prefix_style(orange.fg(Style::new().for_stderr()))
+    ///     .build();
+    /// ```
+    pub fn fg(self, style: Style) -> Style {
+        match self.closest(Palette::detect()) {
+            idx @ 0..=7 => style.fg(ansi16_color(idx)),
+            idx @ 8..=15 => style.fg(ansi16_color(idx - 8)).bright(),
+            idx => style.color256(idx),
+        }
+    }
+
+    /// Sets the background of `style` to the closest color the terminal supports.
+    pub fn bg(self, style: Style) -> Style {
+        match self.closest(Palette::detect()) {
+            idx @ 0..=7 => style.bg(ansi16_color(idx)),
+            idx @ 8..=15 => style.bg(ansi16_color(idx - 8)).on_bright(),
+            idx => style.on_color256(idx),
+        }
+    }
+}
+
+/// Returns the standard color with the given index below 8.
+fn ansi16_color(idx: u8) -> Color {
+    match idx {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::White,
+    }
+}
+
+/// Formats the color as a hex triplet like `#ff8800`.
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Parses a hex triplet like `#ff8800`, `ff8800` or `#f80`.
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim().trim_start_matches('#');
+        let invalid = || format!("'{}' is not a valid hex color", s.trim());
+
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let component = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| invalid());
+
+        match hex.len() {
+            6 => Ok(Rgb::new(
+                component(&hex[0..2])?,
+                component(&hex[2..4])?,
+                component(&hex[4..6])?,
+            )),
+            3 => {
+                let short = |idx: usize| component(&hex[idx..=idx]).map(|c| c * 17);
+                Ok(Rgb::new(short(0)?, short(1)?, short(2)?))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// The swatches offered by a [`ColorSelect`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Palette {
+    /// The 16 standard terminal colors.
+    Ansi16,
+    /// The 256 colors of the xterm palette.
+    Ansi256,
+    /// A range of hues and lightnesses rendered with true-color escapes.
+    TrueColor,
+}
+
+impl Palette {
+    /// Detects the richest palette the terminal supports.
+    ///
+    /// `COLORTERM` set to `truecolor` or `24bit` indicates true color support, a `TERM`
+    /// containing `256color` the 256 color palette. Otherwise only the 16 standard colors
+    /// are assumed.
+    pub fn detect() -> Palette {
+        let var = |name| env::var(name).unwrap_or_default();
+
+        if matches!(&*var("COLORTERM"), "truecolor" | "24bit") {
+            Palette::TrueColor
+        } else if var("TERM").contains("256color") {
+            Palette::Ansi256
+        } else {
+            Palette::Ansi16
+        }
+    }
+}
+
+/// The RGB values xterm uses for the 16 standard colors.
+const ANSI16: [Rgb; 16] = [
+    Rgb::new(0, 0, 0),
+    Rgb::new(205, 0, 0),
+    Rgb::new(0, 205, 0),
+    Rgb::new(205, 205, 0),
+    Rgb::new(0, 0, 238),
+    Rgb::new(205, 0, 205),
+    Rgb::new(0, 205, 205),
+    Rgb::new(229, 229, 229),
+    Rgb::new(127, 127, 127),
+    Rgb::new(255, 0, 0),
+    Rgb::new(0, 255, 0),
+    Rgb::new(255, 255, 0),
+    Rgb::new(92, 92, 255),
+    Rgb::new(255, 0, 255),
+    Rgb::new(0, 255, 255),
+    Rgb::new(255, 255, 255),
+];
+
+/// Returns the RGB value of an entry of the xterm 256 color palette.
+fn ansi256(idx: u8) -> Rgb {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match idx {
+        0..=15 => ANSI16[idx as usize],
+        16..=231 => {
+            let idx = idx - 16;
+            Rgb::new(
+                LEVELS[(idx / 36) as usize],
+                LEVELS[(idx / 6 % 6) as usize],
+                LEVELS[(idx % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (idx - 232);
+            Rgb::new(level, level, level)
+        }
+    }
+}
+
+/// Converts a color from HSL (hue in degrees, saturation and lightness in `0.0..=1.0`) to RGB.
+fn hsl(hue: f64, saturation: f64, lightness: f64) -> Rgb {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let hue = hue / 60.0;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    Rgb::new(channel(r), channel(g), channel(b))
+}
+
+impl Palette {
+    /// Returns the swatches of the palette with their terminal color index, if any.
+    pub(crate) fn swatches(self) -> Vec<(Rgb, Option<u8>)> {
+        match self {
+            Palette::Ansi16 => (0..16).map(|idx| (ANSI16[idx], Some(idx as u8))).collect(),
+            Palette::Ansi256 => (0..=255).map(|idx| (ansi256(idx), Some(idx))).collect(),
+            Palette::TrueColor => {
+                let mut swatches = vec![];
+                for row in 0..7 {
+                    let lightness = 0.8 - 0.1 * row as f64;
+                    for column in 0..18 {
+                        swatches.push((hsl(column as f64 * 20.0, 1.0, lightness), None));
+                    }
+                }
+                for column in 0..18 {
+                    let level = (255.0 * column as f64 / 17.0).round() as u8;
+                    swatches.push((Rgb::new(level, level, level), None));
+                }
+                swatches
+            }
+        }
+    }
+
+    /// Returns the number of swatches per row.
+    pub(crate) fn columns(self) -> usize {
+        match self {
+            Palette::Ansi16 => 8,
+            Palette::Ansi256 => 16,
+            Palette::TrueColor => 18,
+        }
+    }
+}
+
+/// Renders a color picker prompt.
+///
+/// The swatches of the palette are laid out in a grid which is navigated with the arrow
+/// keys. 'Tab' switches to a hex entry mode where a color like `#ff8800` can be typed.
+/// 'Enter' confirms the color.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{ColorSelect, Palette};
+///
+/// let color = ColorSelect::new()
+///     .with_prompt("Label color")
+///     .palette(Palette::Ansi256)
+///     .interact()?;
+/// println!("Picked {}", color);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ColorSelect<'a> {
+    prompt: String,
+    report: bool,
+    clear: bool,
+    default: Option<Rgb>,
+    palette: Palette,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for ColorSelect<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColorSelect<'static> {
+    /// Creates a color select prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl ColorSelect<'_> {
+    /// Sets the color select prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the picked color after interaction.
+    ///
+    /// The default is to report the picked color.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Indicates whether the palette should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the palette.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Sets the initially highlighted color.
+    ///
+    /// If the color is not part of the palette the prompt starts in hex entry mode.
+    pub fn default(&mut self, val: Rgb) -> &mut Self {
+        self.default = Some(val);
+        self
+    }
+
+    /// Sets the palette to pick from.
+    ///
+    /// The default is [`Palette::Ansi16`].
+    pub fn palette(&mut self, val: Palette) -> &mut Self {
+        self.palette = val;
+        self
+    }
+
+    /// Gives up waiting for the color after `timeout`, resolving to the
+    /// [default](Self::default), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the picked color if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<Rgb> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(color)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<Rgb>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Rgb> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<Rgb> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Rgb>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<Rgb>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            let default = self
+                .default
+                .ok_or_else(|| fallback::no_default(&self.prompt))?;
+            if self.report {
+                render.color_select_prompt_selection(&self.prompt, default)?;
+            }
+            term.flush()?;
+
+            return Ok(Some(default));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut state = ColorSelectState::new(self.palette).allow_quit(allow_quit);
+        if let Some(default) = self.default {
+            state = state.default(default);
+        }
+
+        render.hide_cursor()?;
+
+        loop {
+            render.color_select_prompt(&self.prompt, state.color(), state.hex_input())?;
+
+            if let Some(err) = state.error() {
+                render.error(err)?;
+            }
+
+            let columns = state.columns();
+            for (row_idx, row) in state.swatches().chunks(columns).enumerate() {
+                let active =
+                    if state.hex_input().is_none() && state.selection() / columns == row_idx {
+                        Some(state.selection() % columns)
+                    } else {
+                        None
+                    };
+                render.color_select_row(row, active)?;
+            }
+
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(color) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    if self.report {
+                        render.color_select_prompt_selection(&self.prompt, color)?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(color));
+                }
+                // The error is shown with the next frame.
+                _ if state.error().is_some() => {}
+                _ => render.reject_key(&key)?,
+            }
+
+            render.clear()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl ColorSelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Rgb>> {
+        crate::task::interact_async(std::mem::take(self), |color_select| color_select.interact())
+    }
+}
+
+impl<'a> ColorSelect<'a> {
+    /// Creates a color select prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            clear: true,
+            default: None,
+            palette: Palette::Ansi16,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!("#ff8800".parse(), Ok(Rgb::new(255, 136, 0)));
+        assert_eq!("0a0B0c".parse(), Ok(Rgb::new(10, 11, 12)));
+        assert_eq!("#f80".parse(), Ok(Rgb::new(255, 136, 0)));
+        assert!("#ff880".parse::<Rgb>().is_err());
+        assert!("#gg8800".parse::<Rgb>().is_err());
+        assert!("#ñ12".parse::<Rgb>().is_err());
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        let color = Rgb::new(1, 171, 255);
+
+        assert_eq!(color.to_string(), "#01abff");
+        assert_eq!(color.to_string().parse(), Ok(color));
+    }
+
+    #[test]
+    fn test_closest() {
+        let orange = Rgb::new(255, 120, 0);
+        assert_eq!(orange.closest(Palette::Ansi256), 208);
+        assert_eq!(orange.closest(Palette::TrueColor), 208);
+        assert_eq!(orange.closest(Palette::Ansi16), 3);
+        assert_eq!(Rgb::new(0, 0, 0).closest(Palette::Ansi256), 16);
+    }
+
+    #[test]
+    fn test_ansi256() {
+        assert_eq!(ansi256(9), Rgb::new(255, 0, 0));
+        assert_eq!(ansi256(16), Rgb::new(0, 0, 0));
+        assert_eq!(ansi256(196), Rgb::new(255, 0, 0));
+        assert_eq!(ansi256(255), Rgb::new(238, 238, 238));
+    }
+
+    #[test]
+    fn test_hsl() {
+        assert_eq!(hsl(0.0, 1.0, 0.5), Rgb::new(255, 0, 0));
+        assert_eq!(hsl(120.0, 1.0, 0.5), Rgb::new(0, 255, 0));
+        assert_eq!(hsl(240.0, 1.0, 0.25), Rgb::new(0, 0, 128));
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| ColorSelect::new().interact_on(&term)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -1,8 +1,16 @@
-use std::io;
+use std::{io, time::Duration};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::answers;
+use crate::backend::{default_term, with_keys};
+use crate::events;
+use crate::fallback;
+use crate::handle;
+use crate::state::{ConfirmState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::{Backend, PromptHandle};
 
-use console::{Key, Term};
+use console::Key;
 
 /// Renders a confirm prompt.
 ///
@@ -25,7 +33,9 @@ pub struct Confirm<'a> {
     default: Option<bool>,
     show_default: bool,
     wait_for_newline: bool,
-    theme: &'a dyn Theme,
+    timeout: Option<Duration>,
+    handle: Option<PromptHandle>,
+    theme: ThemeRef<'a>,
 }
 
 impl Default for Confirm<'static> {
@@ -70,7 +80,9 @@ impl Confirm<'_> {
     ///
     /// When `true`, the user must type their choice and hit the Enter key before
     /// proceeding. Valid inputs can be "yes", "no", "y", "n", or an empty string
-    /// to accept the default.
+    /// to accept the default. The arrow keys toggle the choice.
+    ///
+    /// [Compact](crate::theme::Theme::compact) themes always wait for the Enter key.
     pub fn wait_for_newline(&mut self, wait: bool) -> &mut Self {
         self.wait_for_newline = wait;
         self
@@ -94,26 +106,46 @@ impl Confirm<'_> {
         self
     }
 
+    /// Gives up waiting for the answer after `timeout`, resolving to the
+    /// [default](Self::default), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Splits off a handle controlling the prompt from other threads, which can render it
+    /// again, cancel it or change its text while it waits for the answer.
+    ///
+    /// See [`PromptHandle`] for an example.
+    pub fn into_parts(mut self) -> (Self, PromptHandle) {
+        let handle = PromptHandle::new();
+        self.handle = Some(handle.clone());
+        (self, handle)
+    }
+
     /// Enables user interaction and returns the result.
     ///
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     ///
     /// Result contains `bool` if user answered "yes" or "no" or `default` (configured in [`default`](Self::default) if pushes enter.
     /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
     #[inline]
     pub fn interact(&self) -> io::Result<bool> {
-        self.interact_on(&Term::stderr())
+        self.interact_on(&*default_term())
     }
 
     /// Enables user interaction and returns the result.
     ///
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     ///
     /// Result contains `Some(bool)` if user answered "yes" or "no" or `Some(default)` (configured in [`default`](Self::default)) if pushes enter,
     /// or `None` if user cancelled with 'Esc' or 'q'.
     #[inline]
     pub fn interact_opt(&self) -> io::Result<Option<bool>> {
-        self.interact_on_opt(&Term::stderr())
+        self.interact_on_opt(&*default_term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
@@ -132,11 +164,18 @@ impl Confirm<'_> {
     /// # }
     /// ```
     #[inline]
-    pub fn interact_on(&self, term: &Term) -> io::Result<bool> {
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<bool> {
         self._interact_on(term, false)?
             .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
     }
 
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<bool> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
     /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
     ///
     /// ## Examples
@@ -157,12 +196,52 @@ impl Confirm<'_> {
     /// }
     /// ```
     #[inline]
-    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<bool>> {
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<bool>> {
         self._interact_on(term, true)
     }
 
-    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<bool>> {
-        let mut render = TermThemeRenderer::new(term, self.theme);
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<bool>> {
+        if let Some(answer) = answers::replayed(&self.prompt) {
+            let value = fallback::parse_confirm(&answer, self.default)
+                .ok_or_else(|| fallback::invalid_answer(&answer, "y or n"))?;
+            if self.report {
+                TermThemeRenderer::new(term, &*self.theme)
+                    .confirm_prompt_selection(&self.prompt, Some(value))?;
+            }
+            term.flush()?;
+
+            return Ok(Some(value));
+        }
+
+        let value = timeout::ask(term, self.timeout, |term| {
+            events::observe_opt(term, |term| {
+                handle::ask(term, self.handle.as_ref(), |term| {
+                    self.ask(term, allow_quit)
+                })
+            })
+        })?;
+        if let Some(value) = value {
+            answers::record(&self.prompt, if value { "yes" } else { "no" })?;
+        }
+
+        Ok(value)
+    }
+
+    /// Renders the prompt and waits for the answer.
+    fn ask(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<bool>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            let default = self
+                .default
+                .ok_or_else(|| fallback::no_default(&self.prompt))?;
+            if self.report {
+                render.confirm_prompt_selection(&self.prompt, Some(default))?;
+            }
+            term.flush()?;
+
+            return Ok(Some(default));
+        }
 
         let default_if_show = if self.show_default {
             self.default
@@ -170,81 +249,77 @@ impl Confirm<'_> {
             None
         };
 
-        render.confirm_prompt(&self.prompt, default_if_show)?;
+        let mut prompt =
+            handle::prompt(self.handle.as_ref()).unwrap_or_else(|| self.prompt.clone());
+        render.confirm_prompt(&prompt, default_if_show)?;
 
-        term.hide_cursor()?;
-        term.flush()?;
+        if fallback::is_active(term) {
+            return fallback::ask_lines(term, |error| {
+                if let Some(err) = error {
+                    render.error(err)?;
+                    render.confirm_prompt(&self.prompt, default_if_show)?;
+                }
+                let answer = fallback::read_answer(term, true)?;
+                fallback::parse_confirm(&answer, self.default)
+                    .map(Some)
+                    .ok_or_else(|| fallback::invalid_answer(&answer, "y or n"))
+            });
+        }
 
-        let rv;
+        render.hide_cursor()?;
+        term.flush()?;
 
-        if self.wait_for_newline {
-            // Waits for user input and for the user to hit the Enter key
-            // before validation.
-            let mut value = default_if_show;
+        // Compact prompts wait for 'Enter' and the arrow keys toggle the answer.
+        let wait_for_newline = self.wait_for_newline || self.theme.compact();
+        let mut state = ConfirmState::new()
+            .wait_for_newline(wait_for_newline)
+            .allow_quit(allow_quit);
+        if let Some(default) = self.default {
+            state = state.default(default, self.show_default);
+        }
 
-            loop {
-                let input = term.read_key()?;
+        let rv = loop {
+            let input = term.read_key()?;
 
-                match input {
-                    Key::Char('y') | Key::Char('Y') => {
-                        value = Some(true);
-                    }
-                    Key::Char('n') | Key::Char('N') => {
-                        value = Some(false);
+            match state.handle_key(input.clone()) {
+                Transition::Done(value) => break Some(value),
+                Transition::Cancelled => break None,
+                Transition::Rejected if input != Key::Unknown => render.reject_key(&input)?,
+                // Reads after a resize, Ctrl-Z, a tick of the countdown or a redraw
+                // requested through the handle return unknown keys to render the prompt
+                // again.
+                _ => {
+                    if let Some(text) = handle::prompt(self.handle.as_ref()) {
+                        prompt = text;
                     }
-                    Key::Enter => {
-                        if !allow_quit {
-                            value = value.or(self.default);
-                        }
-
-                        if value.is_some() || allow_quit {
-                            rv = value;
-                            break;
-                        }
-                        continue;
-                    }
-                    Key::Escape | Key::Char('q') if allow_quit => {
-                        value = None;
-                    }
-                    _ => {
-                        continue;
-                    }
-                };
-
-                term.clear_line()?;
-                render.confirm_prompt(&self.prompt, value)?;
+                    term.clear_line()?;
+                    render.confirm_prompt(&prompt, state.value())?;
+                }
             }
-        } else {
-            // Default behavior: matches continuously on every keystroke,
-            // and does not wait for user to hit the Enter key.
-            loop {
-                let input = term.read_key()?;
-                let value = match input {
-                    Key::Char('y') | Key::Char('Y') => Some(true),
-                    Key::Char('n') | Key::Char('N') => Some(false),
-                    Key::Enter if self.default.is_some() => Some(self.default.unwrap()),
-                    Key::Escape | Key::Char('q') if allow_quit => None,
-                    _ => {
-                        continue;
-                    }
-                };
-
-                rv = value;
-                break;
-            }
-        }
+        };
 
         term.clear_line()?;
         if self.report {
-            render.confirm_prompt_selection(&self.prompt, rv)?;
+            render.confirm_prompt_selection(&prompt, rv)?;
         }
-        term.show_cursor()?;
+        render.show_cursor()?;
         term.flush()?;
 
         Ok(rv)
     }
 }
 
+#[cfg(feature = "tokio")]
+impl Confirm<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<bool>> {
+        crate::task::interact_async(std::mem::take(self), |confirm| confirm.interact())
+    }
+}
+
 impl<'a> Confirm<'a> {
     /// Creates a confirm prompt with a specific theme.
     ///
@@ -262,14 +337,16 @@ impl<'a> Confirm<'a> {
     /// #    Ok(())
     /// # }
     /// ```
-    pub fn with_theme(theme: &'a dyn Theme) -> Self {
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
         Self {
             prompt: "".into(),
             report: true,
             default: None,
             show_default: true,
             wait_for_newline: false,
-            theme,
+            timeout: None,
+            handle: None,
+            theme: theme.into(),
         }
     }
 }
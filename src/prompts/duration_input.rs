@@ -0,0 +1,383 @@
+use std::{io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{DurationInputState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// The recognized units with their length in milliseconds, the canonical name first.
+const UNITS: &[(&[&str], u64)] = &[
+    (&["w", "week", "weeks"], 604_800_000),
+    (&["d", "day", "days"], 86_400_000),
+    (&["h", "hr", "hrs", "hour", "hours"], 3_600_000),
+    (&["m", "min", "mins", "minute", "minutes"], 60_000),
+    (&["s", "sec", "secs", "second", "seconds"], 1_000),
+    (&["ms", "msec", "millis", "millisecond", "milliseconds"], 1),
+];
+
+/// Splits `input` into `(amount, unit length in milliseconds)` components.
+///
+/// A single number without unit is taken as seconds.
+fn components(input: &str) -> Result<Vec<(f64, u64)>, String> {
+    let mut components = vec![];
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let amount: f64 = rest[..number_len]
+            .parse()
+            .map_err(|_| format!("Expected a number at '{}'", rest))?;
+        rest = rest[number_len..].trim_start();
+
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = rest[..unit_len].to_lowercase();
+        rest = rest[unit_len..].trim_start();
+
+        let millis = if unit.is_empty() && components.is_empty() && rest.is_empty() {
+            1_000
+        } else {
+            UNITS
+                .iter()
+                .find(|(names, _)| names.contains(&unit.as_str()))
+                .map(|&(_, millis)| millis)
+                .ok_or_else(|| match unit.as_str() {
+                    "" => format!("Missing unit after {}", amount),
+                    unit => format!("Unknown unit '{}'", unit),
+                })?
+        };
+
+        components.push((amount, millis));
+    }
+
+    if components.is_empty() {
+        return Err("Expected a duration like 90s, 1h30m or 2d".into());
+    }
+
+    Ok(components)
+}
+
+/// Parses a human-friendly duration like `90s`, `1h30m` or `2d`.
+pub(crate) fn parse_duration(input: &str) -> Result<Duration, String> {
+    let millis: f64 = components(input)?
+        .iter()
+        .map(|&(amount, millis)| amount * millis as f64)
+        .sum();
+
+    Ok(Duration::from_millis(millis.round() as u64))
+}
+
+/// Formats `duration` in its canonical form like `1h 30m`.
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let mut millis = duration.as_millis() as u64;
+    let mut parts = vec![];
+
+    // Weeks are left out as "2w 3d" reads worse than "17d".
+    for &(names, length) in &UNITS[1..] {
+        if millis >= length {
+            parts.push(format!("{}{}", millis / length, names[0]));
+            millis %= length;
+        }
+    }
+
+    if parts.is_empty() {
+        "0s".into()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Describes `duration` in seconds, like `= 5400 seconds`.
+fn describe_duration(input: &str, duration: Duration) -> String {
+    let seconds = if duration.subsec_millis() == 0 {
+        duration.as_secs().to_string()
+    } else {
+        format!("{:.3}", duration.as_secs_f64())
+    };
+    let unit = if seconds == "1" { "second" } else { "seconds" };
+
+    let canonical = format_duration(duration);
+    if canonical.replace(' ', "") == input.replace(' ', "") {
+        format!("= {} {}", seconds, unit)
+    } else {
+        format!("= {} = {} {}", canonical, seconds, unit)
+    }
+}
+
+/// Steps the duration in `input` up or down by one of the unit given last.
+///
+/// Invalid input is taken as zero and stepped by seconds.
+pub(crate) fn step_duration(input: &str, up: bool) -> String {
+    let step = components(input)
+        .ok()
+        .and_then(|components| components.last().map(|&(_, millis)| millis))
+        .unwrap_or(1_000);
+    let current = parse_duration(input).unwrap_or_default().as_millis() as u64;
+
+    let stepped = if up {
+        current.saturating_add(step)
+    } else {
+        current.saturating_sub(step)
+    };
+
+    format_duration(Duration::from_millis(stepped))
+}
+
+/// Renders a duration input prompt.
+///
+/// Durations are typed in a human-friendly form like `90s`, `1h30m`, `2d` or `1.5h`.
+/// While typing the parsed duration is shown next to the input. 'Up' and 'Down' step the
+/// duration by one of the unit typed last.
+/// Interaction returns a [`Duration`].
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::DurationInput;
+/// use std::time::Duration;
+///
+/// let timeout = DurationInput::new()
+///     .with_prompt("Timeout")
+///     .default(Duration::from_secs(90))
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DurationInput<'a> {
+    prompt: String,
+    report: bool,
+    default: Option<Duration>,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for DurationInput<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DurationInput<'static> {
+    /// Creates a duration input prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl DurationInput<'_> {
+    /// Sets the duration input prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the entered duration after interaction.
+    ///
+    /// The default is to report the entered duration.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Sets the initial duration.
+    pub fn default(&mut self, val: Duration) -> &mut Self {
+        self.default = Some(val);
+        self
+    }
+
+    /// Gives up waiting for the duration after `timeout`, resolving to the
+    /// [default](Self::default), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the duration if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<Duration> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(duration)` if user hit 'Enter' or `None` if user cancelled with 'Esc'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<Duration>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Duration> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<Duration> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Duration>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<Duration>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            let default = self
+                .default
+                .ok_or_else(|| fallback::no_default(&self.prompt))?;
+            if self.report {
+                render.duration_input_prompt_selection(&self.prompt, &format_duration(default))?;
+            }
+            term.flush()?;
+
+            return Ok(Some(default));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut state = DurationInputState::new().allow_quit(allow_quit);
+        if let Some(default) = self.default {
+            state = state.initial_duration(default);
+        }
+
+        loop {
+            let description = state
+                .duration()
+                .map(|duration| describe_duration(state.buffer(), duration));
+
+            term.clear_line()?;
+            render.duration_input_prompt(&self.prompt, state.buffer(), description.as_deref())?;
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    term.clear_line()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(duration) => {
+                    term.clear_line()?;
+                    render.clear()?;
+
+                    if self.report {
+                        render.duration_input_prompt_selection(
+                            &self.prompt,
+                            &format_duration(duration),
+                        )?;
+                    }
+
+                    term.flush()?;
+
+                    return Ok(Some(duration));
+                }
+                _ => match state.error() {
+                    Some(err) => {
+                        term.clear_line()?;
+                        render.error(err)?;
+                    }
+                    None => render.reject_key(&key)?,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl DurationInput<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Duration>> {
+        crate::task::interact_async(std::mem::take(self), |input| input.interact())
+    }
+}
+
+impl<'a> DurationInput<'a> {
+    /// Creates a duration input prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            default: None,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("1h 30 min"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("2d"), Ok(Duration::from_secs(172_800)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("45"), Ok(Duration::from_secs(45)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("3 fortnights").is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(5400)), "1h 30m");
+        assert_eq!(format_duration(Duration::from_secs(1_209_600)), "14d");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1s 500ms");
+        assert_eq!(format_duration(Duration::from_secs(0)), "0s");
+    }
+
+    #[test]
+    fn test_describe_and_step() {
+        assert_eq!(
+            describe_duration("1h30m", Duration::from_secs(5400)),
+            "= 5400 seconds"
+        );
+        assert_eq!(
+            describe_duration("90m", Duration::from_secs(5400)),
+            "= 1h 30m = 5400 seconds"
+        );
+        assert_eq!(step_duration("1h30m", true), "1h 31m");
+        assert_eq!(step_duration("2d", false), "1d");
+        assert_eq!(step_duration("", false), "0s");
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err =
+            fallback::test_without_tty(|| DurationInput::new().interact_on(&term)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,356 @@
+use std::{io, time::Duration};
+
+use crate::{
+    backend::{default_term, with_keys},
+    fallback,
+    paging::Paging,
+    state::{EditListState, Transition},
+    theme::{SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout, Backend, Input,
+};
+
+use console::Key;
+
+/// Renders a prompt for editing a list of entries.
+///
+/// The current entries are rendered as a list. 'a' adds an entry below the highlighted
+/// one, 'e' (or 'Space') edits the highlighted entry, 'd' (or 'Del') deletes it and 'K'/'J'
+/// move it up or down. New and edited entries are typed into an inline input; submitting
+/// it empty leaves the list unchanged. 'Enter' confirms the list.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::EditList;
+///
+/// let hosts = EditList::new()
+///     .with_prompt("Allowed hosts")
+///     .items(&["localhost", "example.com"])
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct EditList<'a> {
+    items: Vec<String>,
+    prompt: String,
+    entry_prompt: String,
+    report: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for EditList<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EditList<'static> {
+    /// Creates a list editing prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl EditList<'_> {
+    /// Sets the list editing prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Sets the prompt of the inline input used for adding and editing entries.
+    ///
+    /// The default is "Entry".
+    pub fn entry_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.entry_prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the entries after interaction.
+    ///
+    /// The default is to report the entries.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Adds a single entry to the initial list.
+    pub fn item<T: ToString>(&mut self, item: T) -> &mut Self {
+        self.items.push(item.to_string());
+        self
+    }
+
+    /// Adds multiple entries to the initial list.
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Self {
+        for item in items {
+            self.items.push(item.to_string());
+        }
+        self
+    }
+
+    /// Gives up waiting for the edits after `timeout`, resolving to the
+    /// [items](Self::items) unchanged.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the edited entries if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<Vec<String>> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(entries)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<Vec<String>>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Vec<String>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(
+        &self,
+        keys: K,
+    ) -> io::Result<Vec<String>> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Vec<String>>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    /// Asks for the text of an entry, returns `None` if the input was left empty.
+    fn read_entry(&self, term: &dyn Backend, initial: &str) -> io::Result<Option<String>> {
+        term.show_cursor()?;
+
+        let entry: String = Input::with_theme(self.theme.clone())
+            .with_prompt(self.entry_prompt.as_str())
+            .with_initial_text(initial)
+            .allow_empty(true)
+            .report(false)
+            .interact_text_on(term)?;
+
+        term.hide_cursor()?;
+
+        let entry = entry.trim();
+        Ok(if entry.is_empty() {
+            None
+        } else {
+            Some(entry.to_string())
+        })
+    }
+
+    fn _interact_on(
+        &self,
+        term: &dyn Backend,
+        allow_quit: bool,
+    ) -> io::Result<Option<Vec<String>>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            if self.report {
+                render.edit_list_prompt_selection(&self.prompt, &self.items)?;
+            }
+            term.flush()?;
+
+            return Ok(Some(self.items.clone()));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut state = EditListState::new(self.items.clone()).allow_quit(allow_quit);
+        let mut paging = Paging::new(term, state.items().len(), None, 0).scrolling(true);
+
+        render.hide_cursor()?;
+
+        loop {
+            paging.set_items_len(state.items().len(), true);
+            paging.update(state.selection())?;
+
+            render.edit_list_prompt(&self.prompt)?;
+
+            if state.items().is_empty() {
+                render.edit_list_empty()?;
+            }
+
+            render.set_scrollbar(paging.scrollbar());
+            for (idx, item) in state
+                .items()
+                .iter()
+                .enumerate()
+                .skip(paging.first_item())
+                .take(paging.capacity)
+            {
+                render.edit_list_item(item, idx == state.selection())?;
+            }
+            render.set_scrollbar(None);
+
+            term.flush()?;
+
+            let key = term.read_key()?;
+            // Entries are added and edited in place of the list.
+            render.clear()?;
+
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {
+                    if let Some(initial) = state.entry() {
+                        let entry = self.read_entry(term, initial)?;
+                        state.submit_entry(entry);
+                    }
+                }
+                Transition::Cancelled => {
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(items) => {
+                    if self.report {
+                        render.edit_list_prompt_selection(&self.prompt, &items)?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(items));
+                }
+                _ => render.reject_key(&key)?,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl EditList<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Vec<String>>> {
+        crate::task::interact_async(std::mem::take(self), |edit_list| edit_list.interact())
+    }
+}
+
+impl<'a> EditList<'a> {
+    /// Creates a list editing prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            items: vec![],
+            prompt: "".into(),
+            entry_prompt: "Entry".into(),
+            report: true,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Headless;
+
+    fn edit(items: &[&str], keys: Vec<Key>) -> Vec<String> {
+        let term = Headless::new(10, 40)
+            .colors(false)
+            .keys(keys.into_iter().chain(Some(Key::Enter)));
+        EditList::new().items(items).interact_on(&term).unwrap()
+    }
+
+    fn typed(text: &str) -> impl Iterator<Item = Key> + '_ {
+        text.chars().map(Key::Char).chain(Some(Key::Enter))
+    }
+
+    #[test]
+    fn test_reorder() {
+        let keys = vec![Key::Char('J'), Key::Char('J'), Key::Char('J')];
+        assert_eq!(edit(&["a", "b", "c"], keys), ["b", "c", "a"]);
+        let keys = vec![Key::ArrowUp, Key::Char('K'), Key::Char('K'), Key::Char('K')];
+        assert_eq!(edit(&["a", "b", "c"], keys), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_insert_and_edit() {
+        let keys = Some(Key::Char('a')).into_iter().chain(typed("b")).collect();
+        assert_eq!(edit(&["a", "c"], keys), ["a", "b", "c"]);
+
+        let keys = vec![Key::ArrowDown, Key::Char('e'), Key::Backspace]
+            .into_iter()
+            .chain(typed("x"))
+            .collect();
+        assert_eq!(edit(&["a", "b"], keys), ["a", "x"]);
+
+        // Entries left empty don't change the list.
+        let keys = vec![Key::Char('a'), Key::Enter];
+        assert_eq!(edit(&["a"], keys), ["a"]);
+        let keys = vec![Key::Char('e'), Key::Backspace, Key::Enter];
+        assert_eq!(edit(&["a"], keys), ["a"]);
+    }
+
+    #[test]
+    fn test_delete() {
+        let keys = vec![Key::ArrowDown, Key::Char('d')];
+        assert_eq!(edit(&["a", "b", "c"], keys), ["a", "c"]);
+        // The highlight moves up when the last entry is deleted.
+        let keys = vec![Key::ArrowUp, Key::Char('d'), Key::Del];
+        assert_eq!(edit(&["a", "b", "c"], keys), ["a"]);
+    }
+
+    #[test]
+    fn test_empty_list() {
+        let keys = vec![
+            Key::Char('d'),
+            Key::Char('J'),
+            Key::Char('e'),
+            Key::ArrowDown,
+        ];
+        assert!(edit(&[], keys).is_empty());
+        let keys = vec![Key::Char('d'), Key::Del];
+        assert!(edit(&["a"], keys).is_empty());
+
+        let keys = Some(Key::Char('a')).into_iter().chain(typed("x")).collect();
+        assert_eq!(edit(&[], keys), ["x"]);
+    }
+
+    #[test]
+    fn test_scrolls_to_highlight() {
+        let items: Vec<_> = (0..20).map(|idx| idx.to_string()).collect();
+        let term = Headless::new(6, 40).colors(false).keys(vec![Key::ArrowUp]);
+        let _ = EditList::new()
+            .with_prompt("Hosts")
+            .items(&items)
+            .interact_on(&term);
+
+        assert_eq!(term.screen(), "Hosts:\n  16\n  17\n  18\n> 19");
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| EditList::new().interact_on(&term)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -1,7 +1,13 @@
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
-use console::{Key, Term};
-use fuzzy_matcher::FuzzyMatcher;
-use std::{io, ops::Rem};
+use crate::{
+    backend::{default_term, with_keys, RepeatedKeys},
+    events, fallback,
+    paging::Paging,
+    state::{FuzzySelectState, Transition},
+    theme::{SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout, updates, Backend, ItemUpdate,
+};
+use console::Key;
+use std::{io, sync::mpsc::Receiver, time::Duration};
 
 /// Renders a selection menu that user can fuzzy match to reduce set.
 ///
@@ -32,7 +38,6 @@ use std::{io, ops::Rem};
 ///     Ok(())
 /// }
 /// ```
-
 pub struct FuzzySelect<'a> {
     default: usize,
     items: Vec<String>,
@@ -40,7 +45,11 @@ pub struct FuzzySelect<'a> {
     report: bool,
     clear: bool,
     highlight_matches: bool,
-    theme: &'a dyn Theme,
+    alt_screen: bool,
+    bottom: bool,
+    timeout: Option<Duration>,
+    updates: Option<Receiver<ItemUpdate>>,
+    theme: ThemeRef<'a>,
 }
 
 impl Default for FuzzySelect<'static> {
@@ -110,181 +119,230 @@ impl FuzzySelect<'_> {
         self
     }
 
+    /// Indicates whether the prompt renders in the alternate screen.
+    ///
+    /// The primary screen is restored when the prompt ends, showing only the reported
+    /// selection. The default is to render inline.
+    pub fn render_in_alt_screen(&mut self, val: bool) -> &mut Self {
+        self.alt_screen = val;
+        self
+    }
+
+    /// Indicates whether the prompt renders pinned to the bottom of the terminal.
+    ///
+    /// Like fzf, the prompt is drawn on the last rows of the terminal with the earlier output
+    /// left above it, instead of right below that output. The default is to render right
+    /// below.
+    pub fn render_at_bottom(&mut self, val: bool) -> &mut Self {
+        self.bottom = val;
+        self
+    }
+
+    /// Gives up waiting for the selection after `timeout`, resolving to the
+    /// [default](Self::default), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Changes the items while the prompt is open with the updates received from `updates`,
+    /// like in [`Select::item_updates`](crate::Select::item_updates).
+    ///
+    /// The matches are updated with the items, and the cursor stays on its item while it
+    /// matches.
+    pub fn item_updates(&mut self, updates: Receiver<ItemUpdate>) -> &mut Self {
+        self.updates = Some(updates);
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// The user can select the items using 'Enter' and the index of selected item will be returned.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     /// Result contains `index` of selected item if user hit 'Enter'.
     /// This unlike [interact_opt](#method.interact_opt) does not allow to quit with 'Esc' or 'q'.
     #[inline]
     pub fn interact(&self) -> io::Result<usize> {
-        self.interact_on(&Term::stderr())
+        self.interact_on(&*default_term())
     }
 
     /// Enables user interaction and returns the result.
     ///
     /// The user can select the items using 'Enter' and the index of selected item will be returned.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     /// Result contains `Some(index)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
     #[inline]
     pub fn interact_opt(&self) -> io::Result<Option<usize>> {
-        self.interact_on_opt(&Term::stderr())
+        self.interact_on_opt(&*default_term())
     }
 
     /// Like `interact` but allows a specific terminal to be set.
     #[inline]
-    pub fn interact_on(&self, term: &Term) -> io::Result<usize> {
-        self._interact_on(term, false)?
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<usize> {
+        timeout::ask(term, self.timeout, |term| {
+            events::observe_opt(term, |term| self._interact_on(term, false))
+        })?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<usize> {
+        with_keys(keys, |term| self.interact_on(term))
     }
 
     /// Like `interact` but allows a specific terminal to be set.
     #[inline]
-    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<usize>> {
-        self._interact_on(term, true)
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<usize>> {
+        timeout::ask(term, self.timeout, |term| {
+            events::observe_opt(term, |term| self._interact_on(term, true))
+        })
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<usize>> {
-        let mut position = 0;
-        let mut search_term = String::new();
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<usize>> {
+        if fallback::defaults_mode() {
+            if self.default >= self.items.len() {
+                return Err(fallback::no_default(&self.prompt));
+            }
 
-        let mut render = TermThemeRenderer::new(term, self.theme);
-        let mut sel = self.default;
+            let mut render = TermThemeRenderer::new(term, &*self.theme);
+            if self.report {
+                render.input_prompt_selection(&self.prompt, &self.items[self.default])?;
+            }
+            term.flush()?;
 
-        let mut size_vec = Vec::new();
-        for items in self.items.iter().as_slice() {
-            let size = &items.len();
-            size_vec.push(*size);
+            return Ok(Some(self.default));
         }
 
-        // Fuzzy matcher
-        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+        fallback::require_tty(term)?;
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let mut state = FuzzySelectState::new(self.items.clone())
+            .default(if self.default == !0 {
+                None
+            } else {
+                Some(self.default)
+            })
+            .allow_quit(allow_quit);
+
+        // The visible matches scroll with the cursor, without a page indicator.
+        let mut paging = Paging::new(term, self.items.len(), None, 0)
+            .scrolling(true)
+            .scroll_context(Some(0));
 
-        // Subtract -2 because we need space to render the prompt.
-        let visible_term_rows = (term.size().0 as usize).max(3) - 2;
-        // Variable used to determine if we need to scroll through the list.
-        let mut starting_row = 0;
+        if self.alt_screen {
+            render.enter_alternate_screen()?;
+        }
+        if self.bottom {
+            render.anchor_to_bottom();
+        }
+        render.hide_cursor()?;
 
-        term.hide_cursor()?;
+        let mut keys = RepeatedKeys::default();
+        let mut active = None;
 
         loop {
             render.clear()?;
-            render.fuzzy_select_prompt(self.prompt.as_str(), &search_term, position)?;
+            render.fuzzy_select_prompt(
+                self.prompt.as_str(),
+                state.search_term(),
+                state.position(),
+            )?;
 
-            // Maps all items to a tuple of item and its match score.
-            let mut filtered_list = self
-                .items
-                .iter()
-                .map(|item| (item, matcher.fuzzy_match(item, &search_term)))
-                .filter_map(|(item, score)| score.map(|s| (item, s)))
-                .collect::<Vec<_>>();
+            paging.set_items_len(state.matches().len(), true);
+            paging.update(state.selection().unwrap_or(!0))?;
 
-            // Renders all matching items, from best match to worst.
-            filtered_list.sort_unstable_by(|(_, s1), (_, s2)| s2.cmp(s1));
+            let current = state.selected_item();
+            events::selection_changed(active, current);
+            active = current;
 
-            for (idx, (item, _)) in filtered_list
+            for (idx, &item) in state
+                .matches()
                 .iter()
                 .enumerate()
-                .skip(starting_row)
-                .take(visible_term_rows)
+                .skip(paging.first_item())
+                .take(paging.capacity)
             {
                 render.fuzzy_select_prompt_item(
-                    item,
-                    idx == sel,
+                    &state.items()[item],
+                    state.selection() == Some(idx),
                     self.highlight_matches,
-                    &matcher,
-                    &search_term,
+                    state.search_term(),
                 )?;
                 term.flush()?;
             }
 
-            match term.read_key()? {
-                Key::Escape if allow_quit => {
-                    if self.clear {
-                        term.clear_last_lines(filtered_list.len())?;
-                        term.flush()?;
-                    }
-                    term.show_cursor()?;
-                    return Ok(None);
-                }
-                Key::ArrowUp | Key::BackTab if !filtered_list.is_empty() => {
-                    if sel == 0 {
-                        starting_row =
-                            filtered_list.len().max(visible_term_rows) - visible_term_rows;
-                    } else if sel == starting_row {
-                        starting_row -= 1;
-                    }
-                    if sel == !0 {
-                        sel = filtered_list.len() - 1;
-                    } else {
-                        sel = ((sel as i64 - 1 + filtered_list.len() as i64)
-                            % (filtered_list.len() as i64)) as usize;
-                    }
-                    term.flush()?;
-                }
-                Key::ArrowDown | Key::Tab if !filtered_list.is_empty() => {
-                    if sel == !0 {
-                        sel = 0;
-                    } else {
-                        sel = (sel as u64 + 1).rem(filtered_list.len() as u64) as usize;
-                    }
-                    if sel == visible_term_rows + starting_row {
-                        starting_row += 1;
-                    } else if sel == 0 {
-                        starting_row = 0;
-                    }
-                    term.flush()?;
-                }
-                Key::ArrowLeft if position > 0 => {
-                    position -= 1;
-                    term.flush()?;
-                }
-                Key::ArrowRight if position < search_term.len() => {
-                    position += 1;
-                    term.flush()?;
-                }
-                Key::Enter if !filtered_list.is_empty() => {
-                    if self.clear {
-                        render.clear()?;
-                    }
+            render.finish_frame()?;
+            term.flush()?;
+
+            let updates = updates::wait(term, self.updates.as_ref(), keys.pending())?;
+            if !updates.is_empty() {
+                let mut items = state.items().to_vec();
+                let moved = updates::apply(&mut items, updates);
+                let followed = active.and_then(|idx: usize| moved[idx]);
+                state.set_items(items, followed);
+                render.rewind_preserve_prompt()?;
+                continue;
+            }
 
-                    if self.report {
-                        render
-                            .input_prompt_selection(self.prompt.as_str(), filtered_list[sel].0)?;
+            let (key, repeats) = keys.read(term)?;
+            for _ in 0..repeats {
+                match state.handle_key(key.clone()) {
+                    Transition::Continue => {}
+                    Transition::Cancelled => {
+                        if self.clear {
+                            render.clear()?;
+                            term.flush()?;
+                        }
+                        render.leave_alternate_screen()?;
+                        render.show_cursor()?;
+                        return Ok(None);
                     }
-
-                    let sel_string = filtered_list[sel].0;
-                    let sel_string_pos_in_items =
-                        self.items.iter().position(|item| item.eq(sel_string));
-
-                    term.show_cursor()?;
-                    return Ok(sel_string_pos_in_items);
-                }
-                Key::Backspace if position > 0 => {
-                    position -= 1;
-                    search_term.remove(position);
-                    term.flush()?;
-                }
-                Key::Char(chr) if !chr.is_ascii_control() => {
-                    search_term.insert(position, chr);
-                    position += 1;
-                    term.flush()?;
-                    sel = 0;
-                    starting_row = 0;
+                    Transition::Done(idx) => {
+                        if self.clear {
+                            render.clear()?;
+                        }
+
+                        render.leave_alternate_screen()?;
+
+                        if self.report {
+                            render.input_prompt_selection(
+                                self.prompt.as_str(),
+                                &state.items()[idx],
+                            )?;
+                        }
+
+                        render.show_cursor()?;
+                        return Ok(Some(idx));
+                    }
+                    _ => render.reject_key(&key)?,
                 }
-
-                _ => {}
             }
 
-            render.clear_preserve_prompt(&size_vec)?;
+            render.rewind_preserve_prompt()?;
         }
     }
 }
 
+#[cfg(feature = "tokio")]
+impl FuzzySelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<usize>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
+    }
+}
+
 impl<'a> FuzzySelect<'a> {
     /// Same as `new` but with a specific theme.
-    pub fn with_theme(theme: &'a dyn Theme) -> Self {
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
         Self {
             default: !0,
             items: vec![],
@@ -292,7 +350,25 @@ impl<'a> FuzzySelect<'a> {
             report: true,
             clear: true,
             highlight_matches: true,
-            theme,
+            alt_screen: false,
+            bottom: false,
+            timeout: None,
+            updates: None,
+            theme: theme.into(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err =
+            fallback::test_without_tty(|| FuzzySelect::new().items(&["a", "b"]).interact_on(&term))
+                .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
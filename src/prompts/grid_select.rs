@@ -0,0 +1,342 @@
+use std::{io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{GridSelectState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::width::text_width;
+use crate::Backend;
+
+use console::{pad_str, Alignment, Key};
+
+/// The number of characters the themes render around every cell.
+const CELL_PADDING: usize = 3;
+
+/// Returns the number of columns of `width` characters fitting into `available` characters.
+fn fitting_columns(width: usize, available: usize) -> usize {
+    (available / (width + CELL_PADDING)).max(1)
+}
+
+/// Renders a select prompt laying out short items in a grid.
+///
+/// The arrow keys move the selection in two dimensions, 'PageUp'/'PageDown' move by a
+/// screenful of rows. Unless set with [`columns`](Self::columns) the number of columns is
+/// derived from the terminal width.
+/// Interaction returns the index of the selected item in the flat list of items.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::GridSelect;
+///
+/// fn main() -> std::io::Result<()> {
+///     let months = [
+///         "Jan", "Feb", "Mar", "Apr", "May", "Jun",
+///         "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+///     ];
+///     let month = GridSelect::new()
+///         .with_prompt("Month")
+///         .items(&months)
+///         .columns(4)
+///         .interact()?;
+///
+///     println!("Picked {}", months[month]);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct GridSelect<'a> {
+    default: usize,
+    items: Vec<String>,
+    prompt: Option<String>,
+    report: bool,
+    clear: bool,
+    columns: Option<usize>,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for GridSelect<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GridSelect<'static> {
+    /// Creates a grid select prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl GridSelect<'_> {
+    /// Indicates whether the grid should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the grid.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Sets the initially selected item.
+    pub fn default(&mut self, val: usize) -> &mut Self {
+        self.default = val;
+        self
+    }
+
+    /// Sets the number of columns of the grid.
+    ///
+    /// The number is reduced if the columns don't fit into the terminal. By default as
+    /// many columns as fit into the terminal are used.
+    pub fn columns(&mut self, val: usize) -> &mut Self {
+        self.columns = Some(val.max(1));
+        self
+    }
+
+    /// Adds a single item to the grid.
+    pub fn item<T: ToString>(&mut self, item: T) -> &mut Self {
+        self.items.push(item.to_string());
+        self
+    }
+
+    /// Adds multiple items to the grid.
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Self {
+        for item in items {
+            self.items.push(item.to_string());
+        }
+        self
+    }
+
+    /// Sets the grid select prompt.
+    ///
+    /// By default, when a prompt is set the system also prints out a confirmation after
+    /// the selection. You can opt-out of this with [`report`](#method.report).
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self.report = true;
+        self
+    }
+
+    /// Indicates whether to report the selected item after interaction.
+    ///
+    /// The default is to report the selection.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Gives up waiting for the selection after `timeout`, resolving to the
+    /// [default](Self::default).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the index of the item if user hit 'Enter' or 'Space'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<usize> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(index)` if user hit 'Enter' or 'Space' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<usize>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<usize> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<usize> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<usize>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<usize>> {
+        if self.items.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of items given to `GridSelect`",
+            ));
+        }
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let mut state = GridSelectState::new(self.items.len())
+            .default(self.default)
+            .allow_quit(allow_quit);
+
+        if fallback::defaults_mode() {
+            if let Some(ref prompt) = self.prompt {
+                if self.report {
+                    render.select_prompt_selection(prompt, &self.items[state.selection()])?;
+                }
+            }
+            term.flush()?;
+
+            return Ok(Some(state.selection()));
+        }
+
+        fallback::require_tty(term)?;
+
+        let width = self
+            .items
+            .iter()
+            .map(|item| text_width(item))
+            .max()
+            .unwrap_or(0);
+        let cells: Vec<String> = self
+            .items
+            .iter()
+            .map(|item| pad_str(item, width, Alignment::Left, None).into_owned())
+            .collect();
+
+        render.hide_cursor()?;
+
+        loop {
+            let fitting = fitting_columns(width, term.size().1 as usize);
+            let columns = self.columns.map_or(fitting, |columns| columns.min(fitting));
+            let rows = (cells.len() + columns - 1) / columns;
+            // Subtract -2 because we need space to render the prompt, and the footer below.
+            let term_rows = (term.size().0 as usize).saturating_sub(render.footer_rows(false));
+            let page_rows = term_rows.max(3) - 2;
+            let pages = (rows + page_rows - 1) / page_rows;
+            let sel = state.selection();
+            let page = sel / columns / page_rows;
+            let paging_info = if pages > 1 {
+                Some((page + 1, Some(pages)))
+            } else {
+                None
+            };
+
+            if let Some(ref prompt) = self.prompt {
+                render.select_prompt(prompt, paging_info)?;
+            }
+
+            for (row_idx, row) in cells
+                .chunks(columns)
+                .enumerate()
+                .skip(page * page_rows)
+                .take(page_rows)
+            {
+                let active = if sel / columns == row_idx {
+                    Some(sel % columns)
+                } else {
+                    None
+                };
+                render.grid_select_row(row, active)?;
+            }
+
+            if let Some(paging_info) = paging_info {
+                render.paging_info_footer(paging_info)?;
+            }
+
+            term.flush()?;
+
+            state.set_layout(columns, page_rows);
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(sel) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    if let Some(ref prompt) = self.prompt {
+                        if self.report {
+                            render.select_prompt_selection(prompt, &self.items[sel])?;
+                        }
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(sel));
+                }
+                _ => render.reject_key(&key)?,
+            }
+
+            render.clear()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl GridSelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<usize>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
+    }
+}
+
+impl<'a> GridSelect<'a> {
+    /// Creates a grid select prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            default: 0,
+            items: vec![],
+            prompt: None,
+            report: false,
+            clear: true,
+            columns: None,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fitting_columns() {
+        assert_eq!(fitting_columns(3, 80), 13);
+        assert_eq!(fitting_columns(2, 10), 2);
+        assert_eq!(fitting_columns(100, 80), 1);
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err =
+            fallback::test_without_tty(|| GridSelect::new().items(&["a", "b"]).interact_on(&term))
+                .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
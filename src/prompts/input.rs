@@ -1,15 +1,103 @@
-use std::{fmt::Debug, io, iter, str::FromStr};
+use std::{fmt::Debug, io, str::FromStr, time::Duration};
 
+#[cfg(all(feature = "completion", feature = "async"))]
+use crate::completion::AsyncCompletion;
 #[cfg(feature = "completion")]
 use crate::completion::Completion;
 #[cfg(feature = "history")]
 use crate::history::History;
+#[cfg(feature = "completion")]
+use crate::loading::{Loading, SPIN_INTERVAL};
 use crate::{
-    theme::{SimpleTheme, TermThemeRenderer, Theme},
-    validate::Validator,
+    answers,
+    backend::{default_term, with_keys},
+    events, fallback,
+    state::{InputState, Transition},
+    theme::{Redaction, SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout,
+    validate::{TransformingValidator, Validator},
+    width::{char_width, text_width},
+    Backend,
 };
 
-use console::{Key, Term};
+use console::Key;
+
+type ValidatorCallback<'a, T> = Box<dyn FnMut(&T) -> Option<String> + Send + 'a>;
+type TransformCallback<'a, T> = Box<dyn Fn(&str) -> Result<T, String> + Send + 'a>;
+
+/// Returns the number of terminal columns `chars` take up.
+fn columns(chars: &[char]) -> usize {
+    chars.iter().map(|&c| char_width(c)).sum()
+}
+
+/// Renders the edit of the input by `key`, which `state` handled, in place. `removed` is the
+/// character in front of the cursor before, which 'Backspace' removed.
+fn render_edit(
+    term: &dyn Backend,
+    state: &InputState,
+    key: &Key,
+    removed: Option<char>,
+) -> io::Result<()> {
+    let chars = state.chars();
+    let position = state.position();
+
+    match *key {
+        Key::Backspace => {
+            term.clear_chars(removed.map_or(0, char_width))?;
+
+            let tail: String = chars[position..].iter().collect();
+            if !tail.is_empty() {
+                term.write_str(&tail)?;
+                term.move_cursor_left(text_width(&tail))?;
+            }
+        }
+        Key::Char(chr) => {
+            let tail: String = chars[position - 1..].iter().collect();
+            term.write_str(&tail)?;
+            term.move_cursor_left(text_width(&tail) - char_width(chr))?;
+        }
+        Key::ArrowLeft => term.move_cursor_left(char_width(chars[position]))?,
+        Key::ArrowRight => term.move_cursor_right(char_width(chars[position - 1]))?,
+        _ => {}
+    }
+    term.flush()
+}
+
+/// Replaces the input in `state` and on the terminal with `text`.
+#[cfg(any(feature = "completion", feature = "history"))]
+fn replace_input(term: &dyn Backend, state: &mut InputState, text: &str) -> io::Result<()> {
+    term.clear_chars(columns(&state.chars()[..state.position()]))?;
+    state.set_text(text);
+    term.write_str(text)?;
+    term.flush()
+}
+
+/// Writes `text` after the input `chars` in place of the `previous` columns written there,
+/// keeping the cursor at `position`, and returns the columns of `text`.
+#[cfg(feature = "completion")]
+fn write_after_input(
+    term: &dyn Backend,
+    chars: &[char],
+    position: usize,
+    previous: usize,
+    text: &str,
+) -> io::Result<usize> {
+    let tail = columns(&chars[position..]);
+    let width = text_width(text);
+    let blanks = previous.saturating_sub(width);
+
+    if tail > 0 {
+        term.move_cursor_right(tail)?;
+    }
+    term.write_str(text)?;
+    term.write_str(&" ".repeat(blanks))?;
+    if tail + width + blanks > 0 {
+        term.move_cursor_left(tail + width + blanks)?;
+    }
+    term.flush()?;
+
+    Ok(width)
+}
 
 /// Renders an input prompt.
 ///
@@ -42,14 +130,19 @@ pub struct Input<'a, T> {
     report: bool,
     default: Option<T>,
     show_default: bool,
+    redaction: Option<Redaction>,
     initial_text: Option<String>,
-    theme: &'a dyn Theme,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
     permit_empty: bool,
-    validator: Option<Box<dyn FnMut(&T) -> Option<String> + 'a>>,
+    validator: Option<ValidatorCallback<'a, T>>,
+    transform: Option<TransformCallback<'a, T>>,
     #[cfg(feature = "history")]
-    history: Option<&'a mut dyn History<T>>,
+    history: Option<&'a mut (dyn History<T> + Send)>,
     #[cfg(feature = "completion")]
-    completion: Option<&'a dyn Completion>,
+    completion: Option<&'a (dyn Completion + Sync)>,
+    #[cfg(all(feature = "completion", feature = "async"))]
+    async_completion: Option<&'a (dyn AsyncCompletion + Sync)>,
 }
 
 impl<T> Default for Input<'static, T> {
@@ -112,24 +205,84 @@ impl<T> Input<'_, T> {
         self.show_default = val;
         self
     }
+
+    /// Gives up waiting for the input after `timeout`, resolving to the
+    /// [default](Self::default) or else the initial text, or failing with
+    /// [`timed_out`](crate::timed_out) without either.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown), and counts
+    /// down while typing with [`interact_text`](Self::interact_text).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Masks the input value when it is reported after interaction.
+    ///
+    /// Use this for secrets like API tokens which should not end up in the scrollback.
+    /// The default is to report the value as entered.
+    pub fn redact(&mut self, redaction: Redaction) -> &mut Self {
+        self.redaction = Some(redaction);
+        self
+    }
+
+    /// Reports the input value, masked if redaction is enabled.
+    fn report_selection(&self, render: &mut TermThemeRenderer, sel: &str) -> io::Result<()> {
+        match self.redaction {
+            Some(redaction) => render.redacted_input_prompt_selection(&self.prompt, sel, redaction),
+            None => render.input_prompt_selection(&self.prompt, sel),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> Input<'static, T>
+where
+    T: Clone + ToString + FromStr + Send + 'static,
+    <T as FromStr>::Err: ToString,
+{
+    /// Like [`interact_text`](Self::interact_text) but returns a future, running the prompt
+    /// on the blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_text_async(&mut self) -> impl std::future::Future<Output = io::Result<T>>
+    where
+        <T as FromStr>::Err: Debug,
+    {
+        crate::task::interact_async(std::mem::take(self), |mut input| input.interact_text())
+    }
+
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<T>> {
+        crate::task::interact_async(std::mem::take(self), |mut input| input.interact())
+    }
 }
 
 impl<'a, T> Input<'a, T> {
     /// Creates an input prompt with a specific theme.
-    pub fn with_theme(theme: &'a dyn Theme) -> Self {
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
         Self {
             prompt: "".into(),
             report: true,
             default: None,
             show_default: true,
+            redaction: None,
             initial_text: None,
-            theme,
+            timeout: None,
+            theme: theme.into(),
             permit_empty: false,
             validator: None,
+            transform: None,
             #[cfg(feature = "history")]
             history: None,
             #[cfg(feature = "completion")]
             completion: None,
+            #[cfg(all(feature = "completion", feature = "async"))]
+            async_completion: None,
         }
     }
 
@@ -177,7 +330,7 @@ impl<'a, T> Input<'a, T> {
     #[cfg(feature = "history")]
     pub fn history_with<H>(&mut self, history: &'a mut H) -> &mut Self
     where
-        H: History<T>,
+        H: History<T> + Send,
     {
         self.history = Some(history);
         self
@@ -187,11 +340,38 @@ impl<'a, T> Input<'a, T> {
     #[cfg(feature = "completion")]
     pub fn completion_with<C>(&mut self, completion: &'a C) -> &mut Self
     where
-        C: Completion,
+        C: Completion + Sync,
     {
         self.completion = Some(completion);
         self
     }
+
+    /// Enable completion supplied by a future, like from an LSP-like backend or an HTTP API
+    ///
+    /// The future is polled by the prompt between keys, with a spinner rendered after the
+    /// input until it resolves. Typing discards it, so a completion never replaces text it
+    /// wasn't meant for. It takes precedence over a completion set with
+    /// [`completion_with`](Self::completion_with).
+    #[cfg(all(feature = "completion", feature = "async"))]
+    pub fn completion_with_async<C>(&mut self, completion: &'a C) -> &mut Self
+    where
+        C: AsyncCompletion + Sync,
+    {
+        self.async_completion = Some(completion);
+        self
+    }
+
+    /// Starts completing `input`, if a completion is set.
+    #[cfg(feature = "completion")]
+    fn complete(&self, input: &str) -> Option<Loading<'a, Option<String>>> {
+        #[cfg(feature = "async")]
+        if let Some(completion) = self.async_completion {
+            return Some(Loading::new(completion.get(input)));
+        }
+
+        self.completion
+            .map(|completion| Loading::new(std::future::ready(completion.get(input))))
+    }
 }
 
 impl<'a, T> Input<'a, T>
@@ -218,7 +398,7 @@ where
     /// ```
     pub fn validate_with<V>(&mut self, mut validator: V) -> &mut Self
     where
-        V: Validator<T> + 'a,
+        V: Validator<T> + Send + 'a,
         V::Err: ToString,
     {
         let mut old_validator_func = self.validator.take();
@@ -238,6 +418,34 @@ where
 
         self
     }
+
+    /// Registers a validator turning the text entered into the value, in place of its
+    /// [`FromStr`] implementation.
+    ///
+    /// Validators registered with [`validate_with`](Self::validate_with) check the value
+    /// afterwards. Defaults are values already and aren't transformed. Registering another
+    /// transforming validator replaces this one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use dialoguer::Input;
+    /// let port: u16 = Input::new()
+    ///     .with_prompt("Port")
+    ///     .validate_into(|input: &str| match input.parse::<u16>() {
+    ///         Ok(port) if port >= 1024 => Ok(port),
+    ///         _ => Err("Enter a port from 1024 to 65535"),
+    ///     })
+    ///     .interact_text()
+    ///     .unwrap();
+    /// ```
+    pub fn validate_into<V>(&mut self, validator: V) -> &mut Self
+    where
+        V: TransformingValidator<T> + Send + 'a,
+    {
+        self.transform = Some(Box::new(move |input: &str| validator.validate(input)));
+        self
+    }
 }
 
 impl<T> Input<'_, T>
@@ -250,161 +458,180 @@ where
     /// Its difference from [`interact`](#method.interact) is that it only allows ascii characters for string,
     /// while [`interact`](#method.interact) allows virtually any character to be used e.g arrow keys.
     ///
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     pub fn interact_text(&mut self) -> io::Result<T> {
-        self.interact_text_on(&Term::stderr())
+        self.interact_text_on(&*default_term())
+    }
+
+    /// Like [`interact_text`](#method.interact_text) but reads keys from `keys` instead of
+    /// the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&mut self, keys: K) -> io::Result<T> {
+        with_keys(keys, |term| self.interact_text_on(term))
     }
 
     /// Like [`interact_text`](#method.interact_text) but allows a specific terminal to be set.
-    pub fn interact_text_on(&mut self, term: &Term) -> io::Result<T> {
-        let mut render = TermThemeRenderer::new(term, self.theme);
+    pub fn interact_text_on(&mut self, term: &dyn Backend) -> io::Result<T> {
+        if let Some(value) = self.replayed(term)? {
+            return Ok(value);
+        }
+
+        let value = timeout::ask(term, self.timeout, |term| {
+            events::observe(term, |term| self.ask_text(term))
+        })?;
+        self.record(&value)?;
+
+        Ok(value)
+    }
+
+    /// Renders the prompt and reads the answer by keystroke.
+    fn ask_text(&mut self, term: &dyn Backend) -> io::Result<T> {
+        if fallback::defaults_mode() {
+            let value = self.default_value()?;
+            if self.report {
+                let mut render = TermThemeRenderer::new(term, &*self.theme);
+                self.report_selection(&mut render, &value.to_string())?;
+            }
+            term.flush()?;
+
+            return Ok(value);
+        }
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
 
         loop {
             let default_string = self.default.as_ref().map(ToString::to_string);
+            let shown_default = if self.show_default {
+                default_string.as_deref()
+            } else {
+                None
+            };
 
-            render.input_prompt(
-                &self.prompt,
-                if self.show_default {
-                    default_string.as_deref()
-                } else {
-                    None
-                },
-            )?;
+            render.input_prompt(&self.prompt, shown_default)?;
             term.flush()?;
 
+            if fallback::is_active(term) {
+                drop(render);
+                return fallback::ask_lines(term, |error| self.interact_line(term, error));
+            }
+
             // Read input by keystroke so that we can suppress ascii control characters
-            if !term.features().is_attended() {
+            if !term.is_attended() {
                 return Ok("".to_owned().parse::<T>().unwrap());
             }
 
-            let mut chars: Vec<char> = Vec::new();
-            let mut position = 0;
+            let mut state = InputState::new();
             #[cfg(feature = "history")]
             let mut hist_pos = 0;
+            #[cfg(feature = "completion")]
+            let mut completing: Option<Loading<Option<String>>> = None;
+            // The columns of the spinner after the input while completing.
+            #[cfg(feature = "completion")]
+            let mut spinner = 0;
 
             if let Some(initial) = self.initial_text.as_ref() {
                 term.write_str(initial)?;
-                chars = initial.chars().collect();
-                position = chars.len();
+                state.set_text(initial);
             }
 
-            loop {
-                match term.read_key()? {
-                    Key::Backspace if position > 0 => {
-                        position -= 1;
-                        chars.remove(position);
-                        term.clear_chars(1)?;
-
-                        let tail: String = chars[position..].iter().collect();
-
-                        if !tail.is_empty() {
-                            term.write_str(&tail)?;
-                            term.move_cursor_left(tail.len())?;
-                        }
-
-                        term.flush()?;
-                    }
-                    Key::Char(chr) if !chr.is_ascii_control() => {
-                        chars.insert(position, chr);
-                        position += 1;
-                        let tail: String =
-                            iter::once(&chr).chain(chars[position..].iter()).collect();
-                        term.write_str(&tail)?;
-                        term.move_cursor_left(tail.len() - 1)?;
-                        term.flush()?;
+            let input = loop {
+                #[cfg(feature = "completion")]
+                if let Some(ref mut loading) = completing {
+                    let completion = loading.poll();
+                    let frame = render.loading_inline(loading.frame())?;
+                    let waiting = completion.is_none() && !term.poll_key(SPIN_INTERVAL)?;
+
+                    if waiting {
+                        spinner = write_after_input(
+                            term,
+                            state.chars(),
+                            state.position(),
+                            spinner,
+                            &format!(" {}", frame),
+                        )?;
+                        loading.tick();
+                        continue;
                     }
-                    Key::ArrowLeft if position > 0 => {
-                        term.move_cursor_left(1)?;
-                        position -= 1;
-                        term.flush()?;
-                    }
-                    Key::ArrowRight if position < chars.len() => {
-                        term.move_cursor_right(1)?;
-                        position += 1;
-                        term.flush()?;
+
+                    // A key typed meanwhile discards the completion.
+                    spinner =
+                        write_after_input(term, state.chars(), state.position(), spinner, "")?;
+                    completing = None;
+                    if let Some(x) = completion.flatten() {
+                        replace_input(term, &mut state, &x)?;
+                        continue;
                     }
-                    #[cfg(feature = "completion")]
-                    Key::ArrowRight | Key::Tab => {
-                        if let Some(completion) = &self.completion {
-                            let input: String = chars.clone().into_iter().collect();
-                            if let Some(x) = completion.get(&input) {
-                                term.clear_chars(chars.len())?;
-                                chars.clear();
-                                position = 0;
-                                for ch in x.chars() {
-                                    chars.insert(position, ch);
-                                    position += 1;
-                                }
-                                term.write_str(&x)?;
-                                term.flush()?;
-                            }
+                }
+
+                let key = term.read_key()?;
+                let removed = state
+                    .position()
+                    .checked_sub(1)
+                    .map(|pos| state.chars()[pos]);
+                match state.handle_key(key.clone()) {
+                    Transition::Continue => render_edit(term, &state, &key, removed)?,
+                    Transition::Done(input) => break input,
+                    _ => match key {
+                        #[cfg(feature = "completion")]
+                        Key::ArrowRight | Key::Tab => {
+                            completing = self.complete(&state.text());
                         }
-                    }
-                    #[cfg(feature = "history")]
-                    Key::ArrowUp => {
-                        if let Some(history) = &self.history {
-                            if let Some(previous) = history.read(hist_pos) {
-                                hist_pos += 1;
-                                term.clear_chars(chars.len())?;
-                                chars.clear();
-                                position = 0;
-                                for ch in previous.chars() {
-                                    chars.insert(position, ch);
-                                    position += 1;
+                        #[cfg(feature = "history")]
+                        Key::ArrowUp => {
+                            if let Some(history) = &self.history {
+                                if let Some(previous) = history.read(hist_pos) {
+                                    hist_pos += 1;
+                                    replace_input(term, &mut state, &previous)?;
                                 }
-                                term.write_str(&previous)?;
-                                term.flush()?;
                             }
                         }
-                    }
-                    #[cfg(feature = "history")]
-                    Key::ArrowDown => {
-                        if let Some(history) = &self.history {
-                            // Move the history position back one in case we have up arrowed into it
-                            // and the position is sitting on the next to read
-                            if let Some(pos) = hist_pos.checked_sub(1) {
-                                hist_pos = pos;
-                                // Move it back again to get the previous history entry
-                                if let Some(pos) = pos.checked_sub(1) {
-                                    if let Some(previous) = history.read(pos) {
-                                        term.clear_chars(chars.len())?;
-                                        chars.clear();
-                                        position = 0;
-                                        for ch in previous.chars() {
-                                            chars.insert(position, ch);
-                                            position += 1;
+                        #[cfg(feature = "history")]
+                        Key::ArrowDown => {
+                            if let Some(history) = &self.history {
+                                // Move the history position back one in case we have up arrowed into it
+                                // and the position is sitting on the next to read
+                                if let Some(pos) = hist_pos.checked_sub(1) {
+                                    hist_pos = pos;
+                                    // Move it back again to get the previous history entry
+                                    if let Some(pos) = pos.checked_sub(1) {
+                                        if let Some(previous) = history.read(pos) {
+                                            replace_input(term, &mut state, &previous)?;
                                         }
-                                        term.write_str(&previous)?;
-                                        term.flush()?;
+                                    } else {
+                                        replace_input(term, &mut state, "")?;
                                     }
                                 } else {
-                                    term.clear_chars(chars.len())?;
-                                    chars.clear();
-                                    position = 0;
+                                    replace_input(term, &mut state, "")?;
                                 }
-                            } else {
-                                term.clear_chars(chars.len())?;
-                                chars.clear();
-                                position = 0;
                             }
                         }
-                    }
-                    Key::Enter => break,
-                    Key::Unknown => {
-                        return Err(io::Error::new(
-                            io::ErrorKind::NotConnected,
-                            "Not a terminal",
-                        ))
-                    }
-                    _ => (),
+                        // Attended terminals return unknown keys after resizes.
+                        Key::Unknown if !term.is_attended() => {
+                            return Err(io::Error::new(
+                                io::ErrorKind::NotConnected,
+                                "Not a terminal",
+                            ))
+                        }
+                        Key::Unknown if render.countdown_ticked() => {
+                            if render.input_prompt_again(
+                                &self.prompt,
+                                shown_default,
+                                &state.text(),
+                            )? {
+                                term.move_cursor_left(columns(&state.chars()[state.position()..]))?;
+                            }
+                            term.flush()?;
+                        }
+                        key => render.reject_key(&key)?,
+                    },
                 }
-            }
-            let input = chars.iter().collect::<String>();
+            };
 
             term.clear_line()?;
             render.clear()?;
 
-            if chars.is_empty() {
+            if input.is_empty() {
                 if let Some(ref default) = self.default {
                     if let Some(ref mut validator) = self.validator {
                         if let Some(err) = validator(default) {
@@ -414,7 +641,7 @@ where
                     }
 
                     if self.report {
-                        render.input_prompt_selection(&self.prompt, &default.to_string())?;
+                        self.report_selection(&mut render, &default.to_string())?;
                     }
                     term.flush()?;
                     return Ok(default.clone());
@@ -423,7 +650,7 @@ where
                 }
             }
 
-            match input.parse::<T>() {
+            match self.parse(&input) {
                 Ok(value) => {
                     if let Some(ref mut validator) = self.validator {
                         if let Some(err) = validator(&value) {
@@ -438,14 +665,14 @@ where
                     }
 
                     if self.report {
-                        render.input_prompt_selection(&self.prompt, &input)?;
+                        self.report_selection(&mut render, &input)?;
                     }
                     term.flush()?;
 
                     return Ok(value);
                 }
                 Err(err) => {
-                    render.error(&err.to_string())?;
+                    render.error(&err)?;
                     continue;
                 }
             }
@@ -465,14 +692,39 @@ where
     /// For more limited version, see [`interact_text`](#method.interact_text).
     ///
     /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     pub fn interact(&mut self) -> io::Result<T> {
-        self.interact_on(&Term::stderr())
+        self.interact_on(&*default_term())
     }
 
     /// Like [`interact`](#method.interact) but allows a specific terminal to be set.
-    pub fn interact_on(&mut self, term: &Term) -> io::Result<T> {
-        let mut render = TermThemeRenderer::new(term, self.theme);
+    pub fn interact_on(&mut self, term: &dyn Backend) -> io::Result<T> {
+        if let Some(value) = self.replayed(term)? {
+            return Ok(value);
+        }
+
+        let value = timeout::ask(term, self.timeout, |term| {
+            events::observe(term, |term| self.ask(term))
+        })?;
+        self.record(&value)?;
+
+        Ok(value)
+    }
+
+    /// Renders the prompt and reads the answer as a line.
+    fn ask(&mut self, term: &dyn Backend) -> io::Result<T> {
+        if fallback::defaults_mode() {
+            let value = self.default_value()?;
+            if self.report {
+                let mut render = TermThemeRenderer::new(term, &*self.theme);
+                self.report_selection(&mut render, &value.to_string())?;
+            }
+            term.flush()?;
+
+            return Ok(value);
+        }
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
 
         loop {
             let default_string = self.default.as_ref().map(ToString::to_string);
@@ -487,6 +739,11 @@ where
             )?;
             term.flush()?;
 
+            if fallback::is_active(term) {
+                drop(render);
+                return fallback::ask_lines(term, |error| self.interact_line(term, error));
+            }
+
             let input = if let Some(initial_text) = self.initial_text.as_ref() {
                 term.read_line_initial_text(initial_text)?
             } else {
@@ -507,7 +764,7 @@ where
                     }
 
                     if self.report {
-                        render.input_prompt_selection(&self.prompt, &default.to_string())?;
+                        self.report_selection(&mut render, &default.to_string())?;
                     }
                     term.flush()?;
                     return Ok(default.clone());
@@ -516,7 +773,7 @@ where
                 }
             }
 
-            match input.parse::<T>() {
+            match self.parse(&input) {
                 Ok(value) => {
                     if let Some(ref mut validator) = self.validator {
                         if let Some(err) = validator(&value) {
@@ -526,17 +783,139 @@ where
                     }
 
                     if self.report {
-                        render.input_prompt_selection(&self.prompt, &input)?;
+                        self.report_selection(&mut render, &input)?;
                     }
                     term.flush()?;
 
                     return Ok(value);
                 }
                 Err(err) => {
-                    render.error(&err.to_string())?;
+                    render.error(&err)?;
                     continue;
                 }
             }
         }
     }
+
+    /// Turns `input` into a value with the transforming validator, or else by parsing it.
+    fn parse(&self, input: &str) -> Result<T, String> {
+        match self.transform {
+            Some(ref transform) => transform(input),
+            None => input.parse::<T>().map_err(|err| err.to_string()),
+        }
+    }
+
+    /// Returns the default, or the initial text if there is none, as accepted in defaults
+    /// mode.
+    fn default_value(&mut self) -> io::Result<T> {
+        let invalid = |err: String| io::Error::new(io::ErrorKind::InvalidInput, err);
+
+        let value = match (&self.default, &self.initial_text) {
+            (Some(default), _) => default.clone(),
+            (None, Some(initial_text)) => self.parse(initial_text).map_err(invalid)?,
+            (None, None) => return Err(fallback::no_default(&self.prompt)),
+        };
+
+        if let Some(ref mut validator) = self.validator {
+            if let Some(err) = validator(&value) {
+                return Err(invalid(err));
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Reads the input from a line of stdin, or renders the `error` of the previous answer
+    /// and the prompt again first, see [`fallback::ask_lines`].
+    fn interact_line(&mut self, term: &dyn Backend, error: Option<&str>) -> io::Result<T> {
+        if let Some(err) = error {
+            let default = self
+                .default
+                .as_ref()
+                .filter(|_| self.show_default)
+                .map(ToString::to_string);
+            let mut render = TermThemeRenderer::new(term, &*self.theme);
+            render.error(err)?;
+            render.input_prompt(&self.prompt, default.as_deref())?;
+        }
+
+        let answer = fallback::read_answer(term, self.redaction.is_none())?;
+        self.check_answer(&answer)
+    }
+
+    /// Parses and validates an answer which wasn't typed, failing when it's invalid.
+    fn check_answer(&mut self, answer: &str) -> io::Result<T> {
+        let invalid = |err: String| io::Error::new(io::ErrorKind::InvalidInput, err);
+
+        let value = match self.default {
+            Some(ref default) if answer.is_empty() => default.clone(),
+            _ if answer.is_empty() && !self.permit_empty => {
+                return Err(fallback::invalid_answer(answer, "a non-empty answer"))
+            }
+            _ => self.parse(answer).map_err(invalid)?,
+        };
+
+        if let Some(ref mut validator) = self.validator {
+            if let Some(err) = validator(&value) {
+                return Err(invalid(err));
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Returns the next replayed answer, reported like a typed one.
+    ///
+    /// Redacted inputs are neither replayed nor recorded.
+    fn replayed(&mut self, term: &dyn Backend) -> io::Result<Option<T>> {
+        if self.redaction.is_some() {
+            return Ok(None);
+        }
+
+        let answer = match answers::replayed(&self.prompt) {
+            Some(answer) => answer,
+            None => return Ok(None),
+        };
+        let value = self.check_answer(&answer)?;
+
+        if self.report {
+            let mut render = TermThemeRenderer::new(term, &*self.theme);
+            self.report_selection(&mut render, &value.to_string())?;
+        }
+        term.flush()?;
+
+        Ok(Some(value))
+    }
+
+    /// Records the answer to replay it later, see [`replayed`](Self::replayed).
+    fn record(&self, value: &T) -> io::Result<()> {
+        if self.redaction.is_none() {
+            answers::record(&self.prompt, &value.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Headless;
+
+    #[test]
+    fn test_resize_keeps_waiting() {
+        // Attended terminals read an unknown key when resized.
+        let term = Headless::new(10, 40).colors(false).keys(vec![
+            Key::Char('a'),
+            Key::Unknown,
+            Key::Char('b'),
+            Key::Enter,
+        ]);
+        let name: String = Input::new()
+            .with_prompt("Name")
+            .interact_text_on(&term)
+            .unwrap();
+
+        assert_eq!(name, "ab");
+        assert_eq!(term.screen(), "Name: ab");
+    }
 }
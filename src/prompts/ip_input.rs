@@ -0,0 +1,360 @@
+use std::{
+    fmt::Display,
+    io,
+    marker::PhantomData,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{IpInputState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+const IPV4_PREFIXES: &[&str] = &["10.", "127.0.0.1", "172.16.", "192.168."];
+const IPV6_PREFIXES: &[&str] = &["::1", "fd", "fe80::"];
+#[cfg(feature = "ipnet")]
+const IPV4_BLOCKS: &[&str] = &[
+    "10.0.0.0/8",
+    "100.64.0.0/10",
+    "127.0.0.0/8",
+    "169.254.0.0/16",
+    "172.16.0.0/12",
+    "192.168.0.0/16",
+];
+#[cfg(feature = "ipnet")]
+const IPV6_BLOCKS: &[&str] = &["::1/128", "fc00::/7", "fd00::/8", "fe80::/10"];
+
+/// Trait for values that can be entered with [`IpInput`].
+///
+/// It is implemented for [`IpAddr`], [`Ipv4Addr`] and [`Ipv6Addr`]. With the `ipnet`
+/// feature enabled it is also implemented for the CIDR blocks `IpNet`, `Ipv4Net` and
+/// `Ipv6Net` of the `ipnet` crate.
+pub trait IpValue: Display + FromStr {
+    /// Describes the value in error messages, like "IPv4 address".
+    const KIND: &'static str;
+
+    /// Returns whether `chr` can be part of the value.
+    fn accepts_char(chr: char) -> bool;
+
+    /// Returns the common (private) ranges offered for completion.
+    fn completions() -> Vec<&'static str>;
+}
+
+fn ipv4_char(chr: char) -> bool {
+    chr.is_ascii_digit() || chr == '.'
+}
+
+fn ipv6_char(chr: char) -> bool {
+    // Dots appear in IPv4-mapped addresses like `::ffff:10.0.0.1`.
+    chr.is_ascii_hexdigit() || chr == ':' || chr == '.'
+}
+
+impl IpValue for IpAddr {
+    const KIND: &'static str = "IP address";
+
+    fn accepts_char(chr: char) -> bool {
+        ipv6_char(chr)
+    }
+
+    fn completions() -> Vec<&'static str> {
+        IPV4_PREFIXES.iter().chain(IPV6_PREFIXES).copied().collect()
+    }
+}
+
+impl IpValue for Ipv4Addr {
+    const KIND: &'static str = "IPv4 address";
+
+    fn accepts_char(chr: char) -> bool {
+        ipv4_char(chr)
+    }
+
+    fn completions() -> Vec<&'static str> {
+        IPV4_PREFIXES.to_vec()
+    }
+}
+
+impl IpValue for Ipv6Addr {
+    const KIND: &'static str = "IPv6 address";
+
+    fn accepts_char(chr: char) -> bool {
+        ipv6_char(chr)
+    }
+
+    fn completions() -> Vec<&'static str> {
+        IPV6_PREFIXES.to_vec()
+    }
+}
+
+#[cfg(feature = "ipnet")]
+impl IpValue for ipnet::IpNet {
+    const KIND: &'static str = "CIDR block";
+
+    fn accepts_char(chr: char) -> bool {
+        ipv6_char(chr) || chr == '/'
+    }
+
+    fn completions() -> Vec<&'static str> {
+        IPV4_BLOCKS.iter().chain(IPV6_BLOCKS).copied().collect()
+    }
+}
+
+#[cfg(feature = "ipnet")]
+impl IpValue for ipnet::Ipv4Net {
+    const KIND: &'static str = "IPv4 CIDR block";
+
+    fn accepts_char(chr: char) -> bool {
+        ipv4_char(chr) || chr == '/'
+    }
+
+    fn completions() -> Vec<&'static str> {
+        IPV4_BLOCKS.to_vec()
+    }
+}
+
+#[cfg(feature = "ipnet")]
+impl IpValue for ipnet::Ipv6Net {
+    const KIND: &'static str = "IPv6 CIDR block";
+
+    fn accepts_char(chr: char) -> bool {
+        ipv6_char(chr) || chr == '/'
+    }
+
+    fn completions() -> Vec<&'static str> {
+        IPV6_BLOCKS.to_vec()
+    }
+}
+
+/// Renders an input prompt for IP addresses and CIDR blocks.
+///
+/// Characters which can't be part of the value are ignored and the input is validated
+/// while typing. 'Tab' (or 'Right') completes common private ranges like `192.168.`.
+/// Interaction returns the parsed value, see [`IpValue`] for the supported types.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::IpInput;
+/// use std::net::Ipv4Addr;
+///
+/// let gateway: Ipv4Addr = IpInput::new()
+///     .with_prompt("Gateway")
+///     .default(Ipv4Addr::new(192, 168, 0, 1))
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct IpInput<'a, T> {
+    prompt: String,
+    report: bool,
+    default: Option<T>,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+    _value: PhantomData<T>,
+}
+
+impl<T: IpValue> Default for IpInput<'static, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IpValue> IpInput<'static, T> {
+    /// Creates an IP input prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl<T: IpValue> IpInput<'_, T> {
+    /// Sets the IP input prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the entered value after interaction.
+    ///
+    /// The default is to report the entered value.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Sets the initial value.
+    pub fn default(&mut self, val: T) -> &mut Self {
+        self.default = Some(val);
+        self
+    }
+
+    /// Gives up waiting for the address after `timeout`, resolving to the
+    /// [default](Self::default), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the value if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<T> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(value)` if user hit 'Enter' or `None` if user cancelled with 'Esc'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<T>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<T> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<T> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<T>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<T>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            let default = match self.default {
+                Some(ref default) => default.to_string(),
+                None => return Err(fallback::no_default(&self.prompt)),
+            };
+            if self.report {
+                render.input_prompt_selection(&self.prompt, &default)?;
+            }
+            term.flush()?;
+
+            return default.parse::<T>().map(Some).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("'{}' is not a valid {}", default, T::KIND),
+                )
+            });
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut state = IpInputState::new().allow_quit(allow_quit);
+        if let Some(ref default) = self.default {
+            state = state.initial_value(default);
+        }
+
+        loop {
+            term.clear_line()?;
+            render.ip_input_prompt(
+                &self.prompt,
+                state.buffer(),
+                state.completion(),
+                state.value().is_some(),
+            )?;
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    term.clear_line()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(value) => {
+                    term.clear_line()?;
+                    render.clear()?;
+
+                    if self.report {
+                        render.input_prompt_selection(&self.prompt, &value.to_string())?;
+                    }
+
+                    term.flush()?;
+
+                    return Ok(Some(value));
+                }
+                _ => match state.error() {
+                    Some(err) => {
+                        term.clear_line()?;
+                        render.error(&err)?;
+                    }
+                    None => render.reject_key(&key)?,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: IpValue + Send + 'static> IpInput<'static, T> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<T>> {
+        crate::task::interact_async(std::mem::take(self), |input| input.interact())
+    }
+}
+
+impl<'a, T: IpValue> IpInput<'a, T> {
+    /// Creates an IP input prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            default: None,
+            timeout: None,
+            theme: theme.into(),
+            _value: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepted_chars() {
+        assert!(Ipv4Addr::accepts_char('7'));
+        assert!(!Ipv4Addr::accepts_char('a'));
+        assert!(IpAddr::accepts_char('a'));
+        assert!(!IpAddr::accepts_char('/'));
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| IpInput::<Ipv4Addr>::new().interact_on(&term))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,234 @@
+use std::{io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{KeyPromptState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// Returns a short human readable name for `key`.
+fn key_label(key: &Key) -> String {
+    match key {
+        Key::Char(' ') => "space".into(),
+        Key::Char(chr) => chr.to_string(),
+        Key::Enter => "enter".into(),
+        Key::Escape => "esc".into(),
+        Key::Backspace => "backspace".into(),
+        Key::Tab => "tab".into(),
+        Key::BackTab => "shift+tab".into(),
+        Key::Del => "del".into(),
+        Key::Insert => "insert".into(),
+        Key::Home => "home".into(),
+        Key::End => "end".into(),
+        Key::PageUp => "pgup".into(),
+        Key::PageDown => "pgdn".into(),
+        Key::ArrowUp => "up".into(),
+        Key::ArrowDown => "down".into(),
+        Key::ArrowLeft => "left".into(),
+        Key::ArrowRight => "right".into(),
+        other => format!("{:?}", other).to_lowercase(),
+    }
+}
+
+/// Renders a message and waits for a key press.
+///
+/// By default any key finishes the prompt. When allowed keys are set, other keys are
+/// ignored and the allowed ones are hinted after the message.
+/// Interaction returns the pressed key.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{console::Key, KeyPrompt};
+///
+/// KeyPrompt::new()
+///     .with_prompt("Press any key to continue")
+///     .interact()?;
+///
+/// let key = KeyPrompt::new()
+///     .with_prompt("Overwrite the file?")
+///     .allowed_keys(vec![Key::Char('y'), Key::Char('n'), Key::Char('a')])
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct KeyPrompt<'a> {
+    prompt: String,
+    report: bool,
+    allowed_keys: Vec<Key>,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for KeyPrompt<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyPrompt<'static> {
+    /// Creates a key prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl KeyPrompt<'_> {
+    /// Sets the message of the key prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the pressed key after interaction.
+    ///
+    /// The default is to not report the key and to only clear the message.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Restricts the keys which finish the prompt.
+    ///
+    /// The default is to accept any key.
+    pub fn allowed_keys<I: IntoIterator<Item = Key>>(&mut self, keys: I) -> &mut Self {
+        self.allowed_keys = keys.into_iter().collect();
+        self
+    }
+
+    /// Gives up waiting for a key after `timeout`, failing with
+    /// [`timed_out`](crate::timed_out).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the pressed key.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<Key> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(key)` if user pressed a key or `None` if user cancelled with 'Esc'.
+    /// If 'Esc' is one of the allowed keys it is returned like any other key.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<Key>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Key> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<Key> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Key>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<Key>> {
+        if fallback::defaults_mode() {
+            return Err(fallback::no_default(&self.prompt));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let labels: Vec<String> = self.allowed_keys.iter().map(key_label).collect();
+
+        render.hide_cursor()?;
+        render.key_prompt(&self.prompt, &labels)?;
+        term.flush()?;
+
+        let mut state = KeyPromptState::new(self.allowed_keys.clone()).allow_quit(allow_quit);
+        let key = loop {
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Done(key) => break Some(key),
+                Transition::Cancelled => break None,
+                _ => render.reject_key(&key)?,
+            }
+        };
+
+        term.clear_line()?;
+
+        if let Some(ref key) = key {
+            if self.report {
+                render.key_prompt_selection(&self.prompt, &key_label(key))?;
+            }
+        }
+
+        render.show_cursor()?;
+        term.flush()?;
+
+        Ok(key)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl KeyPrompt<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Key>> {
+        crate::task::interact_async(std::mem::take(self), |prompt| prompt.interact())
+    }
+}
+
+impl<'a> KeyPrompt<'a> {
+    /// Creates a key prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: false,
+            allowed_keys: vec![],
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_label() {
+        assert_eq!(key_label(&Key::Char('y')), "y");
+        assert_eq!(key_label(&Key::Char(' ')), "space");
+        assert_eq!(key_label(&Key::PageDown), "pgdn");
+        assert_eq!(key_label(&Key::CtrlC), "ctrlc");
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| KeyPrompt::new().interact_on(&term)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,324 @@
+use std::{io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{MenuState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// An entry of a [`Menu`].
+///
+/// Entries with children open a submenu when chosen.
+///
+/// ## Examples
+///
+/// ```rust
+/// use dialoguer::MenuItem;
+///
+/// let settings = MenuItem::new("Settings")
+///     .child(MenuItem::new("Display"))
+///     .child(MenuItem::new("Network").child(MenuItem::new("Proxy")));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuItem {
+    label: String,
+    pub(crate) children: Vec<MenuItem>,
+}
+
+impl MenuItem {
+    /// Creates an entry without a submenu.
+    pub fn new<T: ToString>(label: T) -> Self {
+        Self {
+            label: label.to_string(),
+            children: vec![],
+        }
+    }
+
+    /// Adds an entry to the submenu of this entry.
+    pub fn child(mut self, item: MenuItem) -> Self {
+        self.children.push(item);
+        self
+    }
+
+    /// Adds multiple entries to the submenu of this entry.
+    pub fn children<I: IntoIterator<Item = MenuItem>>(mut self, items: I) -> Self {
+        self.children.extend(items);
+        self
+    }
+
+    /// Returns the label of the entry.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns `true` if choosing the entry opens a submenu.
+    pub fn has_submenu(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
+
+/// Renders a menu with nested submenus.
+///
+/// 'Enter' (or 'Right') on an entry with children opens its submenu, 'Esc' (or 'Left')
+/// goes back to the parent menu. The path of opened submenus is rendered as a breadcrumb
+/// in the prompt line. Interaction returns the path of the chosen entry as a list of
+/// indices, one per menu level.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::{Menu, MenuItem};
+///
+/// fn main() -> std::io::Result<()> {
+///     let path = Menu::new()
+///         .with_prompt("Main menu")
+///         .item(MenuItem::new("New game"))
+///         .item(
+///             MenuItem::new("Settings")
+///                 .child(MenuItem::new("Audio"))
+///                 .child(MenuItem::new("Video")),
+///         )
+///         .item(MenuItem::new("Quit"))
+///         .interact()?;
+///
+///     println!("Chose entry at {:?}", path);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct Menu<'a> {
+    items: Vec<MenuItem>,
+    prompt: String,
+    report: bool,
+    clear: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for Menu<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Menu<'static> {
+    /// Creates a menu prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl Menu<'_> {
+    /// Indicates whether the menu should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the menu.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Adds an entry to the top level menu.
+    pub fn item(&mut self, item: MenuItem) -> &mut Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Adds multiple entries to the top level menu.
+    pub fn items<I: IntoIterator<Item = MenuItem>>(&mut self, items: I) -> &mut Self {
+        self.items.extend(items);
+        self
+    }
+
+    /// Sets the menu prompt.
+    ///
+    /// The breadcrumb of opened submenus is rendered after it.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the chosen entry after interaction.
+    ///
+    /// The default is to report the chosen entry.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Gives up waiting for the selection after `timeout`, failing with
+    /// [`timed_out`](crate::timed_out).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the path of the entry if user hit 'Enter' on an entry without submenu.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<Vec<usize>> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(path)` if user hit 'Enter' on an entry without submenu or `None`
+    /// if user cancelled with 'q' or with 'Esc' in the top level menu.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<Vec<usize>>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Vec<usize>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(
+        &self,
+        keys: K,
+    ) -> io::Result<Vec<usize>> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Vec<usize>>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<Vec<usize>>> {
+        if self.items.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of items given to `Menu`",
+            ));
+        }
+
+        if fallback::defaults_mode() {
+            return Err(fallback::no_default(&self.prompt));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let mut state = MenuState::new(self.items.clone()).allow_quit(allow_quit);
+        let mut starting_row = 0;
+
+        render.hide_cursor()?;
+
+        loop {
+            let sel = state.selection();
+            // Subtract -2 because we need space to render the prompt.
+            let visible_term_rows = (term.size().0 as usize).max(3) - 2;
+
+            if sel < starting_row {
+                starting_row = sel;
+            } else if sel >= starting_row + visible_term_rows {
+                starting_row = sel + 1 - visible_term_rows;
+            }
+
+            render.menu_prompt(&self.prompt, &state.breadcrumb())?;
+
+            for (idx, item) in state
+                .entries()
+                .iter()
+                .enumerate()
+                .skip(starting_row)
+                .take(visible_term_rows)
+            {
+                render.menu_item(item.label(), item.has_submenu(), idx == sel)?;
+            }
+
+            term.flush()?;
+
+            let depth = state.path().len();
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                // Submenus are shown from their first entry.
+                Transition::Continue if state.path().len() > depth => starting_row = 0,
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(path) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    if self.report {
+                        render.menu_prompt_selection(&self.prompt, &state.breadcrumb())?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(path));
+                }
+                _ => render.reject_key(&key)?,
+            }
+
+            render.clear()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Menu<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Vec<usize>>> {
+        crate::task::interact_async(std::mem::take(self), |menu| menu.interact())
+    }
+}
+
+impl<'a> Menu<'a> {
+    /// Creates a menu prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            items: vec![],
+            prompt: "".into(),
+            report: true,
+            clear: true,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err =
+            fallback::test_without_tty(|| Menu::new().item(MenuItem::new("a")).interact_on(&term))
+                .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
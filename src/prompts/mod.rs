@@ -1,13 +1,83 @@
 #![allow(clippy::needless_doctest_main)]
 
+pub mod autocomplete;
+pub mod color_select;
 pub mod confirm;
+pub mod duration_input;
+pub mod edit_list;
+pub mod grid_select;
 pub mod input;
+pub mod ip_input;
+pub mod key_prompt;
+pub mod menu;
 pub mod multi_select;
+pub mod number_input;
+pub mod path_select;
+pub mod pin_input;
+pub mod range_select;
+pub mod review_each;
 pub mod select;
+pub mod slider;
 pub mod sort;
+pub mod table_select;
+pub mod tag_input;
+pub mod toggle;
+pub mod transfer_select;
+pub mod tree_multi_select;
+pub mod tree_select;
 
 #[cfg(feature = "fuzzy-select")]
 pub mod fuzzy_select;
 
 #[cfg(feature = "password")]
 pub mod password;
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn assert_send<T: Send>() {}
+
+    #[test]
+    fn test_builders_are_send() {
+        assert_send::<Autocomplete>();
+        assert_send::<ColorSelect>();
+        assert_send::<Confirm>();
+        assert_send::<DurationInput>();
+        assert_send::<EditList>();
+        assert_send::<GridSelect>();
+        assert_send::<Input<String>>();
+        assert_send::<IpInput<std::net::IpAddr>>();
+        assert_send::<KeyPrompt>();
+        assert_send::<Menu>();
+        assert_send::<MultiSelect>();
+        assert_send::<NumberInput<i32>>();
+        assert_send::<PathSelect>();
+        assert_send::<PinInput>();
+        assert_send::<RangeSelect>();
+        assert_send::<ReviewEach>();
+        assert_send::<Select>();
+        assert_send::<Slider<i32>>();
+        assert_send::<Sort>();
+        assert_send::<TableSelect>();
+        assert_send::<TagInput>();
+        assert_send::<Toggle>();
+        assert_send::<TransferSelect>();
+        assert_send::<TreeMultiSelect>();
+        assert_send::<TreeSelect>();
+        #[cfg(feature = "fuzzy-select")]
+        assert_send::<FuzzySelect>();
+        #[cfg(feature = "password")]
+        assert_send::<Password>();
+        #[cfg(feature = "editor")]
+        assert_send::<Editor>();
+    }
+
+    #[test]
+    fn test_with_theme_accepts_trait_objects() {
+        let simple = theme::SimpleTheme;
+        let theme: &dyn theme::Theme = &simple;
+        let select = Select::with_theme(theme);
+        std::thread::scope(|scope| scope.spawn(move || drop(select)).join().unwrap());
+    }
+}
@@ -1,14 +1,22 @@
-use std::{io, iter::repeat, ops::Rem};
+use std::{io, iter::repeat, sync::mpsc::Receiver, time::Duration};
 
 use crate::{
-    theme::{SimpleTheme, TermThemeRenderer, Theme},
-    Paging,
+    answers,
+    backend::{default_term, with_keys, RepeatedKeys},
+    events, fallback, handle,
+    prompts::select::{align_icons, link_item, number_icons},
+    state::{MultiSelectState, Transition},
+    theme::{SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout, updates, Backend, ItemUpdate, Paging, PromptHandle,
 };
 
-use console::{Key, Term};
+use console::Key;
 
 /// Renders a multi select prompt.
 ///
+/// 'PageUp' and 'PageDown' move the cursor by a screenful, 'Home' and 'End' to the first
+/// and last item.
+///
 /// ## Example usage
 /// ```rust,no_run
 /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
@@ -24,11 +32,29 @@ use console::{Key, Term};
 pub struct MultiSelect<'a> {
     defaults: Vec<bool>,
     items: Vec<String>,
+    icons: Vec<Option<String>>,
+    links: Vec<Option<String>>,
+    disabled: Vec<bool>,
     prompt: Option<String>,
+    hint: Option<String>,
+    right_segment: Option<String>,
     report: bool,
     clear: bool,
     max_length: Option<usize>,
-    theme: &'a dyn Theme,
+    max_rows: Option<usize>,
+    scrolling: bool,
+    scroll_context: Option<usize>,
+    alt_screen: bool,
+    bottom: bool,
+    numbered: bool,
+    horizontal_scroll: bool,
+    min_selections: Option<usize>,
+    max_selections: Option<usize>,
+    counter: bool,
+    timeout: Option<Duration>,
+    updates: Option<Receiver<ItemUpdate>>,
+    handle: Option<PromptHandle>,
+    theme: ThemeRef<'a>,
 }
 
 impl Default for MultiSelect<'static> {
@@ -77,6 +103,75 @@ impl MultiSelect<'_> {
         self
     }
 
+    /// Sets the maximum number of terminal rows the prompt takes up.
+    ///
+    /// The prompt line and the footer count towards it and items beyond it are paged, which
+    /// keeps room for output above the prompt. The terminal height still applies if it is
+    /// smaller.
+    pub fn max_rows(&mut self, val: usize) -> &mut Self {
+        self.max_rows = Some(val);
+        self
+    }
+
+    /// Indicates whether the visible items scroll with the cursor instead of flipping pages.
+    ///
+    /// Scrolling keeps a few items visible above and below the cursor. The default is to
+    /// flip pages.
+    pub fn scrolling(&mut self, val: bool) -> &mut Self {
+        self.scrolling = val;
+        self
+    }
+
+    /// Sets the number of items kept visible above and below the cursor when
+    /// [`scrolling`](Self::scrolling).
+    ///
+    /// A context of half the visible items or more keeps the cursor vertically centered.
+    /// The default is 2.
+    pub fn scroll_context(&mut self, val: usize) -> &mut Self {
+        self.scroll_context = Some(val);
+        self
+    }
+
+    /// Indicates whether the prompt renders in the alternate screen.
+    ///
+    /// This keeps large lists from scrolling the shell history away. The primary screen is
+    /// restored when the prompt ends, showing only the reported selection. The default is to
+    /// render inline.
+    pub fn render_in_alt_screen(&mut self, val: bool) -> &mut Self {
+        self.alt_screen = val;
+        self
+    }
+
+    /// Indicates whether the prompt renders pinned to the bottom of the terminal.
+    ///
+    /// Like fzf, the prompt is drawn on the last rows of the terminal with the earlier output
+    /// left above it, instead of right below that output. The default is to render right
+    /// below.
+    pub fn render_at_bottom(&mut self, val: bool) -> &mut Self {
+        self.bottom = val;
+        self
+    }
+
+    /// Indicates whether the items are rendered with their numbers.
+    ///
+    /// Typing a number jumps to its item once 'Enter' is pressed, or as soon as no further
+    /// digit could make a valid number. The typed digits are shown in the prompt line. The
+    /// default is to render no numbers.
+    pub fn numbered(&mut self, val: bool) -> &mut Self {
+        self.numbered = val;
+        self
+    }
+
+    /// Indicates whether 'Left' and 'Right' scroll the active item horizontally.
+    ///
+    /// This reveals the end of items wider than the terminal, which are cut off otherwise.
+    /// Pages are still flipped with 'h', 'l', 'PageUp' and 'PageDown'. The default is to
+    /// flip pages with 'Left' and 'Right' too.
+    pub fn horizontal_scroll(&mut self, val: bool) -> &mut Self {
+        self.horizontal_scroll = val;
+        self
+    }
+
     /// Add a single item to the selector.
     #[inline]
     pub fn item<T: ToString>(&mut self, item: T) -> &mut Self {
@@ -86,14 +181,64 @@ impl MultiSelect<'_> {
     /// Add a single item to the selector with a default checked state.
     pub fn item_checked<T: ToString>(&mut self, item: T, checked: bool) -> &mut Self {
         self.items.push(item.to_string());
+        self.icons.push(None);
+        self.links.push(None);
+        self.disabled.push(false);
         self.defaults.push(checked);
         self
     }
 
+    /// Add a single item to the selector which is shown but can't be checked.
+    pub fn item_disabled<T: ToString>(&mut self, item: T) -> &mut Self {
+        self.items.push(item.to_string());
+        self.icons.push(None);
+        self.links.push(None);
+        self.disabled.push(true);
+        self.defaults.push(false);
+        self
+    }
+
+    /// Add a single item to the selector which is checked and can't be unchecked.
+    pub fn item_locked<T: ToString>(&mut self, item: T) -> &mut Self {
+        self.items.push(item.to_string());
+        self.icons.push(None);
+        self.links.push(None);
+        self.disabled.push(true);
+        self.defaults.push(true);
+        self
+    }
+
+    /// Add a single item with an icon to the selector.
+    ///
+    /// See [`Select::item_with_icon`](crate::Select::item_with_icon).
+    pub fn item_with_icon<T: ToString, I: ToString>(&mut self, item: T, icon: I) -> &mut Self {
+        self.items.push(item.to_string());
+        self.icons.push(Some(icon.to_string()));
+        self.links.push(None);
+        self.disabled.push(false);
+        self.defaults.push(false);
+        self
+    }
+
+    /// Add a single item linking to `url` to the selector.
+    ///
+    /// See [`Select::item_with_link`](crate::Select::item_with_link).
+    pub fn item_with_link<T: ToString, U: ToString>(&mut self, item: T, url: U) -> &mut Self {
+        self.items.push(item.to_string());
+        self.icons.push(None);
+        self.links.push(Some(url.to_string()));
+        self.disabled.push(false);
+        self.defaults.push(false);
+        self
+    }
+
     /// Adds multiple items to the selector.
     pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Self {
         for item in items {
             self.items.push(item.to_string());
+            self.icons.push(None);
+            self.links.push(None);
+            self.disabled.push(false);
             self.defaults.push(false);
         }
         self
@@ -103,6 +248,9 @@ impl MultiSelect<'_> {
     pub fn items_checked<T: ToString>(&mut self, items: &[(T, bool)]) -> &mut Self {
         for &(ref item, checked) in items {
             self.items.push(item.to_string());
+            self.icons.push(None);
+            self.links.push(None);
+            self.disabled.push(false);
             self.defaults.push(checked);
         }
         self
@@ -125,25 +273,103 @@ impl MultiSelect<'_> {
         self
     }
 
+    /// Shows a hint line below the items, like `"↑↓ move · space toggle · enter confirm"`.
+    ///
+    /// The theme styles the hint with [`Theme::format_hint`](crate::theme::Theme::format_hint)
+    /// and may suppress it.
+    pub fn with_hint<S: Into<String>>(&mut self, hint: S) -> &mut Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Shows a segment like a key map or a status at the right edge of the prompt line.
+    ///
+    /// The theme styles the segment with
+    /// [`Theme::format_right_segment`](crate::theme::Theme::format_right_segment). It is only
+    /// shown together with a prompt.
+    pub fn with_right_segment<S: Into<String>>(&mut self, segment: S) -> &mut Self {
+        self.right_segment = Some(segment.into());
+        self
+    }
+
+    /// Sets the minimum number of items to check before 'Enter' is accepted.
+    ///
+    /// Setting a constraint shows the counter, see [`counter`](Self::counter).
+    pub fn min_selections(&mut self, val: usize) -> &mut Self {
+        self.min_selections = Some(val);
+        self
+    }
+
+    /// Sets the maximum number of items which can be checked.
+    ///
+    /// Once reached, further items can't be checked until another one is unchecked.
+    /// Setting a constraint shows the counter, see [`counter`](Self::counter).
+    pub fn max_selections(&mut self, val: usize) -> &mut Self {
+        self.max_selections = Some(val);
+        self
+    }
+
+    /// Indicates whether to show a counter of checked items below the items.
+    ///
+    /// The theme formats the counter together with the constraints with
+    /// [`Theme::format_multi_select_counter`](crate::theme::Theme::format_multi_select_counter).
+    /// The default is to show it only if [`min_selections`](Self::min_selections) or
+    /// [`max_selections`](Self::max_selections) is set.
+    pub fn counter(&mut self, val: bool) -> &mut Self {
+        self.counter = val;
+        self
+    }
+
+    /// Gives up waiting for the selection after `timeout`, resolving to the items checked
+    /// by [`defaults`](Self::defaults).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Changes the items while the prompt is open with the updates received from `updates`,
+    /// like in [`Select::item_updates`](crate::Select::item_updates).
+    ///
+    /// The checks and the cursor stay on their items. Added items start out unchecked,
+    /// and the indices returned are the ones of the items with all updates received so far
+    /// applied.
+    pub fn item_updates(&mut self, updates: Receiver<ItemUpdate>) -> &mut Self {
+        self.updates = Some(updates);
+        self
+    }
+
+    /// Splits off a handle controlling the prompt from other threads, which can render it
+    /// again, cancel it or change its text while it waits for the answer.
+    ///
+    /// See [`PromptHandle`] for an example.
+    pub fn into_parts(mut self) -> (Self, PromptHandle) {
+        let handle = PromptHandle::new();
+        self.handle = Some(handle.clone());
+        (self, handle)
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// The user can select the items with the 'Space' bar and on 'Enter' the indices of selected items will be returned.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     /// Result contains `Vec<index>` if user hit 'Enter'.
     /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
     #[inline]
     pub fn interact(&self) -> io::Result<Vec<usize>> {
-        self.interact_on(&Term::stderr())
+        self.interact_on(&*default_term())
     }
 
     /// Enables user interaction and returns the result.
     ///
     /// The user can select the items with the 'Space' bar and on 'Enter' the indices of selected items will be returned.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     /// Result contains `Some(Vec<index>)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
     #[inline]
     pub fn interact_opt(&self) -> io::Result<Option<Vec<usize>>> {
-        self.interact_on_opt(&Term::stderr())
+        self.interact_on_opt(&*default_term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
@@ -165,9 +391,25 @@ impl MultiSelect<'_> {
     /// }
     ///```
     #[inline]
-    pub fn interact_on(&self, term: &Term) -> io::Result<Vec<usize>> {
-        self._interact_on(term, false)?
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Vec<usize>> {
+        timeout::ask(term, self.timeout, |term| {
+            events::observe_opt(term, |term| {
+                handle::ask(term, self.handle.as_ref(), |term| {
+                    self._interact_on(term, false)
+                })
+            })
+        })?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(
+        &self,
+        keys: K,
+    ) -> io::Result<Vec<usize>> {
+        with_keys(keys, |term| self.interact_on(term))
     }
 
     /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
@@ -192,11 +434,49 @@ impl MultiSelect<'_> {
     /// }
     /// ```
     #[inline]
-    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<Vec<usize>>> {
-        self._interact_on(term, true)
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Vec<usize>>> {
+        timeout::ask(term, self.timeout, |term| {
+            events::observe_opt(term, |term| {
+                handle::ask(term, self.handle.as_ref(), |term| {
+                    self._interact_on(term, true)
+                })
+            })
+        })
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<Vec<usize>>> {
+        // Answers are keyed by the prompt, and changing items aren't known up front.
+        let prompt = match self.prompt {
+            Some(ref prompt) if self.updates.is_none() => prompt,
+            _ => return self.ask(term, allow_quit),
+        };
+
+        if let Some(answer) = answers::replayed(prompt) {
+            let checked =
+                self.check_choices(&answer, fallback::parse_choices(&answer, &self.items))?;
+            if self.report {
+                let selections: Vec<_> = checked
+                    .iter()
+                    .map(|&idx| self.items[idx].as_str())
+                    .collect();
+                TermThemeRenderer::new(term, &*self.theme)
+                    .multi_select_prompt_selection(prompt, &selections)?;
+            }
+            term.flush()?;
+
+            return Ok(Some(checked));
+        }
+
+        let checked = self.ask(term, allow_quit)?;
+        if let Some(ref checked) = checked {
+            answers::record(prompt, &fallback::choices_answer(checked, &self.items))?;
+        }
+
+        Ok(checked)
     }
 
-    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<Vec<usize>>> {
+    /// Renders the prompt and waits for the answer.
+    fn ask(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<Vec<usize>>> {
         if self.items.is_empty() {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
@@ -204,146 +484,352 @@ impl MultiSelect<'_> {
             ));
         }
 
-        let mut paging = Paging::new(term, self.items.len(), self.max_length);
-        let mut render = TermThemeRenderer::new(term, self.theme);
-        let mut sel = 0;
+        if fallback::defaults_mode() {
+            let checked = self.checked_defaults();
+            if self.min_selections.is_some_and(|min| checked.len() < min) {
+                return Err(fallback::no_default(self.prompt.as_deref().unwrap_or("")));
+            }
 
-        let mut size_vec = Vec::new();
+            let mut render = TermThemeRenderer::new(term, &*self.theme);
+            if let Some(ref prompt) = self.prompt {
+                if self.report {
+                    let selections: Vec<_> = checked
+                        .iter()
+                        .map(|&idx| self.items[idx].as_str())
+                        .collect();
+                    render.multi_select_prompt_selection(prompt, &selections)?;
+                }
+            }
+            term.flush()?;
 
-        for items in self
-            .items
-            .iter()
-            .flat_map(|i| i.split('\n'))
-            .collect::<Vec<_>>()
-        {
-            let size = &items.len();
-            size_vec.push(*size);
+            return Ok(Some(checked));
         }
 
-        let mut checked: Vec<bool> = self.defaults.clone();
+        if fallback::is_active(term) {
+            return fallback::ask_lines(term, |error| self.interact_line(term, error)).map(Some);
+        }
 
-        term.hide_cursor()?;
+        let counter =
+            self.counter || self.min_selections.is_some() || self.max_selections.is_some();
+
+        let mut items = self.items.clone();
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        render.set_right_segment(self.right_segment.as_deref());
+        let mut paging = Paging::new(
+            term,
+            items.len(),
+            self.max_length,
+            render.footer_rows(self.hint.is_some()) + counter as usize,
+        )
+        .max_rows(self.max_rows)
+        .scrolling(self.scrolling)
+        .scroll_context(self.scroll_context);
+        let mut icons = self.item_icons(&mut render, items.len())?;
+        let mut state = MultiSelectState::new(items.len())
+            .checked(self.defaults.clone())
+            .disabled(self.disabled.clone())
+            .min_selections(self.min_selections)
+            .max_selections(self.max_selections)
+            .allow_quit(allow_quit)
+            .numbered(self.numbered)
+            .horizontal_scroll(self.horizontal_scroll);
+
+        if self.alt_screen {
+            render.enter_alternate_screen()?;
+        }
+        if self.bottom {
+            render.anchor_to_bottom();
+        }
+        render.hide_cursor()?;
+
+        let mut keys = RepeatedKeys::default();
+        let mut active = None;
+        let mut prompt = self.prompt.clone();
 
         loop {
-            if let Some(ref prompt) = self.prompt {
-                paging
-                    .render_prompt(|paging_info| render.multi_select_prompt(prompt, paging_info))?;
+            events::selection_changed(active, state.selection());
+            active = state.selection();
+
+            let ticked = render.countdown_ticked();
+            if handle::update_prompt(self.handle.as_ref(), &mut prompt) || ticked {
+                paging.redraw_prompt();
+            }
+            if let Some(ref prompt) = prompt {
+                paging.render_prompt(|paging_info| {
+                    render.clear_prompt()?;
+                    render.multi_select_prompt(prompt, paging_info)
+                })?;
             }
 
-            for (idx, item) in self
-                .items
+            render.set_scrollbar(paging.scrollbar());
+            for (idx, item) in items
                 .iter()
                 .enumerate()
-                .skip(paging.current_page * paging.capacity)
+                .skip(paging.first_item())
                 .take(paging.capacity)
             {
-                render.multi_select_prompt_item(item, checked[idx], sel == idx)?;
+                render.multi_select_prompt_item(
+                    &link_item(item, &self.links, idx),
+                    icons[idx].as_deref(),
+                    state.checked_items()[idx],
+                    self.disabled.get(idx).copied().unwrap_or(false),
+                    state.selection() == Some(idx),
+                )?;
+            }
+
+            render.set_scrollbar(None);
+            paging.render_footer(|paging_info| render.paging_info_footer(paging_info))?;
+
+            if counter {
+                render.multi_select_counter(
+                    state.count(),
+                    items.len(),
+                    self.min_selections,
+                    self.max_selections,
+                )?;
+            }
+
+            if let Some(ref hint) = self.hint {
+                render.hint(hint)?;
             }
 
+            render.finish_frame()?;
             term.flush()?;
 
-            match term.read_key()? {
-                Key::ArrowDown | Key::Tab | Key::Char('j') => {
-                    if sel == !0 {
-                        sel = 0;
-                    } else {
-                        sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
+            let updates = updates::wait(term, self.updates.as_ref(), keys.pending())?;
+            if !updates.is_empty() {
+                let moved = updates::apply(&mut items, updates);
+                let mut checked = vec![false; items.len()];
+                for (&was_checked, pos) in state.checked_items().iter().zip(&moved) {
+                    if let Some(pos) = *pos {
+                        checked[pos] = was_checked;
                     }
                 }
-                Key::ArrowUp | Key::BackTab | Key::Char('k') => {
-                    if sel == !0 {
-                        sel = self.items.len() - 1;
-                    } else {
-                        sel = ((sel as i64 - 1 + self.items.len() as i64)
-                            % (self.items.len() as i64)) as usize;
-                    }
-                }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if paging.active {
-                        sel = paging.previous_page();
-                    }
-                }
-                Key::ArrowRight | Key::Char('l') => {
-                    if paging.active {
-                        sel = paging.next_page();
-                    }
-                }
-                Key::Char(' ') => {
-                    checked[sel] = !checked[sel];
-                }
-                Key::Escape | Key::Char('q') => {
-                    if allow_quit {
+                let sel = updates::follow(state.selection(), &moved, items.len());
+                state.set_checked(checked);
+                state.set_selection(sel);
+                icons = self.item_icons(&mut render, items.len())?;
+
+                paging.set_items_len(items.len(), true);
+                paging.update(state.selection().unwrap_or(!0))?;
+                render.rewind_preserve_prompt()?;
+                continue;
+            }
+
+            let (key, repeats) = keys.read(term)?;
+            for _ in 0..repeats {
+                let had_digits = state.jump_input().is_some();
+                state.set_page(paging.capacity, paging.active);
+                state.set_horizontal_offset(render.horizontal_offset());
+
+                match state.handle_key(key.clone()) {
+                    Transition::Continue => {}
+                    Transition::Cancelled => {
                         if self.clear {
                             render.clear()?;
                         } else {
-                            term.clear_last_lines(paging.capacity)?;
+                            render.clear_items()?;
                         }
 
-                        term.show_cursor()?;
+                        render.leave_alternate_screen()?;
+
+                        render.show_cursor()?;
                         term.flush()?;
 
                         return Ok(None);
                     }
-                }
-                Key::Enter => {
-                    if self.clear {
-                        render.clear()?;
-                    }
+                    Transition::Done(checked) => {
+                        if self.clear {
+                            render.clear()?;
+                        }
+
+                        render.leave_alternate_screen()?;
 
-                    if let Some(ref prompt) = self.prompt {
-                        if self.report {
-                            let selections: Vec<_> = checked
-                                .iter()
-                                .enumerate()
-                                .filter_map(|(idx, &checked)| {
-                                    if checked {
-                                        Some(self.items[idx].as_str())
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect();
-
-                            render.multi_select_prompt_selection(prompt, &selections[..])?;
+                        if let Some(ref prompt) = prompt {
+                            if self.report {
+                                let selections: Vec<_> =
+                                    checked.iter().map(|&idx| items[idx].as_str()).collect();
+
+                                render.multi_select_prompt_selection(prompt, &selections[..])?;
+                            }
                         }
+
+                        render.show_cursor()?;
+                        term.flush()?;
+
+                        return Ok(Some(checked));
                     }
+                    _ => render.reject_key(&key)?,
+                }
 
-                    term.show_cursor()?;
-                    term.flush()?;
+                let prompt_changed = had_digits || state.jump_input().is_some();
+                render.set_jump_input(state.jump_input());
+                render.set_horizontal_offset(state.horizontal_offset());
 
-                    return Ok(Some(
-                        checked
-                            .into_iter()
-                            .enumerate()
-                            .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
-                            .collect(),
-                    ));
+                paging.update(state.selection().unwrap_or(!0))?;
+                if prompt_changed {
+                    paging.redraw_prompt();
                 }
-                _ => {}
             }
 
-            paging.update(sel)?;
+            render.rewind_preserve_prompt()?;
+        }
+    }
+
+    /// Returns the icons of `len` items, aligned and numbered as configured.
+    fn item_icons(
+        &self,
+        render: &mut TermThemeRenderer,
+        len: usize,
+    ) -> io::Result<Vec<Option<String>>> {
+        let mut icons = self.icons.clone();
+        icons.resize(len, None);
+        let icons = align_icons(&icons);
+
+        if self.numbered {
+            number_icons(render, icons)
+        } else {
+            Ok(icons)
+        }
+    }
+
+    /// Returns the indices of the items checked up front.
+    fn checked_defaults(&self) -> Vec<usize> {
+        (0..self.items.len())
+            .filter(|&idx| self.defaults.get(idx).copied().unwrap_or(false))
+            .collect()
+    }
 
-            if paging.active {
-                render.clear()?;
-            } else {
-                render.clear_preserve_prompt(&size_vec)?;
+    /// Lists the numbered items and reads the numbers or texts of the checked ones from
+    /// stdin, or renders the `error` of the previous answer and asks again.
+    fn interact_line(&self, term: &dyn Backend, error: Option<&str>) -> io::Result<Vec<usize>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if let Some(err) = error {
+            render.error(err)?;
+        } else {
+            if let Some(ref prompt) = self.prompt {
+                render.multi_select_prompt(prompt, None)?;
+            }
+            for (idx, item) in self.items.iter().enumerate() {
+                let number = format!("{})", idx + 1);
+                render.multi_select_prompt_item(
+                    item,
+                    Some(&number),
+                    self.defaults.get(idx).copied().unwrap_or(false),
+                    self.disabled.get(idx).copied().unwrap_or(false),
+                    false,
+                )?;
             }
         }
+
+        let defaults = self.checked_defaults();
+        let default = if defaults.is_empty() {
+            None
+        } else {
+            let numbers: Vec<String> = defaults.iter().map(|idx| (idx + 1).to_string()).collect();
+            Some(numbers.join(","))
+        };
+        render.input_prompt("Choices (comma separated)", default.as_deref())?;
+
+        let answer = fallback::read_answer(term, true)?;
+        let choices = if answer.trim().is_empty() {
+            Some(defaults)
+        } else {
+            fallback::parse_choices(&answer, &self.items)
+        };
+
+        self.check_choices(&answer, choices)
+    }
+
+    /// Checks the parsed `choices` of an `answer` against the disabled items and the
+    /// selection limits.
+    fn check_choices(
+        &self,
+        answer: &str,
+        mut choices: Option<Vec<usize>>,
+    ) -> io::Result<Vec<usize>> {
+        if let Some(ref mut choices) = choices {
+            choices.sort_unstable();
+            choices.dedup();
+        }
+
+        match choices {
+            Some(choices)
+                if choices
+                    .iter()
+                    .all(|&idx| !self.disabled.get(idx).copied().unwrap_or(false))
+                    && self.min_selections.map_or(true, |min| choices.len() >= min)
+                    && self.max_selections.map_or(true, |max| choices.len() <= max) =>
+            {
+                Ok(choices)
+            }
+            _ => Err(fallback::invalid_answer(
+                answer,
+                "a comma separated list of item numbers or texts",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl MultiSelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Vec<usize>>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
     }
 }
 
 impl<'a> MultiSelect<'a> {
     /// Creates a multi select prompt with a specific theme.
-    pub fn with_theme(theme: &'a dyn Theme) -> Self {
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
         Self {
             items: vec![],
+            icons: vec![],
+            links: vec![],
+            disabled: vec![],
             defaults: vec![],
             clear: true,
             prompt: None,
+            hint: None,
+            right_segment: None,
             report: true,
             max_length: None,
-            theme,
+            max_rows: None,
+            scrolling: false,
+            scroll_context: None,
+            alt_screen: false,
+            bottom: false,
+            numbered: false,
+            horizontal_scroll: false,
+            min_selections: None,
+            max_selections: None,
+            counter: false,
+            timeout: None,
+            updates: None,
+            handle: None,
+            theme: theme.into(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::ColorfulTheme;
+
+    #[test]
+    fn test_quit_and_page_keys_ignored() {
+        let theme = ColorfulTheme::builder().bell(true).build();
+        let term = crate::StreamBackend::new(&b"lhq \r"[..], Vec::new());
+        let checked = MultiSelect::with_theme(&theme)
+            .items(&["a", "b"])
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(checked, [0]);
+        assert!(!term.into_inner().1.contains(&b'\x07'));
+    }
+}
@@ -0,0 +1,361 @@
+use std::{fmt::Display, io, str::FromStr, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{NumberInputState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// Trait for values that can be used with [`NumberInput`].
+///
+/// It is implemented for all primitive integer and floating point types.
+pub trait Numeric: Copy + PartialOrd + Display + FromStr {
+    /// The step used when none is configured.
+    fn one() -> Self;
+
+    /// Adds `step` to the value without overflowing.
+    fn step_up(self, step: Self) -> Self;
+
+    /// Subtracts `step` from the value without overflowing.
+    fn step_down(self, step: Self) -> Self;
+
+    /// Converts the value to a float, used to compute relative positions.
+    fn as_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric_int {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn one() -> Self {
+                    1
+                }
+
+                fn step_up(self, step: Self) -> Self {
+                    self.saturating_add(step)
+                }
+
+                fn step_down(self, step: Self) -> Self {
+                    self.saturating_sub(step)
+                }
+
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_numeric_float {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn one() -> Self {
+                    1.0
+                }
+
+                fn step_up(self, step: Self) -> Self {
+                    self + step
+                }
+
+                fn step_down(self, step: Self) -> Self {
+                    self - step
+                }
+
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_numeric_float!(f32, f64);
+
+/// Renders a numeric stepper prompt.
+///
+/// The value can be adjusted with the 'Up' and 'Down' arrow keys by a configurable
+/// step or typed in directly. Stepping always keeps the value within the
+/// configured bounds, typed values outside of them are rejected on 'Enter'.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::NumberInput;
+///
+/// let port: u16 = NumberInput::new()
+///     .with_prompt("Port")
+///     .default(8080)
+///     .min(1024)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct NumberInput<'a, T> {
+    prompt: String,
+    report: bool,
+    default: Option<T>,
+    min: Option<T>,
+    max: Option<T>,
+    step: Option<T>,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl<T> Default for NumberInput<'static, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> NumberInput<'static, T> {
+    /// Creates a number input prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl<T> NumberInput<'_, T> {
+    /// Sets the number input prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the chosen value after interaction.
+    ///
+    /// The default is to report the chosen value.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Sets the initial value.
+    ///
+    /// Without a default the prompt starts at the lower bound, or at zero if
+    /// no lower bound was set.
+    pub fn default(&mut self, val: T) -> &mut Self {
+        self.default = Some(val);
+        self
+    }
+
+    /// Sets the smallest allowed value.
+    pub fn min(&mut self, val: T) -> &mut Self {
+        self.min = Some(val);
+        self
+    }
+
+    /// Sets the largest allowed value.
+    pub fn max(&mut self, val: T) -> &mut Self {
+        self.max = Some(val);
+        self
+    }
+
+    /// Sets the amount the value changes by on every 'Up' or 'Down' key press.
+    ///
+    /// The default is one.
+    pub fn step(&mut self, val: T) -> &mut Self {
+        self.step = Some(val);
+        self
+    }
+}
+
+impl<T: Numeric> NumberInput<'_, T> {
+    /// Gives up waiting for the number after `timeout`, resolving to the
+    /// [default](Self::default), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the value if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<T> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(value)` if user hit 'Enter' or `None` if user cancelled with 'Esc'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<T>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<T> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<T> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<T>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    /// Returns the state the prompt starts with.
+    fn state(&self) -> NumberInputState<T> {
+        let zero = T::one().step_down(T::one());
+        NumberInputState::new(self.default.or(self.min).unwrap_or(zero))
+            .bounds(self.min, self.max)
+            .step(self.step.unwrap_or_else(T::one))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<T>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            let default = match self.default {
+                Some(default) => NumberInputState::new(default)
+                    .bounds(self.min, self.max)
+                    .value(),
+                None => return Err(fallback::no_default(&self.prompt)),
+            };
+            if self.report {
+                render.number_input_prompt_selection(&self.prompt, &default.to_string())?;
+            }
+            term.flush()?;
+
+            return Ok(Some(default));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut state = self.state().allow_quit(allow_quit);
+
+        render.hide_cursor()?;
+
+        loop {
+            term.clear_line()?;
+            render.number_input_prompt(&self.prompt, state.buffer())?;
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    term.clear_line()?;
+                    render.clear()?;
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(parsed) => {
+                    term.clear_line()?;
+                    render.clear()?;
+
+                    if self.report {
+                        render.number_input_prompt_selection(&self.prompt, &parsed.to_string())?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(parsed));
+                }
+                _ => match state.error() {
+                    Some(err) => {
+                        term.clear_line()?;
+                        render.error(err)?;
+                    }
+                    None => render.reject_key(&key)?,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: Numeric + Send + 'static> NumberInput<'static, T> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<T>> {
+        crate::task::interact_async(std::mem::take(self), |input| input.interact())
+    }
+}
+
+impl<'a, T> NumberInput<'a, T> {
+    /// Creates a number input prompt with a specific theme.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::{
+    ///     NumberInput,
+    ///     theme::ColorfulTheme
+    /// };
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let volume: u8 = NumberInput::with_theme(&ColorfulTheme::default())
+    ///     .with_prompt("Volume")
+    ///     .max(100)
+    ///     .step(5)
+    ///     .interact()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            default: None,
+            min: None,
+            max: None,
+            step: None,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initial_value_is_clamped() {
+        assert_eq!(NumberInput::<u8>::new().state().value(), 0);
+        assert_eq!(NumberInput::new().min(3).state().value(), 3);
+        assert_eq!(NumberInput::new().default(50).max(10).state().value(), 10);
+    }
+
+    #[test]
+    fn test_step_saturates() {
+        assert_eq!(250u8.step_up(10), 255);
+        assert_eq!(5u8.step_down(10), 0);
+        assert_eq!(1.5f64.step_up(0.5), 2.0);
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| NumberInput::<u32>::new().interact_on(&term))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
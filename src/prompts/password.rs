@@ -1,8 +1,14 @@
-use std::io;
+use std::{io, time::Duration};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::backend::{default_term, with_keys};
+use crate::events;
+use crate::fallback;
+use crate::state::{PasswordState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
 
-use console::Term;
+use console::Key;
 use zeroize::Zeroizing;
 
 /// Renders a password input prompt.
@@ -22,7 +28,8 @@ use zeroize::Zeroizing;
 pub struct Password<'a> {
     prompt: String,
     report: bool,
-    theme: &'a dyn Theme,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
     allow_empty_password: bool,
     confirmation_prompt: Option<(String, String)>,
 }
@@ -73,72 +80,167 @@ impl Password<'_> {
         self
     }
 
+    /// Gives up waiting for the password after `timeout`, failing with
+    /// [`timed_out`](crate::timed_out).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     pub fn interact(&self) -> io::Result<String> {
-        self.interact_on(&Term::stderr())
+        self.interact_on(&*default_term())
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<String> {
-        let mut render = TermThemeRenderer::new(term, self.theme);
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<String> {
+        timeout::ask(term, self.timeout, |term| {
+            events::observe(term, |term| self.ask(term))
+        })
+    }
+
+    /// Renders the prompt and waits for the password.
+    fn ask(&self, term: &dyn Backend) -> io::Result<String> {
+        if fallback::defaults_mode() {
+            return Err(fallback::no_default(&self.prompt));
+        }
+
+        if fallback::is_active(term) {
+            return fallback::ask_lines(term, |error| self.interact_line(term, error));
+        }
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
         render.set_prompts_reset_height(false);
 
+        let mut state = PasswordState::new()
+            .allow_empty(self.allow_empty_password)
+            .confirmation(self.confirmation_prompt.is_some());
+
         loop {
-            let password = Zeroizing::new(self.prompt_password(&mut render, &self.prompt)?);
+            let prompt = match self.confirmation_prompt {
+                Some((ref prompt, _)) if state.confirming() => prompt,
+                _ => &self.prompt,
+            };
+            render.password_prompt(prompt)?;
+            term.flush()?;
 
-            if let Some((ref prompt, ref err)) = self.confirmation_prompt {
-                let pw2 = Zeroizing::new(self.prompt_password(&mut render, prompt)?);
+            let input = term.read_secure_line()?;
+            render.add_line();
 
-                if *password == *pw2 {
+            match state.handle_password(input) {
+                Transition::Done(password) => {
+                    let password = Zeroizing::new(password);
                     render.clear()?;
                     if self.report {
                         render.password_prompt_selection(&self.prompt)?;
                     }
                     term.flush()?;
+
                     return Ok((*password).clone());
                 }
-
-                render.error(err)?;
-            } else {
-                render.clear()?;
-                if self.report {
-                    render.password_prompt_selection(&self.prompt)?;
+                Transition::Rejected if state.mismatched() => {
+                    if let Some((_, ref err)) = self.confirmation_prompt {
+                        render.error(err)?;
+                    }
                 }
-                term.flush()?;
-
-                return Ok((*password).clone());
+                _ => {}
             }
         }
     }
 
-    fn prompt_password(&self, render: &mut TermThemeRenderer, prompt: &str) -> io::Result<String> {
-        loop {
-            render.password_prompt(prompt)?;
-            render.term().flush()?;
-
-            let input = render.term().read_secure_line()?;
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<String> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
 
-            render.add_line();
+    /// Reads the password from a line of stdin, or renders the `error` of the previous
+    /// answer first, see [`fallback::ask_lines`].
+    fn interact_line(&self, term: &dyn Backend, error: Option<&str>) -> io::Result<String> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        if let Some(err) = error {
+            render.error(err)?;
+        }
 
-            if !input.is_empty() || self.allow_empty_password {
-                return Ok(input);
+        let password = Zeroizing::new(self.read_answer(&mut render, &self.prompt)?);
+        if let Some((ref prompt, ref err)) = self.confirmation_prompt {
+            let pw2 = Zeroizing::new(self.read_answer(&mut render, prompt)?);
+            if *password != *pw2 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, err.clone()));
             }
         }
+
+        Ok((*password).clone())
+    }
+
+    /// Renders `prompt` and reads a line of stdin, failing when it's empty but mustn't be.
+    fn read_answer(&self, render: &mut TermThemeRenderer, prompt: &str) -> io::Result<String> {
+        render.password_prompt(prompt)?;
+        let answer = fallback::read_answer(render.term(), false)?;
+        if answer.is_empty() && !self.allow_empty_password {
+            return Err(fallback::invalid_answer(&answer, "a non-empty password"));
+        }
+        Ok(answer)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Password<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<String>> {
+        crate::task::interact_async(std::mem::take(self), |password| password.interact())
     }
 }
 
 impl<'a> Password<'a> {
     /// Creates a password input prompt with a specific theme.
-    pub fn with_theme(theme: &'a dyn Theme) -> Self {
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
         Self {
             prompt: "".into(),
             report: true,
-            theme,
+            timeout: None,
+            theme: theme.into(),
             allow_empty_password: false,
             confirmation_prompt: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Capabilities, StreamBackend};
+
+    #[test]
+    fn test_lines_without_cursor_movement() {
+        let capabilities = Capabilities {
+            cursor_movement: false,
+            ..Capabilities::default()
+        };
+        let term = StreamBackend::new(&b"\nhunter2\nhunter2\n"[..], Vec::new())
+            .colors(false)
+            .capabilities(capabilities);
+
+        let password = Password::new()
+            .with_prompt("Password")
+            .with_confirmation("Repeat", "Mismatch")
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(password, "hunter2");
+
+        // The empty answer is asked for again.
+        let output = String::from_utf8(term.into_inner().1).unwrap();
+        assert!(output.contains("a non-empty password"));
+        assert!(!output.contains("hunter2"));
+    }
+}
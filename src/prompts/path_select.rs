@@ -0,0 +1,428 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{PathEntry, PathSelectState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// Renders an interactive file and directory browser.
+///
+/// The user navigates the listing with the arrow keys, descends into a directory with
+/// 'Right' or 'Enter' and ascends with 'Left' or 'Backspace'. Hidden files are toggled
+/// with '.'. 'Enter' on a file picks it, directories are picked by choosing the `.`
+/// entry on top of the listing.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::PathSelect;
+///
+/// fn main() -> std::io::Result<()> {
+///     let path = PathSelect::new()
+///         .with_prompt("Pick a config file")
+///         .extension("toml")
+///         .interact()?;
+///
+///     println!("Using {}", path.display());
+///
+///     Ok(())
+/// }
+/// ```
+pub struct PathSelect<'a> {
+    prompt: Option<String>,
+    report: bool,
+    clear: bool,
+    start_dir: Option<PathBuf>,
+    extensions: Vec<String>,
+    show_hidden: bool,
+    select_files: bool,
+    select_dirs: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for PathSelect<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PathSelect<'static> {
+    /// Creates a path select prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl PathSelect<'_> {
+    /// Indicates whether the listing should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the listing.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Sets the directory the browser starts in.
+    ///
+    /// The default is the current working directory.
+    pub fn start_dir<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.start_dir = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Only lists files with the given extension.
+    ///
+    /// Can be called multiple times to allow several extensions. Directories are always listed.
+    pub fn extension<S: Into<String>>(&mut self, ext: S) -> &mut Self {
+        self.extensions
+            .push(ext.into().trim_start_matches('.').to_string());
+        self
+    }
+
+    /// Sets whether hidden entries are listed initially.
+    ///
+    /// The default is to hide them. The user can toggle this with '.'.
+    pub fn show_hidden(&mut self, val: bool) -> &mut Self {
+        self.show_hidden = val;
+        self
+    }
+
+    /// Sets whether files can be picked.
+    ///
+    /// The default is `true`.
+    pub fn select_files(&mut self, val: bool) -> &mut Self {
+        self.select_files = val;
+        self
+    }
+
+    /// Sets whether directories can be picked.
+    ///
+    /// The default is `false`.
+    pub fn select_dirs(&mut self, val: bool) -> &mut Self {
+        self.select_dirs = val;
+        self
+    }
+
+    /// Prefaces the listing with a prompt.
+    ///
+    /// By default, when a prompt is set the system also prints out a confirmation after
+    /// the selection. You can opt-out of this with [`report`](#method.report).
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Indicates whether to report the picked path after interaction.
+    ///
+    /// The default is to report the picked path.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Gives up waiting for the path after `timeout`, failing with
+    /// [`timed_out`](crate::timed_out).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the picked path if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<PathBuf> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(path)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<PathBuf>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<PathBuf> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<PathBuf> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<PathBuf>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    /// Lists `dir` as it should be rendered.
+    fn list(&self, dir: &Path, show_hidden: bool) -> io::Result<Vec<PathEntry>> {
+        let mut entries = vec![];
+
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // Follow symlinks so that links to directories can be entered.
+            let is_dir = fs::metadata(entry.path())
+                .map(|meta| meta.is_dir())
+                .unwrap_or(false);
+
+            entries.push(if is_dir {
+                PathEntry::Dir(name)
+            } else {
+                PathEntry::File(name)
+            });
+        }
+
+        Ok(self.filter_entries(entries, dir.parent().is_some(), show_hidden))
+    }
+
+    fn filter_entries(
+        &self,
+        entries: Vec<PathEntry>,
+        has_parent: bool,
+        show_hidden: bool,
+    ) -> Vec<PathEntry> {
+        let mut entries: Vec<_> = entries
+            .into_iter()
+            .filter(|entry| show_hidden || !entry.name().starts_with('.'))
+            .filter(|entry| match entry {
+                PathEntry::File(name) => {
+                    self.extensions.is_empty()
+                        || self.extensions.iter().any(|ext| {
+                            Path::new(name)
+                                .extension()
+                                .is_some_and(|actual| actual == ext.as_str())
+                        })
+                }
+                _ => true,
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            b.is_dir()
+                .cmp(&a.is_dir())
+                .then_with(|| a.name().to_lowercase().cmp(&b.name().to_lowercase()))
+        });
+
+        let mut listing = vec![];
+        if self.select_dirs {
+            listing.push(PathEntry::Current);
+        }
+        if has_parent {
+            listing.push(PathEntry::Parent);
+        }
+        listing.extend(entries);
+        listing
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<PathBuf>> {
+        if fallback::defaults_mode() {
+            return Err(fallback::no_default(self.prompt.as_deref().unwrap_or("")));
+        }
+
+        fallback::require_tty(term)?;
+
+        let dir = match self.start_dir {
+            Some(ref dir) => dir.clone(),
+            None => env::current_dir()?,
+        }
+        .canonicalize()?;
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let entries = self.list(&dir, self.show_hidden)?;
+        let mut state = PathSelectState::new(dir, entries)
+            .show_hidden(self.show_hidden)
+            .select_files(self.select_files)
+            .allow_quit(allow_quit);
+        let mut starting_row = 0;
+
+        render.hide_cursor()?;
+
+        loop {
+            let sel = state.selection();
+            // Subtract -3 because we need space to render the prompt and a possible error.
+            let visible_term_rows = (term.size().0 as usize).max(4) - 3;
+
+            if sel < starting_row {
+                starting_row = sel;
+            } else if sel >= starting_row + visible_term_rows {
+                starting_row = sel + 1 - visible_term_rows;
+            }
+
+            render.path_select_prompt(
+                self.prompt.as_deref().unwrap_or(""),
+                &state.dir().display().to_string(),
+            )?;
+
+            if let Some(err) = state.error() {
+                render.error(err)?;
+            }
+
+            for (idx, entry) in state
+                .entries()
+                .iter()
+                .enumerate()
+                .skip(starting_row)
+                .take(visible_term_rows)
+            {
+                render.path_select_prompt_item(entry.name(), entry.is_dir(), idx == sel)?;
+            }
+
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {
+                    if let Some(target) = state.target() {
+                        let listing = self.list(target, state.shows_hidden());
+                        if listing.is_ok() {
+                            starting_row = 0;
+                        }
+                        state.set_listing(listing);
+                    }
+                }
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(path) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    if let Some(ref prompt) = self.prompt {
+                        if self.report {
+                            render.select_prompt_selection(prompt, &path.display().to_string())?;
+                        }
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(path));
+                }
+                _ => render.reject_key(&key)?,
+            }
+
+            render.clear()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl PathSelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<PathBuf>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
+    }
+}
+
+impl<'a> PathSelect<'a> {
+    /// Creates a path select prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: None,
+            report: true,
+            clear: true,
+            start_dir: None,
+            extensions: vec![],
+            show_hidden: false,
+            select_files: true,
+            select_dirs: false,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<PathEntry> {
+        vec![
+            PathEntry::File("b.rs".into()),
+            PathEntry::File("a.toml".into()),
+            PathEntry::Dir("src".into()),
+            PathEntry::File(".hidden.rs".into()),
+            PathEntry::Dir(".git".into()),
+            PathEntry::Dir("Examples".into()),
+        ]
+    }
+
+    #[test]
+    fn test_filter_entries_sorts_directories_first() {
+        let listing = PathSelect::new().filter_entries(entries(), true, false);
+
+        assert_eq!(
+            listing,
+            [
+                PathEntry::Parent,
+                PathEntry::Dir("Examples".into()),
+                PathEntry::Dir("src".into()),
+                PathEntry::File("a.toml".into()),
+                PathEntry::File("b.rs".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_entries_by_extension() {
+        let listing = PathSelect::new()
+            .extension(".rs")
+            .select_dirs(true)
+            .filter_entries(entries(), false, true);
+
+        assert_eq!(
+            listing,
+            [
+                PathEntry::Current,
+                PathEntry::Dir(".git".into()),
+                PathEntry::Dir("Examples".into()),
+                PathEntry::Dir("src".into()),
+                PathEntry::File(".hidden.rs".into()),
+                PathEntry::File("b.rs".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| PathSelect::new().interact_on(&term)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
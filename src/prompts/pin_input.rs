@@ -0,0 +1,231 @@
+use std::{io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{PinInputState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// Renders a prompt for PINs and one-time codes.
+///
+/// A fixed number of cells is rendered which fill up as characters are typed, 'Backspace'
+/// moves back a cell. The prompt submits as soon as the last cell is filled.
+/// By default only digits are accepted.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::PinInput;
+///
+/// let code = PinInput::new()
+///     .with_prompt("Authentication code")
+///     .length(6)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PinInput<'a> {
+    prompt: String,
+    report: bool,
+    length: usize,
+    mask: bool,
+    alphanumeric: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for PinInput<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PinInput<'static> {
+    /// Creates a PIN input prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl PinInput<'_> {
+    /// Sets the PIN input prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the entered code after interaction.
+    ///
+    /// Masked codes are reported as hidden. The default is to report the code.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Sets the number of characters of the code.
+    ///
+    /// The default is 6.
+    pub fn length(&mut self, val: usize) -> &mut Self {
+        self.length = val.max(1);
+        self
+    }
+
+    /// Indicates whether the typed characters are masked.
+    ///
+    /// The default is to show the characters.
+    pub fn mask(&mut self, val: bool) -> &mut Self {
+        self.mask = val;
+        self
+    }
+
+    /// Indicates whether letters are accepted in addition to digits.
+    ///
+    /// Letters are turned to uppercase. The default is to accept digits only.
+    pub fn alphanumeric(&mut self, val: bool) -> &mut Self {
+        self.alphanumeric = val;
+        self
+    }
+
+    /// Gives up waiting for the PIN after `timeout`, failing with
+    /// [`timed_out`](crate::timed_out).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the code once all cells are filled.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<String> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(code)` once all cells are filled or `None` if user cancelled with 'Esc'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<String>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<String> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<String> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<String>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<String>> {
+        if fallback::defaults_mode() {
+            return Err(fallback::no_default(&self.prompt));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let mut state = PinInputState::new(self.length)
+            .alphanumeric(self.alphanumeric)
+            .allow_quit(allow_quit);
+
+        render.hide_cursor()?;
+
+        loop {
+            term.clear_line()?;
+            render.pin_input_prompt(&self.prompt, state.code(), self.length, self.mask)?;
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    term.clear_line()?;
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(code) => {
+                    // The filled cells are shown before the prompt goes away.
+                    term.clear_line()?;
+                    render.pin_input_prompt(&self.prompt, &code, self.length, self.mask)?;
+                    term.flush()?;
+
+                    term.clear_line()?;
+                    render.clear()?;
+
+                    if self.report {
+                        render.pin_input_prompt_selection(&self.prompt, &code, self.mask)?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(code));
+                }
+                _ => render.reject_key(&key)?,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl PinInput<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<String>> {
+        crate::task::interact_async(std::mem::take(self), |input| input.interact())
+    }
+}
+
+impl<'a> PinInput<'a> {
+    /// Creates a PIN input prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            length: 6,
+            mask: false,
+            alphanumeric: false,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| PinInput::new().interact_on(&term)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
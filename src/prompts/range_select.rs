@@ -0,0 +1,401 @@
+use std::{io, time::Duration};
+
+use crate::{
+    backend::{default_term, with_keys},
+    fallback,
+    state::{RangeSelectState, Transition},
+    theme::{SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout, Backend, Paging,
+};
+
+use console::Key;
+
+/// Renders a prompt for selecting a contiguous range of items.
+///
+/// 'Space' anchors the start of the range at the current item, moving the cursor
+/// afterwards extends the highlighted range up to it. 'Enter' confirms the range, without
+/// an anchor only the current item is selected. 'Esc' drops the anchor.
+/// Interaction returns the indices of the first and the last item of the range.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::RangeSelect;
+///
+/// fn main() -> std::io::Result<()> {
+///     let commits = vec!["a1b2c3 Fix typo", "d4e5f6 Add tests", "0a9b8c Bump version"];
+///     let (start, end) = RangeSelect::new()
+///         .with_prompt("Pick the commits to squash")
+///         .items(&commits)
+///         .interact()?;
+///
+///     println!("Squashing {} commits", end - start + 1);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct RangeSelect<'a> {
+    default: usize,
+    items: Vec<String>,
+    prompt: Option<String>,
+    report: bool,
+    clear: bool,
+    max_length: Option<usize>,
+    max_rows: Option<usize>,
+    scrolling: bool,
+    scroll_context: Option<usize>,
+    alt_screen: bool,
+    bottom: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for RangeSelect<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RangeSelect<'static> {
+    /// Creates a range select prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl RangeSelect<'_> {
+    /// Indicates whether the list should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the list.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Sets the item the cursor starts on.
+    pub fn default(&mut self, val: usize) -> &mut Self {
+        self.default = val;
+        self
+    }
+
+    /// Sets an optional max length for a page.
+    ///
+    /// Max length is disabled by None
+    pub fn max_length(&mut self, val: usize) -> &mut Self {
+        // Paging subtracts two from the capacity, see `Select::max_length`.
+        self.max_length = Some(val + 2);
+        self
+    }
+
+    /// Sets the maximum number of terminal rows the prompt takes up.
+    ///
+    /// The prompt line and the footer count towards it and items beyond it are paged, which
+    /// keeps room for output above the prompt. The terminal height still applies if it is
+    /// smaller.
+    pub fn max_rows(&mut self, val: usize) -> &mut Self {
+        self.max_rows = Some(val);
+        self
+    }
+
+    /// Indicates whether the visible items scroll with the cursor instead of flipping pages.
+    ///
+    /// Scrolling keeps a few items visible above and below the cursor. The default is to
+    /// flip pages.
+    pub fn scrolling(&mut self, val: bool) -> &mut Self {
+        self.scrolling = val;
+        self
+    }
+
+    /// Sets the number of items kept visible above and below the cursor when
+    /// [`scrolling`](Self::scrolling).
+    ///
+    /// A context of half the visible items or more keeps the cursor vertically centered.
+    /// The default is 2.
+    pub fn scroll_context(&mut self, val: usize) -> &mut Self {
+        self.scroll_context = Some(val);
+        self
+    }
+
+    /// Indicates whether the prompt renders in the alternate screen.
+    ///
+    /// This keeps large lists from scrolling the shell history away. The primary screen is
+    /// restored when the prompt ends, showing only the reported selection. The default is to
+    /// render inline.
+    pub fn render_in_alt_screen(&mut self, val: bool) -> &mut Self {
+        self.alt_screen = val;
+        self
+    }
+
+    /// Indicates whether the prompt renders pinned to the bottom of the terminal.
+    ///
+    /// Like fzf, the prompt is drawn on the last rows of the terminal with the earlier output
+    /// left above it, instead of right below that output. The default is to render right
+    /// below.
+    pub fn render_at_bottom(&mut self, val: bool) -> &mut Self {
+        self.bottom = val;
+        self
+    }
+
+    /// Adds a single item to the list.
+    pub fn item<T: ToString>(&mut self, item: T) -> &mut Self {
+        self.items.push(item.to_string());
+        self
+    }
+
+    /// Adds multiple items to the list.
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Self {
+        for item in items {
+            self.items.push(item.to_string());
+        }
+        self
+    }
+
+    /// Sets the range select prompt.
+    ///
+    /// By default, when a prompt is set the system also prints out a confirmation after
+    /// the selection. You can opt-out of this with [`report`](#method.report).
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self.report = true;
+        self
+    }
+
+    /// Indicates whether to report the selected range after interaction.
+    ///
+    /// The default is to report the selection.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Gives up waiting for the range after `timeout`, resolving to the
+    /// range of just the [default](Self::default) item.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the first and last index of the range if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<(usize, usize)> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some((start, end))` if user hit 'Enter' or `None` if user cancelled
+    /// with 'q' or with 'Esc' while no range was anchored.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<(usize, usize)>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<(usize, usize)> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(
+        &self,
+        keys: K,
+    ) -> io::Result<(usize, usize)> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<(usize, usize)>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(
+        &self,
+        term: &dyn Backend,
+        allow_quit: bool,
+    ) -> io::Result<Option<(usize, usize)>> {
+        if self.items.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of items given to `RangeSelect`",
+            ));
+        }
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            let sel = self.default.min(self.items.len() - 1);
+            if let Some(ref prompt) = self.prompt {
+                if self.report {
+                    render.range_select_prompt_selection(
+                        prompt,
+                        &self.items[sel],
+                        &self.items[sel],
+                    )?;
+                }
+            }
+            term.flush()?;
+
+            return Ok(Some((sel, sel)));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut paging = Paging::new(
+            term,
+            self.items.len(),
+            self.max_length,
+            render.footer_rows(false),
+        )
+        .max_rows(self.max_rows)
+        .scrolling(self.scrolling)
+        .scroll_context(self.scroll_context);
+        let mut state = RangeSelectState::new(self.items.len())
+            .default(self.default)
+            .allow_quit(allow_quit);
+
+        paging.update(state.selection())?;
+
+        if self.alt_screen {
+            render.enter_alternate_screen()?;
+        }
+        if self.bottom {
+            render.anchor_to_bottom();
+        }
+        render.hide_cursor()?;
+
+        loop {
+            if let Some(ref prompt) = self.prompt {
+                paging.render_prompt(|paging_info| {
+                    render.clear_prompt()?;
+                    render.select_prompt(prompt, paging_info)
+                })?;
+            }
+
+            let (start, end) = state.range();
+
+            render.set_scrollbar(paging.scrollbar());
+            for (idx, item) in self
+                .items
+                .iter()
+                .enumerate()
+                .skip(paging.first_item())
+                .take(paging.capacity)
+            {
+                let in_range = state.anchor().is_some() && start <= idx && idx <= end;
+                render.range_select_prompt_item(item, in_range, state.selection() == idx)?;
+            }
+
+            render.set_scrollbar(None);
+            paging.render_footer(|paging_info| render.paging_info_footer(paging_info))?;
+
+            render.finish_frame()?;
+            term.flush()?;
+
+            state.set_page_size(paging.capacity);
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.leave_alternate_screen()?;
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done((start, end)) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.leave_alternate_screen()?;
+
+                    if let Some(ref prompt) = self.prompt {
+                        if self.report {
+                            render.range_select_prompt_selection(
+                                prompt,
+                                &self.items[start],
+                                &self.items[end],
+                            )?;
+                        }
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some((start, end)));
+                }
+                _ => render.reject_key(&key)?,
+            }
+
+            paging.update(state.selection())?;
+
+            render.rewind_preserve_prompt()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl RangeSelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(
+        &mut self,
+    ) -> impl std::future::Future<Output = io::Result<(usize, usize)>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
+    }
+}
+
+impl<'a> RangeSelect<'a> {
+    /// Creates a range select prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            default: 0,
+            items: vec![],
+            prompt: None,
+            report: false,
+            clear: true,
+            max_length: None,
+            max_rows: None,
+            scrolling: false,
+            scroll_context: None,
+            alt_screen: false,
+            bottom: false,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err =
+            fallback::test_without_tty(|| RangeSelect::new().items(&["a", "b"]).interact_on(&term))
+                .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,379 @@
+use std::{io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{ReviewEachState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// The decision taken for an item in a [`ReviewEach`] prompt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReviewDecision {
+    /// The item was accepted.
+    Yes,
+    /// The item was rejected.
+    No,
+    /// The item should be edited, this is left to the caller.
+    Edit,
+}
+
+/// Renders a yes/no review prompt for every item of a sequence.
+///
+/// Every item is rendered with a formatter, followed by a prompt asking for a decision:
+/// 'y' accepts and 'n' rejects the item. Unless disabled, 'a' accepts and 'd' rejects
+/// the item and all remaining ones, and 'q' quits the review leaving the remaining items
+/// undecided. 'e' is available if editing is enabled with [`allow_edit`](Self::allow_edit).
+/// This works like the interactive mode of `git add -p`.
+/// Interaction returns the reviewed items together with their decisions.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{ReviewDecision, ReviewEach};
+///
+/// let files = vec!["Cargo.toml", "src/lib.rs", "README.md"];
+/// let reviewed = ReviewEach::new()
+///     .with_prompt("Delete this file?")
+///     .interact(files, |file| format!("--- {}", file))?;
+///
+/// for (file, decision) in reviewed {
+///     if decision == ReviewDecision::Yes {
+///         println!("Deleting {}", file);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReviewEach<'a> {
+    prompt: String,
+    report: bool,
+    clear: bool,
+    allow_all: bool,
+    allow_quit: bool,
+    allow_edit: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for ReviewEach<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReviewEach<'static> {
+    /// Creates a review prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl ReviewEach<'_> {
+    /// Sets the question asked for every item.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report every decision after it was taken.
+    ///
+    /// The default is to report the decisions.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Indicates whether every item should be erased from the screen after its decision.
+    ///
+    /// The default is to clear the items.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Indicates whether 'a' and 'd' decide all remaining items at once.
+    ///
+    /// The default is to allow this.
+    pub fn allow_all(&mut self, val: bool) -> &mut Self {
+        self.allow_all = val;
+        self
+    }
+
+    /// Indicates whether 'q' quits the review.
+    ///
+    /// The default is to allow quitting.
+    pub fn allow_quit(&mut self, val: bool) -> &mut Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Indicates whether 'e' marks items for editing.
+    ///
+    /// The default is to not offer editing.
+    pub fn allow_edit(&mut self, val: bool) -> &mut Self {
+        self.allow_edit = val;
+        self
+    }
+
+    /// Gives up waiting for the decisions after `timeout`, failing with
+    /// [`timed_out`](crate::timed_out).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the reviewed items with their decisions. After quitting with 'q'
+    /// the remaining items are left out.
+    #[inline]
+    pub fn interact<I, F>(&self, items: I, format: F) -> io::Result<Vec<(I::Item, ReviewDecision)>>
+    where
+        I: IntoIterator,
+        F: Fn(&I::Item) -> String,
+    {
+        self.interact_on(&*default_term(), items, format)
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<I, F, K>(
+        &self,
+        items: I,
+        format: F,
+        keys: K,
+    ) -> io::Result<Vec<(I::Item, ReviewDecision)>>
+    where
+        I: IntoIterator,
+        F: Fn(&I::Item) -> String,
+        K: IntoIterator<Item = Key>,
+    {
+        with_keys(keys, |term| self.interact_on(term, items, format))
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    pub fn interact_on<I, F>(
+        &self,
+        term: &dyn Backend,
+        items: I,
+        format: F,
+    ) -> io::Result<Vec<(I::Item, ReviewDecision)>>
+    where
+        I: IntoIterator,
+        F: Fn(&I::Item) -> String,
+    {
+        // Timing out asks again in defaults mode, where there is no default.
+        let mut args = Some((items, format));
+        timeout::ask(term, self.timeout, |term| match args.take() {
+            Some((items, format)) => self.review(term, items, format),
+            None => Err(fallback::no_default(&self.prompt)),
+        })
+    }
+
+    fn review<I, F>(
+        &self,
+        term: &dyn Backend,
+        items: I,
+        format: F,
+    ) -> io::Result<Vec<(I::Item, ReviewDecision)>>
+    where
+        I: IntoIterator,
+        F: Fn(&I::Item) -> String,
+    {
+        if fallback::defaults_mode() {
+            return Err(fallback::no_default(&self.prompt));
+        }
+
+        fallback::require_tty(term)?;
+
+        // Keeps progress bars suspended in between the renderers of the single items.
+        let _pause = crate::pause_renderer();
+        let mut items = items.into_iter();
+        let total = match items.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        };
+
+        let mut state = ReviewEachState::new()
+            .allow_all(self.allow_all)
+            .allow_edit(self.allow_edit)
+            .allow_quit(self.allow_quit);
+        let keys = state.keys();
+
+        let mut reviewed = vec![];
+        let mut remaining = None;
+
+        for item in &mut items {
+            // Every item gets a fresh renderer so clearing it leaves earlier reports alone.
+            let mut render = TermThemeRenderer::new(term, &*self.theme);
+            render.hide_cursor()?;
+
+            render.review_each_item(&format(&item))?;
+            render.review_each_prompt(&self.prompt, reviewed.len() + 1, total, &keys)?;
+            term.flush()?;
+
+            let decision = loop {
+                let key = term.read_key()?;
+                match state.handle_key(key.clone()) {
+                    Transition::Done(decision) => break decision,
+                    Transition::Cancelled => {
+                        term.clear_line()?;
+                        if self.clear {
+                            render.clear()?;
+                        }
+                        render.show_cursor()?;
+                        term.flush()?;
+
+                        return Ok(reviewed);
+                    }
+                    _ => render.reject_key(&key)?,
+                }
+            };
+
+            term.clear_line()?;
+            if self.clear {
+                render.clear()?;
+            }
+
+            if self.report {
+                render.review_each_prompt_selection(&self.prompt, decision)?;
+            }
+
+            render.show_cursor()?;
+            reviewed.push((item, decision));
+
+            if state.remaining().is_some() {
+                remaining = state.remaining();
+                break;
+            }
+        }
+
+        if let Some(decision) = remaining {
+            reviewed.extend(items.map(|item| (item, decision)));
+        }
+
+        term.flush()?;
+
+        Ok(reviewed)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl ReviewEach<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    #[allow(clippy::type_complexity)]
+    pub fn interact_async<I, F>(
+        &mut self,
+        items: I,
+        format: F,
+    ) -> impl std::future::Future<Output = io::Result<Vec<(I::Item, ReviewDecision)>>>
+    where
+        I: IntoIterator + Send + 'static,
+        I::Item: Send + 'static,
+        F: Fn(&I::Item) -> String + Send + 'static,
+    {
+        crate::task::interact_async(std::mem::take(self), |review| {
+            review.interact(items, format)
+        })
+    }
+}
+
+impl<'a> ReviewEach<'a> {
+    /// Creates a review prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            clear: true,
+            allow_all: true,
+            allow_quit: true,
+            allow_edit: false,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Headless;
+
+    use ReviewDecision::*;
+
+    fn review(items: Vec<u8>, keys: Vec<Key>) -> (Vec<(u8, ReviewDecision)>, String) {
+        let term = Headless::new(10, 40).colors(false).keys(keys);
+        let reviewed = ReviewEach::new()
+            .with_prompt("Keep?")
+            .allow_edit(true)
+            .interact_on(&term, items, |item| format!("item {}", item))
+            .unwrap();
+        (reviewed, term.screen())
+    }
+
+    #[test]
+    fn test_accept_and_reject() {
+        let keys = vec![Key::Char('y'), Key::Char('n'), Key::Char('e')];
+        let (reviewed, screen) = review(vec![1, 2, 3], keys);
+        assert_eq!(reviewed, [(1, Yes), (2, No), (3, Edit)]);
+        assert_eq!(screen, "Keep?: yes\nKeep?: no\nKeep?: edit");
+
+        // Other keys are rejected without deciding.
+        let keys = vec![Key::Char('x'), Key::Enter, Key::Char('n')];
+        assert_eq!(review(vec![1], keys).0, [(1, No)]);
+    }
+
+    #[test]
+    fn test_decide_remaining() {
+        let keys = vec![Key::Char('n'), Key::Char('a')];
+        let (reviewed, _) = review(vec![1, 2, 3, 4], keys);
+        assert_eq!(reviewed, [(1, No), (2, Yes), (3, Yes), (4, Yes)]);
+
+        let keys = vec![Key::Char('d')];
+        assert_eq!(review(vec![1, 2], keys).0, [(1, No), (2, No)]);
+    }
+
+    #[test]
+    fn test_quit_skips_remaining() {
+        let keys = vec![Key::Char('y'), Key::Char('q')];
+        let (reviewed, screen) = review(vec![1, 2, 3], keys);
+        assert_eq!(reviewed, [(1, Yes)]);
+        assert!(!screen.contains("item"));
+
+        let term = Headless::new(10, 40).keys(vec![Key::Char('q'), Key::Char('y')]);
+        let reviewed = ReviewEach::new()
+            .allow_quit(false)
+            .interact_on(&term, vec![1], |item| item.to_string())
+            .unwrap();
+        assert_eq!(reviewed, [(1, Yes)]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let (reviewed, screen) = review(vec![], vec![]);
+        assert!(reviewed.is_empty());
+        assert_eq!(screen, "");
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| {
+            ReviewEach::new().interact_on(&term, vec!["a"], |item| item.to_string())
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -1,15 +1,77 @@
-use std::{io, ops::Rem};
+use std::{borrow::Cow, cell::RefCell, io, sync::mpsc::Receiver, time::Duration};
 
+use crate::answers;
+use crate::backend::{default_term, with_keys, RepeatedKeys};
+use crate::events;
+use crate::fallback;
+use crate::handle;
 use crate::paging::Paging;
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::provider::{ItemProvider, LoadedItems};
+use crate::state::{SelectState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::updates;
+use crate::width::text_width;
+use crate::{hyperlink, Backend, ItemUpdate, PromptHandle};
 
-use console::{Key, Term};
+use console::{pad_str, Alignment, Key};
+
+/// The number of items fetched from a provider at once to list them all.
+const FALLBACK_PAGE_SIZE: usize = 100;
+
+/// Pads the icons of items to a common width, so the texts line up.
+///
+/// Items without an icon get blanks, unless no item has an icon at all.
+pub(crate) fn align_icons(icons: &[Option<String>]) -> Vec<Option<String>> {
+    let width = icons.iter().flatten().map(|icon| text_width(icon)).max();
+
+    icons
+        .iter()
+        .map(|icon| {
+            width.map(|width| {
+                let icon = icon.as_deref().unwrap_or_default();
+                pad_str(icon, width, Alignment::Left, None).into_owned()
+            })
+        })
+        .collect()
+}
+
+/// Returns `item` as a hyperlink to its URL in `links`, if it has one.
+pub(crate) fn link_item<'b>(item: &'b str, links: &[Option<String>], idx: usize) -> Cow<'b, str> {
+    match links.get(idx) {
+        Some(Some(url)) => Cow::Owned(hyperlink(item, url)),
+        _ => Cow::Borrowed(item),
+    }
+}
+
+/// Puts the number of every item in front of its icon, for numbered prompts.
+pub(crate) fn number_icons(
+    render: &mut TermThemeRenderer,
+    icons: Vec<Option<String>>,
+) -> io::Result<Vec<Option<String>>> {
+    let width = icons.len().to_string().len();
+
+    icons
+        .into_iter()
+        .enumerate()
+        .map(|(idx, icon)| {
+            let number = render.item_number(idx + 1, width)?;
+            Ok(Some(match icon {
+                Some(icon) => format!("{} {}", number, icon),
+                None => number,
+            }))
+        })
+        .collect()
+}
 
 /// Renders a select prompt.
 ///
 /// User can select from one or more options.
 /// Interaction returns index of an item selected in the order they appear in `item` invocation or `items` slice.
 ///
+/// 'PageUp' and 'PageDown' move the cursor by a screenful, 'Home' and 'End' to the first
+/// and last item.
+///
 /// ## Examples
 ///
 /// ```rust,no_run
@@ -37,11 +99,27 @@ use console::{Key, Term};
 pub struct Select<'a> {
     default: usize,
     items: Vec<String>,
+    icons: Vec<Option<String>>,
+    links: Vec<Option<String>>,
+    disabled: Vec<bool>,
     prompt: Option<String>,
+    hint: Option<String>,
+    right_segment: Option<String>,
     report: bool,
     clear: bool,
-    theme: &'a dyn Theme,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
     max_length: Option<usize>,
+    max_rows: Option<usize>,
+    scrolling: bool,
+    scroll_context: Option<usize>,
+    alt_screen: bool,
+    bottom: bool,
+    numbered: bool,
+    horizontal_scroll: bool,
+    provider: Option<RefCell<Box<dyn ItemProvider + Send + 'a>>>,
+    updates: Option<Receiver<ItemUpdate>>,
+    handle: Option<PromptHandle>,
 }
 
 impl Default for Select<'static> {
@@ -86,6 +164,75 @@ impl Select<'_> {
         self
     }
 
+    /// Sets the maximum number of terminal rows the prompt takes up.
+    ///
+    /// The prompt line and the footer count towards it and items beyond it are paged, which
+    /// keeps room for output above the prompt. The terminal height still applies if it is
+    /// smaller.
+    pub fn max_rows(&mut self, val: usize) -> &mut Self {
+        self.max_rows = Some(val);
+        self
+    }
+
+    /// Indicates whether the visible items scroll with the cursor instead of flipping pages.
+    ///
+    /// Scrolling keeps a few items visible above and below the cursor. The default is to
+    /// flip pages.
+    pub fn scrolling(&mut self, val: bool) -> &mut Self {
+        self.scrolling = val;
+        self
+    }
+
+    /// Sets the number of items kept visible above and below the cursor when
+    /// [`scrolling`](Self::scrolling).
+    ///
+    /// A context of half the visible items or more keeps the cursor vertically centered.
+    /// The default is 2.
+    pub fn scroll_context(&mut self, val: usize) -> &mut Self {
+        self.scroll_context = Some(val);
+        self
+    }
+
+    /// Indicates whether the prompt renders in the alternate screen.
+    ///
+    /// This keeps large lists from scrolling the shell history away. The primary screen is
+    /// restored when the prompt ends, showing only the reported selection. The default is to
+    /// render inline.
+    pub fn render_in_alt_screen(&mut self, val: bool) -> &mut Self {
+        self.alt_screen = val;
+        self
+    }
+
+    /// Indicates whether the prompt renders pinned to the bottom of the terminal.
+    ///
+    /// Like fzf, the prompt is drawn on the last rows of the terminal with the earlier output
+    /// left above it, instead of right below that output. The default is to render right
+    /// below.
+    pub fn render_at_bottom(&mut self, val: bool) -> &mut Self {
+        self.bottom = val;
+        self
+    }
+
+    /// Indicates whether the items are rendered with their numbers.
+    ///
+    /// Typing a number jumps to its item once 'Enter' is pressed, or as soon as no further
+    /// digit could make a valid number. The typed digits are shown in the prompt line. The
+    /// default is to render no numbers.
+    pub fn numbered(&mut self, val: bool) -> &mut Self {
+        self.numbered = val;
+        self
+    }
+
+    /// Indicates whether 'Left' and 'Right' scroll the active item horizontally.
+    ///
+    /// This reveals the end of items wider than the terminal, which are cut off otherwise.
+    /// Pages are still flipped with 'h', 'l', 'PageUp' and 'PageDown'. The default is to
+    /// flip pages with 'Left' and 'Right' too.
+    pub fn horizontal_scroll(&mut self, val: bool) -> &mut Self {
+        self.horizontal_scroll = val;
+        self
+    }
+
     /// Add a single item to the selector.
     ///
     /// ## Examples
@@ -103,6 +250,74 @@ impl Select<'_> {
     /// ```
     pub fn item<T: ToString>(&mut self, item: T) -> &mut Self {
         self.items.push(item.to_string());
+        self.icons.push(None);
+        self.links.push(None);
+        self.disabled.push(false);
+        self
+    }
+
+    /// Adds a single item which is shown but can't be selected.
+    ///
+    /// The theme renders it differently, for example dimmed or struck through.
+    pub fn item_disabled<T: ToString>(&mut self, item: T) -> &mut Self {
+        self.items.push(item.to_string());
+        self.icons.push(None);
+        self.links.push(None);
+        self.disabled.push(true);
+        self
+    }
+
+    /// Adds a single item with an icon, like a file type glyph or a status dot.
+    ///
+    /// The theme renders the icon in front of the item, icons of different widths are
+    /// padded so all items stay aligned. Icons aren't part of the reported selection.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let selection: usize = Select::new()
+    ///         .item_with_icon("Cargo.toml", "⚙")
+    ///         .item_with_icon("src", "📁")
+    ///         .interact()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn item_with_icon<T: ToString, I: ToString>(&mut self, item: T, icon: I) -> &mut Self {
+        self.items.push(item.to_string());
+        self.icons.push(Some(icon.to_string()));
+        self.links.push(None);
+        self.disabled.push(false);
+        self
+    }
+
+    /// Adds a single item linking to `url`, like the page of an issue or a pull request.
+    ///
+    /// On terminals supporting hyperlinks the item can be opened with a click while the
+    /// selection is still made with the keyboard, elsewhere only the item is shown. The link
+    /// isn't part of the reported selection.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let selection: usize = Select::new()
+    ///         .with_prompt("Review which pull request?")
+    ///         .item_with_link("#41 Fix paging", "https://github.com/console-rs/dialoguer/pull/41")
+    ///         .item_with_link("#42 Add hints", "https://github.com/console-rs/dialoguer/pull/42")
+    ///         .interact()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn item_with_link<T: ToString, U: ToString>(&mut self, item: T, url: U) -> &mut Self {
+        self.items.push(item.to_string());
+        self.icons.push(None);
+        self.links.push(Some(url.to_string()));
+        self.disabled.push(false);
         self
     }
 
@@ -126,6 +341,9 @@ impl Select<'_> {
     pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Self {
         for item in items {
             self.items.push(item.to_string());
+            self.icons.push(None);
+            self.links.push(None);
+            self.disabled.push(false);
         }
         self
     }
@@ -163,25 +381,54 @@ impl Select<'_> {
         self
     }
 
+    /// Shows a hint line below the items, like `"↑↓ move · enter select"`.
+    ///
+    /// The theme styles the hint with [`Theme::format_hint`](crate::theme::Theme::format_hint)
+    /// and may suppress it.
+    pub fn with_hint<S: Into<String>>(&mut self, hint: S) -> &mut Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Shows a segment like a key map or a status at the right edge of the prompt line.
+    ///
+    /// The theme styles the segment with
+    /// [`Theme::format_right_segment`](crate::theme::Theme::format_right_segment). It is only
+    /// shown together with a prompt.
+    pub fn with_right_segment<S: Into<String>>(&mut self, segment: S) -> &mut Self {
+        self.right_segment = Some(segment.into());
+        self
+    }
+
+    /// Gives up waiting for the selection after `timeout`, resolving to the
+    /// [default](Self::default), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// The user can select the items with the 'Space' bar or 'Enter' and the index of selected item will be returned.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     /// Result contains `index` if user selected one of items using 'Enter'.
     /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
     #[inline]
     pub fn interact(&self) -> io::Result<usize> {
-        self.interact_on(&Term::stderr())
+        self.interact_on(&*default_term())
     }
 
     /// Enables user interaction and returns the result.
     ///
     /// The user can select the items with the 'Space' bar or 'Enter' and the index of selected item will be returned.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     /// Result contains `Some(index)` if user selected one of items using 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
     #[inline]
     pub fn interact_opt(&self) -> io::Result<Option<usize>> {
-        self.interact_on_opt(&Term::stderr())
+        self.interact_on_opt(&*default_term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
@@ -203,9 +450,41 @@ impl Select<'_> {
     /// }
     ///```
     #[inline]
-    pub fn interact_on(&self, term: &Term) -> io::Result<usize> {
-        self._interact_on(term, false)?
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<usize> {
+        timeout::ask(term, self.timeout, |term| {
+            events::observe_opt(term, |term| {
+                handle::ask(term, self.handle.as_ref(), |term| {
+                    self._interact_on(term, false)
+                })
+            })
+        })?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// The prompt still renders on the [default terminal](crate::set_default_term), and
+    /// fails with an [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) error if it waits for
+    /// more keys than given. This drives prompts from code, for integration tests or for
+    /// recording demos of a fixed interaction. Every prompt has this method.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::{console::Key, Select};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let selection = Select::new()
+    ///         .items(&["Option A", "Option B"])
+    ///         .default(0)
+    ///         .interact_with_keys(vec![Key::ArrowDown, Key::Enter])?;
+    ///
+    ///     assert_eq!(selection, 1);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<usize> {
+        with_keys(keys, |term| self.interact_on(term))
     }
 
     /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
@@ -230,123 +509,457 @@ impl Select<'_> {
     /// }
     /// ```
     #[inline]
-    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<usize>> {
-        self._interact_on(term, true)
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<usize>> {
+        timeout::ask(term, self.timeout, |term| {
+            events::observe_opt(term, |term| {
+                handle::ask(term, self.handle.as_ref(), |term| {
+                    self._interact_on(term, true)
+                })
+            })
+        })
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<usize>> {
-        if self.items.is_empty() {
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<usize>> {
+        // Answers are keyed by the prompt, and the items of a provider or changing ones
+        // aren't known up front.
+        let prompt = match self.prompt {
+            Some(ref prompt) if self.provider.is_none() && self.updates.is_none() => prompt,
+            _ => return self.ask(term, allow_quit),
+        };
+
+        if let Some(answer) = answers::replayed(prompt) {
+            let sel = fallback::parse_choice(&answer, &self.items)
+                .filter(|&idx| !self.is_disabled(idx))
+                .ok_or_else(|| {
+                    fallback::invalid_answer(&answer, "the number or text of an item")
+                })?;
+            if self.report {
+                TermThemeRenderer::new(term, &*self.theme)
+                    .select_prompt_selection(prompt, &self.items[sel])?;
+            }
+            term.flush()?;
+
+            return Ok(Some(sel));
+        }
+
+        let sel = self.ask(term, allow_quit)?;
+        if let Some(sel) = sel {
+            answers::record(prompt, &fallback::choice_answer(sel, &self.items))?;
+        }
+
+        Ok(sel)
+    }
+
+    /// Renders the prompt and waits for the answer.
+    fn ask(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<usize>> {
+        if self.items.is_empty() && self.provider.is_none() {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
                 "Empty list of items given to `Select`",
             ));
         }
+        if self.updates.is_some() && self.provider.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Item updates can't be combined with an item provider in `Select`",
+            ));
+        }
 
-        let mut paging = Paging::new(term, self.items.len(), self.max_length);
-        let mut render = TermThemeRenderer::new(term, self.theme);
+        if fallback::defaults_mode() {
+            if self.default >= self.items.len() || self.is_disabled(self.default) {
+                return Err(fallback::no_default(self.prompt.as_deref().unwrap_or("")));
+            }
+
+            let mut render = TermThemeRenderer::new(term, &*self.theme);
+            if let Some(ref prompt) = self.prompt {
+                if self.report {
+                    render.select_prompt_selection(prompt, &self.items[self.default])?;
+                }
+            }
+            term.flush()?;
+
+            return Ok(Some(self.default));
+        }
+
+        if fallback::is_active(term) {
+            return fallback::ask_lines(term, |error| self.interact_line(term, error)).map(Some);
+        }
+
+        if self.theme.compact() && self.provider.is_none() {
+            return self.interact_compact(term, allow_quit);
+        }
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        render.set_right_segment(self.right_segment.as_deref());
+        let mut provider = self.provider.as_ref().map(|provider| provider.borrow_mut());
+        let mut items = LoadedItems::new(
+            &self.items,
+            provider
+                .as_deref_mut()
+                .map(|provider| &mut **provider as &mut dyn ItemProvider),
+        );
+        let paging = Paging::new(
+            term,
+            items.len(),
+            self.max_length,
+            render.footer_rows(self.hint.is_some()),
+        )
+        .max_rows(self.max_rows)
+        .scrolling(self.scrolling)
+        .scroll_context(self.scroll_context);
         let mut sel = self.default;
 
-        let mut size_vec = Vec::new();
+        // Loads the items up to a screenful past the selected one.
+        let first = if sel == !0 { 0 } else { sel };
+        items.load_through(first + paging.capacity - 1, paging.capacity)?;
+        if items.loaded().is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of items given to `Select`",
+            ));
+        }
+        if sel != !0 {
+            sel = sel.min(items.loaded().len() - 1);
+        }
+
+        let mut paging = paging.items_len(items.len(), items.complete());
+        let mut icons = self.item_icons(&mut render, items.loaded().len())?;
+        let mut state = SelectState::new(items.len())
+            .default(if sel == !0 { None } else { Some(sel) })
+            .disabled(self.disabled.clone())
+            .allow_quit(allow_quit)
+            .numbered(self.numbered)
+            .horizontal_scroll(self.horizontal_scroll);
 
-        for items in self
-            .items
-            .iter()
-            .flat_map(|i| i.split('\n'))
-            .collect::<Vec<_>>()
-        {
-            let size = &items.len();
-            size_vec.push(*size);
+        if self.alt_screen {
+            render.enter_alternate_screen()?;
         }
+        if self.bottom {
+            render.anchor_to_bottom();
+        }
+        render.hide_cursor()?;
 
-        term.hide_cursor()?;
+        let mut keys = RepeatedKeys::default();
+        let mut active = None;
+        let mut prompt = self.prompt.clone();
 
         loop {
-            if let Some(ref prompt) = self.prompt {
-                paging.render_prompt(|paging_info| render.select_prompt(prompt, paging_info))?;
+            events::selection_changed(active, state.selection());
+            active = state.selection();
+
+            let ticked = render.countdown_ticked();
+            if handle::update_prompt(self.handle.as_ref(), &mut prompt) || ticked {
+                paging.redraw_prompt();
+            }
+            if let Some(ref prompt) = prompt {
+                paging.render_prompt(|paging_info| {
+                    render.clear_prompt()?;
+                    render.select_prompt(prompt, paging_info)
+                })?;
             }
 
-            for (idx, item) in self
-                .items
+            render.set_scrollbar(paging.scrollbar());
+            for (idx, item) in items
+                .loaded()
                 .iter()
                 .enumerate()
-                .skip(paging.current_page * paging.capacity)
+                .skip(paging.first_item())
                 .take(paging.capacity)
             {
-                render.select_prompt_item(item, sel == idx)?;
+                render.select_prompt_item(
+                    &link_item(item, &self.links, idx),
+                    icons[idx].as_deref(),
+                    self.is_disabled(idx),
+                    state.selection() == Some(idx),
+                )?;
             }
 
+            render.set_scrollbar(None);
+            paging.render_footer(|paging_info| render.paging_info_footer(paging_info))?;
+
+            if let Some(ref hint) = self.hint {
+                render.hint(hint)?;
+            }
+
+            render.finish_frame()?;
             term.flush()?;
 
-            match term.read_key()? {
-                Key::ArrowDown | Key::Tab | Key::Char('j') => {
-                    if sel == !0 {
-                        sel = 0;
-                    } else {
-                        sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
-                    }
+            let updates = updates::wait(term, self.updates.as_ref(), keys.pending())?;
+            if !updates.is_empty() {
+                let moved = items.apply(updates);
+                let sel = updates::follow(state.selection(), &moved, items.len());
+                state.set_len(items.len());
+                state.set_selection(sel);
+                if icons.len() != items.loaded().len() {
+                    icons = self.item_icons(&mut render, items.loaded().len())?;
                 }
-                Key::Escape | Key::Char('q') => {
-                    if allow_quit {
+
+                paging.set_items_len(items.len(), items.complete());
+                paging.update(state.selection().unwrap_or(!0))?;
+                render.rewind_preserve_prompt()?;
+                continue;
+            }
+
+            let (key, repeats) = keys.read(term)?;
+            for _ in 0..repeats {
+                let had_digits = state.jump_input().is_some();
+                state.set_page(paging.capacity, paging.active);
+                state.set_horizontal_offset(render.horizontal_offset());
+
+                match state.handle_key(key.clone()) {
+                    Transition::Continue => {}
+                    Transition::Cancelled => {
                         if self.clear {
                             render.clear()?;
                         } else {
-                            term.clear_last_lines(paging.capacity)?;
+                            render.clear_items()?;
                         }
 
-                        term.show_cursor()?;
+                        render.leave_alternate_screen()?;
+
+                        render.show_cursor()?;
                         term.flush()?;
 
                         return Ok(None);
                     }
-                }
-                Key::ArrowUp | Key::BackTab | Key::Char('k') => {
-                    if sel == !0 {
-                        sel = self.items.len() - 1;
-                    } else {
-                        sel = ((sel as i64 - 1 + self.items.len() as i64)
-                            % (self.items.len() as i64)) as usize;
+                    Transition::Done(sel) => {
+                        if self.clear {
+                            render.clear()?;
+                        }
+
+                        render.leave_alternate_screen()?;
+
+                        if let Some(ref prompt) = prompt {
+                            if self.report {
+                                render.select_prompt_selection(prompt, &items.loaded()[sel])?;
+                            }
+                        }
+
+                        render.show_cursor()?;
+                        term.flush()?;
+
+                        return Ok(Some(sel));
                     }
+                    _ => render.reject_key(&key)?,
                 }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if paging.active {
-                        sel = paging.previous_page();
-                    }
+
+                let prompt_changed = had_digits || state.jump_input().is_some();
+                render.set_jump_input(state.jump_input());
+                render.set_horizontal_offset(state.horizontal_offset());
+
+                if let Some(sel) = state.selection() {
+                    items.load_through(sel + paging.capacity - 1, paging.capacity)?;
+                    // Moving past the last item of an exhausted provider stays on it.
+                    state.set_selection(Some(sel.min(items.loaded().len() - 1)));
+                }
+                state.set_len(items.len());
+                if icons.len() != items.loaded().len() {
+                    icons = self.item_icons(&mut render, items.loaded().len())?;
                 }
-                Key::ArrowRight | Key::Char('l') => {
-                    if paging.active {
-                        sel = paging.next_page();
+
+                paging.set_items_len(items.len(), items.complete());
+                paging.update(state.selection().unwrap_or(!0))?;
+                if prompt_changed {
+                    paging.redraw_prompt();
+                }
+            }
+
+            render.rewind_preserve_prompt()?;
+        }
+    }
+
+    /// Returns the icons of the first `len` items, aligned and numbered as configured.
+    fn item_icons(
+        &self,
+        render: &mut TermThemeRenderer,
+        len: usize,
+    ) -> io::Result<Vec<Option<String>>> {
+        let mut icons = self.icons.clone();
+        icons.resize(len, None);
+        let icons = align_icons(&icons);
+
+        if self.numbered {
+            number_icons(render, icons)
+        } else {
+            Ok(icons)
+        }
+    }
+
+    /// Returns whether the item at `idx` can't be selected, items of a provider never are.
+    fn is_disabled(&self, idx: usize) -> bool {
+        self.disabled.get(idx).copied().unwrap_or(false)
+    }
+
+    /// Lists the numbered items and reads the number or text of one from stdin, or renders
+    /// the `error` of the previous answer and asks again.
+    ///
+    /// All items of a provider are fetched to list them.
+    fn interact_line(&self, term: &dyn Backend, error: Option<&str>) -> io::Result<usize> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let mut provider = self.provider.as_ref().map(|provider| provider.borrow_mut());
+        let mut items = LoadedItems::new(
+            &self.items,
+            provider
+                .as_deref_mut()
+                .map(|provider| &mut **provider as &mut dyn ItemProvider),
+        );
+        items.load_through(usize::MAX - 1, FALLBACK_PAGE_SIZE)?;
+
+        if let Some(err) = error {
+            render.error(err)?;
+        } else {
+            if let Some(ref prompt) = self.prompt {
+                render.select_prompt(prompt, None)?;
+            }
+            for (idx, item) in items.loaded().iter().enumerate() {
+                let number = format!("{})", idx + 1);
+                render.select_prompt_item(item, Some(&number), self.is_disabled(idx), false)?;
+            }
+        }
+
+        let default = if self.default == !0 {
+            None
+        } else {
+            Some((self.default + 1).to_string())
+        };
+        render.input_prompt("Choice", default.as_deref())?;
+
+        let answer = fallback::read_answer(term, true)?;
+        let choice = if answer.trim().is_empty() && self.default != !0 {
+            Some(self.default)
+        } else {
+            fallback::parse_choice(&answer, items.loaded())
+        };
+
+        match choice {
+            Some(idx) if idx < items.loaded().len() && !self.is_disabled(idx) => Ok(idx),
+            _ => Err(fallback::invalid_answer(
+                &answer,
+                "the number or text of an item",
+            )),
+        }
+    }
+
+    /// Renders the prompt on a single line showing only the current item.
+    fn interact_compact(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<usize>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let mut prompt = self.prompt.clone();
+        let mut items = self.items.clone();
+        let default = if self.default == !0 { 0 } else { self.default };
+        let mut state = SelectState::new(items.len())
+            .default(Some(default))
+            .disabled(self.disabled.clone())
+            .compact(true)
+            .allow_quit(allow_quit);
+        let mut icons = self.compact_icons(items.len());
+
+        render.hide_cursor()?;
+
+        loop {
+            handle::update_prompt(self.handle.as_ref(), &mut prompt);
+            let sel = state.selection().unwrap_or(0);
+            let prompt = prompt.as_deref().unwrap_or("");
+            render.select_prompt_compact(
+                prompt,
+                &link_item(&items[sel], &self.links, sel),
+                icons[sel].as_deref(),
+                self.is_disabled(sel),
+                sel,
+                items.len(),
+            )?;
+            term.flush()?;
+
+            let updates = updates::wait(term, self.updates.as_ref(), false)?;
+            if !updates.is_empty() {
+                let moved = updates::apply(&mut items, updates);
+                match updates::follow(Some(sel), &moved, items.len()) {
+                    Some(sel) => {
+                        state.set_len(items.len());
+                        state.set_selection(Some(sel));
+                    }
+                    None => {
+                        render.clear()?;
+                        render.show_cursor()?;
+                        return Err(io::Error::new(
+                            io::ErrorKind::Other,
+                            "All items were removed from `Select`",
+                        ));
                     }
                 }
+                icons = self.compact_icons(items.len());
+                render.clear()?;
+                continue;
+            }
 
-                Key::Enter | Key::Char(' ') if sel != !0 => {
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    render.clear()?;
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(sel) => {
                     if self.clear {
                         render.clear()?;
                     }
 
-                    if let Some(ref prompt) = self.prompt {
-                        if self.report {
-                            render.select_prompt_selection(prompt, &self.items[sel])?;
-                        }
+                    if !prompt.is_empty() && self.report {
+                        render.select_prompt_selection(prompt, &items[sel])?;
                     }
 
-                    term.show_cursor()?;
+                    render.show_cursor()?;
                     term.flush()?;
 
                     return Ok(Some(sel));
                 }
-                _ => {}
+                _ => render.reject_key(&key)?,
             }
 
-            paging.update(sel)?;
-
-            if paging.active {
-                render.clear()?;
-            } else {
-                render.clear_preserve_prompt(&size_vec)?;
-            }
+            render.clear()?;
         }
     }
+
+    /// Returns the aligned icons of `len` items for the compact prompt.
+    fn compact_icons(&self, len: usize) -> Vec<Option<String>> {
+        let mut icons = self.icons.clone();
+        icons.resize(len, None);
+        align_icons(&icons)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Select<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio with `spawn_blocking`, so awaiting it in a task doesn't
+    /// stall the other tasks with their timers and network IO.
+    ///
+    /// The prompt is moved there, leaving this builder with its defaults. It starts right
+    /// away and keeps the terminal until it is answered, also when the future is dropped.
+    /// A panicking prompt resolves to an error.
+    ///
+    /// Panics when called outside of a tokio runtime.
+    ///
+    /// ## Example usage
+    ///
+    /// ```rust,no_run
+    /// # async fn test() -> std::io::Result<()> {
+    /// use dialoguer::Select;
+    ///
+    /// let selection = Select::new()
+    ///     .with_prompt("Do you want to continue?")
+    ///     .items(&["Yes", "No"])
+    ///     .interact_async()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<usize>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
+    }
 }
 
 impl<'a> Select<'a> {
@@ -368,22 +981,113 @@ impl<'a> Select<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub fn with_theme(theme: &'a dyn Theme) -> Self {
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
         Self {
             default: !0,
             items: vec![],
+            icons: vec![],
+            links: vec![],
+            disabled: vec![],
             prompt: None,
+            hint: None,
+            right_segment: None,
             report: false,
             clear: true,
             max_length: None,
-            theme,
+            max_rows: None,
+            scrolling: false,
+            scroll_context: None,
+            alt_screen: false,
+            bottom: false,
+            numbered: false,
+            horizontal_scroll: false,
+            provider: None,
+            updates: None,
+            handle: None,
+            timeout: None,
+            theme: theme.into(),
         }
     }
+
+    /// Loads further items on demand from `provider`, after the ones added otherwise.
+    ///
+    /// Items are fetched a page at a time as the user navigates past the loaded ones, and
+    /// the page indicator shows the number of pages as unknown until the provider runs out
+    /// or reports its [`total`](ItemProvider::total). Providers can't be combined with
+    /// compact themes, the prompt renders the list instead.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let selection = Select::new()
+    ///         .with_prompt("Pick a line")
+    ///         .item_provider(|start: usize, count: usize| {
+    ///             Ok((start..start + count).map(|i| format!("line {}", i)).collect())
+    ///         })
+    ///         .interact()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn item_provider<P: ItemProvider + Send + 'a>(&mut self, provider: P) -> &mut Self {
+        self.provider = Some(RefCell::new(Box::new(provider)));
+        self
+    }
+
+    /// Changes the items while the prompt is open with the updates received from `updates`,
+    /// like devices being plugged in or removed.
+    ///
+    /// The cursor stays on its item, or at its position if the item was removed. The
+    /// selection is the index of the item with all updates received so far applied. Icons,
+    /// links and disabled items stay at the index they were given for. Updates can't be
+    /// combined with an [item provider](Self::item_provider).
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use std::{sync::mpsc, thread};
+    ///
+    /// use dialoguer::{ItemUpdate, Select};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let (sender, updates) = mpsc::channel();
+    ///     thread::spawn(move || {
+    ///         // Watches for devices being plugged in.
+    ///         let _ = sender.send(ItemUpdate::Push("/dev/sdb".into()));
+    ///     });
+    ///
+    ///     let device = Select::new()
+    ///         .with_prompt("Pick a device")
+    ///         .item("/dev/sda")
+    ///         .item_updates(updates)
+    ///         .interact()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn item_updates(&mut self, updates: Receiver<ItemUpdate>) -> &mut Self {
+        self.updates = Some(updates);
+        self
+    }
+
+    /// Splits off a handle controlling the prompt from other threads, which can render it
+    /// again, cancel it or change its text while it waits for the answer.
+    ///
+    /// See [`PromptHandle`] for an example.
+    pub fn into_parts(mut self) -> (Self, PromptHandle) {
+        let handle = PromptHandle::new();
+        self.handle = Some(handle.clone());
+        (self, handle)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{theme::ColorfulTheme, Headless};
+
+    use std::sync::mpsc;
 
     #[test]
     fn test_str() {
@@ -422,4 +1126,82 @@ mod tests {
             selections
         );
     }
+
+    #[test]
+    fn test_align_icons() {
+        let icons = vec![Some("ab".to_string()), None, Some("📁".to_string())];
+        assert_eq!(
+            align_icons(&icons),
+            [Some("ab".into()), Some("  ".into()), Some("📁".into())]
+        );
+        assert_eq!(align_icons(&[None, None]), [None, None]);
+
+        let icons = vec![Some("❤\u{fe0f}".to_string()), Some("日本".to_string())];
+        assert_eq!(
+            align_icons(&icons),
+            [Some("❤\u{fe0f}  ".into()), Some("日本".into())]
+        );
+    }
+
+    #[test]
+    fn test_compact_clamps_default() {
+        let theme = ColorfulTheme::builder().compact(true).build();
+        let term = Headless::new(10, 40).keys(vec![Key::Enter]);
+        let sel = Select::with_theme(&theme)
+            .items(&["a", "b"])
+            .default(5)
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(sel, 1);
+    }
+
+    #[test]
+    fn test_compact_applies_item_updates() {
+        let (sender, updates) = mpsc::channel();
+        sender.send(ItemUpdate::Insert(0, "z".into())).unwrap();
+        let theme = ColorfulTheme::builder().compact(true).build();
+        let term = Headless::new(10, 40).keys(vec![Key::ArrowUp, Key::Enter]);
+        let sel = Select::with_theme(&theme)
+            .items(&["a", "b"])
+            .default(1)
+            .item_updates(updates)
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(sel, 1);
+
+        let (sender, updates) = mpsc::channel();
+        sender.send(ItemUpdate::Replace(vec![])).unwrap();
+        let term = Headless::new(10, 40).keys(vec![Key::Enter]);
+        assert!(Select::with_theme(&theme)
+            .items(&["a"])
+            .item_updates(updates)
+            .interact_on(&term)
+            .is_err());
+    }
+
+    #[test]
+    fn test_quit_and_page_keys_ignored() {
+        // Like before rejected keys rang the bell, 'q' without quitting and the page keys
+        // without pages do nothing.
+        for compact in [false, true] {
+            let theme = ColorfulTheme::builder().bell(true).compact(compact).build();
+            let term = crate::StreamBackend::new(&b"qlhq\r"[..], Vec::new());
+            let sel = Select::with_theme(&theme)
+                .items(&["a", "b"])
+                .default(0)
+                .interact_on(&term)
+                .unwrap();
+            assert_eq!(sel, 0);
+            assert!(!term.into_inner().1.contains(&b'\x07'));
+        }
+
+        let theme = ColorfulTheme::builder().bell(true).build();
+        let term = crate::StreamBackend::new(&b"x\r"[..], Vec::new());
+        Select::with_theme(&theme)
+            .items(&["a", "b"])
+            .default(0)
+            .interact_on(&term)
+            .unwrap();
+        assert!(term.into_inner().1.contains(&b'\x07'));
+    }
 }
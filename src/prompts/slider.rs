@@ -0,0 +1,359 @@
+use std::{io, time::Duration};
+
+use crate::{
+    backend::{default_term, with_keys},
+    fallback,
+    prompts::number_input::Numeric,
+    state::{SliderState, Transition},
+    theme::{SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout, Backend,
+};
+
+use console::Key;
+
+/// Renders a slider prompt for a bounded range.
+///
+/// The value is moved with the 'Left' and 'Right' arrow keys (or 'h' and 'l').
+/// Big steps are taken with 'Shift' and the arrow keys, or with 'PageUp' and 'PageDown'
+/// (or 'H' and 'L') in terminals which don't report 'Shift'. 'Home' and 'End' jump to the
+/// bounds. Both bounds have to be set before interacting.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::Slider;
+///
+/// let volume: u8 = Slider::new()
+///     .with_prompt("Volume")
+///     .min(0)
+///     .max(100)
+///     .default(50)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Slider<'a, T> {
+    prompt: String,
+    report: bool,
+    default: Option<T>,
+    min: Option<T>,
+    max: Option<T>,
+    step: Option<T>,
+    big_step: Option<T>,
+    width: usize,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl<T> Default for Slider<'static, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Slider<'static, T> {
+    /// Creates a slider prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl<T> Slider<'_, T> {
+    /// Sets the slider prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the chosen value after interaction.
+    ///
+    /// The default is to report the chosen value.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Sets the initial value.
+    ///
+    /// Without a default the slider starts at the lower bound.
+    pub fn default(&mut self, val: T) -> &mut Self {
+        self.default = Some(val);
+        self
+    }
+
+    /// Sets the lower bound of the slider.
+    pub fn min(&mut self, val: T) -> &mut Self {
+        self.min = Some(val);
+        self
+    }
+
+    /// Sets the upper bound of the slider.
+    pub fn max(&mut self, val: T) -> &mut Self {
+        self.max = Some(val);
+        self
+    }
+
+    /// Sets the amount the value changes by on every 'Left' or 'Right' key press.
+    ///
+    /// The default is one.
+    pub fn step(&mut self, val: T) -> &mut Self {
+        self.step = Some(val);
+        self
+    }
+
+    /// Sets the amount the value changes by on every big step, like 'Shift' + 'Right'.
+    ///
+    /// The default is ten steps.
+    pub fn big_step(&mut self, val: T) -> &mut Self {
+        self.big_step = Some(val);
+        self
+    }
+
+    /// Sets the number of characters used to draw the slider bar.
+    ///
+    /// The default is 20.
+    pub fn width(&mut self, val: usize) -> &mut Self {
+        self.width = val;
+        self
+    }
+}
+
+impl<T: Numeric> Slider<'_, T> {
+    /// Gives up waiting for the value after `timeout`, resolving to the
+    /// [default](Self::default), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the value if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<T> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(value)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<T>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<T> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<T> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<T>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<T>> {
+        let (min, max) = match (self.min, self.max) {
+            (Some(min), Some(max)) if min <= max => (min, max),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Invalid or missing bounds given to `Slider`",
+                ))
+            }
+        };
+
+        let mut state = SliderState::new(min, max).step(self.step.unwrap_or_else(T::one));
+        if let Some(big_step) = self.big_step {
+            state = state.big_step(big_step);
+        }
+        if let Some(default) = self.default {
+            state = state.default(default);
+        }
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            let default = match self.default {
+                Some(_) => state.value(),
+                None => return Err(fallback::no_default(&self.prompt)),
+            };
+            if self.report {
+                render.slider_prompt_selection(&self.prompt, &default.to_string())?;
+            }
+            term.flush()?;
+
+            return Ok(Some(default));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut state = state.allow_quit(allow_quit);
+
+        render.hide_cursor()?;
+
+        loop {
+            term.clear_line()?;
+            render.slider_prompt(
+                &self.prompt,
+                &state.value().to_string(),
+                state.ratio(),
+                self.width,
+            )?;
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    term.clear_line()?;
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(value) => {
+                    term.clear_line()?;
+
+                    if self.report {
+                        render.slider_prompt_selection(&self.prompt, &value.to_string())?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(value));
+                }
+                _ => render.reject_key(&key)?,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: Numeric + Send + 'static> Slider<'static, T> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<T>> {
+        crate::task::interact_async(std::mem::take(self), |input| input.interact())
+    }
+}
+
+impl<'a, T> Slider<'a, T> {
+    /// Creates a slider prompt with a specific theme.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::{
+    ///     Slider,
+    ///     theme::ColorfulTheme
+    /// };
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let quality: f32 = Slider::with_theme(&ColorfulTheme::default())
+    ///     .with_prompt("Quality")
+    ///     .min(0.0)
+    ///     .max(1.0)
+    ///     .step(0.05)
+    ///     .interact()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            default: None,
+            min: None,
+            max: None,
+            step: None,
+            big_step: None,
+            width: 20,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Headless;
+
+    fn slide<T: Numeric>(slider: &mut Slider<T>, keys: Vec<Key>) -> T {
+        let term = Headless::new(5, 60).keys(keys.into_iter().chain(Some(Key::Enter)));
+        slider.interact_on(&term).unwrap()
+    }
+
+    #[test]
+    fn test_steps() {
+        let mut slider = Slider::new();
+        slider.min(0).max(100).default(50);
+
+        assert_eq!(
+            slide(&mut slider, vec![Key::ArrowRight, Key::ArrowRight]),
+            52
+        );
+        assert_eq!(slide(&mut slider, vec![Key::ArrowLeft]), 49);
+        let shift_right = crate::escape::shift_arrow(&Key::ArrowRight);
+        assert_eq!(slide(&mut slider, vec![shift_right]), 60);
+        assert_eq!(slide(&mut slider, vec![Key::PageDown]), 40);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut slider = Slider::new();
+        slider.min(0).max(100).default(95);
+
+        assert_eq!(slide(&mut slider, vec![Key::PageUp]), 100);
+        assert_eq!(slide(&mut slider, vec![Key::PageUp, Key::ArrowRight]), 100);
+        assert_eq!(slide(&mut slider, vec![Key::Home, Key::ArrowLeft]), 0);
+
+        slider.default(200);
+        assert_eq!(slide(&mut slider, vec![]), 100);
+        slider.min(101);
+        let term = Headless::new(5, 60).keys(vec![Key::Enter]);
+        assert!(slider.interact_on(&term).is_err());
+    }
+
+    #[test]
+    fn test_float_steps_are_rounded() {
+        let mut slider = Slider::new();
+        slider.min(0.0).max(1.0).step(0.1);
+
+        let keys = vec![Key::ArrowRight; 3];
+        assert_eq!(slide(&mut slider, keys), 0.3);
+
+        let mut slider = Slider::new();
+        slider.min(0.0f32).max(1.0).step(0.05).default(0.1);
+        assert_eq!(slide(&mut slider, vec![Key::ArrowRight; 5]), 0.35);
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = Headless::new(10, 40);
+        let err =
+            fallback::test_without_tty(|| Slider::<u32>::new().min(0).max(10).interact_on(&term))
+                .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
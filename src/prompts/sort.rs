@@ -1,16 +1,22 @@
-use std::{io, ops::Rem};
+use std::{io, time::Duration};
 
 use crate::{
-    theme::{SimpleTheme, TermThemeRenderer, Theme},
-    Paging,
+    backend::{default_term, with_keys, RepeatedKeys},
+    events, fallback,
+    state::{SortState, Transition},
+    theme::{SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout, Backend, Paging,
 };
 
-use console::{Key, Term};
+use console::Key;
 
 /// Renders a sort prompt.
 ///
 /// Returns list of indices in original items list sorted according to user input.
 ///
+/// 'PageUp' and 'PageDown' move the cursor by a screenful, 'Home' and 'End' to the first
+/// and last item, carrying a picked item along.
+///
 /// ## Example usage
 /// ```rust,no_run
 /// use dialoguer::Sort;
@@ -27,10 +33,18 @@ use console::{Key, Term};
 pub struct Sort<'a> {
     items: Vec<String>,
     prompt: Option<String>,
+    hint: Option<String>,
+    right_segment: Option<String>,
     report: bool,
     clear: bool,
     max_length: Option<usize>,
-    theme: &'a dyn Theme,
+    max_rows: Option<usize>,
+    scrolling: bool,
+    scroll_context: Option<usize>,
+    alt_screen: bool,
+    bottom: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
 }
 
 impl Default for Sort<'static> {
@@ -67,6 +81,55 @@ impl Sort<'_> {
         self
     }
 
+    /// Sets the maximum number of terminal rows the prompt takes up.
+    ///
+    /// The prompt line and the footer count towards it and items beyond it are paged, which
+    /// keeps room for output above the prompt. The terminal height still applies if it is
+    /// smaller.
+    pub fn max_rows(&mut self, val: usize) -> &mut Self {
+        self.max_rows = Some(val);
+        self
+    }
+
+    /// Indicates whether the visible items scroll with the cursor instead of flipping pages.
+    ///
+    /// Scrolling keeps a few items visible above and below the cursor. The default is to
+    /// flip pages.
+    pub fn scrolling(&mut self, val: bool) -> &mut Self {
+        self.scrolling = val;
+        self
+    }
+
+    /// Sets the number of items kept visible above and below the cursor when
+    /// [`scrolling`](Self::scrolling).
+    ///
+    /// A context of half the visible items or more keeps the cursor vertically centered.
+    /// The default is 2.
+    pub fn scroll_context(&mut self, val: usize) -> &mut Self {
+        self.scroll_context = Some(val);
+        self
+    }
+
+    /// Indicates whether the prompt renders in the alternate screen.
+    ///
+    /// This keeps large lists from scrolling the shell history away. The primary screen is
+    /// restored when the prompt ends, showing only the reported selection. The default is to
+    /// render inline.
+    pub fn render_in_alt_screen(&mut self, val: bool) -> &mut Self {
+        self.alt_screen = val;
+        self
+    }
+
+    /// Indicates whether the prompt renders pinned to the bottom of the terminal.
+    ///
+    /// Like fzf, the prompt is drawn on the last rows of the terminal with the earlier output
+    /// left above it, instead of right below that output. The default is to render right
+    /// below.
+    pub fn render_at_bottom(&mut self, val: bool) -> &mut Self {
+        self.bottom = val;
+        self
+    }
+
     /// Add a single item to the selector.
     pub fn item<T: ToString>(&mut self, item: T) -> &mut Self {
         self.items.push(item.to_string());
@@ -98,25 +161,53 @@ impl Sort<'_> {
         self
     }
 
+    /// Shows a hint line below the items, like `"↑↓ move · space pick · enter confirm"`.
+    ///
+    /// The theme styles the hint with [`Theme::format_hint`](crate::theme::Theme::format_hint)
+    /// and may suppress it.
+    pub fn with_hint<S: Into<String>>(&mut self, hint: S) -> &mut Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Shows a segment like a key map or a status at the right edge of the prompt line.
+    ///
+    /// The theme styles the segment with
+    /// [`Theme::format_right_segment`](crate::theme::Theme::format_right_segment). It is only
+    /// shown together with a prompt.
+    pub fn with_right_segment<S: Into<String>>(&mut self, segment: S) -> &mut Self {
+        self.right_segment = Some(segment.into());
+        self
+    }
+
+    /// Gives up waiting for the order after `timeout`, resolving to the original order.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// The user can order the items with the 'Space' bar and the arrows. On 'Enter' ordered list of the incides of items will be returned.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     /// Result contains `Vec<index>` if user hit 'Enter'.
     /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
     #[inline]
     pub fn interact(&self) -> io::Result<Vec<usize>> {
-        self.interact_on(&Term::stderr())
+        self.interact_on(&*default_term())
     }
 
     /// Enables user interaction and returns the result.
     ///
     /// The user can order the items with the 'Space' bar and the arrows. On 'Enter' ordered list of the incides of items will be returned.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
     /// Result contains `Some(Vec<index>)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
     #[inline]
     pub fn interact_opt(&self) -> io::Result<Option<Vec<usize>>> {
-        self.interact_on_opt(&Term::stderr())
+        self.interact_on_opt(&*default_term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
@@ -138,9 +229,21 @@ impl Sort<'_> {
     /// }
     ///```
     #[inline]
-    pub fn interact_on(&self, term: &Term) -> io::Result<Vec<usize>> {
-        self._interact_on(term, false)?
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Vec<usize>> {
+        timeout::ask(term, self.timeout, |term| {
+            events::observe_opt(term, |term| self._interact_on(term, false))
+        })?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(
+        &self,
+        keys: K,
+    ) -> io::Result<Vec<usize>> {
+        with_keys(keys, |term| self.interact_on(term))
     }
 
     /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
@@ -165,11 +268,13 @@ impl Sort<'_> {
     /// }
     /// ```
     #[inline]
-    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<Vec<usize>>> {
-        self._interact_on(term, true)
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Vec<usize>>> {
+        timeout::ask(term, self.timeout, |term| {
+            events::observe_opt(term, |term| self._interact_on(term, true))
+        })
     }
 
-    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<Vec<usize>>> {
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<Vec<usize>>> {
         if self.items.is_empty() {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
@@ -177,172 +282,191 @@ impl Sort<'_> {
             ));
         }
 
-        let mut paging = Paging::new(term, self.items.len(), self.max_length);
-        let mut render = TermThemeRenderer::new(term, self.theme);
-        let mut sel = 0;
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        render.set_right_segment(self.right_segment.as_deref());
 
-        let mut size_vec = Vec::new();
+        if fallback::defaults_mode() {
+            if let Some(ref prompt) = self.prompt {
+                if self.report {
+                    let list: Vec<_> = self.items.iter().map(String::as_str).collect();
+                    render.sort_prompt_selection(prompt, &list)?;
+                }
+            }
+            term.flush()?;
 
-        for items in self.items.iter().as_slice() {
-            let size = &items.len();
-            size_vec.push(*size);
+            return Ok(Some((0..self.items.len()).collect()));
         }
 
-        let mut order: Vec<_> = (0..self.items.len()).collect();
-        let mut checked: bool = false;
+        fallback::require_tty(term)?;
+
+        let mut paging = Paging::new(
+            term,
+            self.items.len(),
+            self.max_length,
+            render.footer_rows(self.hint.is_some()),
+        )
+        .max_rows(self.max_rows)
+        .scrolling(self.scrolling)
+        .scroll_context(self.scroll_context);
+        let mut state = SortState::new(self.items.len()).allow_quit(allow_quit);
+
+        if self.alt_screen {
+            render.enter_alternate_screen()?;
+        }
+        if self.bottom {
+            render.anchor_to_bottom();
+        }
+        render.hide_cursor()?;
 
-        term.hide_cursor()?;
+        let mut keys = RepeatedKeys::default();
+        let mut active = None;
 
         loop {
+            let current = state
+                .selection()
+                .and_then(|sel| state.order().get(sel).copied());
+            events::selection_changed(active, current);
+            active = current;
+
             if let Some(ref prompt) = self.prompt {
-                paging.render_prompt(|paging_info| render.sort_prompt(prompt, paging_info))?;
+                paging.render_prompt(|paging_info| {
+                    render.clear_prompt()?;
+                    render.sort_prompt(prompt, paging_info)
+                })?;
             }
 
-            for (idx, item) in order
+            render.set_scrollbar(paging.scrollbar());
+            for (idx, item) in state
+                .order()
                 .iter()
                 .enumerate()
-                .skip(paging.current_page * paging.capacity)
+                .skip(paging.first_item())
                 .take(paging.capacity)
             {
-                render.sort_prompt_item(&self.items[*item], checked, sel == idx)?;
+                render.sort_prompt_item(
+                    &self.items[*item],
+                    state.picked(),
+                    state.selection() == Some(idx),
+                )?;
             }
 
-            term.flush()?;
+            render.set_scrollbar(None);
+            paging.render_footer(|paging_info| render.paging_info_footer(paging_info))?;
 
-            match term.read_key()? {
-                Key::ArrowDown | Key::Tab | Key::Char('j') => {
-                    let old_sel = sel;
+            if let Some(ref hint) = self.hint {
+                render.hint(hint)?;
+            }
 
-                    if sel == !0 {
-                        sel = 0;
-                    } else {
-                        sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
-                    }
+            render.finish_frame()?;
+            term.flush()?;
 
-                    if checked && old_sel != sel {
-                        order.swap(old_sel, sel);
-                    }
-                }
-                Key::ArrowUp | Key::BackTab | Key::Char('k') => {
-                    let old_sel = sel;
-
-                    if sel == !0 {
-                        sel = self.items.len() - 1;
-                    } else {
-                        sel = ((sel as i64 - 1 + self.items.len() as i64)
-                            % (self.items.len() as i64)) as usize;
-                    }
+            let (key, repeats) = keys.read(term)?;
+            for _ in 0..repeats {
+                state.set_page(paging.capacity, paging.active);
 
-                    if checked && old_sel != sel {
-                        order.swap(old_sel, sel);
-                    }
-                }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if paging.active {
-                        let old_sel = sel;
-                        let old_page = paging.current_page;
-
-                        sel = paging.previous_page();
-
-                        if checked {
-                            let indexes: Vec<_> = if old_page == 0 {
-                                let indexes1: Vec<_> = (0..=old_sel).rev().collect();
-                                let indexes2: Vec<_> = (sel..self.items.len()).rev().collect();
-                                [indexes1, indexes2].concat()
-                            } else {
-                                (sel..=old_sel).rev().collect()
-                            };
-
-                            for index in 0..(indexes.len() - 1) {
-                                order.swap(indexes[index], indexes[index + 1]);
-                            }
-                        }
-                    }
-                }
-                Key::ArrowRight | Key::Char('l') => {
-                    if paging.active {
-                        let old_sel = sel;
-                        let old_page = paging.current_page;
-
-                        sel = paging.next_page();
-
-                        if checked {
-                            let indexes: Vec<_> = if old_page == paging.pages - 1 {
-                                let indexes1: Vec<_> = (old_sel..self.items.len()).collect();
-                                let indexes2: Vec<_> = vec![0];
-                                [indexes1, indexes2].concat()
-                            } else {
-                                (old_sel..=sel).collect()
-                            };
-
-                            for index in 0..(indexes.len() - 1) {
-                                order.swap(indexes[index], indexes[index + 1]);
-                            }
-                        }
-                    }
-                }
-                Key::Char(' ') => {
-                    checked = !checked;
-                }
-                Key::Escape | Key::Char('q') => {
-                    if allow_quit {
+                match state.handle_key(key.clone()) {
+                    Transition::Continue => {}
+                    Transition::Cancelled => {
                         if self.clear {
                             render.clear()?;
                         } else {
-                            term.clear_last_lines(paging.capacity)?;
+                            render.clear_items()?;
                         }
 
-                        term.show_cursor()?;
+                        render.leave_alternate_screen()?;
+
+                        render.show_cursor()?;
                         term.flush()?;
 
                         return Ok(None);
                     }
-                }
-                Key::Enter => {
-                    if self.clear {
-                        render.clear()?;
-                    }
+                    Transition::Done(order) => {
+                        if self.clear {
+                            render.clear()?;
+                        }
 
-                    if let Some(ref prompt) = self.prompt {
-                        if self.report {
-                            let list: Vec<_> = order
-                                .iter()
-                                .enumerate()
-                                .map(|(_, item)| self.items[*item].as_str())
-                                .collect();
-                            render.sort_prompt_selection(prompt, &list[..])?;
+                        render.leave_alternate_screen()?;
+
+                        if let Some(ref prompt) = self.prompt {
+                            if self.report {
+                                let list: Vec<_> = order
+                                    .iter()
+                                    .map(|item| self.items[*item].as_str())
+                                    .collect();
+                                render.sort_prompt_selection(prompt, &list[..])?;
+                            }
                         }
-                    }
 
-                    term.show_cursor()?;
-                    term.flush()?;
+                        render.show_cursor()?;
+                        term.flush()?;
 
-                    return Ok(Some(order));
+                        return Ok(Some(order));
+                    }
+                    _ => render.reject_key(&key)?,
                 }
-                _ => {}
-            }
 
-            paging.update(sel)?;
-
-            if paging.active {
-                render.clear()?;
-            } else {
-                render.clear_preserve_prompt(&size_vec)?;
+                paging.update(state.selection().unwrap_or(!0))?;
             }
+
+            render.rewind_preserve_prompt()?;
         }
     }
 }
 
+#[cfg(feature = "tokio")]
+impl Sort<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Vec<usize>>> {
+        crate::task::interact_async(std::mem::take(self), |sort| sort.interact())
+    }
+}
+
 impl<'a> Sort<'a> {
     /// Creates a sort prompt with a specific theme.
-    pub fn with_theme(theme: &'a dyn Theme) -> Self {
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
         Self {
             items: vec![],
             clear: true,
             prompt: None,
+            hint: None,
+            right_segment: None,
             report: true,
             max_length: None,
-            theme,
+            max_rows: None,
+            scrolling: false,
+            scroll_context: None,
+            alt_screen: false,
+            bottom: false,
+            timeout: None,
+            theme: theme.into(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quit_and_page_keys_ignored() {
+        let theme = crate::theme::ColorfulTheme::builder().bell(true).build();
+        let term = crate::StreamBackend::new(&b" lhq \r"[..], Vec::new());
+        let order = Sort::with_theme(&theme)
+            .items(&["a", "b"])
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(order, [0, 1]);
+        assert!(!term.into_inner().1.contains(&b'\x07'));
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| Sort::new().items(&["a", "b"]).interact_on(&term))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
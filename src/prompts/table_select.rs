@@ -0,0 +1,508 @@
+use std::{io, time::Duration};
+
+use crate::{
+    backend::{default_term, with_keys},
+    fallback,
+    state::{TableSelectState, Transition},
+    theme::{SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout,
+    width::{text_width, truncate_text},
+    Backend, Paging,
+};
+
+use console::{pad_str, Alignment, Key};
+
+/// The number of characters rendered between two columns.
+const COLUMN_GAP: usize = 2;
+
+/// The number of characters taken up by the selection marker in front of a row.
+const ROW_PREFIX: usize = 2;
+
+/// Shrinks the widest columns until all of them fit into `available` characters.
+fn layout_columns(widths: &[usize], available: usize) -> Vec<usize> {
+    let mut widths = widths.to_vec();
+    let gaps = COLUMN_GAP * widths.len().saturating_sub(1);
+
+    while widths.iter().sum::<usize>() + gaps > available {
+        match widths.iter_mut().max() {
+            Some(widest) if *widest > 1 => *widest -= 1,
+            _ => break,
+        }
+    }
+
+    widths
+}
+
+/// Pads (and truncates with `ellipsis` if needed) every cell to the width of its column.
+fn format_cells(cells: &[String], widths: &[usize], ellipsis: &str) -> Vec<String> {
+    widths
+        .iter()
+        .enumerate()
+        .map(|(idx, &width)| {
+            let cell = cells.get(idx).map(String::as_str).unwrap_or("");
+            if text_width(cell) > width {
+                truncate_text(cell, width, ellipsis).into_owned()
+            } else {
+                pad_str(cell, width, Alignment::Left, None).into_owned()
+            }
+        })
+        .collect()
+}
+
+/// Renders a select prompt over rows of aligned columns.
+///
+/// Every item is a row of cells. Columns are padded to the width of their widest cell
+/// and shrunk (truncating the cells with `…`) when the terminal is too narrow.
+/// Interaction returns the index of the selected row.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::TableSelect;
+///
+/// fn main() -> std::io::Result<()> {
+///     let selection = TableSelect::new()
+///         .with_prompt("Pick a VM")
+///         .header(&["Name", "Region", "Status"])
+///         .row(&["web-1", "eu-west-1", "running"])
+///         .row(&["db-1", "us-east-2", "stopped"])
+///         .interact()?;
+///
+///     println!("User selected row {}", selection);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct TableSelect<'a> {
+    default: usize,
+    header: Option<Vec<String>>,
+    rows: Vec<Vec<String>>,
+    prompt: Option<String>,
+    report: bool,
+    clear: bool,
+    max_length: Option<usize>,
+    max_rows: Option<usize>,
+    scrolling: bool,
+    scroll_context: Option<usize>,
+    alt_screen: bool,
+    bottom: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for TableSelect<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableSelect<'static> {
+    /// Creates a table select prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl TableSelect<'_> {
+    /// Indicates whether the table should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the table.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Sets initial selected row when the table is rendered.
+    pub fn default(&mut self, val: usize) -> &mut Self {
+        self.default = val;
+        self
+    }
+
+    /// Sets an optional max length for a page.
+    ///
+    /// Max length is disabled by None
+    pub fn max_length(&mut self, val: usize) -> &mut Self {
+        // Paging subtracts two from the capacity, see `Select::max_length`.
+        self.max_length = Some(val + 2);
+        self
+    }
+
+    /// Sets the maximum number of terminal rows the prompt takes up.
+    ///
+    /// The prompt line and the footer count towards it and items beyond it are paged, which
+    /// keeps room for output above the prompt. The terminal height still applies if it is
+    /// smaller.
+    pub fn max_rows(&mut self, val: usize) -> &mut Self {
+        self.max_rows = Some(val);
+        self
+    }
+
+    /// Indicates whether the visible items scroll with the cursor instead of flipping pages.
+    ///
+    /// Scrolling keeps a few items visible above and below the cursor. The default is to
+    /// flip pages.
+    pub fn scrolling(&mut self, val: bool) -> &mut Self {
+        self.scrolling = val;
+        self
+    }
+
+    /// Sets the number of items kept visible above and below the cursor when
+    /// [`scrolling`](Self::scrolling).
+    ///
+    /// A context of half the visible items or more keeps the cursor vertically centered.
+    /// The default is 2.
+    pub fn scroll_context(&mut self, val: usize) -> &mut Self {
+        self.scroll_context = Some(val);
+        self
+    }
+
+    /// Indicates whether the prompt renders in the alternate screen.
+    ///
+    /// This keeps large lists from scrolling the shell history away. The primary screen is
+    /// restored when the prompt ends, showing only the reported selection. The default is to
+    /// render inline.
+    pub fn render_in_alt_screen(&mut self, val: bool) -> &mut Self {
+        self.alt_screen = val;
+        self
+    }
+
+    /// Indicates whether the prompt renders pinned to the bottom of the terminal.
+    ///
+    /// Like fzf, the prompt is drawn on the last rows of the terminal with the earlier output
+    /// left above it, instead of right below that output. The default is to render right
+    /// below.
+    pub fn render_at_bottom(&mut self, val: bool) -> &mut Self {
+        self.bottom = val;
+        self
+    }
+
+    /// Sets the header row rendered above the table.
+    pub fn header<T: ToString>(&mut self, cells: &[T]) -> &mut Self {
+        self.header = Some(cells.iter().map(ToString::to_string).collect());
+        self
+    }
+
+    /// Adds a single row to the table.
+    pub fn row<T: ToString>(&mut self, cells: &[T]) -> &mut Self {
+        self.rows
+            .push(cells.iter().map(ToString::to_string).collect());
+        self
+    }
+
+    /// Adds multiple rows to the table.
+    pub fn rows<R, T>(&mut self, rows: &[R]) -> &mut Self
+    where
+        R: AsRef<[T]>,
+        T: ToString,
+    {
+        for row in rows {
+            self.row(row.as_ref());
+        }
+        self
+    }
+
+    /// Sets the table select prompt.
+    ///
+    /// By default, when a prompt is set the system also prints out a confirmation after
+    /// the selection. You can opt-out of this with [`report`](#method.report).
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self.report = true;
+        self
+    }
+
+    /// Indicates whether to report the selected row after interaction.
+    ///
+    /// The default is to report the selection.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Gives up waiting for the selection after `timeout`, resolving to the
+    /// [default](Self::default), or failing with [`timed_out`](crate::timed_out) without one.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the index of the row if user hit 'Enter' or 'Space'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<usize> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(index)` if user hit 'Enter' or 'Space' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<usize>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<usize> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<usize> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<usize>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    /// Returns the natural width of every column.
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = vec![];
+
+        for row in self.header.iter().chain(self.rows.iter()) {
+            for (idx, cell) in row.iter().enumerate() {
+                let width = text_width(cell);
+                match widths.get_mut(idx) {
+                    Some(max) => *max = (*max).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+
+        widths
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<usize>> {
+        if self.rows.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of rows given to `TableSelect`",
+            ));
+        }
+
+        if fallback::defaults_mode() {
+            if self.default >= self.rows.len() {
+                return Err(fallback::no_default(self.prompt.as_deref().unwrap_or("")));
+            }
+
+            let mut render = TermThemeRenderer::new(term, &*self.theme);
+            if let Some(ref prompt) = self.prompt {
+                if self.report {
+                    render.select_prompt_selection(prompt, &self.rows[self.default].join(" "))?;
+                }
+            }
+            term.flush()?;
+
+            return Ok(Some(self.default));
+        }
+
+        fallback::require_tty(term)?;
+
+        // The header takes up one of the rows available for the page.
+        let max_length = if self.header.is_some() {
+            Some(
+                self.max_length
+                    .unwrap_or(usize::MAX)
+                    .min(self.max_rows.unwrap_or(usize::MAX))
+                    .min(term.size().0 as usize)
+                    .saturating_sub(1),
+            )
+        } else {
+            self.max_length
+        };
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let mut paging = Paging::new(term, self.rows.len(), max_length, render.footer_rows(false))
+            .max_rows(self.max_rows)
+            .scrolling(self.scrolling)
+            .scroll_context(self.scroll_context);
+        let mut state = TableSelectState::new(self.rows.len())
+            .default(Some(self.default))
+            .allow_quit(allow_quit);
+
+        let natural_widths = self.column_widths();
+        let mut header_widths = None;
+
+        if self.alt_screen {
+            render.enter_alternate_screen()?;
+        }
+        if self.bottom {
+            render.anchor_to_bottom();
+        }
+        render.hide_cursor()?;
+
+        loop {
+            let available = (term.size().1 as usize).saturating_sub(ROW_PREFIX + 1);
+            let widths = layout_columns(&natural_widths, available);
+
+            // The header is pinned below the prompt and only redrawn when the columns change.
+            if self.header.is_some() && header_widths.as_ref() != Some(&widths) {
+                paging.redraw_prompt();
+                header_widths = Some(widths.clone());
+            }
+
+            paging.render_prompt(|paging_info| {
+                render.clear_prompt()?;
+                if let Some(ref prompt) = self.prompt {
+                    render.select_prompt(prompt, paging_info)?;
+                }
+                if let Some(ref header) = self.header {
+                    render.table_select_header(&format_cells(
+                        header,
+                        &widths,
+                        self.theme.ellipsis(),
+                    ))?;
+                    render.pin_to_prompt();
+                }
+                Ok(())
+            })?;
+
+            render.set_scrollbar(paging.scrollbar());
+            for (idx, row) in self
+                .rows
+                .iter()
+                .enumerate()
+                .skip(paging.first_item())
+                .take(paging.capacity)
+            {
+                let cells = format_cells(row, &widths, self.theme.ellipsis());
+                render.table_select_row(&cells, state.selection() == Some(idx))?;
+            }
+
+            render.set_scrollbar(None);
+            paging.render_footer(|paging_info| render.paging_info_footer(paging_info))?;
+
+            render.finish_frame()?;
+            term.flush()?;
+
+            state.set_page(paging.capacity, paging.active);
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.leave_alternate_screen()?;
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(sel) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.leave_alternate_screen()?;
+
+                    if let Some(ref prompt) = self.prompt {
+                        if self.report {
+                            render.select_prompt_selection(prompt, &self.rows[sel].join(" "))?;
+                        }
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(sel));
+                }
+                _ => render.reject_key(&key)?,
+            }
+
+            paging.update(state.selection().unwrap_or(!0))?;
+
+            render.rewind_preserve_prompt()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl TableSelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<usize>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
+    }
+}
+
+impl<'a> TableSelect<'a> {
+    /// Creates a table select prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            default: !0,
+            header: None,
+            rows: vec![],
+            prompt: None,
+            report: false,
+            clear: true,
+            max_length: None,
+            max_rows: None,
+            scrolling: false,
+            scroll_context: None,
+            alt_screen: false,
+            bottom: false,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_columns_shrinks_widest_first() {
+        assert_eq!(layout_columns(&[4, 10, 6], 40), [4, 10, 6]);
+        assert_eq!(layout_columns(&[4, 10, 6], 18), [4, 5, 5]);
+        assert_eq!(layout_columns(&[2, 2], 0), [1, 1]);
+    }
+
+    #[test]
+    fn test_format_cells_pads_and_truncates() {
+        let cells = vec!["web-1".to_string(), "eu-west-1".to_string()];
+
+        assert_eq!(
+            format_cells(&cells, &[6, 5, 2], "…"),
+            ["web-1 ", "eu-w…", "  "]
+        );
+        assert_eq!(format_cells(&cells, &[5, 9], "…"), ["web-1", "eu-west-1"]);
+    }
+
+    #[test]
+    fn test_column_widths_include_header() {
+        let widths = TableSelect::new()
+            .header(&["Name", "Region"])
+            .row(&["a", "eu-west-1"])
+            .row(&["longer name"])
+            .column_widths();
+
+        assert_eq!(widths, [11, 9]);
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| TableSelect::new().row(&["a"]).interact_on(&term))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,234 @@
+use std::{io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{TagInputState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// Renders a tag input prompt.
+///
+/// Typed text is turned into a tag with 'Enter' (or ','), 'Backspace' on an empty input
+/// removes the last tag and 'Enter' on an empty input finishes the prompt.
+/// If suggestions are set, the first one matching the typed text is shown and can be
+/// accepted with 'Tab'. Duplicate tags are rejected.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::TagInput;
+///
+/// let labels = TagInput::new()
+///     .with_prompt("Labels")
+///     .suggestions(&["bug", "enhancement", "documentation"])
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TagInput<'a> {
+    prompt: String,
+    report: bool,
+    defaults: Vec<String>,
+    suggestions: Vec<String>,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for TagInput<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TagInput<'static> {
+    /// Creates a tag input prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl TagInput<'_> {
+    /// Sets the tag input prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the tags after interaction.
+    ///
+    /// The default is to report the tags.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Sets the tags the prompt starts with.
+    pub fn defaults<T: ToString>(&mut self, tags: &[T]) -> &mut Self {
+        self.defaults = tags.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Sets the tags which are suggested while typing.
+    pub fn suggestions<T: ToString>(&mut self, tags: &[T]) -> &mut Self {
+        self.suggestions = tags.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Gives up waiting for the tags after `timeout`, resolving to the
+    /// [defaults](Self::defaults).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the tags if user hit 'Enter' on an empty input.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<Vec<String>> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(tags)` if user hit 'Enter' on an empty input or `None` if user cancelled with 'Esc'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<Vec<String>>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Vec<String>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(
+        &self,
+        keys: K,
+    ) -> io::Result<Vec<String>> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Vec<String>>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(
+        &self,
+        term: &dyn Backend,
+        allow_quit: bool,
+    ) -> io::Result<Option<Vec<String>>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            if self.report {
+                render.tag_input_prompt_selection(&self.prompt, &self.defaults)?;
+            }
+            term.flush()?;
+
+            return Ok(Some(self.defaults.clone()));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut state = TagInputState::new(self.defaults.clone())
+            .suggestions(self.suggestions.clone())
+            .allow_quit(allow_quit);
+
+        loop {
+            term.clear_line()?;
+            render.tag_input_prompt(
+                &self.prompt,
+                state.tags(),
+                state.input(),
+                state.suggestion(),
+            )?;
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    term.clear_line()?;
+                    render.clear()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(tags) => {
+                    term.clear_line()?;
+                    render.clear()?;
+
+                    if self.report {
+                        render.tag_input_prompt_selection(&self.prompt, &tags)?;
+                    }
+
+                    term.flush()?;
+
+                    return Ok(Some(tags));
+                }
+                _ => match state.duplicate() {
+                    Some(tag) => {
+                        term.clear_line()?;
+                        render.error(&format!("'{}' was already added", tag))?;
+                    }
+                    None => render.reject_key(&key)?,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl TagInput<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Vec<String>>> {
+        crate::task::interact_async(std::mem::take(self), |input| input.interact())
+    }
+}
+
+impl<'a> TagInput<'a> {
+    /// Creates a tag input prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            defaults: vec![],
+            suggestions: vec![],
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| TagInput::new().interact_on(&term)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,316 @@
+use std::{io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{ToggleState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// Renders a toggle prompt for binary choices.
+///
+/// Both choices are rendered side by side. 'Left' picks the disabled choice, 'Right' picks
+/// the enabled one and 'Tab' or 'Space' switch between them. 'Enter' confirms.
+/// Interaction returns `true` if the enabled choice was picked.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::Toggle;
+///
+/// let https = Toggle::new()
+///     .with_prompt("Protocol")
+///     .disabled_label("HTTP")
+///     .enabled_label("HTTPS")
+///     .default(true)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Toggle<'a> {
+    prompt: String,
+    report: bool,
+    default: bool,
+    enabled_label: String,
+    disabled_label: String,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for Toggle<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toggle<'static> {
+    /// Creates a toggle prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl Toggle<'_> {
+    /// Sets the toggle prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Indicates whether to report the chosen label after interaction.
+    ///
+    /// The default is to report the chosen label.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Sets the initially picked choice.
+    ///
+    /// The default is the disabled choice.
+    pub fn default(&mut self, val: bool) -> &mut Self {
+        self.default = val;
+        self
+    }
+
+    /// Sets the label of the enabled choice, rendered on the right.
+    ///
+    /// The default is "On".
+    pub fn enabled_label<S: Into<String>>(&mut self, label: S) -> &mut Self {
+        self.enabled_label = label.into();
+        self
+    }
+
+    /// Sets the label of the disabled choice, rendered on the left.
+    ///
+    /// The default is "Off".
+    pub fn disabled_label<S: Into<String>>(&mut self, label: S) -> &mut Self {
+        self.disabled_label = label.into();
+        self
+    }
+
+    /// Gives up waiting for the choice after `timeout`, resolving to the
+    /// [default](Self::default).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `true` if the enabled choice was confirmed with 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<bool> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(bool)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<bool>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<bool> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(&self, keys: K) -> io::Result<bool> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<bool>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<bool>> {
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        if fallback::defaults_mode() {
+            if self.report {
+                let label = if self.default {
+                    &self.enabled_label
+                } else {
+                    &self.disabled_label
+                };
+                render.toggle_prompt_selection(&self.prompt, label)?;
+            }
+            term.flush()?;
+
+            return Ok(Some(self.default));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut state = ToggleState::new(self.default).allow_quit(allow_quit);
+
+        render.hide_cursor()?;
+
+        loop {
+            term.clear_line()?;
+            render.toggle_prompt(
+                &self.prompt,
+                &self.disabled_label,
+                &self.enabled_label,
+                state.value(),
+            )?;
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    term.clear_line()?;
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(value) => {
+                    term.clear_line()?;
+
+                    if self.report {
+                        let label = if value {
+                            &self.enabled_label
+                        } else {
+                            &self.disabled_label
+                        };
+                        render.toggle_prompt_selection(&self.prompt, label)?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(value));
+                }
+                _ => render.reject_key(&key)?,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Toggle<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<bool>> {
+        crate::task::interact_async(std::mem::take(self), |toggle| toggle.interact())
+    }
+}
+
+impl<'a> Toggle<'a> {
+    /// Creates a toggle prompt with a specific theme.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::{
+    ///     Toggle,
+    ///     theme::ColorfulTheme
+    /// };
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let verbose = Toggle::with_theme(&ColorfulTheme::default())
+    ///     .with_prompt("Logging")
+    ///     .disabled_label("Quiet")
+    ///     .enabled_label("Verbose")
+    ///     .interact()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            prompt: "".into(),
+            report: true,
+            default: false,
+            enabled_label: "On".into(),
+            disabled_label: "Off".into(),
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Headless;
+
+    fn toggle(keys: Vec<Key>) -> (io::Result<Option<bool>>, String) {
+        let term = Headless::new(5, 40).colors(false).keys(keys);
+        let result = Toggle::new()
+            .with_prompt("Protocol")
+            .disabled_label("HTTP")
+            .enabled_label("HTTPS")
+            .interact_on_opt(&term);
+        (result, term.screen())
+    }
+
+    #[test]
+    fn test_switching() {
+        assert_eq!(
+            toggle(vec![Key::ArrowRight, Key::Enter]).0.unwrap(),
+            Some(true)
+        );
+        let keys = vec![Key::ArrowRight, Key::ArrowLeft, Key::Enter];
+        assert_eq!(toggle(keys).0.unwrap(), Some(false));
+        let keys = vec![Key::Tab, Key::Char(' '), Key::Tab, Key::Enter];
+        assert_eq!(toggle(keys).0.unwrap(), Some(true));
+        let keys = vec![Key::ArrowRight, Key::ArrowRight, Key::Enter];
+        assert_eq!(toggle(keys).0.unwrap(), Some(true));
+    }
+
+    #[test]
+    fn test_default() {
+        let term = Headless::new(5, 40).colors(false).keys(vec![Key::Enter]);
+        let https = Toggle::new()
+            .disabled_label("HTTP")
+            .enabled_label("HTTPS")
+            .default(true)
+            .interact_on(&term)
+            .unwrap();
+        assert!(https);
+
+        assert_eq!(toggle(vec![Key::Enter]).0.unwrap(), Some(false));
+    }
+
+    #[test]
+    fn test_enter_and_escape() {
+        let (result, screen) = toggle(vec![Key::ArrowRight, Key::Enter]);
+        assert_eq!(result.unwrap(), Some(true));
+        assert_eq!(screen, "Protocol: HTTPS");
+
+        let (result, screen) = toggle(vec![Key::ArrowRight, Key::Escape]);
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(screen, "");
+
+        let term = Headless::new(5, 40).keys(vec![Key::Escape]);
+        assert!(Toggle::new().interact_on(&term).is_err());
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| Toggle::new().interact_on(&term)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,378 @@
+use std::{io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{TransferSelectState, Transition};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::width::text_width;
+use crate::Backend;
+
+use console::Key;
+
+/// The number of characters rendered between the two panes.
+const PANE_GAP: usize = 3;
+
+/// Scrolls `starting_row` so that `sel` is one of the `rows` visible rows.
+fn scroll(starting_row: &mut usize, sel: usize, rows: usize) {
+    if sel < *starting_row {
+        *starting_row = sel;
+    } else if sel >= *starting_row + rows {
+        *starting_row = sel + 1 - rows;
+    }
+}
+
+/// Renders a prompt with two lists for moving items between them.
+///
+/// The left pane holds the available items, the right pane the chosen ones in the order
+/// they were moved over. 'Tab' (or 'Left'/'Right') switches between the panes and 'Space'
+/// or 'Enter' moves the highlighted item to the other pane. 'c' confirms.
+/// Interaction returns the indices of the chosen items in the order they were chosen.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::TransferSelect;
+///
+/// fn main() -> std::io::Result<()> {
+///     let columns = vec!["Name", "Size", "Owner", "Modified"];
+///     let chosen = TransferSelect::new()
+///         .with_prompt("Columns to show")
+///         .items(&columns)
+///         .interact()?;
+///
+///     for idx in chosen {
+///         println!("{}", columns[idx]);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub struct TransferSelect<'a> {
+    items: Vec<String>,
+    defaults: Vec<usize>,
+    prompt: Option<String>,
+    report: bool,
+    clear: bool,
+    available_title: String,
+    chosen_title: String,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for TransferSelect<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransferSelect<'static> {
+    /// Creates a transfer select prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl TransferSelect<'_> {
+    /// Indicates whether the panes should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the panes.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Adds a single item to the available pane.
+    pub fn item<T: ToString>(&mut self, item: T) -> &mut Self {
+        self.items.push(item.to_string());
+        self
+    }
+
+    /// Adds multiple items to the available pane.
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Self {
+        for item in items {
+            self.items.push(item.to_string());
+        }
+        self
+    }
+
+    /// Sets the indices of the items which start out in the chosen pane, in order.
+    pub fn defaults(&mut self, val: &[usize]) -> &mut Self {
+        self.defaults = val.to_vec();
+        self
+    }
+
+    /// Sets the titles rendered above the available and the chosen pane.
+    ///
+    /// The defaults are "Available" and "Chosen".
+    pub fn titles<S: Into<String>, T: Into<String>>(
+        &mut self,
+        available: S,
+        chosen: T,
+    ) -> &mut Self {
+        self.available_title = available.into();
+        self.chosen_title = chosen.into();
+        self
+    }
+
+    /// Sets the transfer select prompt.
+    ///
+    /// By default, when a prompt is set the system also prints out a confirmation after
+    /// the selection. You can opt-out of this with [`report`](#method.report).
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self.report = true;
+        self
+    }
+
+    /// Indicates whether to report the chosen items after interaction.
+    ///
+    /// The default is to report the chosen items.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Gives up waiting for the selection after `timeout`, resolving to the
+    /// items chosen by [`defaults`](Self::defaults).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the indices of the chosen items if user hit 'c'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<Vec<usize>> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(indices)` if user hit 'c' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<Vec<usize>>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Vec<usize>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(
+        &self,
+        keys: K,
+    ) -> io::Result<Vec<usize>> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Vec<usize>>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<Vec<usize>>> {
+        if self.items.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of items given to `TransferSelect`",
+            ));
+        }
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+
+        let mut state = TransferSelectState::new(self.items.len())
+            .defaults(&self.defaults)
+            .allow_quit(allow_quit);
+
+        if fallback::defaults_mode() {
+            if let Some(ref prompt) = self.prompt {
+                if self.report {
+                    let selections: Vec<_> = state
+                        .chosen()
+                        .iter()
+                        .map(|&idx| self.items[idx].as_str())
+                        .collect();
+                    render.multi_select_prompt_selection(prompt, &selections)?;
+                }
+            }
+            term.flush()?;
+
+            return Ok(Some(state.chosen().to_vec()));
+        }
+
+        fallback::require_tty(term)?;
+
+        let (mut available_start, mut chosen_start) = (0, 0);
+
+        // Leave room for the prefix the themes render in front of items.
+        let natural_width = self
+            .items
+            .iter()
+            .chain(vec![&self.available_title, &self.chosen_title])
+            .map(|item| text_width(item) + 2)
+            .max()
+            .unwrap_or(0);
+
+        render.hide_cursor()?;
+
+        loop {
+            // Subtract -3 because we need space to render the prompt and the titles.
+            let visible_term_rows = (term.size().0 as usize).max(4) - 3;
+            let width = natural_width.min((term.size().1 as usize).saturating_sub(PANE_GAP) / 2);
+
+            let (available, chosen) = (state.available(), state.chosen());
+            let (available_sel, chosen_sel) =
+                (state.available_selection(), state.chosen_selection());
+            scroll(&mut available_start, available_sel, visible_term_rows);
+            scroll(&mut chosen_start, chosen_sel, visible_term_rows);
+
+            if let Some(ref prompt) = self.prompt {
+                render.select_prompt(prompt, None)?;
+            }
+
+            render.transfer_select_titles(
+                &self.available_title,
+                &self.chosen_title,
+                state.chosen_focused(),
+                width,
+                PANE_GAP,
+            )?;
+
+            let rows = available.len().max(chosen.len()).min(visible_term_rows);
+            for row in 0..rows {
+                let cell = |list: &[usize], start: usize, sel: usize, focused: bool| {
+                    list.get(start + row)
+                        .map(|&idx| (self.items[idx].as_str(), focused && start + row == sel))
+                };
+
+                render.transfer_select_row(
+                    cell(
+                        available,
+                        available_start,
+                        available_sel,
+                        !state.chosen_focused(),
+                    ),
+                    cell(chosen, chosen_start, chosen_sel, state.chosen_focused()),
+                    width,
+                    PANE_GAP,
+                )?;
+            }
+
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(chosen) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    if let Some(ref prompt) = self.prompt {
+                        if self.report {
+                            let selections: Vec<_> =
+                                chosen.iter().map(|&idx| self.items[idx].as_str()).collect();
+
+                            render.multi_select_prompt_selection(prompt, &selections)?;
+                        }
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(chosen));
+                }
+                _ => render.reject_key(&key)?,
+            }
+
+            render.clear()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl TransferSelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Vec<usize>>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
+    }
+}
+
+impl<'a> TransferSelect<'a> {
+    /// Creates a transfer select prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            items: vec![],
+            defaults: vec![],
+            prompt: None,
+            report: false,
+            clear: true,
+            available_title: "Available".into(),
+            chosen_title: "Chosen".into(),
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Headless;
+
+    #[test]
+    fn test_transfer_keys() {
+        let term = Headless::new(10, 40).keys(vec![
+            Key::Enter,
+            Key::Char(' '),
+            Key::Tab,
+            Key::Enter,
+            Key::Char('c'),
+        ]);
+        let chosen = TransferSelect::new()
+            .items(&["a", "b", "c"])
+            .interact_on(&term)
+            .unwrap();
+
+        // Both items moved over, then the first one back.
+        assert_eq!(chosen, [1]);
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| {
+            TransferSelect::new().items(&["a", "b"]).interact_on(&term)
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,327 @@
+use std::{io, time::Duration};
+
+use crate::{
+    backend::{default_term, with_keys},
+    fallback,
+    prompts::tree_select::node,
+    state::{Transition, TreeMultiSelectState},
+    theme::{SimpleTheme, TermThemeRenderer, ThemeRef},
+    timeout, Backend, TreeNode,
+};
+
+use console::Key;
+
+/// The check mark of a node in a [`TreeMultiSelect`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckState {
+    /// The node and all of its descendants are checked.
+    Checked,
+    /// Some but not all descendants of the node are checked.
+    Partial,
+    /// Neither the node nor any of its descendants is checked.
+    Unchecked,
+}
+
+/// Renders a multi select prompt over a tree.
+///
+/// 'Space' checks or unchecks the highlighted node together with all of its descendants.
+/// Nodes whose descendants are only partially checked are marked as such. Nodes with
+/// children can be expanded with 'Right' and collapsed with 'Left'.
+/// Interaction returns the paths of the checked leaves in tree order, every path being a
+/// list of child indices.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::{TreeMultiSelect, TreeNode};
+///
+/// fn main() -> std::io::Result<()> {
+///     let paths = TreeMultiSelect::new()
+///         .with_prompt("Directories to back up")
+///         .item(
+///             TreeNode::new("home")
+///                 .child(TreeNode::new("documents").checked(true))
+///                 .child(TreeNode::new("downloads"))
+///                 .expanded(true),
+///         )
+///         .item(TreeNode::new("etc"))
+///         .interact()?;
+///
+///     println!("Backing up {:?}", paths);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct TreeMultiSelect<'a> {
+    items: Vec<TreeNode>,
+    prompt: Option<String>,
+    report: bool,
+    clear: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for TreeMultiSelect<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TreeMultiSelect<'static> {
+    /// Creates a tree multi select prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl TreeMultiSelect<'_> {
+    /// Indicates whether the tree should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the tree.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Adds a single root node to the tree.
+    pub fn item(&mut self, node: TreeNode) -> &mut Self {
+        self.items.push(node);
+        self
+    }
+
+    /// Adds multiple root nodes to the tree.
+    pub fn items<I: IntoIterator<Item = TreeNode>>(&mut self, nodes: I) -> &mut Self {
+        self.items.extend(nodes);
+        self
+    }
+
+    /// Prefaces the tree with a prompt.
+    ///
+    /// By default, when a prompt is set the system also prints out a confirmation after
+    /// the selection. You can opt-out of this with [`report`](#method.report).
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Indicates whether to report the checked leaves after interaction.
+    ///
+    /// The default is to report the checked leaves.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Gives up waiting for the selection after `timeout`, resolving to the
+    /// leaves [checked](crate::TreeNode::checked) up front.
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the paths of the checked leaves if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<Vec<Vec<usize>>> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(paths)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<Vec<Vec<usize>>>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Vec<Vec<usize>>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(
+        &self,
+        keys: K,
+    ) -> io::Result<Vec<Vec<usize>>> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Vec<Vec<usize>>>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(
+        &self,
+        term: &dyn Backend,
+        allow_quit: bool,
+    ) -> io::Result<Option<Vec<Vec<usize>>>> {
+        if self.items.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of items given to `TreeMultiSelect`",
+            ));
+        }
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let mut state = TreeMultiSelectState::new(self.items.clone()).allow_quit(allow_quit);
+
+        if fallback::defaults_mode() {
+            let paths = state.checked();
+
+            if let Some(ref prompt) = self.prompt {
+                if self.report {
+                    let labels: Vec<&str> = paths
+                        .iter()
+                        .map(|path| node(&self.items, path).label())
+                        .collect();
+                    render.multi_select_prompt_selection(prompt, &labels)?;
+                }
+            }
+            term.flush()?;
+
+            return Ok(Some(paths));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut starting_row = 0;
+
+        render.hide_cursor()?;
+
+        loop {
+            let rows = state.rows();
+            let sel = state.selection();
+            // Subtract -2 because we need space to render the prompt.
+            let visible_term_rows = (term.size().0 as usize).max(3) - 2;
+
+            if sel < starting_row {
+                starting_row = sel;
+            } else if sel >= starting_row + visible_term_rows {
+                starting_row = sel + 1 - visible_term_rows;
+            }
+
+            if let Some(ref prompt) = self.prompt {
+                render.multi_select_prompt(prompt, None)?;
+            }
+
+            for (idx, (path, node)) in rows
+                .iter()
+                .enumerate()
+                .skip(starting_row)
+                .take(visible_term_rows)
+            {
+                let expansion = if node.is_leaf() {
+                    None
+                } else {
+                    Some(state.is_expanded(path))
+                };
+
+                render.tree_multi_select_prompt_item(
+                    node.label(),
+                    path.len() - 1,
+                    expansion,
+                    state.check_state(path),
+                    idx == sel,
+                )?;
+            }
+
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(paths) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    if let Some(ref prompt) = self.prompt {
+                        if self.report {
+                            let labels: Vec<&str> = paths
+                                .iter()
+                                .map(|path| node(&self.items, path).label())
+                                .collect();
+                            render.multi_select_prompt_selection(prompt, &labels)?;
+                        }
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(paths));
+                }
+                _ => render.reject_key(&key)?,
+            }
+
+            render.clear()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl TreeMultiSelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(
+        &mut self,
+    ) -> impl std::future::Future<Output = io::Result<Vec<Vec<usize>>>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
+    }
+}
+
+impl<'a> TreeMultiSelect<'a> {
+    /// Creates a tree multi select prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            items: vec![],
+            prompt: None,
+            report: true,
+            clear: true,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| {
+            TreeMultiSelect::new()
+                .item(crate::TreeNode::new("a"))
+                .interact_on(&term)
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,473 @@
+use std::{collections::HashSet, io, time::Duration};
+
+use crate::backend::{default_term, with_keys};
+use crate::fallback;
+use crate::state::{Transition, TreeSelectState};
+use crate::theme::{SimpleTheme, TermThemeRenderer, ThemeRef};
+use crate::timeout;
+use crate::Backend;
+
+use console::Key;
+
+/// A node of the tree rendered by [`TreeSelect`].
+///
+/// ## Examples
+///
+/// ```rust
+/// use dialoguer::TreeNode;
+///
+/// let tree = TreeNode::new("Engineering")
+///     .child(TreeNode::new("Backend"))
+///     .child(TreeNode::new("Frontend").child(TreeNode::new("Design system")));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeNode {
+    pub(crate) label: String,
+    pub(crate) children: Vec<TreeNode>,
+    pub(crate) expanded: bool,
+    pub(crate) checked: bool,
+}
+
+impl TreeNode {
+    /// Creates a node without children.
+    pub fn new<T: ToString>(label: T) -> Self {
+        Self {
+            label: label.to_string(),
+            children: vec![],
+            expanded: false,
+            checked: false,
+        }
+    }
+
+    /// Adds a child node.
+    pub fn child(mut self, node: TreeNode) -> Self {
+        self.children.push(node);
+        self
+    }
+
+    /// Adds multiple child nodes.
+    pub fn children<I: IntoIterator<Item = TreeNode>>(mut self, nodes: I) -> Self {
+        self.children.extend(nodes);
+        self
+    }
+
+    /// Sets whether the node is expanded when the prompt is first rendered.
+    ///
+    /// The default is to render nodes collapsed.
+    pub fn expanded(mut self, val: bool) -> Self {
+        self.expanded = val;
+        self
+    }
+
+    /// Sets whether the node is checked when a [`TreeMultiSelect`](crate::TreeMultiSelect)
+    /// is first rendered.
+    ///
+    /// Checking a node with children checks all of its descendants.
+    pub fn checked(mut self, val: bool) -> Self {
+        self.checked = val;
+        self
+    }
+
+    /// Returns the label of the node.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns `true` if the node has no children.
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// A node which is currently visible in a rendered tree.
+pub(crate) struct VisibleNode<'a> {
+    pub path: Vec<usize>,
+    pub node: &'a TreeNode,
+}
+
+/// Collects the paths of all nodes which start out expanded.
+pub(crate) fn initially_expanded(roots: &[TreeNode]) -> HashSet<Vec<usize>> {
+    fn walk(nodes: &[TreeNode], prefix: &mut Vec<usize>, out: &mut HashSet<Vec<usize>>) {
+        for (idx, node) in nodes.iter().enumerate() {
+            prefix.push(idx);
+            if node.expanded && !node.is_leaf() {
+                out.insert(prefix.clone());
+            }
+            walk(&node.children, prefix, out);
+            prefix.pop();
+        }
+    }
+
+    let mut expanded = HashSet::new();
+    walk(roots, &mut vec![], &mut expanded);
+    expanded
+}
+
+/// Flattens the tree into the list of rows visible with the given expansion state.
+pub(crate) fn visible_nodes<'a>(
+    roots: &'a [TreeNode],
+    expanded: &HashSet<Vec<usize>>,
+) -> Vec<VisibleNode<'a>> {
+    fn walk<'a>(
+        nodes: &'a [TreeNode],
+        prefix: &mut Vec<usize>,
+        expanded: &HashSet<Vec<usize>>,
+        out: &mut Vec<VisibleNode<'a>>,
+    ) {
+        for (idx, node) in nodes.iter().enumerate() {
+            prefix.push(idx);
+            out.push(VisibleNode {
+                path: prefix.clone(),
+                node,
+            });
+            if expanded.contains(prefix) {
+                walk(&node.children, prefix, expanded, out);
+            }
+            prefix.pop();
+        }
+    }
+
+    let mut out = vec![];
+    walk(roots, &mut vec![], expanded, &mut out);
+    out
+}
+
+/// Returns the node at `path`.
+pub(crate) fn node<'a>(roots: &'a [TreeNode], path: &[usize]) -> &'a TreeNode {
+    let mut node = &roots[path[0]];
+    for &idx in &path[1..] {
+        node = &node.children[idx];
+    }
+    node
+}
+
+/// Expands the node at `path` if it is collapsed and collapses it otherwise.
+pub(crate) fn toggle_expansion(expanded: &mut HashSet<Vec<usize>>, path: &[usize]) {
+    if !expanded.remove(path) {
+        expanded.insert(path.to_vec());
+    }
+}
+
+/// Renders a tree select prompt.
+///
+/// Nodes with children can be expanded with 'Right' and collapsed with 'Left', 'Space'
+/// toggles the expansion. 'Enter' picks the highlighted leaf, or any node if
+/// [`allow_branch_selection`](Self::allow_branch_selection) is enabled.
+/// Interaction returns the path of the picked node as a list of child indices.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::{TreeNode, TreeSelect};
+///
+/// fn main() -> std::io::Result<()> {
+///     let path = TreeSelect::new()
+///         .with_prompt("Pick a team")
+///         .item(
+///             TreeNode::new("Engineering")
+///                 .child(TreeNode::new("Backend"))
+///                 .child(TreeNode::new("Frontend")),
+///         )
+///         .item(TreeNode::new("Sales"))
+///         .interact()?;
+///
+///     println!("Picked node at {:?}", path);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct TreeSelect<'a> {
+    items: Vec<TreeNode>,
+    prompt: Option<String>,
+    report: bool,
+    clear: bool,
+    allow_branch_selection: bool,
+    timeout: Option<Duration>,
+    theme: ThemeRef<'a>,
+}
+
+impl Default for TreeSelect<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TreeSelect<'static> {
+    /// Creates a tree select prompt.
+    pub fn new() -> Self {
+        Self::with_theme(&SimpleTheme)
+    }
+}
+
+impl TreeSelect<'_> {
+    /// Indicates whether the tree should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the tree.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+
+    /// Adds a single root node to the tree.
+    pub fn item(&mut self, node: TreeNode) -> &mut Self {
+        self.items.push(node);
+        self
+    }
+
+    /// Adds multiple root nodes to the tree.
+    pub fn items<I: IntoIterator<Item = TreeNode>>(&mut self, nodes: I) -> &mut Self {
+        self.items.extend(nodes);
+        self
+    }
+
+    /// Sets whether nodes with children can be picked with 'Enter'.
+    ///
+    /// By default only leaves can be picked and 'Enter' toggles the expansion of other nodes.
+    pub fn allow_branch_selection(&mut self, val: bool) -> &mut Self {
+        self.allow_branch_selection = val;
+        self
+    }
+
+    /// Prefaces the tree with a prompt.
+    ///
+    /// By default, when a prompt is set the system also prints out a confirmation after
+    /// the selection. You can opt-out of this with [`report`](#method.report).
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Indicates whether to report the picked node after interaction.
+    ///
+    /// The default is to report the picked node.
+    pub fn report(&mut self, val: bool) -> &mut Self {
+        self.report = val;
+        self
+    }
+
+    /// Gives up waiting for the selection after `timeout`, failing with
+    /// [`timed_out`](crate::timed_out).
+    ///
+    /// The time left is shown in front of the prompt, formatted by
+    /// [`Theme::format_countdown`](crate::theme::Theme::format_countdown).
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains the path of the picked node if user hit 'Enter'.
+    /// This unlike [`interact_opt`](Self::interact_opt) does not allow to quit with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact(&self) -> io::Result<Vec<usize>> {
+        self.interact_on(&*default_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [default terminal](crate::set_default_term).
+    /// Result contains `Some(path)` if user hit 'Enter' or `None` if user cancelled with 'Esc' or 'q'.
+    #[inline]
+    pub fn interact_opt(&self) -> io::Result<Option<Vec<usize>>> {
+        self.interact_on_opt(&*default_term())
+    }
+
+    /// Like [`interact`](Self::interact) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on(&self, term: &dyn Backend) -> io::Result<Vec<usize>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, false))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+
+    /// Like [`interact`](Self::interact) but reads keys from `keys` instead of the terminal.
+    ///
+    /// See [`Select::interact_with_keys`](crate::Select::interact_with_keys).
+    pub fn interact_with_keys<K: IntoIterator<Item = Key>>(
+        &self,
+        keys: K,
+    ) -> io::Result<Vec<usize>> {
+        with_keys(keys, |term| self.interact_on(term))
+    }
+
+    /// Like [`interact_opt`](Self::interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> io::Result<Option<Vec<usize>>> {
+        timeout::ask(term, self.timeout, |term| self._interact_on(term, true))
+    }
+
+    fn _interact_on(&self, term: &dyn Backend, allow_quit: bool) -> io::Result<Option<Vec<usize>>> {
+        if self.items.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of items given to `TreeSelect`",
+            ));
+        }
+
+        if fallback::defaults_mode() {
+            return Err(fallback::no_default(self.prompt.as_deref().unwrap_or("")));
+        }
+
+        fallback::require_tty(term)?;
+
+        let mut render = TermThemeRenderer::new(term, &*self.theme);
+        let mut state = TreeSelectState::new(self.items.clone())
+            .allow_branch_selection(self.allow_branch_selection)
+            .allow_quit(allow_quit);
+        let mut starting_row = 0;
+
+        render.hide_cursor()?;
+
+        loop {
+            let rows = state.rows();
+            let sel = state.selection();
+            // Subtract -2 because we need space to render the prompt.
+            let visible_term_rows = (term.size().0 as usize).max(3) - 2;
+
+            if sel < starting_row {
+                starting_row = sel;
+            } else if sel >= starting_row + visible_term_rows {
+                starting_row = sel + 1 - visible_term_rows;
+            }
+
+            if let Some(ref prompt) = self.prompt {
+                render.select_prompt(prompt, None)?;
+            }
+
+            for (idx, (path, node)) in rows
+                .iter()
+                .enumerate()
+                .skip(starting_row)
+                .take(visible_term_rows)
+            {
+                let expansion = if node.is_leaf() {
+                    None
+                } else {
+                    Some(state.is_expanded(path))
+                };
+
+                render.tree_select_prompt_item(
+                    node.label(),
+                    path.len() - 1,
+                    expansion,
+                    idx == sel,
+                )?;
+            }
+
+            term.flush()?;
+
+            let key = term.read_key()?;
+            match state.handle_key(key.clone()) {
+                Transition::Continue => {}
+                Transition::Cancelled => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(None);
+                }
+                Transition::Done(path) => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    if let Some(ref prompt) = self.prompt {
+                        if self.report {
+                            render.select_prompt_selection(
+                                prompt,
+                                node(&self.items, &path).label(),
+                            )?;
+                        }
+                    }
+
+                    render.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some(path));
+                }
+                _ => render.reject_key(&key)?,
+            }
+
+            render.clear()?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl TreeSelect<'static> {
+    /// Like [`interact`](Self::interact) but returns a future, running the prompt on the
+    /// blocking threads of tokio.
+    ///
+    /// See [`Select::interact_async`](crate::Select::interact_async).
+    pub fn interact_async(&mut self) -> impl std::future::Future<Output = io::Result<Vec<usize>>> {
+        crate::task::interact_async(std::mem::take(self), |select| select.interact())
+    }
+}
+
+impl<'a> TreeSelect<'a> {
+    /// Creates a tree select prompt with a specific theme.
+    pub fn with_theme(theme: impl Into<ThemeRef<'a>>) -> Self {
+        Self {
+            items: vec![],
+            prompt: None,
+            report: true,
+            clear: true,
+            allow_branch_selection: false,
+            timeout: None,
+            theme: theme.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Vec<TreeNode> {
+        vec![
+            TreeNode::new("a")
+                .child(TreeNode::new("a1"))
+                .child(TreeNode::new("a2").child(TreeNode::new("a2x")))
+                .expanded(true),
+            TreeNode::new("b"),
+        ]
+    }
+
+    #[test]
+    fn test_visible_nodes_respect_expansion() {
+        let items = tree();
+        let mut expanded = initially_expanded(&items);
+
+        let labels: Vec<_> = visible_nodes(&items, &expanded)
+            .iter()
+            .map(|row| row.node.label())
+            .collect();
+        assert_eq!(labels, ["a", "a1", "a2", "b"]);
+
+        expanded.insert(vec![0, 1]);
+        let paths: Vec<_> = visible_nodes(&items, &expanded)
+            .into_iter()
+            .map(|row| row.path)
+            .collect();
+        assert_eq!(
+            paths,
+            [vec![0], vec![0, 0], vec![0, 1], vec![0, 1, 0], vec![1]]
+        );
+    }
+
+    #[test]
+    fn test_without_tty() {
+        let term = crate::Headless::new(10, 40);
+        let err = fallback::test_without_tty(|| {
+            TreeSelect::new()
+                .item(TreeNode::new("a"))
+                .interact_on(&term)
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,150 @@
+//! Loads the items of list prompts on demand.
+use std::io;
+
+use crate::ItemUpdate;
+
+/// Provides the items of a [`Select`](crate::Select) page by page, as the user navigates.
+///
+/// Closures taking the index of the first item and the number of items to fetch implement
+/// this.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::Select;
+///
+/// let selection = Select::new()
+///     .with_prompt("Pick a commit")
+///     .item_provider(|start: usize, count: usize| {
+///         // Like `git log --skip {start} -n {count}`, fewer items mean the end is reached.
+///         Ok((start..(start + count).min(1000)).map(|i| format!("commit {}", i)).collect())
+///     })
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ItemProvider {
+    /// Returns up to `count` items starting at the item at `start`.
+    ///
+    /// Returning fewer items than requested marks the end of the list.
+    fn fetch(&mut self, start: usize, count: usize) -> io::Result<Vec<String>>;
+
+    /// Returns the total number of items, if known up front.
+    ///
+    /// Otherwise the page indicator shows the number of pages as unknown until the last
+    /// page is fetched.
+    fn total(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<F: FnMut(usize, usize) -> io::Result<Vec<String>>> ItemProvider for F {
+    fn fetch(&mut self, start: usize, count: usize) -> io::Result<Vec<String>> {
+        self(start, count)
+    }
+}
+
+/// The items of a prompt, followed by the ones of a provider loaded so far.
+pub(crate) struct LoadedItems<'a, 'p> {
+    items: Vec<String>,
+    own_items: usize,
+    provider: Option<&'a mut (dyn ItemProvider + 'p)>,
+    exhausted: bool,
+}
+
+impl<'a, 'p> LoadedItems<'a, 'p> {
+    pub fn new(items: &[String], provider: Option<&'a mut (dyn ItemProvider + 'p)>) -> Self {
+        LoadedItems {
+            items: items.to_vec(),
+            own_items: items.len(),
+            exhausted: provider.is_none(),
+            provider,
+        }
+    }
+
+    /// Returns the items loaded so far.
+    pub fn loaded(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Returns the number of items to navigate through.
+    ///
+    /// Until the provider is exhausted this counts one more item than is loaded, so moving
+    /// past the loaded items fetches the next page.
+    pub fn len(&self) -> usize {
+        match self.provider {
+            Some(ref provider) if !self.exhausted => match provider.total() {
+                Some(total) => self.own_items + total,
+                None => self.items.len() + 1,
+            },
+            _ => self.items.len(),
+        }
+    }
+
+    /// Returns whether the number of items is known.
+    pub fn complete(&self) -> bool {
+        self.exhausted
+            || self
+                .provider
+                .as_ref()
+                .map_or(true, |provider| provider.total().is_some())
+    }
+
+    /// Applies `updates` to the items of a prompt without a provider, returning where each
+    /// of the previous items ended up.
+    pub fn apply(&mut self, updates: Vec<ItemUpdate>) -> Vec<Option<usize>> {
+        let moved = crate::updates::apply(&mut self.items, updates);
+        self.own_items = self.items.len();
+        moved
+    }
+
+    /// Fetches pages of `page_size` items until the item at `index` is loaded or the
+    /// provider is exhausted.
+    pub fn load_through(&mut self, index: usize, page_size: usize) -> io::Result<()> {
+        let page_size = page_size.max(1);
+
+        while !self.exhausted && self.items.len() <= index {
+            let provider = match self.provider {
+                Some(ref mut provider) => provider,
+                None => break,
+            };
+
+            let fetched = provider.fetch(self.items.len() - self.own_items, page_size)?;
+            self.exhausted = fetched.len() < page_size;
+            self.items.extend(fetched);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_through() {
+        let mut fetches = vec![];
+        let mut provider = |start: usize, count: usize| {
+            fetches.push(start);
+            Ok((start..(start + count).min(7))
+                .map(|i| i.to_string())
+                .collect())
+        };
+        let mut items = LoadedItems::new(&["own".to_string()], Some(&mut provider));
+
+        items.load_through(3, 3).unwrap();
+        assert_eq!(items.loaded().len(), 4);
+        assert_eq!(items.len(), 5);
+        assert!(!items.complete());
+
+        items.load_through(100, 3).unwrap();
+        assert_eq!(items.loaded(), ["own", "0", "1", "2", "3", "4", "5", "6"]);
+        assert_eq!(items.len(), 8);
+        assert!(items.complete());
+
+        drop(items);
+        assert_eq!(fetches, [0, 3, 6]);
+    }
+}
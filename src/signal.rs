@@ -0,0 +1,121 @@
+//! Installs signal handlers shared by all prompts reading keys.
+//!
+//! Prompts on several threads may read keys at the same time, so they don't install and
+//! restore handlers each on their own: the first [`SharedHandler::acquire`] installs the
+//! handler and dropping the last [`HandlerGuard`] restores the previous disposition.
+use std::{
+    os::raw::c_int,
+    sync::{Mutex, MutexGuard},
+};
+
+struct State {
+    guards: usize,
+    previous: Option<libc::sigaction>,
+}
+
+/// A handler for a signal, installed while a [`HandlerGuard`] is alive.
+pub(crate) struct SharedHandler {
+    signal: c_int,
+    handler: extern "C" fn(c_int),
+    state: Mutex<State>,
+}
+
+impl SharedHandler {
+    pub const fn new(signal: c_int, handler: extern "C" fn(c_int)) -> Self {
+        SharedHandler {
+            signal,
+            handler,
+            state: Mutex::new(State {
+                guards: 0,
+                previous: None,
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Installs the handler unless it already is, returning `None` if the signal doesn't
+    /// have its default disposition, so handlers of the application are left alone.
+    ///
+    /// The handler is installed without `SA_RESTART`, which makes blocking reads fail with
+    /// `EINTR` once it ran.
+    pub fn acquire(&'static self) -> Option<HandlerGuard> {
+        let mut state = self.lock();
+        if state.guards == 0 {
+            unsafe {
+                let mut previous: libc::sigaction = std::mem::zeroed();
+                if libc::sigaction(self.signal, std::ptr::null(), &mut previous) != 0
+                    || previous.sa_sigaction != libc::SIG_DFL
+                {
+                    return None;
+                }
+
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = self.handler as libc::sighandler_t;
+                libc::sigemptyset(&mut action.sa_mask);
+                if libc::sigaction(self.signal, &action, std::ptr::null_mut()) != 0 {
+                    return None;
+                }
+                state.previous = Some(previous);
+            }
+        }
+
+        state.guards += 1;
+        Some(HandlerGuard { handler: self })
+    }
+
+    /// Returns whether `action` is this handler.
+    pub fn handles(&self, action: &libc::sigaction) -> bool {
+        action.sa_sigaction == self.handler as libc::sighandler_t
+    }
+}
+
+/// Keeps a [`SharedHandler`] installed until dropped.
+pub(crate) struct HandlerGuard {
+    handler: &'static SharedHandler,
+}
+
+impl Drop for HandlerGuard {
+    fn drop(&mut self) {
+        let mut state = self.handler.lock();
+        state.guards -= 1;
+        if state.guards == 0 {
+            if let Some(previous) = state.previous.take() {
+                unsafe { libc::sigaction(self.handler.signal, &previous, std::ptr::null_mut()) };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn on_signal(_: c_int) {}
+
+    // A signal no other test installs handlers for.
+    static HANDLER: SharedHandler = SharedHandler::new(libc::SIGUSR2, on_signal);
+
+    fn current() -> libc::sigaction {
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            libc::sigaction(libc::SIGUSR2, std::ptr::null(), &mut action);
+            action
+        }
+    }
+
+    #[test]
+    fn test_installed_until_last_guard_drops() {
+        let first = HANDLER.acquire().unwrap();
+        let second = HANDLER.acquire().unwrap();
+        assert!(HANDLER.handles(&current()));
+
+        drop(first);
+        assert!(HANDLER.handles(&current()));
+
+        drop(second);
+        assert_eq!(current().sa_sigaction, libc::SIG_DFL);
+    }
+}
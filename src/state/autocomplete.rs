@@ -0,0 +1,181 @@
+use console::Key;
+
+use super::Transition;
+use crate::width::byte_offset;
+
+/// The state of an [`Autocomplete`](crate::Autocomplete) prompt.
+///
+/// Typed characters are inserted at the cursor, which the left and right arrow keys,
+/// 'Home' and 'End' move, and 'Backspace' and 'Del' delete around it. The up and down arrow
+/// keys highlight a suggestion and 'Tab' copies the highlighted (or the first) suggestion
+/// into the text. 'Enter' submits the highlighted suggestion, or the text without one.
+///
+/// The state doesn't look up suggestions itself: keys changing the text set a
+/// [`query`](Self::query), which the caller looks up and hands back with
+/// [`set_suggestions`](Self::set_suggestions).
+pub struct AutocompleteState {
+    text: String,
+    position: usize,
+    suggestions: Vec<String>,
+    sel: Option<usize>,
+    query: bool,
+    allow_quit: bool,
+}
+
+impl AutocompleteState {
+    /// Creates the state of a prompt starting with `text`, with the cursor at its end.
+    ///
+    /// The suggestions for `text` are queried right away.
+    pub fn new(text: String) -> Self {
+        AutocompleteState {
+            position: text.chars().count(),
+            text,
+            suggestions: vec![],
+            sel: None,
+            query: true,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether 'Esc' cancels the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the highlighted suggestion or the text.
+    pub fn handle_key(&mut self, key: Key) -> Transition<String> {
+        self.query = false;
+
+        let len = self.text.chars().count();
+        match key {
+            Key::ArrowDown if !self.suggestions.is_empty() => {
+                self.sel = match self.sel {
+                    Some(idx) if idx + 1 < self.suggestions.len() => Some(idx + 1),
+                    Some(_) => None,
+                    None => Some(0),
+                };
+            }
+            Key::ArrowUp if !self.suggestions.is_empty() => {
+                self.sel = match self.sel {
+                    Some(0) => None,
+                    Some(idx) => Some(idx - 1),
+                    None => Some(self.suggestions.len() - 1),
+                };
+            }
+            Key::Tab if !self.suggestions.is_empty() => {
+                self.text = self.suggestions[self.sel.unwrap_or(0)].clone();
+                self.position = self.text.chars().count();
+                self.query = true;
+            }
+            Key::ArrowLeft if self.position > 0 => self.position -= 1,
+            Key::ArrowRight if self.position < len => self.position += 1,
+            Key::Home => self.position = 0,
+            Key::End => self.position = len,
+            Key::Backspace if self.position > 0 => {
+                self.position -= 1;
+                self.text.remove(byte_offset(&self.text, self.position));
+                self.query = true;
+            }
+            Key::Del if self.position < len => {
+                self.text.remove(byte_offset(&self.text, self.position));
+                self.query = true;
+            }
+            Key::Char(chr) if !chr.is_ascii_control() => {
+                self.text
+                    .insert(byte_offset(&self.text, self.position), chr);
+                self.position += 1;
+                self.query = true;
+            }
+            Key::Escape if self.allow_quit => return Transition::Cancelled,
+            Key::Enter => {
+                return Transition::Done(match self.sel {
+                    Some(idx) => self.suggestions[idx].clone(),
+                    None => self.text.clone(),
+                });
+            }
+            _ => return Transition::Rejected,
+        }
+
+        if self.query {
+            // Suggestions for the previous text are dropped until the new ones are set.
+            self.suggestions.clear();
+            self.sel = None;
+        }
+        Transition::Continue
+    }
+
+    /// Returns the text whose suggestions the caller looks up, after the key which changed
+    /// it.
+    pub fn query(&self) -> Option<&str> {
+        if self.query {
+            Some(&self.text)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the suggestions for the [query](Self::query), without highlighting one.
+    pub fn set_suggestions(&mut self, suggestions: Vec<String>) {
+        self.suggestions = suggestions;
+        self.sel = None;
+    }
+
+    /// Returns the text typed.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the position of the cursor in the text, in characters.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the suggestions.
+    pub fn suggestions(&self) -> &[String] {
+        &self.suggestions
+    }
+
+    /// Returns the index of the highlighted suggestion, if any.
+    pub fn selection(&self) -> Option<usize> {
+        self.sel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_offset_counts_chars() {
+        assert_eq!(byte_offset("äb", 1), 2);
+        assert_eq!(byte_offset("äb", 2), 3);
+        assert_eq!(byte_offset("", 4), 0);
+    }
+
+    #[test]
+    fn test_autocomplete_state() {
+        let mut state = AutocompleteState::new("g".into());
+        assert_eq!(state.query(), Some("g"));
+        state.set_suggestions(vec!["git".into(), "go".into()]);
+
+        assert_eq!(state.handle_key(Key::ArrowUp), Transition::Continue);
+        assert_eq!(state.selection(), Some(1));
+        assert_eq!(state.query(), None);
+        assert_eq!(state.handle_key(Key::Tab), Transition::Continue);
+        assert_eq!(state.query(), Some("go"));
+        assert!(state.suggestions().is_empty());
+        assert_eq!(state.handle_key(Key::ArrowDown), Transition::Rejected);
+
+        state.handle_key(Key::Home);
+        state.handle_key(Key::Char('ä'));
+        assert_eq!(state.text(), "ägo");
+        assert_eq!(state.position(), 1);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done("ägo".into()));
+    }
+}
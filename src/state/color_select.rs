@@ -0,0 +1,173 @@
+use console::Key;
+
+use super::Transition;
+use crate::prompts::color_select::{Palette, Rgb};
+
+/// The state of a [`ColorSelect`](crate::ColorSelect) prompt.
+///
+/// The cursor moves over the swatches of the palette, laid out in rows, with the arrow keys
+/// (or `h`, `j`, `k` and `l`), and 'Enter' or 'Space' pick the swatch under it. 'Tab'
+/// switches to entering a hex color like `#ff8800`, which 'Enter' submits, and back.
+pub struct ColorSelectState {
+    swatches: Vec<(Rgb, Option<u8>)>,
+    columns: usize,
+    sel: usize,
+    hex_input: Option<String>,
+    error: Option<String>,
+    allow_quit: bool,
+}
+
+impl ColorSelectState {
+    /// Creates the state of a prompt picking from the swatches of `palette`, with the
+    /// cursor on the first one.
+    pub fn new(palette: Palette) -> Self {
+        ColorSelectState {
+            swatches: palette.swatches(),
+            columns: palette.columns(),
+            sel: 0,
+            hex_input: None,
+            error: None,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the color the prompt starts with, moving the cursor to its swatch, or entering
+    /// it as hex color if the palette has none.
+    pub fn default(mut self, color: Rgb) -> Self {
+        match self.swatches.iter().position(|&(rgb, _)| rgb == color) {
+            Some(idx) => self.sel = idx,
+            None => self.hex_input = Some(color.to_string()),
+        }
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the color picked. Submitting a hex color which doesn't parse
+    /// is rejected, see [`error`](Self::error).
+    pub fn handle_key(&mut self, key: Key) -> Transition<Rgb> {
+        self.error = None;
+        let len = self.swatches.len();
+
+        match (key, self.hex_input.as_mut()) {
+            (Key::Tab, _) | (Key::BackTab, _) | (Key::Escape, Some(_)) => {
+                self.hex_input = match self.hex_input {
+                    Some(_) => None,
+                    None => Some(self.swatches[self.sel].0.to_string()),
+                };
+            }
+            (Key::Char(chr), Some(input)) if chr.is_ascii_hexdigit() || chr == '#' => {
+                input.push(chr);
+            }
+            (Key::Backspace, Some(input)) => {
+                input.pop();
+            }
+            (Key::Enter, Some(input)) => {
+                return match input.parse::<Rgb>() {
+                    Ok(color) => Transition::Done(color),
+                    Err(err) => {
+                        self.error = Some(err);
+                        Transition::Rejected
+                    }
+                };
+            }
+            (Key::ArrowLeft, None) | (Key::Char('h'), None) => {
+                self.sel = (self.sel + len - 1) % len;
+            }
+            (Key::ArrowRight, None) | (Key::Char('l'), None) => {
+                self.sel = (self.sel + 1) % len;
+            }
+            (Key::ArrowUp, None) | (Key::Char('k'), None) if self.sel >= self.columns => {
+                self.sel -= self.columns;
+            }
+            (Key::ArrowDown, None) | (Key::Char('j'), None) if self.sel + self.columns < len => {
+                self.sel += self.columns;
+            }
+            (Key::Enter, None) | (Key::Char(' '), None) => {
+                return Transition::Done(self.swatches[self.sel].0);
+            }
+            (Key::Escape, None) | (Key::Char('q'), None) if self.allow_quit => {
+                return Transition::Cancelled;
+            }
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the swatches with their terminal color index, if any.
+    pub fn swatches(&self) -> &[(Rgb, Option<u8>)] {
+        &self.swatches
+    }
+
+    /// Returns the number of swatches per row.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the index of the swatch under the cursor.
+    pub fn selection(&self) -> usize {
+        self.sel
+    }
+
+    /// Returns the color of the swatch under the cursor.
+    pub fn color(&self) -> Rgb {
+        self.swatches[self.sel].0
+    }
+
+    /// Returns the hex color typed, while entering one.
+    pub fn hex_input(&self) -> Option<&str> {
+        self.hex_input.as_deref()
+    }
+
+    /// Returns why the hex color submitted with the last key was rejected, if it was.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_select_state() {
+        let mut state = ColorSelectState::new(Palette::Ansi16);
+
+        assert_eq!(state.handle_key(Key::ArrowLeft), Transition::Continue);
+        assert_eq!(state.selection(), 15);
+        assert_eq!(state.handle_key(Key::ArrowDown), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char('k')), Transition::Continue);
+        assert_eq!(state.selection(), 7);
+
+        assert_eq!(state.handle_key(Key::Tab), Transition::Continue);
+        assert_eq!(state.hex_input(), Some("#e5e5e5"));
+        state.handle_key(Key::Char('0'));
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert!(state.error().is_some());
+        assert_eq!(state.handle_key(Key::Escape), Transition::Continue);
+        assert_eq!(state.hex_input(), None);
+        assert_eq!(state.error(), None);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(
+            state.handle_key(Key::Char(' ')),
+            Transition::Done(state.color())
+        );
+    }
+
+    #[test]
+    fn test_color_select_state_default() {
+        let state = ColorSelectState::new(Palette::Ansi16).default(Rgb::new(0, 205, 0));
+        assert_eq!(state.selection(), 2);
+
+        let state = ColorSelectState::new(Palette::Ansi16).default(Rgb::new(1, 2, 3));
+        assert_eq!(state.hex_input(), Some("#010203"));
+    }
+}
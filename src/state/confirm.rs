@@ -0,0 +1,127 @@
+use console::Key;
+
+use super::Transition;
+
+/// The state of a [`Confirm`](crate::Confirm) prompt.
+///
+/// `y` and `n` answer right away, and 'Enter' takes the default. When waiting for
+/// 'Enter', the keys only change the shown answer instead, which the arrow keys and 'Tab'
+/// toggle.
+pub struct ConfirmState {
+    default: Option<bool>,
+    value: Option<bool>,
+    wait_for_newline: bool,
+    allow_quit: bool,
+}
+
+impl Default for ConfirmState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfirmState {
+    /// Creates the state of a confirm prompt without a default.
+    pub fn new() -> Self {
+        ConfirmState {
+            default: None,
+            value: None,
+            wait_for_newline: false,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the answer 'Enter' takes, and which is shown up front if `show` is true.
+    pub fn default(mut self, val: bool, show: bool) -> Self {
+        self.default = Some(val);
+        self.value = if show { Some(val) } else { None };
+        self
+    }
+
+    /// Sets whether answers wait for 'Enter'.
+    ///
+    /// The default is false.
+    pub fn wait_for_newline(mut self, val: bool) -> Self {
+        self.wait_for_newline = val;
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt. When waiting for 'Enter', they clear
+    /// the answer and 'Enter' cancels without one.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    pub fn handle_key(&mut self, key: Key) -> Transition<bool> {
+        if !self.wait_for_newline {
+            return match key {
+                Key::Char('y') | Key::Char('Y') => Transition::Done(true),
+                Key::Char('n') | Key::Char('N') => Transition::Done(false),
+                Key::Enter if self.default.is_some() => Transition::Done(self.default.unwrap()),
+                Key::Escape | Key::Char('q') if self.allow_quit => Transition::Cancelled,
+                _ => Transition::Rejected,
+            };
+        }
+
+        match key {
+            Key::Char('y') | Key::Char('Y') => self.value = Some(true),
+            Key::Char('n') | Key::Char('N') => self.value = Some(false),
+            Key::ArrowLeft | Key::ArrowRight | Key::ArrowUp | Key::ArrowDown | Key::Tab => {
+                self.value = Some(!self.value.unwrap_or(false));
+            }
+            Key::Enter => {
+                if !self.allow_quit {
+                    self.value = self.value.or(self.default);
+                }
+                return match self.value {
+                    Some(value) => Transition::Done(value),
+                    None if self.allow_quit => Transition::Cancelled,
+                    None => Transition::Rejected,
+                };
+            }
+            Key::Escape | Key::Char('q') if self.allow_quit => self.value = None,
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the answer shown while waiting for 'Enter', if any.
+    pub fn value(&self) -> Option<bool> {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_state() {
+        let mut state = ConfirmState::new();
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char('Y')), Transition::Done(true));
+
+        let mut state = ConfirmState::new().default(false, true);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(false));
+    }
+
+    #[test]
+    fn test_confirm_state_wait_for_newline() {
+        let mut state = ConfirmState::new()
+            .default(true, false)
+            .wait_for_newline(true)
+            .allow_quit(true);
+        assert_eq!(state.value(), None);
+
+        assert_eq!(state.handle_key(Key::Tab), Transition::Continue);
+        assert_eq!(state.value(), Some(true));
+        assert_eq!(state.handle_key(Key::Char('n')), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Continue);
+        assert_eq!(state.value(), None);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Cancelled);
+    }
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use console::Key;
+
+use super::Transition;
+use crate::prompts::duration_input::{format_duration, parse_duration, step_duration};
+
+/// The state of a [`DurationInput`](crate::DurationInput) prompt.
+///
+/// Letters, digits, '.' and spaces type the duration, which 'Backspace' edits, and the up
+/// and down arrow keys step it by one of the unit typed last. 'Enter' submits the text once it
+/// parses to a duration.
+pub struct DurationInputState {
+    buffer: String,
+    // Whether the buffer holds typed text or a value produced by the default or stepping.
+    editing: bool,
+    error: Option<String>,
+    allow_quit: bool,
+}
+
+impl Default for DurationInputState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DurationInputState {
+    /// Creates the state of an empty input.
+    pub fn new() -> Self {
+        DurationInputState {
+            buffer: String::new(),
+            editing: true,
+            error: None,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the duration the input starts with, replaced as soon as something is typed.
+    pub fn initial_duration(mut self, duration: Duration) -> Self {
+        self.buffer = format_duration(duration);
+        self.editing = false;
+        self
+    }
+
+    /// Sets whether 'Esc' cancels the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the duration entered. Submitting text which isn't a duration
+    /// is rejected, see [`error`](Self::error).
+    pub fn handle_key(&mut self, key: Key) -> Transition<Duration> {
+        self.error = None;
+
+        match key {
+            Key::ArrowUp | Key::ArrowDown => {
+                self.buffer = step_duration(&self.buffer, key == Key::ArrowUp);
+                self.editing = false;
+            }
+            Key::Char(chr) if chr.is_ascii_alphanumeric() || chr == '.' || chr == ' ' => {
+                if !self.editing {
+                    self.buffer.clear();
+                    self.editing = true;
+                }
+                self.buffer.push(chr);
+            }
+            Key::Backspace => {
+                self.buffer.pop();
+                self.editing = true;
+            }
+            Key::Escape if self.allow_quit => return Transition::Cancelled,
+            Key::Enter => {
+                return match parse_duration(&self.buffer) {
+                    Ok(duration) => Transition::Done(duration),
+                    Err(err) => {
+                        self.error = Some(err);
+                        Transition::Rejected
+                    }
+                };
+            }
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the text typed, or produced by the default or stepping.
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Returns the duration of the text, if it parses to one.
+    pub fn duration(&self) -> Option<Duration> {
+        parse_duration(&self.buffer).ok()
+    }
+
+    /// Returns why the text submitted with the last key was rejected, if it was.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_input_state() {
+        let mut state = DurationInputState::new().initial_duration(Duration::from_secs(90));
+        assert_eq!(state.buffer(), "1m 30s");
+
+        assert_eq!(state.handle_key(Key::Char('2')), Transition::Continue);
+        assert_eq!(state.buffer(), "2");
+        assert_eq!(state.handle_key(Key::Char('x')), Transition::Continue);
+        assert_eq!(state.duration(), None);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert!(state.error().is_some());
+
+        state.handle_key(Key::Backspace);
+        state.handle_key(Key::Char('h'));
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Transition::Done(Duration::from_secs(7200))
+        );
+    }
+}
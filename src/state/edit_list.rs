@@ -0,0 +1,149 @@
+use console::Key;
+
+use super::Transition;
+
+/// The state of an [`EditList`](crate::EditList) prompt.
+///
+/// The cursor moves over the entries with the up and down arrow keys (or `j`, `k` and
+/// 'Tab'), and `J` and `K` move the entry under it. `a` (or 'Insert') adds an entry below
+/// it and `e` (or 'Space') edits it, after which the caller reads the text of the entry,
+/// see [`entry`](Self::entry). `d` (or 'Del') deletes the entry and 'Enter' confirms the
+/// list.
+pub struct EditListState {
+    items: Vec<String>,
+    sel: usize,
+    entry: Option<String>,
+    adding: bool,
+    allow_quit: bool,
+}
+
+impl EditListState {
+    /// Creates the state of a prompt editing `items`, with the cursor on the first one.
+    pub fn new(items: Vec<String>) -> Self {
+        EditListState {
+            items,
+            sel: 0,
+            entry: None,
+            adding: false,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the entries. Keys are rejected while an entry is read.
+    pub fn handle_key(&mut self, key: Key) -> Transition<Vec<String>> {
+        if self.entry.is_some() {
+            return Transition::Rejected;
+        }
+
+        let len = self.items.len();
+        match key {
+            Key::ArrowDown | Key::Tab | Key::Char('j') if len > 0 => {
+                self.sel = (self.sel + 1) % len;
+            }
+            Key::ArrowUp | Key::BackTab | Key::Char('k') if len > 0 => {
+                self.sel = (self.sel + len - 1) % len;
+            }
+            Key::Char('J') if self.sel + 1 < len => {
+                self.items.swap(self.sel, self.sel + 1);
+                self.sel += 1;
+            }
+            Key::Char('K') if self.sel > 0 => {
+                self.items.swap(self.sel, self.sel - 1);
+                self.sel -= 1;
+            }
+            Key::Char('a') | Key::Insert => {
+                self.entry = Some(String::new());
+                self.adding = true;
+            }
+            Key::Char('e') | Key::Char(' ') if len > 0 => {
+                self.entry = Some(self.items[self.sel].clone());
+                self.adding = false;
+            }
+            Key::Char('d') | Key::Del if len > 0 => {
+                self.items.remove(self.sel);
+                self.sel = self.sel.min(self.items.len().saturating_sub(1));
+            }
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            Key::Enter => return Transition::Done(self.items.clone()),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the text an entry starts with while it is read, empty for a new one.
+    ///
+    /// The caller reads the entry and hands it to [`submit_entry`](Self::submit_entry).
+    pub fn entry(&self) -> Option<&str> {
+        self.entry.as_deref()
+    }
+
+    /// Adds the entry read below the cursor or replaces the entry under it, or leaves the
+    /// list unchanged without one.
+    pub fn submit_entry(&mut self, entry: Option<String>) {
+        if self.entry.take().is_none() {
+            return;
+        }
+
+        match entry {
+            Some(entry) if !self.adding => self.items[self.sel] = entry,
+            Some(entry) if self.items.is_empty() => self.items.push(entry),
+            Some(entry) => {
+                self.sel += 1;
+                self.items.insert(self.sel, entry);
+            }
+            None => {}
+        }
+    }
+
+    /// Returns the entries.
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Returns the index of the entry under the cursor.
+    pub fn selection(&self) -> usize {
+        self.sel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_list_state() {
+        let mut state = EditListState::new(vec!["a".into(), "c".into()]);
+
+        assert_eq!(state.handle_key(Key::Char('a')), Transition::Continue);
+        assert_eq!(state.entry(), Some(""));
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        state.submit_entry(Some("b".into()));
+        assert_eq!(state.items(), ["a", "b", "c"]);
+        assert_eq!(state.selection(), 1);
+
+        state.handle_key(Key::Char('e'));
+        assert_eq!(state.entry(), Some("b"));
+        state.submit_entry(None);
+        assert_eq!(state.entry(), None);
+
+        state.handle_key(Key::ArrowDown);
+        state.handle_key(Key::Del);
+        assert_eq!(state.selection(), 1);
+        assert_eq!(state.handle_key(Key::Char('J')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char('q')), Transition::Rejected);
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Transition::Done(vec!["a".into(), "b".into()])
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use console::Key;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+use super::Transition;
+use crate::width::byte_offset;
+
+/// The state of a [`FuzzySelect`](crate::FuzzySelect) prompt.
+///
+/// Printable characters edit the search term, with the left and right arrow keys moving
+/// the text cursor, and the items matching it are ordered from the best match to the
+/// worst. The cursor moves over the matches with the up and down arrow keys and 'Tab', and
+/// 'Enter' picks the item under it.
+pub struct FuzzySelectState {
+    matcher: SkimMatcherV2,
+    items: Vec<String>,
+    matches: Vec<usize>,
+    search_term: String,
+    position: usize,
+    sel: Option<usize>,
+    allow_quit: bool,
+}
+
+impl FuzzySelectState {
+    /// Creates the state of a prompt searching `items`, with an empty search term.
+    pub fn new(items: Vec<String>) -> Self {
+        let mut state = FuzzySelectState {
+            matcher: SkimMatcherV2::default(),
+            items,
+            matches: vec![],
+            search_term: String::new(),
+            position: 0,
+            sel: None,
+            allow_quit: false,
+        };
+        state.update_matches();
+        state
+    }
+
+    /// Sets the match the cursor starts on, which is the item of that index while the
+    /// search term is empty.
+    pub fn default(mut self, sel: Option<usize>) -> Self {
+        self.sel = sel;
+        self
+    }
+
+    /// Sets whether 'Esc' cancels the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the index of the picked item.
+    pub fn handle_key(&mut self, key: Key) -> Transition<usize> {
+        let len = self.matches.len();
+        match key {
+            Key::Escape if self.allow_quit => return Transition::Cancelled,
+            Key::ArrowUp | Key::BackTab if len > 0 => {
+                self.sel = Some(self.sel.map_or(len - 1, |sel| (sel % len + len - 1) % len));
+            }
+            Key::ArrowDown | Key::Tab if len > 0 => {
+                self.sel = Some(self.sel.map_or(0, |sel| (sel + 1) % len));
+            }
+            Key::ArrowLeft if self.position > 0 => self.position -= 1,
+            Key::ArrowRight if self.position < self.search_term.chars().count() => {
+                self.position += 1;
+            }
+            // No item is picked without a default, or with one beyond the matches.
+            Key::Enter => {
+                return match self.sel.and_then(|sel| self.matches.get(sel)) {
+                    Some(&idx) => Transition::Done(idx),
+                    None => Transition::Rejected,
+                };
+            }
+            Key::Backspace if self.position > 0 => {
+                self.position -= 1;
+                let offset = byte_offset(&self.search_term, self.position);
+                self.search_term.remove(offset);
+                self.update_matches();
+            }
+            Key::Char(chr) if !chr.is_ascii_control() => {
+                let offset = byte_offset(&self.search_term, self.position);
+                self.search_term.insert(offset, chr);
+                self.position += 1;
+                self.sel = Some(0);
+                self.update_matches();
+            }
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the search term.
+    pub fn search_term(&self) -> &str {
+        &self.search_term
+    }
+
+    /// Returns the position of the text cursor in the search term, in characters.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the indices of the items matching the search term, from the best match to
+    /// the worst.
+    pub fn matches(&self) -> &[usize] {
+        &self.matches
+    }
+
+    /// Returns the position of the cursor among the matches, if any.
+    pub fn selection(&self) -> Option<usize> {
+        self.sel
+    }
+
+    /// Returns the index of the item under the cursor, if any.
+    pub fn selected_item(&self) -> Option<usize> {
+        self.sel.and_then(|sel| self.matches.get(sel).copied())
+    }
+
+    /// Returns the items searched.
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Replaces the items, like after they changed, and moves the cursor to the item of
+    /// index `follow` if it matches.
+    ///
+    /// Without that item the cursor stays at its position among the matches.
+    pub fn set_items(&mut self, items: Vec<String>, follow: Option<usize>) {
+        let had_selection = self.selected_item().is_some();
+        self.items = items;
+        self.update_matches();
+
+        match follow.and_then(|idx| self.matches.iter().position(|&other| other == idx)) {
+            Some(sel) => self.sel = Some(sel),
+            None if had_selection => {
+                self.sel = self
+                    .sel
+                    .map(|sel| sel.min(self.matches.len().saturating_sub(1)));
+            }
+            None => {}
+        }
+    }
+
+    fn update_matches(&mut self) {
+        let mut scored: Vec<_> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| {
+                self.matcher
+                    .fuzzy_match(item, &self.search_term)
+                    .map(|score| (idx, score))
+            })
+            .collect();
+        scored.sort_by(|(_, s1), (_, s2)| s2.cmp(s1));
+        self.matches = scored.into_iter().map(|(idx, _)| idx).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<String> {
+        vec!["apple".into(), "banana".into(), "cherry".into()]
+    }
+
+    #[test]
+    fn test_fuzzy_select_state() {
+        let mut state = FuzzySelectState::new(items());
+        assert_eq!(state.matches(), [0, 1, 2]);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+
+        assert_eq!(state.handle_key(Key::Char('a')), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char('n')), Transition::Continue);
+        assert_eq!(state.search_term(), "an");
+        assert_eq!(state.matches(), [1]);
+        assert_eq!(state.selected_item(), Some(1));
+
+        state.handle_key(Key::ArrowLeft);
+        state.handle_key(Key::Backspace);
+        assert_eq!(state.search_term(), "n");
+        assert_eq!(state.position(), 0);
+        assert_eq!(state.handle_key(Key::Backspace), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(1));
+    }
+
+    #[test]
+    fn test_fuzzy_select_state_set_items() {
+        let mut state = FuzzySelectState::new(items())
+            .default(Some(2))
+            .allow_quit(true);
+        state.handle_key(Key::ArrowDown);
+        assert_eq!(state.selection(), Some(0));
+
+        // The cursor follows its item, and stays in place once it's gone.
+        state.set_items(vec!["date".into(), "apple".into()], Some(1));
+        assert_eq!(state.selected_item(), Some(1));
+        state.set_items(vec!["date".into()], None);
+        assert_eq!(state.selected_item(), Some(0));
+        assert_eq!(state.handle_key(Key::Escape), Transition::Cancelled);
+    }
+}
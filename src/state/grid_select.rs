@@ -0,0 +1,123 @@
+use console::Key;
+
+use super::Transition;
+
+/// The state of a [`GridSelect`](crate::GridSelect) prompt.
+///
+/// The items are laid out in rows of a number of columns, and the cursor moves over them
+/// with the arrow keys (or `h`, `j`, `k` and `l`) and 'Tab', and by a page with the page
+/// keys. 'Enter' or 'Space' pick the item under it.
+pub struct GridSelectState {
+    len: usize,
+    sel: usize,
+    columns: usize,
+    page_rows: usize,
+    allow_quit: bool,
+}
+
+impl GridSelectState {
+    /// Creates the state of a grid of `len` items, one per row, with the cursor on the
+    /// first one.
+    ///
+    /// `len` must not be zero.
+    pub fn new(len: usize) -> Self {
+        GridSelectState {
+            len,
+            sel: 0,
+            columns: 1,
+            page_rows: len,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the item the cursor starts on, or the last one if there are fewer items.
+    pub fn default(mut self, sel: usize) -> Self {
+        self.sel = sel.min(self.len - 1);
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the index of the picked item.
+    pub fn handle_key(&mut self, key: Key) -> Transition<usize> {
+        let last = self.len - 1;
+        let columns = self.columns;
+        let rows = (self.len + columns - 1) / columns;
+
+        match key {
+            Key::ArrowRight | Key::Tab | Key::Char('l') => {
+                self.sel = if self.sel == last { 0 } else { self.sel + 1 };
+            }
+            Key::ArrowLeft | Key::BackTab | Key::Char('h') => {
+                self.sel = if self.sel == 0 { last } else { self.sel - 1 };
+            }
+            Key::ArrowDown | Key::Char('j') if self.sel + columns <= last => {
+                self.sel += columns;
+            }
+            Key::ArrowDown | Key::Char('j') if self.sel / columns + 1 < rows => {
+                // The last row is incomplete, jump to its last item.
+                self.sel = last;
+            }
+            Key::ArrowUp | Key::Char('k') if self.sel >= columns => {
+                self.sel -= columns;
+            }
+            Key::PageDown => self.sel = (self.sel + self.page_rows * columns).min(last),
+            Key::PageUp => self.sel = self.sel.saturating_sub(self.page_rows * columns),
+            Key::Home => self.sel = 0,
+            Key::End => self.sel = last,
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            Key::Enter | Key::Char(' ') => return Transition::Done(self.sel),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the index of the item under the cursor.
+    pub fn selection(&self) -> usize {
+        self.sel
+    }
+
+    /// Sets the number of columns of the grid and of rows on a page, which the page keys
+    /// move by.
+    ///
+    /// By default the items are in one column on one page.
+    pub fn set_layout(&mut self, columns: usize, page_rows: usize) {
+        self.columns = columns.max(1);
+        self.page_rows = page_rows;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_select_state() {
+        let mut state = GridSelectState::new(8).default(10);
+        state.set_layout(3, 2);
+        assert_eq!(state.selection(), 7);
+
+        assert_eq!(state.handle_key(Key::ArrowDown), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Continue);
+        assert_eq!(state.selection(), 0);
+        assert_eq!(state.handle_key(Key::ArrowUp), Transition::Rejected);
+        state.handle_key(Key::ArrowRight);
+        state.handle_key(Key::ArrowRight);
+        state.handle_key(Key::ArrowDown);
+        // The last row is incomplete, so the cursor stops at its last item.
+        state.handle_key(Key::ArrowDown);
+        assert_eq!(state.selection(), 7);
+        state.handle_key(Key::PageUp);
+        assert_eq!(state.selection(), 1);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Done(1));
+    }
+}
@@ -0,0 +1,95 @@
+use console::Key;
+
+use super::Transition;
+
+/// The state of the text edited in an [`Input`](crate::Input) prompt.
+///
+/// Printable characters are inserted at the cursor, 'Backspace' removes the character in
+/// front of it and the left and right arrow keys move it. 'Enter' submits the text, which
+/// the prompt then parses and validates. History and completions are left to the caller,
+/// which puts their text in place with [`set_text`](Self::set_text).
+#[derive(Default)]
+pub struct InputState {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl InputState {
+    /// Creates the state of an empty input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the text the input starts with, with the cursor after it.
+    pub fn initial_text(mut self, text: &str) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the text entered.
+    pub fn handle_key(&mut self, key: Key) -> Transition<String> {
+        match key {
+            Key::Backspace if self.position > 0 => {
+                self.position -= 1;
+                self.chars.remove(self.position);
+            }
+            Key::Char(chr) if !chr.is_ascii_control() => {
+                self.chars.insert(self.position, chr);
+                self.position += 1;
+            }
+            Key::ArrowLeft if self.position > 0 => self.position -= 1,
+            Key::ArrowRight if self.position < self.chars.len() => self.position += 1,
+            Key::Enter => return Transition::Done(self.text()),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the text entered.
+    pub fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    /// Returns the characters of the text entered.
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    /// Returns the position of the cursor, in characters.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Replaces the text, like with an entry of the history or a completion, and moves the
+    /// cursor after it.
+    pub fn set_text(&mut self, text: &str) {
+        self.chars = text.chars().collect();
+        self.position = self.chars.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_state() {
+        let mut state = InputState::new().initial_text("ac");
+        assert_eq!(state.position(), 2);
+
+        assert_eq!(state.handle_key(Key::ArrowLeft), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char('b')), Transition::Continue);
+        assert_eq!(state.text(), "abc");
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Continue);
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Backspace), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char('\t')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::ArrowUp), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done("ab".into()));
+
+        state.set_text("xyz");
+        assert_eq!(state.position(), 3);
+    }
+}
@@ -0,0 +1,167 @@
+use std::marker::PhantomData;
+
+use console::Key;
+
+use super::Transition;
+use crate::prompts::ip_input::IpValue;
+
+/// Returns the rest of the first completion starting with `input`.
+fn completion<T: IpValue>(input: &str) -> Option<&'static str> {
+    if input.is_empty() {
+        return None;
+    }
+
+    let input = input.to_lowercase();
+    T::completions()
+        .into_iter()
+        .find(|candidate| candidate.len() > input.len() && candidate.starts_with(&input))
+        .map(|candidate| &candidate[input.len()..])
+}
+
+/// The state of an [`IpInput`](crate::IpInput) prompt.
+///
+/// Characters which can be part of the value are typed into the input, which 'Backspace'
+/// edits, and 'Tab' or 'Right' complete common private ranges. 'Enter' submits the text
+/// once it parses to a value.
+pub struct IpInputState<T> {
+    buffer: String,
+    submitted: bool,
+    allow_quit: bool,
+    _value: PhantomData<T>,
+}
+
+impl<T: IpValue> Default for IpInputState<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IpValue> IpInputState<T> {
+    /// Creates the state of an empty input.
+    pub fn new() -> Self {
+        IpInputState {
+            buffer: String::new(),
+            submitted: false,
+            allow_quit: false,
+            _value: PhantomData,
+        }
+    }
+
+    /// Sets the value the input starts with.
+    pub fn initial_value(mut self, value: &T) -> Self {
+        self.buffer = value.to_string();
+        self
+    }
+
+    /// Sets whether 'Esc' cancels the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the value entered. Submitting text which isn't a value is
+    /// rejected, see [`error`](Self::error).
+    pub fn handle_key(&mut self, key: Key) -> Transition<T> {
+        self.submitted = false;
+
+        match key {
+            Key::Tab | Key::ArrowRight if self.completion().is_some() => {
+                let suffix = self.completion().unwrap_or_default();
+                self.buffer.push_str(suffix);
+            }
+            Key::Char(chr) if T::accepts_char(chr) => self.buffer.push(chr),
+            Key::Backspace => {
+                self.buffer.pop();
+            }
+            Key::Escape if self.allow_quit => return Transition::Cancelled,
+            Key::Enter => {
+                return match self.value() {
+                    Some(value) => Transition::Done(value),
+                    None => {
+                        self.submitted = true;
+                        Transition::Rejected
+                    }
+                };
+            }
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the text typed.
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Returns the rest of the completion for the text typed, if any.
+    pub fn completion(&self) -> Option<&'static str> {
+        completion::<T>(&self.buffer)
+    }
+
+    /// Returns the value of the text typed, if it parses to one.
+    pub fn value(&self) -> Option<T> {
+        self.buffer.parse().ok()
+    }
+
+    /// Returns why the text submitted with the last key was rejected, if it was.
+    pub fn error(&self) -> Option<String> {
+        if self.submitted {
+            Some(format!("'{}' is not a valid {}", self.buffer, T::KIND))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use super::*;
+
+    #[test]
+    fn test_completion() {
+        assert_eq!(completion::<Ipv4Addr>("19"), Some("2.168."));
+        assert_eq!(completion::<Ipv4Addr>("192.168."), None);
+        assert_eq!(completion::<Ipv4Addr>(""), None);
+        assert_eq!(completion::<Ipv6Addr>("FE"), Some("80::"));
+        assert_eq!(completion::<IpAddr>("f"), Some("d"));
+    }
+
+    #[cfg(feature = "ipnet")]
+    #[test]
+    fn test_blocks() {
+        assert!(ipnet::IpNet::accepts_char('/'));
+        assert_eq!(completion::<ipnet::Ipv4Net>("172"), Some(".16.0.0/12"));
+    }
+
+    #[test]
+    fn test_ip_input_state() {
+        let mut state = IpInputState::<Ipv4Addr>::new();
+
+        assert_eq!(state.handle_key(Key::Char('a')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char('1')), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char('9')), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Tab), Transition::Continue);
+        assert_eq!(state.buffer(), "192.168.");
+        assert_eq!(state.handle_key(Key::Tab), Transition::Rejected);
+
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert_eq!(
+            state.error().as_deref(),
+            Some("'192.168.' is not a valid IPv4 address")
+        );
+        state.handle_key(Key::Char('0'));
+        state.handle_key(Key::Char('.'));
+        state.handle_key(Key::Char('1'));
+        assert_eq!(state.error(), None);
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Transition::Done(Ipv4Addr::new(192, 168, 0, 1))
+        );
+    }
+}
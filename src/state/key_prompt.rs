@@ -0,0 +1,74 @@
+use console::Key;
+
+use super::Transition;
+
+/// The state of a [`KeyPrompt`](crate::KeyPrompt).
+///
+/// Any key but the unknown ones finishes the prompt, or only the allowed keys when some
+/// are set.
+pub struct KeyPromptState {
+    allowed_keys: Vec<Key>,
+    allow_quit: bool,
+}
+
+impl KeyPromptState {
+    /// Creates the state of a prompt finished by `allowed_keys`, or by any key when it's
+    /// empty.
+    pub fn new(allowed_keys: Vec<Key>) -> Self {
+        KeyPromptState {
+            allowed_keys,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether 'Esc' cancels the prompt, unless it's one of the allowed keys.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the key pressed.
+    pub fn handle_key(&mut self, key: Key) -> Transition<Key> {
+        match key {
+            Key::Escape if self.allow_quit && !self.allowed_keys.contains(&Key::Escape) => {
+                Transition::Cancelled
+            }
+            key if self.accepts(&key) => Transition::Done(key),
+            _ => Transition::Rejected,
+        }
+    }
+
+    /// Returns `true` if `key` finishes the prompt.
+    fn accepts(&self, key: &Key) -> bool {
+        match key {
+            Key::Unknown | Key::UnknownEscSeq(_) => false,
+            key => self.allowed_keys.is_empty() || self.allowed_keys.contains(key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_prompt_state() {
+        let mut state = KeyPromptState::new(vec![]);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(Key::Enter));
+        assert_eq!(state.handle_key(Key::Unknown), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Done(Key::Escape));
+
+        let mut state = KeyPromptState::new(vec![Key::Char('y'), Key::Char('n')]).allow_quit(true);
+        assert_eq!(
+            state.handle_key(Key::Char('y')),
+            Transition::Done(Key::Char('y'))
+        );
+        assert_eq!(state.handle_key(Key::Char('Y')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Cancelled);
+    }
+}
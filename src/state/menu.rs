@@ -0,0 +1,151 @@
+use console::Key;
+
+use super::Transition;
+use crate::prompts::menu::MenuItem;
+
+/// Returns the entries of the menu reached by following `path` from `items`.
+fn submenu<'a>(items: &'a [MenuItem], path: &[usize]) -> &'a [MenuItem] {
+    path.iter()
+        .fold(items, |items, &idx| items[idx].children.as_slice())
+}
+
+/// Returns the labels of the entries along `path`.
+fn breadcrumb<'a>(items: &'a [MenuItem], path: &[usize]) -> Vec<&'a str> {
+    (0..path.len())
+        .map(|depth| submenu(items, &path[..depth])[path[depth]].label())
+        .collect()
+}
+
+/// The state of a [`Menu`](crate::Menu) prompt.
+///
+/// The cursor moves over the entries of the open menu with the up and down arrow keys (or
+/// `j`, `k` and 'Tab'). 'Enter' (or 'Right', `l` and 'Space') opens the submenu of the
+/// entry under it, or chooses the entry if it has none, and 'Esc' (or 'Left', `h` and
+/// 'Backspace') goes back to the parent menu.
+pub struct MenuState {
+    items: Vec<MenuItem>,
+    path: Vec<usize>,
+    sel: usize,
+    allow_quit: bool,
+}
+
+impl MenuState {
+    /// Creates the state of a menu of `items`, with the cursor on the first one.
+    ///
+    /// `items` must not be empty.
+    pub fn new(items: Vec<MenuItem>) -> Self {
+        MenuState {
+            items,
+            path: vec![],
+            sel: 0,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt in the top menu.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the path of the chosen entry, one index per menu level.
+    pub fn handle_key(&mut self, key: Key) -> Transition<Vec<usize>> {
+        let len = self.entries().len();
+        match key {
+            Key::ArrowDown | Key::Tab | Key::Char('j') => self.sel = (self.sel + 1) % len,
+            Key::ArrowUp | Key::BackTab | Key::Char('k') => self.sel = (self.sel + len - 1) % len,
+            Key::Enter | Key::ArrowRight | Key::Char('l') | Key::Char(' ')
+                if self.entries()[self.sel].has_submenu() =>
+            {
+                self.path.push(self.sel);
+                self.sel = 0;
+            }
+            Key::Escape | Key::ArrowLeft | Key::Backspace | Key::Char('h')
+                if !self.path.is_empty() =>
+            {
+                // Return to the parent menu with the submenu entry selected.
+                self.sel = self.path.pop().unwrap_or(0);
+            }
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            Key::Enter | Key::Char(' ') => {
+                self.path.push(self.sel);
+                return Transition::Done(self.path.clone());
+            }
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the entries of the open menu.
+    pub fn entries(&self) -> &[MenuItem] {
+        submenu(&self.items, &self.path)
+    }
+
+    /// Returns the index of the entry under the cursor in the open menu.
+    pub fn selection(&self) -> usize {
+        self.sel
+    }
+
+    /// Returns the path of the open menu, one index per menu level, which ends with the
+    /// chosen entry once the prompt is done.
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+
+    /// Returns the labels of the entries along the [path](Self::path).
+    pub fn breadcrumb(&self) -> Vec<&str> {
+        breadcrumb(&self.items, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<MenuItem> {
+        vec![
+            MenuItem::new("Play"),
+            MenuItem::new("Settings").child(
+                MenuItem::new("Network")
+                    .child(MenuItem::new("Proxy"))
+                    .child(MenuItem::new("Ports")),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_breadcrumb_follows_path() {
+        let items = items();
+
+        assert_eq!(submenu(&items, &[1, 0]).len(), 2);
+        assert_eq!(breadcrumb(&items, &[]), Vec::<&str>::new());
+        assert_eq!(
+            breadcrumb(&items, &[1, 0, 1]),
+            ["Settings", "Network", "Ports"]
+        );
+    }
+
+    #[test]
+    fn test_menu_state() {
+        let mut state = MenuState::new(items());
+
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        state.handle_key(Key::ArrowUp);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Continue);
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Continue);
+        assert_eq!(state.breadcrumb(), ["Settings", "Network"]);
+        assert_eq!(state.handle_key(Key::ArrowLeft), Transition::Continue);
+        assert_eq!(state.path(), [1]);
+        assert_eq!(state.handle_key(Key::Char('l')), Transition::Continue);
+        state.handle_key(Key::Char('j'));
+        assert_eq!(state.handle_key(Key::Char('l')), Transition::Rejected);
+        assert_eq!(
+            state.handle_key(Key::Char(' ')),
+            Transition::Done(vec![1, 0, 1])
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! The key handling of prompts as state machines, separate from the terminal.
+//!
+//! Every state takes keys with `handle_key` and tells with a [`Transition`] whether the
+//! prompt goes on, rejected the key or ended, without reading or rendering anything
+//! itself. The prompts of this crate put their terminal IO on top of these states, while
+//! applications embedding prompts in their own event loops or UIs can drive them directly
+//! and render the state however they like. Fuzzers and property tests can exercise the
+//! logic the same way.
+//!
+//! ```rust
+//! use dialoguer::{
+//!     console::Key,
+//!     state::{SelectState, Transition},
+//! };
+//!
+//! let mut state = SelectState::new(3).default(Some(0));
+//! assert_eq!(state.handle_key(Key::ArrowDown), Transition::Continue);
+//! assert_eq!(state.selection(), Some(1));
+//! assert_eq!(state.handle_key(Key::Enter), Transition::Done(1));
+//! ```
+use console::Key;
+
+use crate::paging::Jump;
+
+#[cfg(feature = "fuzzy-select")]
+pub use self::fuzzy_select::FuzzySelectState;
+#[cfg(feature = "password")]
+pub use self::password::PasswordState;
+pub use self::{
+    autocomplete::AutocompleteState, color_select::ColorSelectState, confirm::ConfirmState,
+    duration_input::DurationInputState, edit_list::EditListState, grid_select::GridSelectState,
+    input::InputState, ip_input::IpInputState, key_prompt::KeyPromptState, menu::MenuState,
+    multi_select::MultiSelectState, number_input::NumberInputState, path_select::PathEntry,
+    path_select::PathSelectState, pin_input::PinInputState, range_select::RangeSelectState,
+    review_each::ReviewEachState, select::SelectState, slider::SliderState, sort::SortState,
+    table_select::TableSelectState, tag_input::TagInputState, toggle::ToggleState,
+    transfer_select::TransferSelectState, tree_multi_select::TreeMultiSelectState,
+    tree_select::TreeSelectState,
+};
+
+mod autocomplete;
+mod color_select;
+mod confirm;
+mod duration_input;
+mod edit_list;
+#[cfg(feature = "fuzzy-select")]
+mod fuzzy_select;
+mod grid_select;
+mod input;
+mod ip_input;
+mod key_prompt;
+mod menu;
+mod multi_select;
+mod number_input;
+#[cfg(feature = "password")]
+mod password;
+mod path_select;
+mod pin_input;
+mod range_select;
+mod review_each;
+mod select;
+mod slider;
+mod sort;
+mod table_select;
+mod tag_input;
+mod toggle;
+mod transfer_select;
+mod tree_multi_select;
+mod tree_select;
+
+/// The columns 'Left' and 'Right' scroll the active item by.
+pub(crate) const HORIZONTAL_SCROLL_STEP: usize = 8;
+
+/// What happened to a prompt after it handled a key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Transition<T> {
+    /// The prompt goes on and renders its state again.
+    Continue,
+    /// The key isn't handled in the current state and the prompt goes on unchanged, usually
+    /// signalled with the [bell](crate::theme::Theme::bell).
+    Rejected,
+    /// The prompt was answered with the value.
+    Done(T),
+    /// The prompt was quit without an answer.
+    Cancelled,
+}
+
+/// Returns the transition for 'Esc' or `q`, which are ignored unless they may quit.
+fn quit<T>(allow_quit: bool) -> Transition<T> {
+    if allow_quit {
+        Transition::Cancelled
+    } else {
+        Transition::Continue
+    }
+}
+
+/// The cursor of a list, moved by the navigation keys shared by list prompts.
+#[derive(Default)]
+struct ListCursor {
+    len: usize,
+    sel: Option<usize>,
+    page_size: usize,
+    paged: bool,
+    numbered: bool,
+    jump: Jump,
+    horizontal_scroll: bool,
+    horizontal_offset: usize,
+}
+
+impl ListCursor {
+    fn new(len: usize) -> Self {
+        ListCursor {
+            len,
+            page_size: len.max(1),
+            ..Default::default()
+        }
+    }
+
+    /// Moves the cursor for `key`, returning whether the key was a navigation key.
+    fn navigate(&mut self, key: &Key) -> bool {
+        let previous = self.sel;
+        let handled = self.handle(key);
+        if self.sel != previous {
+            self.horizontal_offset = 0;
+        }
+        handled
+    }
+
+    fn handle(&mut self, key: &Key) -> bool {
+        let (consumed, target) = if self.numbered {
+            self.jump.handle(key, self.len)
+        } else {
+            (false, None)
+        };
+        if let Some(target) = target {
+            self.sel = Some(target);
+        }
+        if consumed {
+            return true;
+        }
+        if self.len == 0 {
+            return false;
+        }
+
+        let page_size = self.page_size.max(1);
+        let pages = (self.len + page_size - 1) / page_size;
+        let page = self.sel.map_or(0, |sel| sel / page_size);
+
+        self.sel = Some(match *key {
+            Key::ArrowDown | Key::Tab | Key::Char('j') => match self.sel {
+                Some(sel) => (sel + 1) % self.len,
+                None => 0,
+            },
+            Key::ArrowUp | Key::BackTab | Key::Char('k') => match self.sel {
+                Some(sel) => (sel + self.len - 1) % self.len,
+                None => self.len - 1,
+            },
+            Key::ArrowLeft if self.horizontal_scroll => {
+                self.horizontal_offset = self
+                    .horizontal_offset
+                    .saturating_sub(HORIZONTAL_SCROLL_STEP);
+                return true;
+            }
+            Key::ArrowRight if self.horizontal_scroll => {
+                self.horizontal_offset += HORIZONTAL_SCROLL_STEP;
+                return true;
+            }
+            // Switching pages without paging does nothing, like in earlier versions.
+            Key::ArrowLeft | Key::Char('h') => {
+                if !self.paged {
+                    return true;
+                }
+                (if page == 0 { pages - 1 } else { page - 1 }) * page_size
+            }
+            Key::ArrowRight | Key::Char('l') => {
+                if !self.paged {
+                    return true;
+                }
+                (if page + 1 >= pages { 0 } else { page + 1 }) * page_size
+            }
+            Key::PageUp => self.sel.map_or(0, |sel| sel.saturating_sub(page_size)),
+            Key::PageDown => (self.sel.unwrap_or(0) + page_size).min(self.len - 1),
+            Key::Home => 0,
+            Key::End => self.len - 1,
+            _ => return false,
+        });
+        true
+    }
+}
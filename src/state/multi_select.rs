@@ -0,0 +1,208 @@
+use console::Key;
+
+use super::{quit, ListCursor, Transition};
+
+/// The state of a [`MultiSelect`](crate::MultiSelect) prompt.
+///
+/// The cursor starts on the first item and moves like in a
+/// [`SelectState`](super::SelectState). 'Space' toggles the item under the cursor unless
+/// it's disabled or the maximum is checked, and 'Enter' confirms the checked items once the
+/// minimum is reached.
+pub struct MultiSelectState {
+    cursor: ListCursor,
+    checked: Vec<bool>,
+    disabled: Vec<bool>,
+    min_selections: Option<usize>,
+    max_selections: Option<usize>,
+    allow_quit: bool,
+}
+
+impl MultiSelectState {
+    /// Creates the state of a list of `len` items, none of them checked.
+    pub fn new(len: usize) -> Self {
+        let mut cursor = ListCursor::new(len);
+        cursor.sel = Some(0);
+        MultiSelectState {
+            cursor,
+            checked: vec![false; len],
+            disabled: vec![],
+            min_selections: None,
+            max_selections: None,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets which items start out checked, by index.
+    pub fn checked(mut self, checked: Vec<bool>) -> Self {
+        let len = self.checked.len();
+        self.checked = checked;
+        self.checked.resize(len, false);
+        self
+    }
+
+    /// Sets which items can't be toggled, by index.
+    pub fn disabled(mut self, disabled: Vec<bool>) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Sets the number of items which must be checked to confirm.
+    pub fn min_selections(mut self, min: Option<usize>) -> Self {
+        self.min_selections = min;
+        self
+    }
+
+    /// Sets the number of items which can be checked at most.
+    pub fn max_selections(mut self, max: Option<usize>) -> Self {
+        self.max_selections = max;
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Sets whether typing the number of an item moves the cursor to it.
+    ///
+    /// The default is false.
+    pub fn numbered(mut self, val: bool) -> Self {
+        self.cursor.numbered = val;
+        self
+    }
+
+    /// Sets whether the left and right arrow keys scroll wide items horizontally rather
+    /// than switching pages.
+    ///
+    /// The default is false.
+    pub fn horizontal_scroll(mut self, val: bool) -> Self {
+        self.cursor.horizontal_scroll = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the indices of the checked items.
+    pub fn handle_key(&mut self, key: Key) -> Transition<Vec<usize>> {
+        if self.cursor.navigate(&key) {
+            return Transition::Continue;
+        }
+
+        let count = self.count();
+        match key {
+            Key::Char(' ') => match self.cursor.sel {
+                Some(sel) if sel < self.checked.len() && !self.is_disabled(sel) => {
+                    let full = self.max_selections.is_some_and(|max| count >= max);
+                    if self.checked[sel] || !full {
+                        self.checked[sel] = !self.checked[sel];
+                        Transition::Continue
+                    } else {
+                        Transition::Rejected
+                    }
+                }
+                _ => Transition::Rejected,
+            },
+            Key::Escape | Key::Char('q') => quit(self.allow_quit),
+            Key::Enter if self.min_selections.map_or(true, |min| count >= min) => Transition::Done(
+                (0..self.checked.len())
+                    .filter(|&idx| self.checked[idx])
+                    .collect(),
+            ),
+            _ => Transition::Rejected,
+        }
+    }
+
+    /// Returns the item under the cursor.
+    pub fn selection(&self) -> Option<usize> {
+        self.cursor.sel
+    }
+
+    /// Returns which items are checked, by index.
+    pub fn checked_items(&self) -> &[bool] {
+        &self.checked
+    }
+
+    /// Returns the number of checked items.
+    pub fn count(&self) -> usize {
+        self.checked.iter().filter(|&&checked| checked).count()
+    }
+
+    /// Moves the cursor to `sel`, clamped to the items.
+    pub fn set_selection(&mut self, sel: Option<usize>) {
+        self.cursor.sel = sel.map(|sel| sel.min(self.cursor.len.saturating_sub(1)));
+    }
+
+    /// Replaces the items with `checked.len()` ones checked as given, like after they
+    /// changed.
+    pub fn set_checked(&mut self, checked: Vec<bool>) {
+        self.cursor.len = checked.len();
+        self.checked = checked;
+        let sel = self.cursor.sel;
+        self.set_selection(sel);
+    }
+
+    /// Sets the number of items on a page, which the page keys move by, and whether the
+    /// list is paged so the left and right arrows switch pages.
+    ///
+    /// By default all items are on one page.
+    pub fn set_page(&mut self, page_size: usize, paged: bool) {
+        self.cursor.page_size = page_size;
+        self.cursor.paged = paged;
+    }
+
+    /// Returns the digits typed to jump to an item, if any.
+    pub fn jump_input(&self) -> Option<&str> {
+        self.cursor.jump.digits()
+    }
+
+    /// Returns the columns the items are scrolled to the left.
+    pub fn horizontal_offset(&self) -> usize {
+        self.cursor.horizontal_offset
+    }
+
+    /// Sets the columns the items are scrolled to the left, like after limiting them to
+    /// how far the active item overflows the terminal.
+    pub fn set_horizontal_offset(&mut self, offset: usize) {
+        self.cursor.horizontal_offset = offset;
+    }
+
+    fn is_disabled(&self, idx: usize) -> bool {
+        self.disabled.get(idx).copied().unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multi_select_state() {
+        let mut state = MultiSelectState::new(3)
+            .checked(vec![true])
+            .disabled(vec![false, false, true])
+            .min_selections(Some(2))
+            .max_selections(Some(2));
+
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        state.handle_key(Key::ArrowDown);
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Continue);
+        assert_eq!(state.checked_items(), [true, true, false]);
+        state.handle_key(Key::End);
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_multi_select_state_max() {
+        let mut state = MultiSelectState::new(2).max_selections(Some(1));
+
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Continue);
+        state.handle_key(Key::ArrowDown);
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Rejected);
+        assert_eq!(state.count(), 1);
+    }
+}
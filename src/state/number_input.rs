@@ -0,0 +1,192 @@
+use console::Key;
+
+use super::Transition;
+use crate::prompts::number_input::Numeric;
+
+/// The state of a [`NumberInput`](crate::NumberInput) prompt.
+///
+/// The up and down arrow keys step the value within its bounds, and digits, '.' and '-'
+/// replace it with typed text, which 'Backspace' edits. 'Enter' submits the text once it
+/// parses to a number within the bounds.
+pub struct NumberInputState<T> {
+    value: T,
+    buffer: String,
+    // Whether the buffer holds typed text or a value produced by stepping.
+    editing: bool,
+    min: Option<T>,
+    max: Option<T>,
+    step: T,
+    error: Option<String>,
+    allow_quit: bool,
+}
+
+impl<T: Numeric> NumberInputState<T> {
+    /// Creates the state of a prompt starting with `value`.
+    pub fn new(value: T) -> Self {
+        NumberInputState {
+            value,
+            buffer: value.to_string(),
+            editing: false,
+            min: None,
+            max: None,
+            step: T::one(),
+            error: None,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the bounds of the value, moving the value into them.
+    ///
+    /// By default the value is unbounded.
+    pub fn bounds(mut self, min: Option<T>, max: Option<T>) -> Self {
+        self.min = min;
+        self.max = max;
+        self.value = self.clamp(self.value);
+        self.buffer = self.value.to_string();
+        self
+    }
+
+    /// Sets the amount the arrow keys step the value by.
+    ///
+    /// The default is one.
+    pub fn step(mut self, step: T) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Sets whether 'Esc' cancels the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the number entered. Steps beyond the bounds are rejected, and
+    /// so is submitting text which isn't a number within them, see [`error`](Self::error).
+    pub fn handle_key(&mut self, key: Key) -> Transition<T> {
+        self.error = None;
+
+        match key {
+            Key::ArrowUp | Key::ArrowDown => {
+                let current = self.buffer.parse().unwrap_or(self.value);
+                let stepped = if key == Key::ArrowUp {
+                    current.step_up(self.step)
+                } else {
+                    current.step_down(self.step)
+                };
+                self.value = self.clamp(stepped);
+                self.buffer = self.value.to_string();
+                self.editing = false;
+
+                if self.value == current {
+                    return Transition::Rejected;
+                }
+            }
+            Key::Char(chr) if chr.is_ascii_digit() || chr == '.' || chr == '-' => {
+                if !self.editing {
+                    self.buffer.clear();
+                    self.editing = true;
+                }
+                self.buffer.push(chr);
+            }
+            Key::Backspace => {
+                self.buffer.pop();
+            }
+            Key::Escape if self.allow_quit => return Transition::Cancelled,
+            Key::Enter => {
+                let result = self
+                    .buffer
+                    .parse::<T>()
+                    .map_err(|_| format!("'{}' is not a valid number", self.buffer))
+                    .and_then(|parsed| self.check_bounds(parsed));
+
+                return match result {
+                    Ok(parsed) => Transition::Done(parsed),
+                    Err(err) => {
+                        self.error = Some(err);
+                        self.buffer = self.value.to_string();
+                        self.editing = false;
+                        Transition::Rejected
+                    }
+                };
+            }
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the text shown as the value, typed or produced by stepping.
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Returns the last value stepped to, or the one the prompt started with.
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Returns why the text submitted with the last key was rejected, if it was.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    fn clamp(&self, mut value: T) -> T {
+        if let Some(min) = self.min {
+            if value < min {
+                value = min;
+            }
+        }
+        if let Some(max) = self.max {
+            if value > max {
+                value = max;
+            }
+        }
+        value
+    }
+
+    fn check_bounds(&self, value: T) -> Result<T, String> {
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => Err(format!("Value must be at least {}", min)),
+            (_, Some(max)) if value > max => Err(format!("Value must be at most {}", max)),
+            _ => Ok(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_bounds() {
+        let state = NumberInputState::new(0).bounds(Some(-5i32), Some(5));
+
+        assert_eq!(state.check_bounds(0), Ok(0));
+        assert!(state.check_bounds(-6).is_err());
+        assert!(state.check_bounds(6).is_err());
+    }
+
+    #[test]
+    fn test_number_input_state() {
+        let mut state = NumberInputState::new(8u8).bounds(None, Some(10)).step(2);
+
+        assert_eq!(state.handle_key(Key::ArrowUp), Transition::Continue);
+        assert_eq!(state.handle_key(Key::ArrowUp), Transition::Rejected);
+        assert_eq!(state.buffer(), "10");
+
+        state.handle_key(Key::Char('1'));
+        state.handle_key(Key::Char('2'));
+        assert_eq!(state.buffer(), "12");
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert_eq!(state.error(), Some("Value must be at most 10"));
+        assert_eq!(state.buffer(), "10");
+
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(state.error(), None);
+        state.handle_key(Key::Backspace);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(1));
+    }
+}
@@ -0,0 +1,109 @@
+use zeroize::Zeroizing;
+
+use super::Transition;
+
+/// The state of a [`Password`](crate::Password) prompt.
+///
+/// Passwords are read as whole lines without echoing them, so the state takes every line
+/// entered instead of single keys. An empty password is asked for again unless allowed.
+/// With a confirmation the second password must match the first, or both are asked for
+/// again.
+#[derive(Default)]
+pub struct PasswordState {
+    allow_empty: bool,
+    confirmation: bool,
+    first: Option<Zeroizing<String>>,
+    mismatched: bool,
+}
+
+impl PasswordState {
+    /// Creates the state of a password prompt without a confirmation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether an empty password is accepted.
+    ///
+    /// The default is false.
+    pub fn allow_empty(mut self, val: bool) -> Self {
+        self.allow_empty = val;
+        self
+    }
+
+    /// Sets whether the password is entered a second time to confirm it.
+    ///
+    /// The default is false.
+    pub fn confirmation(mut self, val: bool) -> Self {
+        self.confirmation = val;
+        self
+    }
+
+    /// Handles the `password` entered, returning how the prompt goes on.
+    ///
+    /// The prompt goes on with the confirmation after the first password, and rejects
+    /// empty passwords and confirmations not matching it.
+    pub fn handle_password(&mut self, password: String) -> Transition<String> {
+        let password = Zeroizing::new(password);
+        self.mismatched = false;
+
+        if password.is_empty() && !self.allow_empty {
+            return Transition::Rejected;
+        }
+        if !self.confirmation {
+            return Transition::Done((*password).clone());
+        }
+
+        match self.first.take() {
+            None => {
+                self.first = Some(password);
+                Transition::Continue
+            }
+            Some(first) if *first == *password => Transition::Done((*password).clone()),
+            Some(_) => {
+                self.mismatched = true;
+                Transition::Rejected
+            }
+        }
+    }
+
+    /// Returns whether the next password confirms the first one.
+    pub fn confirming(&self) -> bool {
+        self.first.is_some()
+    }
+
+    /// Returns whether the last password didn't match the first one, which is asked for
+    /// again.
+    pub fn mismatched(&self) -> bool {
+        self.mismatched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_state() {
+        let mut state = PasswordState::new().confirmation(true);
+
+        assert_eq!(state.handle_password("".into()), Transition::Rejected);
+        assert_eq!(state.handle_password("secret".into()), Transition::Continue);
+        assert!(state.confirming());
+        assert_eq!(state.handle_password("".into()), Transition::Rejected);
+        assert!(state.confirming() && !state.mismatched());
+        assert_eq!(state.handle_password("other".into()), Transition::Rejected);
+        assert!(!state.confirming() && state.mismatched());
+
+        state.handle_password("secret".into());
+        assert_eq!(
+            state.handle_password("secret".into()),
+            Transition::Done("secret".into())
+        );
+
+        let mut state = PasswordState::new().allow_empty(true);
+        assert_eq!(
+            state.handle_password("".into()),
+            Transition::Done("".into())
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use console::Key;
+
+use super::Transition;
+
+/// A row of the directory listing of a [`PathSelectState`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathEntry {
+    /// The directory currently listed.
+    Current,
+    /// The parent of the directory currently listed.
+    Parent,
+    /// A directory with its name.
+    Dir(String),
+    /// A file with its name.
+    File(String),
+}
+
+impl PathEntry {
+    /// Returns the name the entry is listed with.
+    pub fn name(&self) -> &str {
+        match self {
+            PathEntry::Current => ".",
+            PathEntry::Parent => "..",
+            PathEntry::Dir(name) | PathEntry::File(name) => name,
+        }
+    }
+
+    /// Returns `true` for directories, including the current and the parent one.
+    pub fn is_dir(&self) -> bool {
+        !matches!(self, PathEntry::File(_))
+    }
+}
+
+/// The state of a [`PathSelect`](crate::PathSelect) prompt.
+///
+/// The cursor moves over the listing of a directory with the up and down arrow keys (or
+/// `j`, `k` and 'Tab'). 'Right' (or `l`) and 'Enter' descend into the directory under it
+/// and 'Left' (or `h` and 'Backspace') ascend to the parent directory, `.` toggles hidden
+/// entries. 'Enter' on a file or the current directory picks it.
+///
+/// The state doesn't touch the file system itself: keys leading to another listing set a
+/// [`target`](Self::target), which the caller lists and hands back with
+/// [`set_listing`](Self::set_listing).
+pub struct PathSelectState {
+    dir: PathBuf,
+    entries: Vec<PathEntry>,
+    sel: usize,
+    target: Option<PathBuf>,
+    error: Option<String>,
+    show_hidden: bool,
+    select_files: bool,
+    allow_quit: bool,
+}
+
+impl PathSelectState {
+    /// Creates the state of a prompt showing the listing `entries` of `dir`, with the cursor
+    /// on the first entry.
+    pub fn new(dir: PathBuf, entries: Vec<PathEntry>) -> Self {
+        PathSelectState {
+            dir,
+            entries,
+            sel: 0,
+            target: None,
+            error: None,
+            show_hidden: false,
+            select_files: true,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether hidden entries are listed, which `.` toggles.
+    ///
+    /// The default is false.
+    pub fn show_hidden(mut self, val: bool) -> Self {
+        self.show_hidden = val;
+        self
+    }
+
+    /// Sets whether files can be picked.
+    ///
+    /// The default is true.
+    pub fn select_files(mut self, val: bool) -> Self {
+        self.select_files = val;
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the path picked.
+    pub fn handle_key(&mut self, key: Key) -> Transition<PathBuf> {
+        self.error = None;
+        self.target = None;
+
+        let len = self.entries.len();
+        let parent = self.dir.parent().map(Path::to_path_buf);
+        match key {
+            Key::ArrowDown | Key::Tab | Key::Char('j') if len > 0 => {
+                self.sel = (self.sel + 1) % len;
+            }
+            Key::ArrowUp | Key::BackTab | Key::Char('k') if len > 0 => {
+                self.sel = (self.sel + len - 1) % len;
+            }
+            Key::ArrowLeft | Key::Backspace | Key::Char('h') => self.target = parent,
+            Key::ArrowRight | Key::Char('l') => match self.entries.get(self.sel) {
+                Some(PathEntry::Dir(name)) => self.target = Some(self.dir.join(name)),
+                Some(PathEntry::Parent) => self.target = parent,
+                _ => {}
+            },
+            Key::Char('.') => {
+                self.show_hidden = !self.show_hidden;
+                self.target = Some(self.dir.clone());
+            }
+            Key::Enter => match self.entries.get(self.sel) {
+                Some(PathEntry::Current) => return Transition::Done(self.dir.clone()),
+                Some(PathEntry::Parent) => self.target = parent,
+                Some(PathEntry::Dir(name)) => self.target = Some(self.dir.join(name)),
+                Some(PathEntry::File(name)) if self.select_files => {
+                    return Transition::Done(self.dir.join(name));
+                }
+                _ => {}
+            },
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the directory the last key leads to, which the caller lists.
+    pub fn target(&self) -> Option<&Path> {
+        self.target.as_deref()
+    }
+
+    /// Enters the [target](Self::target) with its listing, or shows why it couldn't be
+    /// listed.
+    ///
+    /// When ascending, the cursor stays on the directory it came from.
+    pub fn set_listing(&mut self, listing: io::Result<Vec<PathEntry>>) {
+        let target = match self.target.take() {
+            Some(target) => target,
+            None => return,
+        };
+
+        match listing {
+            Ok(listing) => {
+                self.sel = self
+                    .dir
+                    .file_name()
+                    .filter(|_| self.dir.parent() == Some(target.as_path()))
+                    .and_then(|name| {
+                        let name = name.to_string_lossy();
+                        listing.iter().position(|entry| entry.name() == name)
+                    })
+                    .unwrap_or(0);
+                self.entries = listing;
+                self.dir = target;
+            }
+            Err(err) => self.error = Some(format!("{}: {}", target.display(), err)),
+        }
+    }
+
+    /// Returns the directory listed.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the listing of the directory.
+    pub fn entries(&self) -> &[PathEntry] {
+        &self.entries
+    }
+
+    /// Returns the index of the entry under the cursor.
+    pub fn selection(&self) -> usize {
+        self.sel
+    }
+
+    /// Returns whether hidden entries are listed.
+    pub fn shows_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    /// Returns why the directory the last key leads to couldn't be listed, if it couldn't.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_select_state() {
+        let listing = vec![
+            PathEntry::Parent,
+            PathEntry::Dir("src".into()),
+            PathEntry::File("a.toml".into()),
+        ];
+        let mut state = PathSelectState::new("/crate".into(), listing);
+
+        assert_eq!(state.handle_key(Key::ArrowDown), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Continue);
+        assert_eq!(state.target(), Some(Path::new("/crate/src")));
+        state.set_listing(Ok(vec![
+            PathEntry::Parent,
+            PathEntry::File("lib.rs".into()),
+        ]));
+        assert_eq!(state.dir(), Path::new("/crate/src"));
+        assert_eq!(state.selection(), 0);
+
+        // Ascending keeps the cursor on the directory it came from.
+        state.handle_key(Key::ArrowLeft);
+        state.set_listing(Ok(vec![PathEntry::Parent, PathEntry::Dir("src".into())]));
+        assert_eq!(state.selection(), 1);
+
+        state.handle_key(Key::Char('.'));
+        assert!(state.shows_hidden());
+        state.set_listing(Err(io::ErrorKind::PermissionDenied.into()));
+        assert!(state.error().is_some());
+        assert_eq!(state.dir(), Path::new("/crate"));
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(state.error(), None);
+    }
+
+    #[test]
+    fn test_path_select_state_pick() {
+        let listing = vec![PathEntry::Current, PathEntry::File("a.toml".into())];
+        let mut state = PathSelectState::new("/crate".into(), listing).select_files(false);
+
+        state.handle_key(Key::ArrowUp);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Continue);
+        state.handle_key(Key::ArrowUp);
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Transition::Done(PathBuf::from("/crate"))
+        );
+    }
+}
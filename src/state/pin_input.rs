@@ -0,0 +1,114 @@
+use console::Key;
+
+use super::Transition;
+
+/// The state of a [`PinInput`](crate::PinInput) prompt.
+///
+/// Digits fill the cells of the code, and letters too if alphanumeric codes are allowed,
+/// while other characters are ignored. 'Backspace' empties the last filled cell. The
+/// prompt is done as soon as the last cell is filled.
+pub struct PinInputState {
+    code: String,
+    length: usize,
+    alphanumeric: bool,
+    allow_quit: bool,
+}
+
+impl PinInputState {
+    /// Creates the state of a code of `length` characters, at least one.
+    pub fn new(length: usize) -> Self {
+        PinInputState {
+            code: String::new(),
+            length: length.max(1),
+            alphanumeric: false,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether letters are accepted in addition to digits, turned to uppercase.
+    ///
+    /// The default is false.
+    pub fn alphanumeric(mut self, val: bool) -> Self {
+        self.alphanumeric = val;
+        self
+    }
+
+    /// Sets whether 'Esc' cancels the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the code once all cells are filled.
+    pub fn handle_key(&mut self, key: Key) -> Transition<String> {
+        match key {
+            Key::Char(chr) => {
+                if let Some(chr) = self.accept(chr) {
+                    self.code.push(chr);
+                }
+                if self.code.len() == self.length {
+                    return Transition::Done(self.code.clone());
+                }
+            }
+            Key::Backspace => {
+                self.code.pop();
+            }
+            Key::Escape if self.allow_quit => return Transition::Cancelled,
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the characters of the code entered so far.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns the character to add to the code for `chr`, if it is accepted.
+    fn accept(&self, chr: char) -> Option<char> {
+        if chr.is_ascii_digit() {
+            Some(chr)
+        } else if self.alphanumeric && chr.is_ascii_alphabetic() {
+            Some(chr.to_ascii_uppercase())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepted_chars() {
+        let mut state = PinInputState::new(6);
+        assert_eq!(state.accept('4'), Some('4'));
+        assert_eq!(state.accept('a'), None);
+
+        state = state.alphanumeric(true);
+        assert_eq!(state.accept('a'), Some('A'));
+        assert_eq!(state.accept('-'), None);
+    }
+
+    #[test]
+    fn test_pin_input_state() {
+        let mut state = PinInputState::new(3);
+
+        assert_eq!(state.handle_key(Key::Char('1')), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char('x')), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char('2')), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Backspace), Transition::Continue);
+        assert_eq!(state.code(), "1");
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        state.handle_key(Key::Char('2'));
+        assert_eq!(
+            state.handle_key(Key::Char('3')),
+            Transition::Done("123".into())
+        );
+    }
+}
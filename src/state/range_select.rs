@@ -0,0 +1,132 @@
+use console::Key;
+
+use super::Transition;
+
+/// Returns the range spanned by `anchor` and `cursor` with the lower index first.
+fn span(anchor: Option<usize>, cursor: usize) -> (usize, usize) {
+    match anchor {
+        Some(anchor) => (anchor.min(cursor), anchor.max(cursor)),
+        None => (cursor, cursor),
+    }
+}
+
+/// The state of a [`RangeSelect`](crate::RangeSelect) prompt.
+///
+/// The cursor moves over the items with the up and down arrow keys (or `j`, `k` and 'Tab'),
+/// the page keys, 'Home' and 'End', without wrapping around. 'Space' anchors the range at
+/// the cursor or drops the anchor again, and so does 'Esc'. 'Enter' confirms the range
+/// between the anchor and the cursor.
+pub struct RangeSelectState {
+    len: usize,
+    sel: usize,
+    anchor: Option<usize>,
+    page_size: usize,
+    allow_quit: bool,
+}
+
+impl RangeSelectState {
+    /// Creates the state of a list of `len` items, with the cursor on the first one.
+    ///
+    /// `len` must not be zero.
+    pub fn new(len: usize) -> Self {
+        RangeSelectState {
+            len,
+            sel: 0,
+            anchor: None,
+            page_size: len,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the item the cursor starts on, or the last one if there are fewer items.
+    pub fn default(mut self, sel: usize) -> Self {
+        self.sel = sel.min(self.len - 1);
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt while no range is anchored.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the indices of the first and the last item of the range,
+    /// which is only the item under the cursor without an anchor.
+    pub fn handle_key(&mut self, key: Key) -> Transition<(usize, usize)> {
+        let last = self.len - 1;
+        match key {
+            Key::ArrowDown | Key::Tab | Key::Char('j') => self.sel = (self.sel + 1).min(last),
+            Key::ArrowUp | Key::BackTab | Key::Char('k') => self.sel = self.sel.saturating_sub(1),
+            Key::PageDown => self.sel = (self.sel + self.page_size).min(last),
+            Key::PageUp => self.sel = self.sel.saturating_sub(self.page_size),
+            Key::Home => self.sel = 0,
+            Key::End => self.sel = last,
+            Key::Char(' ') => {
+                self.anchor = match self.anchor {
+                    Some(_) => None,
+                    None => Some(self.sel),
+                };
+            }
+            Key::Escape if self.anchor.is_some() => self.anchor = None,
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            Key::Enter => return Transition::Done(self.range()),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the index of the item under the cursor.
+    pub fn selection(&self) -> usize {
+        self.sel
+    }
+
+    /// Returns the index the range is anchored at, if it is.
+    pub fn anchor(&self) -> Option<usize> {
+        self.anchor
+    }
+
+    /// Returns the indices of the first and the last item between the anchor and the
+    /// cursor.
+    pub fn range(&self) -> (usize, usize) {
+        span(self.anchor, self.sel)
+    }
+
+    /// Sets the number of items on a page, which the page keys move by.
+    ///
+    /// By default all items are on one page.
+    pub fn set_page_size(&mut self, page_size: usize) {
+        self.page_size = page_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_orders_bounds() {
+        assert_eq!(span(None, 3), (3, 3));
+        assert_eq!(span(Some(1), 4), (1, 4));
+        assert_eq!(span(Some(4), 1), (1, 4));
+    }
+
+    #[test]
+    fn test_range_select_state() {
+        let mut state = RangeSelectState::new(10).default(4).allow_quit(true);
+        state.set_page_size(3);
+
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Continue);
+        state.handle_key(Key::PageUp);
+        state.handle_key(Key::ArrowUp);
+        assert_eq!(state.range(), (0, 4));
+        assert_eq!(state.handle_key(Key::Escape), Transition::Continue);
+        assert_eq!(state.anchor(), None);
+        assert_eq!(state.handle_key(Key::Char('x')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done((0, 0)));
+        assert_eq!(state.handle_key(Key::Escape), Transition::Cancelled);
+    }
+}
@@ -0,0 +1,110 @@
+use console::Key;
+
+use super::Transition;
+use crate::prompts::review_each::ReviewDecision;
+
+/// The state of the decision for one item of a [`ReviewEach`](crate::ReviewEach) prompt.
+///
+/// `y` accepts and `n` rejects the item. Optionally `a` and `d` accept or reject the item
+/// together with all remaining ones, `e` marks it for editing and `q` or 'Esc' quit the
+/// review.
+#[derive(Default)]
+pub struct ReviewEachState {
+    remaining: Option<ReviewDecision>,
+    allow_all: bool,
+    allow_edit: bool,
+    allow_quit: bool,
+}
+
+impl ReviewEachState {
+    /// Creates the state of a decision with only `y` and `n`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether `a` and `d` decide all remaining items at once.
+    ///
+    /// The default is false.
+    pub fn allow_all(mut self, val: bool) -> Self {
+        self.allow_all = val;
+        self
+    }
+
+    /// Sets whether `e` marks the item for editing.
+    ///
+    /// The default is false.
+    pub fn allow_edit(mut self, val: bool) -> Self {
+        self.allow_edit = val;
+        self
+    }
+
+    /// Sets whether `q` and 'Esc' cancel the review.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the decision for the item. Deciding all remaining items too
+    /// is told by [`remaining`](Self::remaining).
+    pub fn handle_key(&mut self, key: Key) -> Transition<ReviewDecision> {
+        let (decision, remaining) = match key {
+            Key::Char('y') => (ReviewDecision::Yes, None),
+            Key::Char('n') => (ReviewDecision::No, None),
+            Key::Char('a') if self.allow_all => (ReviewDecision::Yes, Some(ReviewDecision::Yes)),
+            Key::Char('d') if self.allow_all => (ReviewDecision::No, Some(ReviewDecision::No)),
+            Key::Char('e') if self.allow_edit => (ReviewDecision::Edit, None),
+            Key::Char('q') | Key::Escape if self.allow_quit => return Transition::Cancelled,
+            _ => return Transition::Rejected,
+        };
+        self.remaining = remaining;
+        Transition::Done(decision)
+    }
+
+    /// Returns the decision taken for all remaining items along with this one, if any.
+    pub fn remaining(&self) -> Option<ReviewDecision> {
+        self.remaining
+    }
+
+    /// Returns the keys deciding the item, in the order they are offered.
+    pub fn keys(&self) -> Vec<&'static str> {
+        vec![
+            (true, "y"),
+            (true, "n"),
+            (self.allow_all, "a"),
+            (self.allow_all, "d"),
+            (self.allow_quit, "q"),
+            (self.allow_edit, "e"),
+        ]
+        .into_iter()
+        .filter(|&(enabled, _)| enabled)
+        .map(|(_, key)| key)
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_each_state() {
+        let mut state = ReviewEachState::new().allow_all(true);
+        assert_eq!(state.keys(), ["y", "n", "a", "d"]);
+
+        assert_eq!(state.handle_key(Key::Char('e')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char('q')), Transition::Rejected);
+        assert_eq!(
+            state.handle_key(Key::Char('d')),
+            Transition::Done(ReviewDecision::No)
+        );
+        assert_eq!(state.remaining(), Some(ReviewDecision::No));
+
+        let mut state = ReviewEachState::new().allow_quit(true);
+        assert_eq!(state.handle_key(Key::Char('a')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Cancelled);
+    }
+}
@@ -0,0 +1,235 @@
+use console::Key;
+
+use super::{quit, ListCursor, Transition};
+
+/// The state of a [`Select`](crate::Select) prompt.
+///
+/// The cursor starts on no item unless a default is given, and moves with the arrow keys,
+/// `j`/`k`, `Tab`, `Home`, `End` and the page keys. 'Enter' or 'Space' picks the item
+/// under the cursor unless it's disabled.
+pub struct SelectState {
+    cursor: ListCursor,
+    disabled: Vec<bool>,
+    compact: bool,
+    allow_quit: bool,
+}
+
+impl SelectState {
+    /// Creates the state of a list of `len` items.
+    pub fn new(len: usize) -> Self {
+        SelectState {
+            cursor: ListCursor::new(len),
+            disabled: vec![],
+            compact: false,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the item the cursor starts on.
+    pub fn default(mut self, sel: Option<usize>) -> Self {
+        self.set_selection(sel);
+        self
+    }
+
+    /// Sets which items can't be picked, by index. Items past the end can be picked.
+    pub fn disabled(mut self, disabled: Vec<bool>) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Sets whether typing the number of an item moves the cursor to it.
+    ///
+    /// The default is false.
+    pub fn numbered(mut self, val: bool) -> Self {
+        self.cursor.numbered = val;
+        self
+    }
+
+    /// Sets whether the left and right arrow keys scroll wide items horizontally rather
+    /// than switching pages.
+    ///
+    /// The default is false.
+    pub fn horizontal_scroll(mut self, val: bool) -> Self {
+        self.cursor.horizontal_scroll = val;
+        self
+    }
+
+    /// Sets whether the items are cycled through on a single line, with the left and right
+    /// arrow keys (or `h` and `l`) moving the cursor like the up and down ones. The other
+    /// navigation keys are rejected.
+    ///
+    /// The default is false.
+    pub fn compact(mut self, val: bool) -> Self {
+        self.compact = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    pub fn handle_key(&mut self, key: Key) -> Transition<usize> {
+        let len = self.cursor.len;
+        if self.compact && len > 0 {
+            let sel = self.cursor.sel.unwrap_or(0);
+            match key {
+                Key::ArrowDown | Key::ArrowRight | Key::Tab | Key::Char('j') | Key::Char('l') => {
+                    self.cursor.sel = Some((sel + 1) % len);
+                    return Transition::Continue;
+                }
+                Key::ArrowUp | Key::ArrowLeft | Key::BackTab | Key::Char('k') | Key::Char('h') => {
+                    self.cursor.sel = Some((sel + len - 1) % len);
+                    return Transition::Continue;
+                }
+                _ => {}
+            }
+        } else if self.cursor.navigate(&key) {
+            return Transition::Continue;
+        }
+
+        match key {
+            Key::Escape | Key::Char('q') => quit(self.allow_quit),
+            Key::Enter | Key::Char(' ') => match self.cursor.sel {
+                Some(sel) if !self.is_disabled(sel) => Transition::Done(sel),
+                _ => Transition::Rejected,
+            },
+            _ => Transition::Rejected,
+        }
+    }
+
+    /// Returns the item under the cursor, if any.
+    pub fn selection(&self) -> Option<usize> {
+        self.cursor.sel
+    }
+
+    /// Moves the cursor to `sel`, clamped to the items.
+    pub fn set_selection(&mut self, sel: Option<usize>) {
+        self.cursor.sel = sel.map(|sel| sel.min(self.cursor.len.saturating_sub(1)));
+    }
+
+    /// Updates the number of items, like after more were loaded.
+    pub fn set_len(&mut self, len: usize) {
+        self.cursor.len = len;
+        let sel = self.cursor.sel;
+        self.set_selection(sel);
+    }
+
+    /// Sets the number of items on a page, which the page keys move by, and whether the
+    /// list is paged so the left and right arrows switch pages.
+    ///
+    /// By default all items are on one page.
+    pub fn set_page(&mut self, page_size: usize, paged: bool) {
+        self.cursor.page_size = page_size;
+        self.cursor.paged = paged;
+    }
+
+    /// Returns the digits typed to jump to an item, if any.
+    pub fn jump_input(&self) -> Option<&str> {
+        self.cursor.jump.digits()
+    }
+
+    /// Returns the columns the items are scrolled to the left.
+    pub fn horizontal_offset(&self) -> usize {
+        self.cursor.horizontal_offset
+    }
+
+    /// Sets the columns the items are scrolled to the left, like after limiting them to
+    /// how far the active item overflows the terminal.
+    pub fn set_horizontal_offset(&mut self, offset: usize) {
+        self.cursor.horizontal_offset = offset;
+    }
+
+    fn is_disabled(&self, idx: usize) -> bool {
+        self.disabled.get(idx).copied().unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::HORIZONTAL_SCROLL_STEP;
+
+    #[test]
+    fn test_select_state() {
+        let mut state = SelectState::new(5)
+            .disabled(vec![false, true])
+            .allow_quit(true);
+        assert_eq!(state.selection(), None);
+
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::ArrowUp), Transition::Continue);
+        assert_eq!(state.selection(), Some(4));
+        assert_eq!(state.handle_key(Key::Home), Transition::Continue);
+        assert_eq!(state.handle_key(Key::ArrowDown), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char('x')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Cancelled);
+        assert_eq!(state.handle_key(Key::ArrowDown), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(2));
+    }
+
+    #[test]
+    fn test_select_state_pages() {
+        let mut state = SelectState::new(10).default(Some(1));
+        state.set_page(4, true);
+
+        state.handle_key(Key::ArrowRight);
+        assert_eq!(state.selection(), Some(4));
+        state.handle_key(Key::PageDown);
+        assert_eq!(state.selection(), Some(8));
+        state.handle_key(Key::ArrowRight);
+        assert_eq!(state.selection(), Some(0));
+        state.handle_key(Key::ArrowLeft);
+        assert_eq!(state.selection(), Some(8));
+
+        state.set_len(3);
+        assert_eq!(state.selection(), Some(2));
+
+        // Without pages and quitting, the keys for them are ignored rather than rejected.
+        let mut state = SelectState::new(3).default(Some(1));
+        assert_eq!(state.handle_key(Key::Char('l')), Transition::Continue);
+        assert_eq!(state.handle_key(Key::ArrowLeft), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char('q')), Transition::Continue);
+        assert_eq!(state.selection(), Some(1));
+    }
+
+    #[test]
+    fn test_select_state_compact() {
+        let mut state = SelectState::new(3).default(Some(0)).compact(true);
+
+        assert_eq!(state.handle_key(Key::ArrowLeft), Transition::Continue);
+        assert_eq!(state.selection(), Some(2));
+        assert_eq!(state.handle_key(Key::Char('l')), Transition::Continue);
+        assert_eq!(state.selection(), Some(0));
+        assert_eq!(state.handle_key(Key::End), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Done(0));
+    }
+
+    #[test]
+    fn test_select_state_jump() {
+        let mut state = SelectState::new(20).numbered(true);
+
+        assert_eq!(state.handle_key(Key::Char('1')), Transition::Continue);
+        assert_eq!(state.jump_input(), Some("1"));
+        assert_eq!(state.handle_key(Key::Char('2')), Transition::Continue);
+        assert_eq!(state.jump_input(), None);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(11));
+    }
+
+    #[test]
+    fn test_select_state_horizontal_scroll() {
+        let mut state = SelectState::new(2).default(Some(0)).horizontal_scroll(true);
+
+        state.handle_key(Key::ArrowRight);
+        state.handle_key(Key::ArrowRight);
+        assert_eq!(state.horizontal_offset(), 2 * HORIZONTAL_SCROLL_STEP);
+        state.handle_key(Key::ArrowDown);
+        assert_eq!(state.horizontal_offset(), 0);
+    }
+}
@@ -0,0 +1,203 @@
+use console::Key;
+
+use super::Transition;
+use crate::prompts::number_input::Numeric;
+
+/// Returns the relative position of `value` between `min` and `max` in the range `0.0..=1.0`.
+fn ratio<T: Numeric>(value: T, min: T, max: T) -> f64 {
+    let span = max.as_f64() - min.as_f64();
+
+    if span <= 0.0 {
+        1.0
+    } else {
+        ((value.as_f64() - min.as_f64()) / span).clamp(0.0, 1.0)
+    }
+}
+
+/// Returns the number of decimal places `value` is displayed with.
+fn decimal_places<T: Numeric>(value: T) -> usize {
+    let text = value.to_string();
+    text.find('.').map_or(0, |dot| text.len() - dot - 1)
+}
+
+/// Rounds `value` to `places` decimal places, so repeated float steps don't accumulate
+/// noise like `0.30000000000000004`.
+fn round<T: Numeric>(value: T, places: usize) -> T {
+    if places == 0 {
+        return value;
+    }
+    format!("{:.*}", places, value.as_f64())
+        .parse()
+        .unwrap_or(value)
+}
+
+/// The state of a [`Slider`](crate::Slider) prompt.
+///
+/// The left and right arrow keys (or `h` and `l`) move the value by a step, and with
+/// 'Shift', 'PageUp' and 'PageDown' (or `H` and `L`) by a big step. 'Home' and 'End' jump
+/// to the bounds, and 'Enter' confirms the value.
+pub struct SliderState<T> {
+    value: T,
+    start: T,
+    min: T,
+    max: T,
+    step: T,
+    big_step: Option<T>,
+    allow_quit: bool,
+}
+
+impl<T: Numeric> SliderState<T> {
+    /// Creates the state of a slider between `min` and `max`, starting at `min`.
+    ///
+    /// `min` must not be greater than `max`.
+    pub fn new(min: T, max: T) -> Self {
+        SliderState {
+            value: min,
+            start: min,
+            min,
+            max,
+            step: T::one(),
+            big_step: None,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the value the slider starts at, moved into the bounds.
+    pub fn default(mut self, value: T) -> Self {
+        self.start = value;
+        self.value = self.clamp(value);
+        self
+    }
+
+    /// Sets the amount the value moves by with the arrow keys.
+    ///
+    /// The default is one.
+    pub fn step(mut self, step: T) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Sets the amount the value moves by with 'Shift' and the arrow keys, and the page keys.
+    ///
+    /// The default is ten steps.
+    pub fn big_step(mut self, big_step: T) -> Self {
+        self.big_step = Some(big_step);
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the value. Steps beyond the bounds are rejected.
+    pub fn handle_key(&mut self, key: Key) -> Transition<T> {
+        let (key, big) = match crate::escape::shifted_arrow(&key) {
+            Some(arrow) => (arrow, true),
+            None => (key, false),
+        };
+        let big_step = self.big_step_or_default();
+        let by = if big { big_step } else { self.step };
+
+        match key {
+            Key::ArrowLeft | Key::ArrowDown | Key::Char('h') if self.value > self.min => {
+                self.move_by(false, by);
+            }
+            Key::ArrowRight | Key::ArrowUp | Key::Char('l') if self.value < self.max => {
+                self.move_by(true, by);
+            }
+            Key::PageDown | Key::Char('H') if self.value > self.min => {
+                self.move_by(false, big_step);
+            }
+            Key::PageUp | Key::Char('L') if self.value < self.max => {
+                self.move_by(true, big_step);
+            }
+            Key::Home => self.value = self.min,
+            Key::End => self.value = self.max,
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            Key::Enter => return Transition::Done(self.value),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the value.
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Returns the relative position of the value between the bounds, in the range
+    /// `0.0..=1.0`.
+    pub fn ratio(&self) -> f64 {
+        ratio(self.value, self.min, self.max)
+    }
+
+    fn clamp(&self, value: T) -> T {
+        if value < self.min {
+            self.min
+        } else if value > self.max {
+            self.max
+        } else {
+            value
+        }
+    }
+
+    // Float steps are rounded to the places of the steps, or more if the start has them.
+    fn places(&self) -> usize {
+        [Some(self.step), self.big_step, Some(self.start)]
+            .iter()
+            .flatten()
+            .map(|&val| decimal_places(val))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn big_step_or_default(&self) -> T {
+        self.big_step.unwrap_or_else(|| {
+            let steps = (0..9).fold(self.step, |acc, _| acc.step_up(self.step));
+            round(steps, self.places())
+        })
+    }
+
+    fn move_by(&mut self, up: bool, by: T) {
+        let moved = if up {
+            self.value.step_up(by)
+        } else {
+            self.value.step_down(by)
+        };
+        self.value = self.clamp(round(moved, self.places()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio() {
+        assert_eq!(5u8.as_f64(), 5.0);
+        assert_eq!((-1.5f32).as_f64(), -1.5);
+        assert_eq!(ratio(25, 0, 100), 0.25);
+        assert_eq!(ratio(0.5, 0.0, 1.0), 0.5);
+        assert_eq!(ratio(3, 3, 3), 1.0);
+    }
+
+    #[test]
+    fn test_slider_state() {
+        let mut state = SliderState::new(0.0, 1.0).step(0.1).default(0.9);
+
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Continue);
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Rejected);
+        assert_eq!(state.value(), 1.0);
+        assert_eq!(state.handle_key(Key::PageDown), Transition::Continue);
+        assert_eq!(state.ratio(), 0.0);
+        assert_eq!(state.handle_key(Key::Char('q')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::End), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(1.0));
+    }
+}
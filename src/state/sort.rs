@@ -0,0 +1,178 @@
+use console::Key;
+
+use super::{quit, ListCursor, Transition};
+
+/// Moves the item at `from` in `order` to `to`, shifting the items in between.
+fn move_item(order: &mut [usize], from: usize, to: usize) {
+    if from < to {
+        order[from..=to].rotate_left(1);
+    } else {
+        order[to..=from].rotate_right(1);
+    }
+}
+
+/// The state of a [`Sort`](crate::Sort) prompt.
+///
+/// The cursor starts on the first item and moves like in a
+/// [`SelectState`](super::SelectState). 'Space' picks up the item under the cursor or
+/// puts it down again, and a picked item is carried along by the cursor. 'Enter' confirms
+/// the order.
+pub struct SortState {
+    cursor: ListCursor,
+    order: Vec<usize>,
+    picked: bool,
+    allow_quit: bool,
+}
+
+impl SortState {
+    /// Creates the state of a list of `len` items in their original order.
+    pub fn new(len: usize) -> Self {
+        let mut cursor = ListCursor::new(len);
+        cursor.sel = Some(0);
+        SortState {
+            cursor,
+            order: (0..len).collect(),
+            picked: false,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the indices of the items in their new order.
+    pub fn handle_key(&mut self, key: Key) -> Transition<Vec<usize>> {
+        let from = self.cursor.sel;
+        if self.cursor.navigate(&key) {
+            if let (true, Some(from), Some(to)) = (self.picked, from, self.cursor.sel) {
+                match key {
+                    Key::ArrowDown
+                    | Key::Tab
+                    | Key::Char('j')
+                    | Key::ArrowRight
+                    | Key::Char('l') => self.carry(from, to, true),
+                    Key::ArrowUp
+                    | Key::BackTab
+                    | Key::Char('k')
+                    | Key::ArrowLeft
+                    | Key::Char('h') => self.carry(from, to, false),
+                    _ => move_item(&mut self.order, from, to),
+                }
+            }
+            return Transition::Continue;
+        }
+
+        match key {
+            Key::Char(' ') => {
+                self.picked = !self.picked;
+                Transition::Continue
+            }
+            Key::Escape | Key::Char('q') => quit(self.allow_quit),
+            Key::Enter => Transition::Done(self.order.clone()),
+            _ => Transition::Rejected,
+        }
+    }
+
+    /// Carries the picked item from `from` to `to` item by item, downwards or upwards and
+    /// wrapping around the ends of the list like the arrow keys do.
+    fn carry(&mut self, mut from: usize, to: usize, down: bool) {
+        let len = self.order.len();
+        while from != to {
+            let next = if down {
+                (from + 1) % len
+            } else {
+                (from + len - 1) % len
+            };
+            self.order.swap(from, next);
+            from = next;
+        }
+    }
+
+    /// Returns the position of the cursor.
+    pub fn selection(&self) -> Option<usize> {
+        self.cursor.sel
+    }
+
+    /// Returns the indices of the items in their current order.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Returns whether the item under the cursor is picked up.
+    pub fn picked(&self) -> bool {
+        self.picked
+    }
+
+    /// Sets the number of items on a page, which the page keys move by, and whether the
+    /// list is paged so the left and right arrows switch pages.
+    ///
+    /// By default all items are on one page.
+    pub fn set_page(&mut self, page_size: usize, paged: bool) {
+        self.cursor.page_size = page_size;
+        self.cursor.paged = paged;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_item() {
+        let mut order = vec![0, 1, 2, 3, 4];
+        move_item(&mut order, 1, 3);
+        assert_eq!(order, [0, 2, 3, 1, 4]);
+        move_item(&mut order, 4, 0);
+        assert_eq!(order, [4, 0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_sort_state() {
+        let mut state = SortState::new(4);
+
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Continue);
+        assert!(state.picked());
+        state.handle_key(Key::ArrowDown);
+        state.handle_key(Key::ArrowDown);
+        assert_eq!(state.order(), [1, 2, 0, 3]);
+        state.handle_key(Key::Char(' '));
+        state.handle_key(Key::ArrowUp);
+        assert_eq!(state.selection(), Some(1));
+        assert_eq!(state.handle_key(Key::Escape), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char('x')), Transition::Rejected);
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Transition::Done(vec![1, 2, 0, 3])
+        );
+    }
+
+    #[test]
+    fn test_sort_state_carry_around() {
+        // Carrying an item up past the first one wraps it around to the end.
+        let mut state = SortState::new(3).allow_quit(true);
+        state.handle_key(Key::Char(' '));
+        state.handle_key(Key::ArrowUp);
+        assert_eq!(state.order(), [2, 1, 0]);
+
+        // The page keys move the item without wrapping.
+        state.handle_key(Key::Home);
+        assert_eq!(state.order(), [0, 2, 1]);
+
+        // Switching to the previous page from the first one carries it around.
+        let mut state = SortState::new(5);
+        state.set_page(2, true);
+        state.handle_key(Key::ArrowDown);
+        state.handle_key(Key::Char(' '));
+        state.handle_key(Key::ArrowLeft);
+        assert_eq!(state.selection(), Some(4));
+        assert_eq!(state.order(), [4, 0, 2, 3, 1]);
+        assert_eq!(state.handle_key(Key::Char('q')), Transition::Continue);
+    }
+}
@@ -0,0 +1,102 @@
+use console::Key;
+
+use super::{ListCursor, Transition};
+
+/// The state of a [`TableSelect`](crate::TableSelect) prompt.
+///
+/// The cursor moves over the rows with the up and down arrow keys (or `j`, `k` and 'Tab'),
+/// and the left and right arrow keys (or `h` and `l`) switch pages if the table is paged.
+/// 'Enter' or 'Space' pick the row under the cursor.
+pub struct TableSelectState {
+    cursor: ListCursor,
+    allow_quit: bool,
+}
+
+impl TableSelectState {
+    /// Creates the state of a table of `len` rows, without a cursor.
+    pub fn new(len: usize) -> Self {
+        TableSelectState {
+            cursor: ListCursor::new(len),
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the row the cursor starts on, if any.
+    pub fn default(mut self, sel: Option<usize>) -> Self {
+        self.cursor.sel = sel.filter(|&sel| sel < self.cursor.len);
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the index of the picked row.
+    pub fn handle_key(&mut self, key: Key) -> Transition<usize> {
+        match key {
+            Key::ArrowDown
+            | Key::Tab
+            | Key::Char('j')
+            | Key::ArrowUp
+            | Key::BackTab
+            | Key::Char('k') => {
+                self.cursor.navigate(&key);
+                Transition::Continue
+            }
+            Key::ArrowLeft | Key::Char('h') | Key::ArrowRight | Key::Char('l')
+                if self.cursor.paged =>
+            {
+                self.cursor.navigate(&key);
+                Transition::Continue
+            }
+            Key::Escape | Key::Char('q') if self.allow_quit => Transition::Cancelled,
+            Key::Enter | Key::Char(' ') => match self.cursor.sel {
+                Some(sel) => Transition::Done(sel),
+                None => Transition::Rejected,
+            },
+            _ => Transition::Rejected,
+        }
+    }
+
+    /// Returns the index of the row under the cursor, if any.
+    pub fn selection(&self) -> Option<usize> {
+        self.cursor.sel
+    }
+
+    /// Sets the number of rows on a page and whether the table is paged so the left and
+    /// right arrows switch pages.
+    ///
+    /// By default all rows are on one page.
+    pub fn set_page(&mut self, page_size: usize, paged: bool) {
+        self.cursor.page_size = page_size;
+        self.cursor.paged = paged;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_select_state() {
+        let mut state = TableSelectState::new(5).default(Some(7));
+        assert_eq!(state.selection(), None);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Rejected);
+
+        state.set_page(2, true);
+        assert_eq!(state.handle_key(Key::ArrowUp), Transition::Continue);
+        assert_eq!(state.selection(), Some(4));
+        assert_eq!(state.handle_key(Key::Char('l')), Transition::Continue);
+        assert_eq!(state.selection(), Some(0));
+        assert_eq!(state.handle_key(Key::Home), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char('q')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Done(0));
+    }
+}
@@ -0,0 +1,160 @@
+use console::Key;
+
+use super::Transition;
+
+/// The state of a [`TagInput`](crate::TagInput) prompt.
+///
+/// Printable characters are typed into the input, which 'Enter' or `,` add as a tag.
+/// 'Backspace' removes the last character of the input, or the last tag when the input
+/// is empty. 'Tab' takes the suggestion for the input, and 'Enter' on an empty input
+/// confirms the tags.
+pub struct TagInputState {
+    tags: Vec<String>,
+    input: String,
+    suggestions: Vec<String>,
+    duplicate: Option<String>,
+    allow_quit: bool,
+}
+
+impl TagInputState {
+    /// Creates the state of a prompt starting with `tags`.
+    pub fn new(tags: Vec<String>) -> Self {
+        TagInputState {
+            tags,
+            input: String::new(),
+            suggestions: vec![],
+            duplicate: None,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the tags suggested while typing.
+    pub fn suggestions(mut self, suggestions: Vec<String>) -> Self {
+        self.suggestions = suggestions;
+        self
+    }
+
+    /// Sets whether 'Esc' cancels the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the tags. Adding a tag twice is rejected, see
+    /// [`duplicate`](Self::duplicate).
+    pub fn handle_key(&mut self, key: Key) -> Transition<Vec<String>> {
+        self.duplicate = None;
+
+        match key {
+            Key::Char(',') | Key::Enter if !self.input.trim().is_empty() => {
+                let tag = self.input.trim().to_string();
+                self.input.clear();
+
+                if self.tags.contains(&tag) {
+                    self.duplicate = Some(tag);
+                    return Transition::Rejected;
+                }
+                self.tags.push(tag);
+            }
+            Key::Enter => return Transition::Done(self.tags.clone()),
+            Key::Tab => {
+                if let Some(suggestion) = self.suggestion() {
+                    self.input = suggestion.to_string();
+                }
+            }
+            Key::Backspace if self.input.is_empty() => {
+                self.tags.pop();
+            }
+            Key::Backspace => {
+                self.input.pop();
+            }
+            Key::Escape if self.allow_quit => return Transition::Cancelled,
+            Key::Char(chr) if !chr.is_ascii_control() => self.input.push(chr),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the tags added.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns the text typed for the next tag.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns the first suggestion starting with the input which is not a tag yet.
+    pub fn suggestion(&self) -> Option<&str> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        let input = self.input.to_lowercase();
+        self.suggestions
+            .iter()
+            .find(|suggestion| {
+                suggestion.to_lowercase().starts_with(&input) && !self.tags.contains(suggestion)
+            })
+            .map(String::as_str)
+    }
+
+    /// Returns the tag the last key tried to add again, if it did.
+    pub fn duplicate(&self) -> Option<&str> {
+        self.duplicate.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(state: &mut TagInputState, text: &str) {
+        for chr in text.chars() {
+            state.handle_key(Key::Char(chr));
+        }
+    }
+
+    #[test]
+    fn test_suggestion_skips_existing_tags() {
+        let suggestions = vec!["Rust".into(), "ruby".into(), "python".into()];
+        let mut state = TagInputState::new(vec![]).suggestions(suggestions.clone());
+        assert_eq!(state.suggestion(), None);
+        typed(&mut state, "ru");
+        assert_eq!(state.suggestion(), Some("Rust"));
+
+        let mut state = TagInputState::new(vec!["Rust".into()]).suggestions(suggestions);
+        typed(&mut state, "ru");
+        assert_eq!(state.suggestion(), Some("ruby"));
+        typed(&mut state, "go");
+        assert_eq!(state.suggestion(), None);
+    }
+
+    #[test]
+    fn test_tag_input_state() {
+        let mut state = TagInputState::new(vec!["a".into()]).suggestions(vec!["bug".into()]);
+
+        typed(&mut state, "b");
+        assert_eq!(state.handle_key(Key::Tab), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char(',')), Transition::Continue);
+        assert_eq!(state.tags(), ["a", "bug"]);
+
+        typed(&mut state, " a ");
+        assert_eq!(state.handle_key(Key::Enter), Transition::Rejected);
+        assert_eq!(state.duplicate(), Some("a"));
+        assert_eq!(state.input(), "");
+
+        assert_eq!(state.handle_key(Key::Backspace), Transition::Continue);
+        assert_eq!(state.duplicate(), None);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Transition::Done(vec!["a".into()])
+        );
+    }
+}
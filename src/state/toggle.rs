@@ -0,0 +1,70 @@
+use console::Key;
+
+use super::Transition;
+
+/// The state of a [`Toggle`](crate::Toggle) prompt.
+///
+/// 'Left' or `h` picks the disabled choice, 'Right' or `l` the enabled one, and 'Tab' or
+/// 'Space' switch between them. 'Enter' confirms the choice.
+pub struct ToggleState {
+    value: bool,
+    allow_quit: bool,
+}
+
+impl ToggleState {
+    /// Creates the state of a toggle starting on the choice of `value`.
+    pub fn new(value: bool) -> Self {
+        ToggleState {
+            value,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with `true` for the enabled choice.
+    pub fn handle_key(&mut self, key: Key) -> Transition<bool> {
+        match key {
+            Key::ArrowLeft | Key::Char('h') => self.value = false,
+            Key::ArrowRight | Key::Char('l') => self.value = true,
+            Key::Tab | Key::BackTab | Key::Char(' ') => self.value = !self.value,
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            Key::Enter => return Transition::Done(self.value),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the choice picked, `true` for the enabled one.
+    pub fn value(&self) -> bool {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_state() {
+        let mut state = ToggleState::new(false);
+
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Continue);
+        assert!(state.value());
+        assert_eq!(state.handle_key(Key::Char('h')), Transition::Continue);
+        assert!(!state.value());
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(false));
+
+        let mut state = ToggleState::new(true).allow_quit(true);
+        assert_eq!(state.handle_key(Key::Char('q')), Transition::Cancelled);
+    }
+}
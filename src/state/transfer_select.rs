@@ -0,0 +1,182 @@
+use console::Key;
+
+use super::Transition;
+
+/// One of the two lists of a [`TransferSelectState`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Pane {
+    Available,
+    Chosen,
+}
+
+/// Moves the item at `sel` from the `from` list to the end of the `to` list.
+///
+/// Returns the selection to use in `from` afterwards.
+fn transfer(from: &mut Vec<usize>, to: &mut Vec<usize>, sel: usize) -> usize {
+    if sel < from.len() {
+        to.push(from.remove(sel));
+    }
+
+    sel.min(from.len().saturating_sub(1))
+}
+
+/// The state of a [`TransferSelect`](crate::TransferSelect) prompt.
+///
+/// The items are split into an available and a chosen pane, each with its own cursor
+/// moved by the up and down arrow keys (or `j` and `k`). 'Tab' switches the focused pane,
+/// 'Left' (or `h`) focuses the available and 'Right' (or `l`) the chosen one. 'Space' or
+/// 'Enter' move the item under the cursor to the other pane, and `c` confirms the chosen
+/// items.
+pub struct TransferSelectState {
+    available: Vec<usize>,
+    chosen: Vec<usize>,
+    focus: Pane,
+    available_sel: usize,
+    chosen_sel: usize,
+    allow_quit: bool,
+}
+
+impl TransferSelectState {
+    /// Creates the state of `len` items, all of them available.
+    pub fn new(len: usize) -> Self {
+        TransferSelectState {
+            available: (0..len).collect(),
+            chosen: vec![],
+            focus: Pane::Available,
+            available_sel: 0,
+            chosen_sel: 0,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets the items chosen initially, in this order.
+    pub fn defaults(mut self, defaults: &[usize]) -> Self {
+        let len = self.available.len() + self.chosen.len();
+        self.chosen.clear();
+        for &idx in defaults {
+            if idx < len && !self.chosen.contains(&idx) {
+                self.chosen.push(idx);
+            }
+        }
+        self.available = (0..len).filter(|idx| !self.chosen.contains(idx)).collect();
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the indices of the chosen items, in the order they were
+    /// chosen.
+    pub fn handle_key(&mut self, key: Key) -> Transition<Vec<usize>> {
+        let (list, sel) = match self.focus {
+            Pane::Available => (&self.available, &mut self.available_sel),
+            Pane::Chosen => (&self.chosen, &mut self.chosen_sel),
+        };
+
+        match key {
+            Key::ArrowDown | Key::Char('j') if !list.is_empty() => {
+                *sel = (*sel + 1) % list.len();
+            }
+            Key::ArrowUp | Key::Char('k') if !list.is_empty() => {
+                *sel = (*sel + list.len() - 1) % list.len();
+            }
+            Key::Tab | Key::BackTab => {
+                self.focus = match self.focus {
+                    Pane::Available => Pane::Chosen,
+                    Pane::Chosen => Pane::Available,
+                };
+            }
+            Key::ArrowLeft | Key::Char('h') => self.focus = Pane::Available,
+            Key::ArrowRight | Key::Char('l') => self.focus = Pane::Chosen,
+            Key::Char(' ') | Key::Enter => match self.focus {
+                Pane::Available => {
+                    self.available_sel =
+                        transfer(&mut self.available, &mut self.chosen, self.available_sel);
+                }
+                Pane::Chosen => {
+                    let item = self.chosen.get(self.chosen_sel).copied();
+                    self.chosen_sel =
+                        transfer(&mut self.chosen, &mut self.available, self.chosen_sel);
+                    // Keep the available pane in the original item order.
+                    self.available.sort_unstable();
+                    if let Some(item) = item {
+                        self.available_sel =
+                            self.available.iter().position(|&i| i == item).unwrap_or(0);
+                    }
+                }
+            },
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            Key::Char('c') => return Transition::Done(self.chosen.clone()),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the indices of the available items, in their original order.
+    pub fn available(&self) -> &[usize] {
+        &self.available
+    }
+
+    /// Returns the indices of the chosen items, in the order they were chosen.
+    pub fn chosen(&self) -> &[usize] {
+        &self.chosen
+    }
+
+    /// Returns the position of the cursor in the available pane.
+    pub fn available_selection(&self) -> usize {
+        self.available_sel
+    }
+
+    /// Returns the position of the cursor in the chosen pane.
+    pub fn chosen_selection(&self) -> usize {
+        self.chosen_sel
+    }
+
+    /// Returns whether the chosen pane is focused rather than the available one.
+    pub fn chosen_focused(&self) -> bool {
+        self.focus == Pane::Chosen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_appends_and_clamps_selection() {
+        let mut from = vec![0, 1, 2];
+        let mut to = vec![];
+
+        assert_eq!(transfer(&mut from, &mut to, 2), 1);
+        assert_eq!(transfer(&mut from, &mut to, 0), 0);
+        assert_eq!((&from[..], &to[..]), (&[1][..], &[2, 0][..]));
+
+        assert_eq!(transfer(&mut from, &mut to, 0), 0);
+        assert_eq!(transfer(&mut from, &mut to, 0), 0);
+        assert_eq!(to, [2, 0, 1]);
+    }
+
+    #[test]
+    fn test_transfer_select_state() {
+        let mut state = TransferSelectState::new(4).defaults(&[2, 2, 9]);
+        assert_eq!(state.available(), [0, 1, 3]);
+
+        state.handle_key(Key::ArrowUp);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Continue);
+        assert_eq!(state.chosen(), [2, 3]);
+        assert_eq!(state.handle_key(Key::Char('l')), Transition::Continue);
+        assert!(state.chosen_focused());
+        state.handle_key(Key::Char(' '));
+        assert_eq!(state.available(), [0, 1, 2]);
+        assert_eq!(state.available_selection(), 2);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Char('c')), Transition::Done(vec![3]));
+    }
+}
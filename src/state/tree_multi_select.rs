@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+
+use console::Key;
+
+use super::{tree_select::TreeCursor, Transition};
+use crate::{CheckState, TreeNode};
+
+/// Collects the paths of the leaves below `node` at `path`, or `path` itself for a leaf.
+fn leaf_paths(node: &TreeNode, path: &[usize]) -> Vec<Vec<usize>> {
+    fn walk(node: &TreeNode, prefix: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+        if node.is_leaf() {
+            out.push(prefix.clone());
+        }
+        for (idx, child) in node.children.iter().enumerate() {
+            prefix.push(idx);
+            walk(child, prefix, out);
+            prefix.pop();
+        }
+    }
+
+    let mut out = vec![];
+    walk(node, &mut path.to_vec(), &mut out);
+    out
+}
+
+/// Collects the paths of all leaves which start out checked.
+fn initially_checked(roots: &[TreeNode]) -> HashSet<Vec<usize>> {
+    fn walk(nodes: &[TreeNode], prefix: &mut Vec<usize>, out: &mut HashSet<Vec<usize>>) {
+        for (idx, node) in nodes.iter().enumerate() {
+            prefix.push(idx);
+            if node.checked {
+                out.extend(leaf_paths(node, prefix));
+            } else {
+                walk(&node.children, prefix, out);
+            }
+            prefix.pop();
+        }
+    }
+
+    let mut checked = HashSet::new();
+    walk(roots, &mut vec![], &mut checked);
+    checked
+}
+
+/// Derives the check mark of a node from the checked state of its leaves.
+fn check_state(leaves: &[Vec<usize>], checked: &HashSet<Vec<usize>>) -> CheckState {
+    let count = leaves.iter().filter(|leaf| checked.contains(*leaf)).count();
+
+    if count == 0 {
+        CheckState::Unchecked
+    } else if count == leaves.len() {
+        CheckState::Checked
+    } else {
+        CheckState::Partial
+    }
+}
+
+/// Checks all leaves in `leaves` unless all of them are checked already, then unchecks them.
+fn toggle_check(checked: &mut HashSet<Vec<usize>>, leaves: Vec<Vec<usize>>) {
+    if check_state(&leaves, checked) == CheckState::Checked {
+        for leaf in &leaves {
+            checked.remove(leaf);
+        }
+    } else {
+        checked.extend(leaves);
+    }
+}
+
+/// The state of a [`TreeMultiSelect`](crate::TreeMultiSelect) prompt.
+///
+/// The cursor moves over the visible nodes with the up and down arrow keys (or `j`, `k` and
+/// 'Tab'). 'Right' (or `l`) expands the node under it or moves on to its first child, and
+/// 'Left' (or `h`) collapses it or moves to its parent. 'Space' checks or unchecks the node
+/// under it together with all of its descendants, and 'Enter' confirms the checked leaves.
+pub struct TreeMultiSelectState {
+    cursor: TreeCursor,
+    checked: HashSet<Vec<usize>>,
+    allow_quit: bool,
+}
+
+impl TreeMultiSelectState {
+    /// Creates the state of a prompt over the trees `items`, with the cursor on the first
+    /// root.
+    ///
+    /// `items` must not be empty.
+    pub fn new(items: Vec<TreeNode>) -> Self {
+        TreeMultiSelectState {
+            checked: initially_checked(&items),
+            cursor: TreeCursor::new(items),
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the [checked](Self::checked) leaves.
+    pub fn handle_key(&mut self, key: Key) -> Transition<Vec<Vec<usize>>> {
+        if self.cursor.navigate(&key) {
+            return Transition::Continue;
+        }
+
+        match key {
+            Key::Char(' ') => {
+                let (path, _) = self.cursor.current();
+                let leaves = leaf_paths(self.node(&path), &path);
+                toggle_check(&mut self.checked, leaves);
+            }
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            Key::Enter => return Transition::Done(self.checked()),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the root nodes.
+    pub fn items(&self) -> &[TreeNode] {
+        &self.cursor.items
+    }
+
+    /// Returns the visible nodes in tree order, with their paths.
+    pub fn rows(&self) -> Vec<(Vec<usize>, &TreeNode)> {
+        self.cursor.rows()
+    }
+
+    /// Returns whether the node at `path` is expanded.
+    pub fn is_expanded(&self, path: &[usize]) -> bool {
+        self.cursor.expanded.contains(path)
+    }
+
+    /// Returns the index of the visible node under the cursor.
+    pub fn selection(&self) -> usize {
+        self.cursor.sel
+    }
+
+    /// Returns the check mark of the node at `path`.
+    pub fn check_state(&self, path: &[usize]) -> CheckState {
+        check_state(&leaf_paths(self.node(path), path), &self.checked)
+    }
+
+    /// Returns the paths of the checked leaves in tree order.
+    pub fn checked(&self) -> Vec<Vec<usize>> {
+        let mut paths: Vec<Vec<usize>> = self.checked.iter().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    fn node(&self, path: &[usize]) -> &TreeNode {
+        crate::prompts::tree_select::node(&self.cursor.items, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Vec<TreeNode> {
+        vec![
+            TreeNode::new("a")
+                .child(TreeNode::new("a1").checked(true))
+                .child(TreeNode::new("a2").child(TreeNode::new("a2x")))
+                .child(TreeNode::new("a3")),
+            TreeNode::new("b").checked(true),
+        ]
+    }
+
+    #[test]
+    fn test_initially_checked_leaves() {
+        let mut checked: Vec<_> = initially_checked(&tree()).into_iter().collect();
+        checked.sort();
+        assert_eq!(checked, [vec![0, 0], vec![1]]);
+    }
+
+    #[test]
+    fn test_parent_toggles_descendants() {
+        let items = tree();
+        let mut checked = initially_checked(&items);
+        let leaves = leaf_paths(&items[0], &[0]);
+        assert_eq!(leaves, [vec![0, 0], vec![0, 1, 0], vec![0, 2]]);
+        assert_eq!(check_state(&leaves, &checked), CheckState::Partial);
+
+        toggle_check(&mut checked, leaves.clone());
+        assert_eq!(check_state(&leaves, &checked), CheckState::Checked);
+
+        toggle_check(&mut checked, leaves.clone());
+        assert_eq!(check_state(&leaves, &checked), CheckState::Unchecked);
+        assert!(checked.contains(&vec![1]));
+    }
+
+    #[test]
+    fn test_tree_multi_select_state() {
+        let mut state = TreeMultiSelectState::new(tree());
+
+        assert_eq!(state.check_state(&[0]), CheckState::Partial);
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Continue);
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Continue);
+        assert_eq!(state.selection(), 1);
+        assert_eq!(state.handle_key(Key::Char(' ')), Transition::Continue);
+        assert_eq!(state.check_state(&[0]), CheckState::Unchecked);
+        assert_eq!(state.handle_key(Key::Escape), Transition::Rejected);
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Transition::Done(vec![vec![1]])
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+
+use console::Key;
+
+use super::Transition;
+use crate::prompts::tree_select::{initially_expanded, toggle_expansion, visible_nodes};
+use crate::TreeNode;
+
+/// The cursor of a tree, moved by the navigation keys shared by tree prompts.
+pub(super) struct TreeCursor {
+    pub items: Vec<TreeNode>,
+    pub expanded: HashSet<Vec<usize>>,
+    pub sel: usize,
+}
+
+impl TreeCursor {
+    pub fn new(items: Vec<TreeNode>) -> Self {
+        TreeCursor {
+            expanded: initially_expanded(&items),
+            items,
+            sel: 0,
+        }
+    }
+
+    /// Returns the path of the node under the cursor and whether it is a leaf.
+    pub fn current(&self) -> (Vec<usize>, bool) {
+        let visible = visible_nodes(&self.items, &self.expanded);
+        let row = &visible[self.sel];
+        (row.path.clone(), row.node.is_leaf())
+    }
+
+    /// Moves the cursor or expands and collapses nodes for `key`, returning whether the key
+    /// was a navigation key.
+    pub fn navigate(&mut self, key: &Key) -> bool {
+        let visible = visible_nodes(&self.items, &self.expanded);
+        let len = visible.len();
+        let current = &visible[self.sel];
+        let is_expanded = self.expanded.contains(&current.path);
+        let parent = if current.path.len() > 1 {
+            let parent = &current.path[..current.path.len() - 1];
+            visible.iter().position(|row| row.path == parent)
+        } else {
+            None
+        };
+        let (path, leaf) = (current.path.clone(), current.node.is_leaf());
+
+        match key {
+            Key::ArrowDown | Key::Tab | Key::Char('j') => self.sel = (self.sel + 1) % len,
+            Key::ArrowUp | Key::BackTab | Key::Char('k') => {
+                self.sel = (self.sel + len - 1) % len;
+            }
+            // Already expanded, move on to the first child.
+            Key::ArrowRight | Key::Char('l') if !leaf && is_expanded => self.sel += 1,
+            Key::ArrowRight | Key::Char('l') if !leaf => {
+                toggle_expansion(&mut self.expanded, &path)
+            }
+            Key::ArrowLeft | Key::Char('h') if is_expanded => {
+                self.expanded.remove(&path);
+            }
+            Key::ArrowLeft | Key::Char('h') if parent.is_some() => {
+                self.sel = parent.unwrap_or(self.sel);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Toggles the expansion of the node at `path`, keeping the cursor on a visible row.
+    pub fn toggle(&mut self, path: &[usize]) {
+        toggle_expansion(&mut self.expanded, path);
+        self.clamp();
+    }
+
+    pub fn clamp(&mut self) {
+        let len = visible_nodes(&self.items, &self.expanded).len();
+        self.sel = self.sel.min(len.saturating_sub(1));
+    }
+
+    pub fn rows(&self) -> Vec<(Vec<usize>, &TreeNode)> {
+        visible_nodes(&self.items, &self.expanded)
+            .into_iter()
+            .map(|row| (row.path, row.node))
+            .collect()
+    }
+}
+
+/// The state of a [`TreeSelect`](crate::TreeSelect) prompt.
+///
+/// The cursor moves over the visible nodes with the up and down arrow keys (or `j`, `k` and
+/// 'Tab'). 'Right' (or `l`) expands the node under it or moves on to its first child, and
+/// 'Left' (or `h`) collapses it or moves to its parent. 'Space' toggles the expansion and
+/// 'Enter' picks a leaf, or toggles the expansion of other nodes unless
+/// [`allow_branch_selection`](Self::allow_branch_selection) is set.
+pub struct TreeSelectState {
+    cursor: TreeCursor,
+    allow_branch_selection: bool,
+    allow_quit: bool,
+}
+
+impl TreeSelectState {
+    /// Creates the state of a prompt over the trees `items`, with the cursor on the first
+    /// root.
+    ///
+    /// `items` must not be empty.
+    pub fn new(items: Vec<TreeNode>) -> Self {
+        TreeSelectState {
+            cursor: TreeCursor::new(items),
+            allow_branch_selection: false,
+            allow_quit: false,
+        }
+    }
+
+    /// Sets whether nodes with children can be picked with 'Enter'.
+    ///
+    /// The default is false.
+    pub fn allow_branch_selection(mut self, val: bool) -> Self {
+        self.allow_branch_selection = val;
+        self
+    }
+
+    /// Sets whether 'Esc' and `q` cancel the prompt.
+    ///
+    /// The default is false.
+    pub fn allow_quit(mut self, val: bool) -> Self {
+        self.allow_quit = val;
+        self
+    }
+
+    /// Handles `key`, returning how the prompt goes on.
+    ///
+    /// The prompt is done with the path of the node picked, as a list of child indices.
+    pub fn handle_key(&mut self, key: Key) -> Transition<Vec<usize>> {
+        if self.cursor.navigate(&key) {
+            return Transition::Continue;
+        }
+
+        let (path, leaf) = self.cursor.current();
+        match key {
+            Key::ArrowRight | Key::Char('l') | Key::Char(' ') if !leaf => {
+                self.cursor.toggle(&path);
+            }
+            Key::Escape | Key::Char('q') if self.allow_quit => return Transition::Cancelled,
+            Key::Enter if leaf || self.allow_branch_selection => return Transition::Done(path),
+            Key::Enter => self.cursor.toggle(&path),
+            _ => return Transition::Rejected,
+        }
+        Transition::Continue
+    }
+
+    /// Returns the root nodes.
+    pub fn items(&self) -> &[TreeNode] {
+        &self.cursor.items
+    }
+
+    /// Returns the visible nodes in tree order, with their paths.
+    pub fn rows(&self) -> Vec<(Vec<usize>, &TreeNode)> {
+        self.cursor.rows()
+    }
+
+    /// Returns whether the node at `path` is expanded.
+    pub fn is_expanded(&self, path: &[usize]) -> bool {
+        self.cursor.expanded.contains(path)
+    }
+
+    /// Returns the index of the visible node under the cursor.
+    pub fn selection(&self) -> usize {
+        self.cursor.sel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_select_state() {
+        let items = vec![
+            TreeNode::new("a")
+                .child(TreeNode::new("a1"))
+                .child(TreeNode::new("a2").child(TreeNode::new("a2x"))),
+            TreeNode::new("b"),
+        ];
+        let mut state = TreeSelectState::new(items);
+
+        assert_eq!(state.handle_key(Key::Enter), Transition::Continue);
+        assert!(state.is_expanded(&[0]));
+        assert_eq!(state.rows().len(), 4);
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Continue);
+        assert_eq!(state.selection(), 1);
+        assert_eq!(state.handle_key(Key::ArrowRight), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::ArrowLeft), Transition::Continue);
+        assert_eq!(state.selection(), 0);
+        assert_eq!(state.handle_key(Key::ArrowUp), Transition::Continue);
+        assert_eq!(state.handle_key(Key::Char('q')), Transition::Rejected);
+        assert_eq!(state.handle_key(Key::Enter), Transition::Done(vec![1]));
+    }
+}
@@ -0,0 +1,558 @@
+//! Runs prompts over arbitrary reader and writer pairs.
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{self, Read, Write},
+    sync::{Mutex, MutexGuard},
+};
+
+use console::Key;
+
+use crate::backend::{interrupted, read_line_keys, Backend, Capabilities};
+
+/// A [`Backend`] reading keys from any [`Read`] and rendering to any [`Write`].
+///
+/// This drives prompts over connections the process doesn't own as its terminal, like SSH
+/// channels, pseudo terminals opened by the application or serial ports. The remote end is
+/// expected to be in raw mode, sending key presses as they are typed: bytes are decoded as
+/// UTF-8 with the escape sequences of xterm compatible terminals, and newlines written are
+/// turned into carriage return and newline pairs.
+///
+/// A stream has no size of its own, so it is 24 rows by 80 columns until
+/// [`set_size`](Self::set_size) reports the actual one, for example on an SSH window change
+/// request. The new size is picked up the next time the prompt renders.
+///
+/// A lone `Esc` is only told apart from the escape sequences starting with it once the next
+/// byte arrives, which is then read as the following key.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::fs::OpenOptions;
+///
+/// use dialoguer::{Confirm, StreamBackend};
+///
+/// let port = OpenOptions::new().read(true).write(true).open("/dev/ttyUSB0")?;
+/// let term = StreamBackend::new(port.try_clone()?, port);
+/// term.set_size(40, 120);
+///
+/// let proceed = Confirm::new()
+///     .with_prompt("Flash the firmware?")
+///     .interact_on(&term)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct StreamBackend<R, W> {
+    reader: Mutex<Reader<R>>,
+    writer: Mutex<W>,
+    size: Mutex<(u16, u16)>,
+    colors: bool,
+    capabilities: Capabilities,
+    #[cfg(unix)]
+    tty: Option<tty::RawMode>,
+}
+
+struct Reader<R> {
+    inner: R,
+    // Bytes read along with earlier ones, and after an `Esc` which didn't start an escape
+    // sequence the byte read ahead.
+    buffered: VecDeque<u8>,
+}
+
+impl<R: Read> Reader<R> {
+    fn next_byte(&mut self) -> io::Result<u8> {
+        if let Some(byte) = self.buffered.pop_front() {
+            return Ok(byte);
+        }
+
+        let mut buf = [0; 64];
+        loop {
+            match self.inner.read(&mut buf) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "stream closed",
+                    ))
+                }
+                Ok(len) => {
+                    self.buffered.extend(&buf[1..len]);
+                    return Ok(buf[0]);
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn read_key(&mut self) -> io::Result<Key> {
+        let mut pending = None;
+        let key = parse_key(|| self.next_byte(), |byte| pending = Some(byte));
+        if let Some(byte) = pending {
+            self.buffered.push_front(byte);
+        }
+        key
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+impl<R, W> StreamBackend<R, W> {
+    /// Creates a backend reading keys from `reader` and rendering to `writer`.
+    pub fn new(reader: R, writer: W) -> Self {
+        StreamBackend {
+            reader: Mutex::new(Reader {
+                inner: reader,
+                buffered: VecDeque::new(),
+            }),
+            writer: Mutex::new(writer),
+            size: Mutex::new((24, 80)),
+            colors: true,
+            capabilities: Capabilities::default(),
+            #[cfg(unix)]
+            tty: None,
+        }
+    }
+
+    /// Sets whether colors are rendered.
+    ///
+    /// The default is true.
+    pub fn colors(mut self, val: bool) -> Self {
+        self.colors = val;
+        self
+    }
+
+    /// Sets what the remote terminal renders beyond plain text.
+    ///
+    /// Turning off [`hide_cursor`](Capabilities::hide_cursor) keeps the output free of
+    /// codes which only toggle the cursor, like for expect-style tests matching it. Without
+    /// [`cursor_movement`](Capabilities::cursor_movement) nothing is cleared or redrawn,
+    /// prompts ask for lines as on a dumb terminal instead. The [`colors`](Self::colors)
+    /// setting overrides the colors of `capabilities`.
+    ///
+    /// The default supports everything.
+    ///
+    /// ## Example usage
+    ///
+    /// ```rust
+    /// use dialoguer::{Capabilities, StreamBackend};
+    ///
+    /// let mut capabilities = Capabilities::default();
+    /// capabilities.hide_cursor = false;
+    ///
+    /// let term = StreamBackend::new(&b"y"[..], Vec::<u8>::new())
+    ///     .colors(false)
+    ///     .capabilities(capabilities);
+    /// ```
+    pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Sets the size of the remote terminal in rows and columns.
+    ///
+    /// Backends created with [`from_fd`](StreamBackend::from_fd) on a terminal take its
+    /// size instead.
+    pub fn set_size(&self, rows: u16, cols: u16) {
+        *lock(&self.size) = (rows.max(1), cols.max(1));
+    }
+
+    /// Returns the reader and the writer.
+    ///
+    /// Keys are read in chunks of the bytes available, bytes read but not used as keys yet
+    /// are dropped.
+    pub fn into_inner(self) -> (R, W) {
+        let reader = self
+            .reader
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner());
+        let writer = self
+            .writer
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner());
+        (reader.inner, writer)
+    }
+}
+
+impl<R, W> fmt::Debug for StreamBackend<R, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamBackend")
+            .field("size", &*lock(&self.size))
+            .field("colors", &self.colors)
+            .field("capabilities", &self.capabilities)
+            .finish()
+    }
+}
+
+impl<R: Read, W: Write> Backend for StreamBackend<R, W> {
+    fn read_key(&self) -> io::Result<Key> {
+        match lock(&self.reader).read_key()? {
+            Key::CtrlC => Err(interrupted()),
+            key => Ok(key),
+        }
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        read_line_keys(self, initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        read_line_keys(self, "", false)
+    }
+
+    fn key_pending(&self) -> bool {
+        !lock(&self.reader).buffered.is_empty()
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        let mut writer = lock(&self.writer);
+        for (idx, line) in s.split('\n').enumerate() {
+            if idx > 0 {
+                writer.write_all(b"\r\n")?;
+            }
+            writer.write_all(line.strip_suffix('\r').unwrap_or(line).as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        lock(&self.writer).flush()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        #[cfg(unix)]
+        {
+            if let Some(size) = self.tty.as_ref().and_then(|tty| tty.size()) {
+                return size;
+            }
+        }
+        *lock(&self.size)
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.colors
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            colors: self.colors,
+            ..self.capabilities
+        }
+    }
+}
+
+#[cfg(unix)]
+impl StreamBackend<std::fs::File, std::fs::File> {
+    /// Creates a backend on the file descriptor `fd`, like the slave end of a pseudo
+    /// terminal opened by an expect-style test with `rexpect` or `expectrl`.
+    ///
+    /// The descriptor is duplicated, so the caller keeps owning it. A terminal is switched
+    /// to raw mode until the backend is dropped, and its size is read whenever prompts
+    /// render, so resizing the pseudo terminal reaches them.
+    ///
+    /// ## Example usage
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::{fs::OpenOptions, os::unix::io::AsRawFd};
+    ///
+    /// use dialoguer::{Capabilities, Confirm, StreamBackend};
+    ///
+    /// // The slave end of a pseudo terminal the test drives from the master end.
+    /// let slave = OpenOptions::new().read(true).write(true).open("/dev/pts/7")?;
+    /// let mut capabilities = Capabilities::default();
+    /// capabilities.hide_cursor = false;
+    /// let term = StreamBackend::from_fd(slave.as_raw_fd())?
+    ///     .colors(false)
+    ///     .capabilities(capabilities);
+    ///
+    /// let proceed = Confirm::new().with_prompt("Deploy?").interact_on(&term)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_fd(fd: std::os::unix::io::RawFd) -> io::Result<Self> {
+        let reader = tty::dup(fd)?;
+        let writer = reader.try_clone()?;
+        let tty = tty::RawMode::enable(&reader)?;
+
+        Ok(StreamBackend {
+            tty,
+            ..StreamBackend::new(reader, writer)
+        })
+    }
+}
+
+#[cfg(unix)]
+mod tty {
+    use std::{
+        fs::File,
+        io,
+        os::unix::io::{AsRawFd, FromRawFd, RawFd},
+    };
+
+    /// Duplicates the file descriptor `fd` into a file of its own.
+    pub(super) fn dup(fd: RawFd) -> io::Result<File> {
+        match unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) } {
+            -1 => Err(io::Error::last_os_error()),
+            // The duplicate is owned by nothing else.
+            dup => Ok(unsafe { File::from_raw_fd(dup) }),
+        }
+    }
+
+    /// A terminal in raw mode, restored when dropped.
+    pub(super) struct RawMode {
+        file: File,
+        original: libc::termios,
+    }
+
+    impl RawMode {
+        /// Switches the terminal `file` to raw mode, returning `None` if it isn't one.
+        pub fn enable(file: &File) -> io::Result<Option<Self>> {
+            let fd = file.as_raw_fd();
+            if unsafe { libc::isatty(fd) } != 1 {
+                return Ok(None);
+            }
+
+            let mut original = unsafe { std::mem::zeroed::<libc::termios>() };
+            if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut raw = original;
+            unsafe { libc::cfmakeraw(&mut raw) };
+            if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Some(RawMode {
+                file: file.try_clone()?,
+                original,
+            }))
+        }
+
+        /// Returns the size of the terminal in rows and columns, if it has one.
+        pub fn size(&self) -> Option<(u16, u16)> {
+            let mut size = unsafe { std::mem::zeroed::<libc::winsize>() };
+            if unsafe { libc::ioctl(self.file.as_raw_fd(), libc::TIOCGWINSZ, &mut size) } != 0
+                || size.ws_row == 0
+                || size.ws_col == 0
+            {
+                return None;
+            }
+            Some((size.ws_row, size.ws_col))
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            unsafe { libc::tcsetattr(self.file.as_raw_fd(), libc::TCSANOW, &self.original) };
+        }
+    }
+}
+
+/// Decodes the key starting with the next byte, handing a byte read ahead which belongs to
+/// the following key to `pend`.
+pub(crate) fn parse_key<F, P>(mut next: F, mut pend: P) -> io::Result<Key>
+where
+    F: FnMut() -> io::Result<u8>,
+    P: FnMut(u8),
+{
+    let byte = next()?;
+    Ok(match byte {
+        b'\x1b' => {
+            let kind = next()?;
+            match kind {
+                b'[' => parse_csi(&mut next)?,
+                b'O' => match next()? {
+                    b'A' => Key::ArrowUp,
+                    b'B' => Key::ArrowDown,
+                    b'C' => Key::ArrowRight,
+                    b'D' => Key::ArrowLeft,
+                    b'H' => Key::Home,
+                    b'F' => Key::End,
+                    other => Key::UnknownEscSeq(vec!['O', char::from(other)]),
+                },
+                _ => {
+                    pend(kind);
+                    Key::Escape
+                }
+            }
+        }
+        b'\r' | b'\n' => Key::Enter,
+        b'\x7f' | b'\x08' => Key::Backspace,
+        b'\t' => Key::Tab,
+        b'\x01' => Key::Home,
+        b'\x03' => Key::CtrlC,
+        b'\x05' => Key::End,
+        byte if byte < 0x80 => Key::Char(char::from(byte)),
+        lead => {
+            let len = match lead {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => return Ok(Key::Unknown),
+            };
+            let mut buf = vec![lead];
+            for _ in 1..len {
+                buf.push(next()?);
+            }
+            match std::str::from_utf8(&buf)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(c) => Key::Char(c),
+                None => Key::Unknown,
+            }
+        }
+    })
+}
+
+/// Decodes a control sequence after its `Esc [` introducer.
+fn parse_csi<F: FnMut() -> io::Result<u8>>(next: &mut F) -> io::Result<Key> {
+    let mut params = String::new();
+    loop {
+        let byte = next()?;
+        match byte {
+            b' '..=b'?' => params.push(char::from(byte)),
+            _ => {
+                let key = match (params.as_str(), byte) {
+                    (_, b'A') => Key::ArrowUp,
+                    (_, b'B') => Key::ArrowDown,
+                    (_, b'C') => Key::ArrowRight,
+                    (_, b'D') => Key::ArrowLeft,
+                    (_, b'H') | ("1", b'~') | ("7", b'~') => Key::Home,
+                    (_, b'F') | ("4", b'~') | ("8", b'~') => Key::End,
+                    ("", b'Z') => Key::BackTab,
+                    ("2", b'~') => Key::Insert,
+                    ("3", b'~') => Key::Del,
+                    ("5", b'~') => Key::PageUp,
+                    ("6", b'~') => Key::PageDown,
+                    _ => {
+                        let mut seq: Vec<char> = vec!['['];
+                        seq.extend(params.chars());
+                        seq.push(char::from(byte));
+                        Key::UnknownEscSeq(seq)
+                    }
+                };
+                return Ok(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(input: &[u8]) -> Vec<Key> {
+        let term = StreamBackend::new(input, Vec::new());
+        let mut keys = Vec::new();
+        loop {
+            match term.read_key() {
+                Ok(key) => keys.push(key),
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return keys,
+                Err(err) => panic!("{}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_keys() {
+        assert_eq!(
+            keys(b"a\r\x7f\t"),
+            [Key::Char('a'), Key::Enter, Key::Backspace, Key::Tab]
+        );
+        assert_eq!(
+            keys("äß日".as_bytes()),
+            [Key::Char('ä'), Key::Char('ß'), Key::Char('日')]
+        );
+    }
+
+    #[test]
+    fn test_read_escape_sequences() {
+        assert_eq!(
+            keys(b"\x1b[A\x1bOB\x1b[3~\x1b[1;5C\x1b[Z"),
+            [
+                Key::ArrowUp,
+                Key::ArrowDown,
+                Key::Del,
+                Key::ArrowRight,
+                Key::BackTab
+            ]
+        );
+        assert_eq!(keys(b"\x1bx"), [Key::Escape, Key::Char('x')]);
+        assert_eq!(keys(b"\x1b\x1b[B"), [Key::Escape, Key::ArrowDown]);
+        assert_eq!(keys(b"\x1b[9~"), [Key::UnknownEscSeq(vec!['[', '9', '~'])]);
+    }
+
+    #[test]
+    fn test_ctrl_c_interrupts() {
+        let term = StreamBackend::new(&b"\x03"[..], Vec::new());
+        let err = term.read_key().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    }
+
+    #[test]
+    fn test_write_returns_carriage() {
+        let term = StreamBackend::new(&b""[..], Vec::new());
+        term.write_str("a\nb\r\n").unwrap();
+        term.write_line("c").unwrap();
+        assert_eq!(term.into_inner().1, b"a\r\nb\r\nc\r\n");
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = Capabilities {
+            hide_cursor: false,
+            ..Capabilities::default()
+        };
+        let term = StreamBackend::new(&b"y"[..], Vec::new())
+            .colors(false)
+            .capabilities(capabilities);
+
+        let proceed = crate::Confirm::new()
+            .with_prompt("Deploy?")
+            .interact_on(&term)
+            .unwrap();
+
+        assert!(proceed);
+        let output = String::from_utf8(term.into_inner().1).unwrap();
+        assert!(!output.contains("\x1b[?25"));
+        assert!(output.ends_with("Deploy? yes\r\n"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_fd() {
+        use std::{ffi::CStr, fs::OpenOptions, os::unix::io::AsRawFd};
+
+        let master = unsafe { libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY) };
+        assert!(master >= 0);
+        assert_eq!(unsafe { libc::grantpt(master) }, 0);
+        assert_eq!(unsafe { libc::unlockpt(master) }, 0);
+        let path = unsafe { CStr::from_ptr(libc::ptsname(master)) };
+        let slave = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path.to_str().unwrap())
+            .unwrap();
+        let size = libc::winsize {
+            ws_row: 10,
+            ws_col: 40,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        unsafe { libc::ioctl(master, libc::TIOCSWINSZ, &size) };
+
+        let term = StreamBackend::from_fd(slave.as_raw_fd()).unwrap();
+        assert_eq!(term.size(), (10, 40));
+
+        // Raw mode passes keys on without waiting for a newline.
+        unsafe { libc::write(master, b"\x1b[B".as_ptr() as *const _, 3) };
+        assert_eq!(term.read_key().unwrap(), Key::ArrowDown);
+
+        drop(term);
+        unsafe { libc::close(master) };
+    }
+}
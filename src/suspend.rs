@@ -0,0 +1,225 @@
+//! Coordinates prompts with other components drawing to the terminal.
+//!
+//! Progress bars (for instance an `indicatif::MultiProgress`) keep redrawing the terminal
+//! from a background thread, which corrupts the output of a prompt rendered at the same
+//! time. A [`SuspendHook`] registered with [`set_suspend_hook`] is suspended while a
+//! prompt is active and resumed afterwards, so both can share the terminal.
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use once_cell::sync::Lazy;
+
+/// Trait for components which have to stop drawing while a prompt is active.
+///
+/// A generic implementation for a pair of `Fn()` closures `(suspend, resume)` is provided
+/// to facilitate development.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use indicatif::{MultiProgress, ProgressDrawTarget};
+///
+/// let progress = MultiProgress::new();
+/// let (hide, show) = (progress.clone(), progress.clone());
+///
+/// dialoguer::set_suspend_hook((
+///     move || hide.set_draw_target(ProgressDrawTarget::hidden()),
+///     move || show.set_draw_target(ProgressDrawTarget::stderr()),
+/// ));
+/// ```
+pub trait SuspendHook: Send + Sync {
+    /// Invoked before a prompt takes over the terminal.
+    ///
+    /// Implementations should stop drawing and clear what they have drawn.
+    fn suspend(&self);
+
+    /// Invoked after the prompt handed the terminal back.
+    fn resume(&self);
+}
+
+impl<S, R> SuspendHook for (S, R)
+where
+    S: Fn() + Send + Sync,
+    R: Fn() + Send + Sync,
+{
+    fn suspend(&self) {
+        (self.0)()
+    }
+
+    fn resume(&self) {
+        (self.1)()
+    }
+}
+
+#[derive(Default)]
+struct State {
+    hook: Option<Arc<dyn SuspendHook>>,
+    // Number of live `PauseGuard`s, the hook is suspended while it is not zero.
+    depth: usize,
+}
+
+static STATE: Lazy<Mutex<State>> = Lazy::new(Default::default);
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    // A panicking hook must not disable prompts for the rest of the program.
+    state.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+// Hooks are called after the lock is released, so they may create prompts or pause the
+// renderer themselves.
+
+fn set_hook(state: &Mutex<State>, hook: Arc<dyn SuspendHook>) {
+    let (old, active) = {
+        let mut state = lock(state);
+        (state.hook.replace(hook.clone()), state.depth > 0)
+    };
+
+    if active {
+        if let Some(old) = old {
+            old.resume();
+        }
+        hook.suspend();
+    }
+}
+
+fn clear_hook(state: &Mutex<State>) {
+    let (old, active) = {
+        let mut state = lock(state);
+        (state.hook.take(), state.depth > 0)
+    };
+
+    if let Some(old) = old {
+        if active {
+            old.resume();
+        }
+    }
+}
+
+fn pause(state: &'static Mutex<State>) -> PauseGuard {
+    let hook = {
+        let mut state = lock(state);
+        state.depth += 1;
+        if state.depth == 1 {
+            state.hook.clone()
+        } else {
+            None
+        }
+    };
+
+    if let Some(hook) = hook {
+        hook.suspend();
+    }
+    PauseGuard { state }
+}
+
+/// Registers the hook which is suspended while prompts are active.
+///
+/// Replaces a previously registered hook. If a prompt is active at the time, the previous
+/// hook is resumed and the new one suspended right away.
+pub fn set_suspend_hook<H: SuspendHook + 'static>(hook: H) {
+    set_hook(&STATE, Arc::new(hook));
+}
+
+/// Removes the registered suspend hook, resuming it if a prompt is active.
+pub fn clear_suspend_hook() {
+    clear_hook(&STATE);
+}
+
+/// Suspends the registered hook until the returned guard is dropped.
+///
+/// All prompts do this for the duration of their interaction, this function is meant for
+/// other output which should not be interleaved with progress bars, like reports printed
+/// between two prompts. Guards can be nested; the hook is resumed when the last one is
+/// dropped.
+pub fn pause_renderer() -> PauseGuard {
+    pause(&STATE)
+}
+
+/// Keeps the registered [`SuspendHook`] suspended while alive.
+///
+/// Created by [`pause_renderer`].
+#[must_use = "the renderer is resumed as soon as the guard is dropped"]
+pub struct PauseGuard {
+    state: &'static Mutex<State>,
+}
+
+impl Drop for PauseGuard {
+    fn drop(&mut self) {
+        let hook = {
+            let mut state = lock(self.state);
+            state.depth -= 1;
+            if state.depth == 0 {
+                state.hook.clone()
+            } else {
+                None
+            }
+        };
+
+        if let Some(hook) = hook {
+            hook.resume();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicIsize, Ordering};
+
+    // Renderers of prompts tested in parallel pause the global state, so the tests use
+    // states of their own.
+
+    #[test]
+    fn test_nested_guards_suspend_once() {
+        static STATE: Lazy<Mutex<State>> = Lazy::new(Default::default);
+        static SUSPENDED: AtomicIsize = AtomicIsize::new(0);
+
+        set_hook(
+            &STATE,
+            Arc::new((
+                || {
+                    SUSPENDED.fetch_add(1, Ordering::SeqCst);
+                },
+                || {
+                    SUSPENDED.fetch_sub(1, Ordering::SeqCst);
+                },
+            )),
+        );
+
+        let outer = pause(&STATE);
+        let inner = pause(&STATE);
+        assert_eq!(SUSPENDED.load(Ordering::SeqCst), 1);
+
+        drop(inner);
+        assert_eq!(SUSPENDED.load(Ordering::SeqCst), 1);
+
+        drop(outer);
+        assert_eq!(SUSPENDED.load(Ordering::SeqCst), 0);
+
+        clear_hook(&STATE);
+    }
+
+    #[test]
+    fn test_hook_may_pause() {
+        static STATE: Lazy<Mutex<State>> = Lazy::new(Default::default);
+        static SUSPENDED: AtomicIsize = AtomicIsize::new(0);
+
+        set_hook(
+            &STATE,
+            Arc::new((
+                || {
+                    // Like a hook printing between two progress bar updates.
+                    let _guard = pause(&STATE);
+                    SUSPENDED.fetch_add(1, Ordering::SeqCst);
+                },
+                || {
+                    SUSPENDED.fetch_sub(1, Ordering::SeqCst);
+                },
+            )),
+        );
+
+        drop(pause(&STATE));
+        assert_eq!(SUSPENDED.load(Ordering::SeqCst), 0);
+        clear_hook(&STATE);
+    }
+}
@@ -0,0 +1,57 @@
+//! Awaits prompts from tokio tasks without blocking the executor.
+use std::{future::Future, io};
+
+/// Moves `prompt` to the blocking threads of tokio, runs `interact` on it there and
+/// returns a future resolving to its result.
+///
+/// Reading keys blocks, so awaiting a prompt directly in a task would stall every other
+/// task of its worker thread, with their timers and network IO. The prompt starts right
+/// away and keeps the terminal until it is answered, even if the future is dropped. A
+/// panicking prompt resolves to an error.
+///
+/// Panics when called outside of a tokio runtime.
+pub(crate) fn interact_async<P, T, F>(prompt: P, interact: F) -> impl Future<Output = io::Result<T>>
+where
+    P: Send + 'static,
+    T: Send + 'static,
+    F: FnOnce(P) -> io::Result<T> + Send + 'static,
+{
+    let handle = tokio::task::spawn_blocking(move || interact(prompt));
+    async move {
+        handle
+            .await
+            .unwrap_or_else(|err| Err(io::Error::new(io::ErrorKind::Other, err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::runtime::{Builder, Runtime};
+
+    use super::*;
+    use crate::{console::Key, Headless, Select};
+
+    fn runtime() -> Runtime {
+        Builder::new_current_thread().build().unwrap()
+    }
+
+    #[test]
+    fn test_interact_async() {
+        let term = Headless::new(10, 40).keys(vec![Key::ArrowDown, Key::Enter]);
+        let mut select = Select::new();
+        select.items(&["a", "b"]).default(0);
+
+        let selection = runtime().block_on(async move {
+            interact_async(select, move |select| select.interact_on(&term)).await
+        });
+        assert_eq!(selection.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_interact_async_panic() {
+        let result = runtime().block_on(async {
+            interact_async((), |()| -> io::Result<()> { panic!("boom") }).await
+        });
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Other);
+    }
+}
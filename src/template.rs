@@ -0,0 +1,114 @@
+//! Layout templates for [`ColorfulTheme`](crate::theme::ColorfulTheme).
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Field(String),
+}
+
+/// A layout like `"{prefix} {prompt} {suffix}"` filled in by a theme.
+///
+/// Fields are names in braces, literal braces are written as `{{` and `}}`. Fields the
+/// theme doesn't provide render as nothing. Which fields are available is documented
+/// where the template is set, like
+/// [`ColorfulThemeBuilder::prompt_template`](crate::theme::ColorfulThemeBuilder::prompt_template).
+///
+/// ```rust
+/// use dialoguer::theme::{ColorfulTheme, Template};
+///
+/// let theme = ColorfulTheme::builder()
+///     .item_template("{item} {cursor}".parse::<Template>().unwrap())
+///     .build();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Renders the template, taking the values of the fields from `fields`.
+    pub(crate) fn render(
+        &self,
+        f: &mut dyn fmt::Write,
+        fields: &[(&str, &dyn fmt::Display)],
+    ) -> fmt::Result {
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => f.write_str(text)?,
+                Part::Field(name) => {
+                    if let Some((_, value)) = fields.iter().find(|(field, _)| field == name) {
+                        write!(f, "{}", value)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a template, failing on unbalanced braces.
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = vec![];
+        let mut text = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) if c.is_alphanumeric() || c == '_' => name.push(c),
+                            _ => return Err(format!("Unclosed field in template '{}'", s)),
+                        }
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Field(name));
+                }
+                '}' => return Err(format!("Unmatched '}}' in template '{}'", s)),
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        Ok(Template { parts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template: Template = "{cursor} {item} {{{missing}}}".parse().unwrap();
+        let mut buf = String::new();
+        template
+            .render(&mut buf, &[("item", &"tea"), ("cursor", &">")])
+            .unwrap();
+        assert_eq!(buf, "> tea {}");
+
+        assert!("{item".parse::<Template>().is_err());
+        assert!("item}".parse::<Template>().is_err());
+        assert!("{it em}".parse::<Template>().is_err());
+    }
+}
@@ -0,0 +1,752 @@
+//! Unit testing prompt flows without a terminal.
+//!
+//! [`FakeTerm`] runs prompts on scripted keys, records every frame they render and fails
+//! instead of waiting once the keys run out, so the flows of an application can be tested
+//! like any other code, without spawning a pseudo terminal. [`RecordingTheme`] records
+//! what prompts render through the theme, for asserting on single elements. The
+//! [`assert_rendered_contains!`](crate::assert_rendered_contains),
+//! [`assert_not_rendered!`](crate::assert_not_rendered) and
+//! [`assert_report!`](crate::assert_report) macros check what a [`FakeTerm`] rendered,
+//! printing its frames when they fail.
+//!
+//! ```rust
+//! # fn test() -> std::io::Result<()> {
+//! use dialoguer::{test::FakeTerm, theme::SimpleTheme, Input, Select};
+//!
+//! fn ask_name(term: &FakeTerm) -> std::io::Result<(String, usize)> {
+//!     let name: String = Input::with_theme(&SimpleTheme)
+//!         .with_prompt("Name")
+//!         .interact_text_on(term)?;
+//!     let role = Select::with_theme(&SimpleTheme)
+//!         .with_prompt("Role")
+//!         .items(&["Admin", "User"])
+//!         .default(0)
+//!         .interact_on(term)?;
+//!     Ok((name, role))
+//! }
+//!
+//! let term = FakeTerm::new().script("Ann<enter><down><enter>");
+//! assert_eq!(ask_name(&term)?, ("Ann".to_string(), 1));
+//! assert_eq!(term.keys_left(), 0);
+//! assert_eq!(term.screen(), "Name: Ann\nRole: User");
+//! # Ok(())
+//! # }
+//! # test().unwrap();
+//! ```
+use std::{
+    collections::VecDeque,
+    fmt, io,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+use console::Key;
+#[cfg(feature = "fuzzy-select")]
+use fuzzy_matcher::skim::SkimMatcherV2;
+#[cfg(feature = "fuzzy-select")]
+use std::ops::Range;
+
+use crate::{
+    key_script::{key_name, parse_keys},
+    theme::{render_with_methods, Redaction, RenderContext, SimpleTheme, Theme},
+    Backend, Capabilities, Headless,
+};
+
+/// A [`Backend`] feeding scripted keys to prompts and recording what they render.
+///
+/// The screen is 24 rows by 80 columns and renders without colors unless configured
+/// otherwise, so frames compare as plain text. Every time a prompt waits for a key the
+/// screen is taken as a frame, see [`frames`](Self::frames). Keys arrive one at a time as
+/// if typed after each frame, and once they run out reading another one fails with an
+/// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) error: a prompt waiting for input the
+/// test didn't script fails rather than hangs.
+#[derive(Debug)]
+pub struct FakeTerm {
+    screen: Headless,
+    keys: Mutex<VecDeque<Key>>,
+    // The frames prompts waited with, and the keys read while they were shown.
+    frames: Mutex<Vec<(String, Option<Key>)>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+impl Default for FakeTerm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeTerm {
+    /// Creates a fake terminal of 24 rows and 80 columns without keys.
+    pub fn new() -> Self {
+        FakeTerm {
+            screen: Headless::new(24, 80).colors(false),
+            keys: Mutex::new(VecDeque::new()),
+            frames: Mutex::new(vec![]),
+        }
+    }
+
+    /// Sets the size of the terminal in rows and columns.
+    pub fn size(self, rows: u16, cols: u16) -> Self {
+        let colors = self.screen.colors_supported();
+        FakeTerm {
+            screen: Headless::new(rows, cols).colors(colors),
+            ..self
+        }
+    }
+
+    /// Sets whether colors are rendered, so frames hold the ANSI codes of the styles.
+    ///
+    /// The default is false.
+    pub fn colors(self, val: bool) -> Self {
+        FakeTerm {
+            screen: self.screen.colors(val),
+            ..self
+        }
+    }
+
+    /// Adds keys fed to the prompts.
+    pub fn keys<I: IntoIterator<Item = Key>>(self, keys: I) -> Self {
+        lock(&self.keys).extend(keys);
+        self
+    }
+
+    /// Adds the keys of `script` fed to the prompts.
+    ///
+    /// Text is typed as it is, while names in angle brackets stand for other keys:
+    /// `<enter>`, `<esc>`, `<tab>`, `<backtab>`, `<backspace>`, `<del>`, `<insert>`, `<up>`,
+    /// `<down>`, `<left>`, `<right>`, `<home>`, `<end>`, `<pageup>` and `<pagedown>`, as well
+    /// as `<lt>` for a literal `<`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if a name isn't one of the above or its closing bracket is missing.
+    pub fn script(self, script: &str) -> Self {
+        self.keys(parse_keys(script).unwrap_or_else(|err| panic!("{}", err)))
+    }
+
+    /// Returns the number of keys which weren't read yet.
+    pub fn keys_left(&self) -> usize {
+        lock(&self.keys).len()
+    }
+
+    /// Returns the frames prompts waited with for a key, in order.
+    ///
+    /// Like [`screen`](Self::screen) trailing blanks are trimmed from every line and blank
+    /// lines from the end.
+    pub fn frames(&self) -> Vec<String> {
+        lock(&self.frames)
+            .iter()
+            .map(|(frame, _)| frame.clone())
+            .collect()
+    }
+
+    /// Returns the frames and the keys read while they were shown as one text, for
+    /// snapshot tests catching changes to the layout of prompts and themes.
+    ///
+    /// Every frame is followed by a line naming its key as in a [`script`](Self::script),
+    /// and the screen after the last key closes the text. If the keys ran out, the last
+    /// frame is followed by a line saying so instead.
+    ///
+    /// ## Example usage
+    ///
+    /// ```rust
+    /// # fn test() -> std::io::Result<()> {
+    /// use dialoguer::{test::FakeTerm, theme::SimpleTheme, Select};
+    ///
+    /// let term = FakeTerm::new().script("<down><enter>");
+    /// Select::with_theme(&SimpleTheme)
+    ///     .items(&["Red", "Green"])
+    ///     .default(0)
+    ///     .interact_on(&term)?;
+    ///
+    /// // With insta: `insta::assert_snapshot!(term.snapshot());`
+    /// assert_eq!(
+    ///     term.snapshot(),
+    ///     "> Red\n  Green\n--- <down> ---\n  Red\n> Green\n--- <enter> ---\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap();
+    /// ```
+    pub fn snapshot(&self) -> String {
+        let mut snapshot = String::new();
+        let frames = lock(&self.frames);
+
+        for (frame, key) in frames.iter() {
+            snapshot.push_str(frame);
+            if !frame.is_empty() {
+                snapshot.push('\n');
+            }
+            match key {
+                Some(key) => snapshot.push_str(&format!("--- {} ---\n", key_name(key))),
+                None => {
+                    snapshot.push_str("--- end of keys ---\n");
+                    return snapshot;
+                }
+            }
+        }
+
+        let screen = self.screen();
+        snapshot.push_str(&screen);
+        if !screen.is_empty() {
+            snapshot.push('\n');
+        }
+        snapshot
+    }
+
+    /// Returns the current contents of the screen.
+    pub fn screen(&self) -> String {
+        self.screen.screen()
+    }
+
+    /// Returns whether `text` was part of a frame or is on the screen, see
+    /// [`assert_rendered_contains!`](crate::assert_rendered_contains).
+    pub fn has_rendered(&self, text: &str) -> bool {
+        lock(&self.frames)
+            .iter()
+            .any(|(frame, _)| frame.contains(text))
+            || self.screen().contains(text)
+    }
+
+    /// Returns the last line on the screen, the report of the prompt answered last, see
+    /// [`assert_report!`](crate::assert_report).
+    pub fn report(&self) -> Option<String> {
+        self.screen().lines().last().map(str::to_string)
+    }
+}
+
+/// Asserts that a [`FakeTerm`] rendered a text in any frame or has it on the screen.
+///
+/// On failure the message shows the [`snapshot`](FakeTerm::snapshot) of the terminal. An
+/// optional message can be given like with [`assert!`].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use dialoguer::{assert_rendered_contains, test::FakeTerm, Confirm};
+///
+/// let term = FakeTerm::new().script("y");
+/// Confirm::new().with_prompt("Deploy?").interact_on(&term).unwrap();
+///
+/// assert_rendered_contains!(term, "Deploy? [y/n]");
+/// ```
+#[macro_export]
+macro_rules! assert_rendered_contains {
+    ($term:expr, $text:expr $(,)?) => {{
+        let term: &$crate::test::FakeTerm = &$term;
+        let text: &str = &$text;
+        if !term.has_rendered(text) {
+            panic!(
+                "assertion failed: nothing rendered contains {:?}\n{}",
+                text,
+                term.snapshot()
+            );
+        }
+    }};
+    ($term:expr, $text:expr, $($arg:tt)+) => {{
+        let term: &$crate::test::FakeTerm = &$term;
+        let text: &str = &$text;
+        if !term.has_rendered(text) {
+            panic!(
+                "assertion failed: nothing rendered contains {:?}: {}\n{}",
+                text,
+                format_args!($($arg)+),
+                term.snapshot()
+            );
+        }
+    }};
+}
+
+/// Asserts that a [`FakeTerm`] never rendered a text, in no frame and not on the screen.
+///
+/// On failure the message shows the [`snapshot`](FakeTerm::snapshot) of the terminal. An
+/// optional message can be given like with [`assert!`].
+#[macro_export]
+macro_rules! assert_not_rendered {
+    ($term:expr, $text:expr $(,)?) => {{
+        let term: &$crate::test::FakeTerm = &$term;
+        let text: &str = &$text;
+        if term.has_rendered(text) {
+            panic!(
+                "assertion failed: {:?} was rendered\n{}",
+                text,
+                term.snapshot()
+            );
+        }
+    }};
+    ($term:expr, $text:expr, $($arg:tt)+) => {{
+        let term: &$crate::test::FakeTerm = &$term;
+        let text: &str = &$text;
+        if term.has_rendered(text) {
+            panic!(
+                "assertion failed: {:?} was rendered: {}\n{}",
+                text,
+                format_args!($($arg)+),
+                term.snapshot()
+            );
+        }
+    }};
+}
+
+/// Asserts that the [`report`](FakeTerm::report) a [`FakeTerm`] shows after the last
+/// prompt equals a text.
+///
+/// The report is the line a prompt leaves on the screen once answered, like
+/// `Name: Ann`. On failure the message shows the whole screen. An optional message can be
+/// given like with [`assert_eq!`].
+///
+/// ## Example usage
+///
+/// ```rust
+/// use dialoguer::{assert_report, test::FakeTerm, theme::SimpleTheme, Select};
+///
+/// let term = FakeTerm::new().script("<down><enter>");
+/// Select::with_theme(&SimpleTheme)
+///     .with_prompt("Role")
+///     .items(&["Admin", "User"])
+///     .default(0)
+///     .interact_on(&term)
+///     .unwrap();
+///
+/// assert_report!(term, "Role: User");
+/// ```
+#[macro_export]
+macro_rules! assert_report {
+    ($term:expr, $expected:expr $(,)?) => {{
+        let term: &$crate::test::FakeTerm = &$term;
+        let expected: &str = &$expected;
+        let report = term.report();
+        if report.as_deref() != Some(expected) {
+            panic!(
+                "assertion failed: the report is {:?}, expected {:?}\nscreen:\n{}",
+                report,
+                expected,
+                term.screen()
+            );
+        }
+    }};
+    ($term:expr, $expected:expr, $($arg:tt)+) => {{
+        let term: &$crate::test::FakeTerm = &$term;
+        let expected: &str = &$expected;
+        let report = term.report();
+        if report.as_deref() != Some(expected) {
+            panic!(
+                "assertion failed: the report is {:?}, expected {:?}: {}\nscreen:\n{}",
+                report,
+                expected,
+                format_args!($($arg)+),
+                term.screen()
+            );
+        }
+    }};
+}
+
+impl Backend for FakeTerm {
+    fn read_key(&self) -> io::Result<Key> {
+        let key = lock(&self.keys).pop_front();
+        lock(&self.frames).push((self.screen.screen(), key.clone()));
+
+        key.ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "the key script ran out"))
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        crate::backend::read_line_keys(self, initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        crate::backend::read_line_keys(self, "", false)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.screen.write_str(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.screen.flush()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        self.screen.size()
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.screen.colors_supported()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.screen.capabilities()
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        self.screen.move_cursor_up(n)
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        self.screen.move_cursor_down(n)
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        self.screen.move_cursor_left(n)
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        self.screen.move_cursor_right(n)
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        self.screen.clear_chars(n)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        self.screen.clear_line()
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        self.screen.clear_to_end_of_screen()
+    }
+
+    fn insert_lines(&self, n: usize) -> io::Result<()> {
+        self.screen.insert_lines(n)
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        self.screen.hide_cursor()
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        self.screen.show_cursor()
+    }
+
+    fn enter_alternate_screen(&self) -> io::Result<()> {
+        self.screen.enter_alternate_screen()
+    }
+
+    fn leave_alternate_screen(&self) -> io::Result<()> {
+        self.screen.leave_alternate_screen()
+    }
+}
+
+/// A call of a `format_*` method recorded by a [`RecordingTheme`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThemeCall {
+    /// The name of the method, like `"format_error"`.
+    pub method: &'static str,
+    /// The arguments following the formatter, formatted with `Debug`.
+    pub args: Vec<String>,
+    /// The text the method wrote, with the styles of the theme.
+    pub text: String,
+}
+
+/// A [`Theme`] recording every call of its `format_*` methods, for tests asserting on
+/// what prompts rendered without parsing frames.
+///
+/// Calls render with the wrapped theme, [`SimpleTheme`] by default, and are kept in order
+/// with their arguments and the text they produced. Elements passed to
+/// [`Theme::render`] are rendered through the `format_*` methods, even if the wrapped
+/// theme overrides `render`. Elements of prompts without dedicated methods are rendered
+/// by the `render` of the wrapped theme and recorded as calls to `render`, with the kind,
+/// prompt and item of their [`RenderContext`] as arguments.
+///
+/// ## Example usage
+///
+/// ```rust
+/// # fn test() -> std::io::Result<()> {
+/// use dialoguer::{
+///     test::{FakeTerm, RecordingTheme},
+///     Input,
+/// };
+///
+/// let theme = RecordingTheme::default();
+/// let term = FakeTerm::new().script("abc<enter>42<enter>");
+/// let age: u32 = Input::with_theme(&theme)
+///     .with_prompt("Age")
+///     .interact_text_on(&term)?;
+///
+/// assert_eq!(age, 42);
+/// let errors = theme.calls_to("format_error");
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].args, ["\"invalid digit found in string\""]);
+/// # Ok(())
+/// # }
+/// # test().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct RecordingTheme<T = SimpleTheme> {
+    theme: T,
+    calls: Mutex<Vec<ThemeCall>>,
+}
+
+impl Default for RecordingTheme {
+    fn default() -> Self {
+        Self::new(SimpleTheme)
+    }
+}
+
+impl<T: Theme> RecordingTheme<T> {
+    /// Creates a theme recording the calls rendered with `theme`.
+    pub fn new(theme: T) -> Self {
+        RecordingTheme {
+            theme,
+            calls: Mutex::new(vec![]),
+        }
+    }
+
+    /// Returns the recorded calls, in order.
+    pub fn calls(&self) -> Vec<ThemeCall> {
+        lock(&self.calls).clone()
+    }
+
+    /// Returns the recorded calls of the method named `method`, in order.
+    pub fn calls_to(&self, method: &str) -> Vec<ThemeCall> {
+        lock(&self.calls)
+            .iter()
+            .filter(|call| call.method == method)
+            .cloned()
+            .collect()
+    }
+
+    /// Forgets the recorded calls, to assert on the ones following.
+    pub fn clear(&self) {
+        lock(&self.calls).clear();
+    }
+
+    /// Returns the wrapped theme.
+    pub fn into_inner(self) -> T {
+        self.theme
+    }
+
+    fn record(&self, method: &'static str, args: Vec<String>, text: String) {
+        lock(&self.calls).push(ThemeCall { method, args, text });
+    }
+}
+
+/// Implements `format_*` methods rendering with the wrapped theme and recording the call.
+macro_rules! record_methods {
+    ($($(#[$attr:meta])* fn $method:ident($($arg:ident: $ty:ty),*);)*) => {$(
+        $(#[$attr])*
+        fn $method(&self, f: &mut dyn fmt::Write, $($arg: $ty),*) -> fmt::Result {
+            let mut text = String::new();
+            self.theme.$method(&mut text, $($arg),*)?;
+            f.write_str(&text)?;
+            self.record(stringify!($method), vec![$(format!("{:?}", $arg)),*], text);
+            Ok(())
+        }
+    )*};
+}
+
+impl<T: Theme> Theme for RecordingTheme<T> {
+    fn inner_theme(&self) -> Option<&dyn Theme> {
+        Some(&self.theme)
+    }
+
+    fn render(&self, f: &mut dyn fmt::Write, ctx: &RenderContext) -> fmt::Result {
+        if ctx.kind.has_methods() {
+            return render_with_methods(self, f, ctx);
+        }
+
+        let mut text = String::new();
+        self.theme.render(&mut text, ctx)?;
+        f.write_str(&text)?;
+        let args = vec![
+            format!("{:?}", ctx.kind),
+            format!("{:?}", ctx.prompt),
+            format!("{:?}", ctx.item),
+        ];
+        self.record("render", args, text);
+        Ok(())
+    }
+
+    fn format_report(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        values: &[&str],
+    ) -> Option<fmt::Result> {
+        let mut text = String::new();
+        if let Err(err) = self.theme.format_report(&mut text, prompt, values)? {
+            return Some(Err(err));
+        }
+        let args = vec![format!("{:?}", prompt), format!("{:?}", values)];
+        self.record("format_report", args, text.clone());
+        Some(f.write_str(&text))
+    }
+
+    // The matcher has no `Debug` implementation, so it isn't part of the arguments.
+    #[cfg(feature = "fuzzy-select")]
+    fn format_fuzzy_select_prompt_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+        highlight_matches: bool,
+        matcher: &SkimMatcherV2,
+        search_term: &str,
+    ) -> fmt::Result {
+        let mut rendered = String::new();
+        self.theme.format_fuzzy_select_prompt_item(
+            &mut rendered,
+            text,
+            active,
+            highlight_matches,
+            matcher,
+            search_term,
+        )?;
+        f.write_str(&rendered)?;
+        let args = vec![
+            format!("{:?}", text),
+            format!("{:?}", active),
+            format!("{:?}", highlight_matches),
+            format!("{:?}", search_term),
+        ];
+        self.record("format_fuzzy_select_prompt_item", args, rendered);
+        Ok(())
+    }
+
+    record_methods! {
+        fn format_paging_info(page: usize, pages: usize);
+        fn format_paging_info_unknown(page: usize);
+        fn format_scrollbar(thumb: bool);
+        fn format_item_number(number: usize, width: usize);
+        fn format_jump_input(digits: &str);
+        fn format_prompt(prompt: &str);
+        fn format_error(err: &str);
+        fn format_hint(hint: &str);
+        fn format_right_segment(text: &str);
+        fn format_step(step: usize, steps: usize);
+        fn format_countdown(remaining: Duration);
+        fn format_confirm_prompt(prompt: &str, default: Option<bool>);
+        fn format_confirm_prompt_selection(prompt: &str, selection: Option<bool>);
+        fn format_loading(frame: usize);
+        fn format_input_prompt(prompt: &str, default: Option<&str>);
+        fn format_input_prompt_selection(prompt: &str, sel: &str);
+        fn format_redacted_value(value: &str, redaction: Redaction);
+        #[cfg(feature = "password")]
+        fn format_password_prompt(prompt: &str);
+        #[cfg(feature = "password")]
+        fn format_password_prompt_selection(prompt: &str);
+        fn format_select_prompt(prompt: &str);
+        fn format_select_prompt_selection(prompt: &str, sel: &str);
+        fn format_multi_select_prompt(prompt: &str);
+        fn format_sort_prompt(prompt: &str);
+        fn format_multi_select_prompt_selection(prompt: &str, selections: &[&str]);
+        fn format_sort_prompt_selection(prompt: &str, selections: &[&str]);
+        fn format_select_prompt_item(text: &str, active: bool);
+        fn format_select_prompt_disabled_item(text: &str, active: bool);
+        fn format_select_prompt_compact(prompt: &str, text: &str, disabled: bool, index: usize, total: usize);
+        fn format_multi_select_prompt_disabled_item(text: &str, active: bool);
+        fn format_multi_select_prompt_locked_item(text: &str, active: bool);
+        fn format_multi_select_counter(checked: usize, total: usize, min: Option<usize>, max: Option<usize>);
+        fn format_multi_select_prompt_item(text: &str, checked: bool, active: bool);
+        fn format_sort_prompt_item(text: &str, picked: bool, active: bool);
+        #[cfg(feature = "fuzzy-select")]
+        fn format_fuzzy_match(text: &str, matches: &[Range<usize>]);
+        #[cfg(feature = "fuzzy-select")]
+        fn format_fuzzy_select_prompt(prompt: &str, search_term: &str, cursor_pos: usize);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{theme::SimpleTheme, Input, Select};
+
+    #[test]
+    #[should_panic(expected = "unknown key <up2>")]
+    fn test_script_unknown_key() {
+        let _ = FakeTerm::new().script("<up2>");
+    }
+
+    #[test]
+    fn test_frames() {
+        let term = FakeTerm::new().script("<down><enter>");
+        let sel = Select::with_theme(&SimpleTheme)
+            .items(&["a", "b"])
+            .default(0)
+            .interact_on(&term)
+            .unwrap();
+
+        assert_eq!(sel, 1);
+        assert_eq!(term.frames(), ["> a\n  b", "  a\n> b"]);
+    }
+
+    #[test]
+    fn test_keys_run_out() {
+        let term = FakeTerm::new().script("ab");
+        let err = Input::<String>::with_theme(&SimpleTheme)
+            .with_prompt("Name")
+            .interact_text_on(&term)
+            .unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(term.keys_left(), 0);
+        assert_eq!(term.screen(), "Name: ab");
+    }
+
+    #[test]
+    fn test_recording_theme() {
+        let theme = RecordingTheme::default();
+        let term = FakeTerm::new().script("<down><enter>");
+        Select::with_theme(&theme)
+            .with_prompt("Pick")
+            .items(&["a", "b"])
+            .default(0)
+            .interact_on(&term)
+            .unwrap();
+
+        let items = theme.calls_to("format_select_prompt_item");
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[3].args, ["\"b\"", "true"]);
+        assert_eq!(items[3].text, "> b");
+
+        let selection = theme.calls().pop().unwrap();
+        assert_eq!(selection.method, "format_select_prompt_selection");
+        assert_eq!(selection.args, ["\"Pick\"", "\"b\""]);
+
+        theme.clear();
+        assert!(theme.calls().is_empty());
+    }
+
+    #[test]
+    fn test_assertions() {
+        let term = FakeTerm::new().script("Ann<enter>");
+        Input::<String>::with_theme(&SimpleTheme)
+            .with_prompt("Name")
+            .interact_text_on(&term)
+            .unwrap();
+
+        assert_rendered_contains!(term, "Name: An");
+        assert_not_rendered!(term, "Age", "only the name was asked");
+        assert_report!(term, "Name: Ann");
+    }
+
+    #[test]
+    #[should_panic(expected = "the report is Some(\"Name: Ann\"), expected \"Name: Bob\"")]
+    fn test_assert_report_fails() {
+        let term = FakeTerm::new().script("Ann<enter>");
+        Input::<String>::with_theme(&SimpleTheme)
+            .with_prompt("Name")
+            .interact_text_on(&term)
+            .unwrap();
+
+        assert_report!(term, "Name: Bob");
+    }
+
+    #[test]
+    fn test_snapshot_keys_run_out() {
+        let term = FakeTerm::new().script("<down>");
+        Select::with_theme(&SimpleTheme)
+            .with_prompt("Pick")
+            .items(&["a", "b"])
+            .default(0)
+            .interact_on(&term)
+            .unwrap_err();
+
+        assert_eq!(
+            term.snapshot(),
+            "Pick:\n> a\n  b\n--- <down> ---\nPick:\n  a\n> b\n--- end of keys ---\n"
+        );
+    }
+}
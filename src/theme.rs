@@ -1,24 +1,1147 @@
 //! Customizes the rendering of the elements.
-use std::{fmt, io};
+use std::{
+    env, fmt, io,
+    ops::{Deref, Range},
+    sync::Arc,
+    time::Duration,
+};
 
-use console::{style, Style, StyledObject, Term};
+use console::{strip_ansi_codes, style, Key, Style, StyledObject};
 #[cfg(feature = "fuzzy-select")]
 use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 
+#[cfg(feature = "fuzzy-select")]
+use crate::width::byte_offset;
+
+use once_cell::sync::Lazy;
+
+use crate::{
+    backend::{AlternateScreen, Capabilities},
+    link::strip_osc,
+    paging::PagingInfo,
+    suspend::PauseGuard,
+    width::{skip_columns, text_width, truncate_text, wrapped_rows},
+    Backend, CheckState, ReviewDecision, Rgb,
+};
+
+pub use crate::template::Template;
+
+/// Controls whether a theme renders colors and other text styles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "theme-file",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum ColorMode {
+    /// Renders colors unless `NO_COLOR` is set or the terminal isn't attended.
+    ///
+    /// Setting `CLICOLOR_FORCE` to a value other than `0` enables colors for unattended
+    /// terminals, setting `CLICOLOR` to `0` disables them.
+    Auto,
+    /// Always renders colors as far as the `console` crate enables them.
+    Always,
+    /// Never renders colors.
+    Never,
+}
+
+impl ColorMode {
+    /// Returns whether colors are rendered on `term` in this mode.
+    pub fn colors_enabled(self, term: &dyn Backend) -> bool {
+        let var = |name| env::var(name).unwrap_or_default();
+
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto if !var("NO_COLOR").is_empty() => false,
+            ColorMode::Auto if !matches!(&*var("CLICOLOR_FORCE"), "" | "0") => true,
+            ColorMode::Auto => term.colors_supported() && var("CLICOLOR") != "0",
+        }
+    }
+}
+
+/// Controls how a sensitive value is rendered after interaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Redaction {
+    /// Masks the whole value without revealing its length.
+    Full,
+    /// Masks all but the given number of trailing characters, like the last digits of a
+    /// token. Values which would be revealed by half or more are masked fully.
+    LastChars(usize),
+}
+
+/// The prompt an element rendered through [`Theme::render`] belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RenderKind {
+    /// An error message, the message is passed as prompt.
+    Error,
+    /// A hint line like a key map below a prompt, the hint is passed as prompt.
+    Hint,
+    /// A [`Confirm`](crate::Confirm) prompt, with the choice in [`RenderContext::choice`].
+    Confirm,
+    /// An [`Input`](crate::Input) prompt.
+    Input,
+    /// A `Password` prompt.
+    Password,
+    /// A [`Select`](crate::Select) prompt.
+    Select,
+    /// A [`MultiSelect`](crate::MultiSelect) prompt.
+    MultiSelect,
+    /// A [`Sort`](crate::Sort) prompt.
+    Sort,
+    /// A `FuzzySelect` prompt.
+    FuzzySelect,
+    /// A [`Toggle`](crate::Toggle) prompt, with the labels in [`RenderContext::values`] and
+    /// the picked one in [`RenderContext::choice`].
+    Toggle,
+    /// A [`ColorSelect`](crate::ColorSelect) prompt, its swatches are items with the color
+    /// in [`RenderContext::color`].
+    ColorSelect,
+    /// An [`Autocomplete`](crate::Autocomplete) prompt, its suggestions are items.
+    Autocomplete,
+    /// A [`GridSelect`](crate::GridSelect) prompt, its cells are items padded to the same
+    /// width.
+    GridSelect,
+    /// An [`EditList`](crate::EditList) prompt, the placeholder of an empty list is a
+    /// disabled item.
+    EditList,
+    /// A [`KeyPrompt`](crate::KeyPrompt), with the names of the accepted keys in
+    /// [`RenderContext::values`], empty if any key is accepted.
+    KeyPrompt,
+    /// A [`NumberInput`](crate::NumberInput) prompt, with the value as input.
+    NumberInput,
+    /// A [`PinInput`](crate::PinInput) prompt, with the code as input and the number of
+    /// cells in [`RenderContext::length`]. Masked codes aren't reported.
+    PinInput,
+    /// An [`IpInput`](crate::IpInput) prompt, with the rest of a suggested range in
+    /// [`RenderContext::detail`].
+    IpInput,
+    /// A [`DurationInput`](crate::DurationInput) prompt, with the parsed duration in
+    /// [`RenderContext::detail`].
+    DurationInput,
+    /// A [`Slider`](crate::Slider) prompt, with the value as input, its position in
+    /// [`RenderContext::ratio`] and the width of the bar in [`RenderContext::length`].
+    Slider,
+    /// A [`TagInput`](crate::TagInput) prompt, with the tags in [`RenderContext::values`]
+    /// and the suggested one in [`RenderContext::detail`].
+    TagInput,
+    /// A [`RangeSelect`](crate::RangeSelect) prompt, reported with the items at both ends
+    /// of the range.
+    RangeSelect,
+    /// A [`TransferSelect`](crate::TransferSelect) prompt, the titles of its panes are
+    /// header items.
+    TransferSelect,
+    /// A [`Menu`](crate::Menu) prompt, with the opened submenus in
+    /// [`RenderContext::values`].
+    Menu,
+    /// A [`TableSelect`](crate::TableSelect) prompt, its rows and header are items with
+    /// the cells in [`RenderContext::values`], padded to the width of their column.
+    TableSelect,
+    /// A [`PathSelect`](crate::PathSelect) prompt, with the directory listed in
+    /// [`RenderContext::detail`].
+    PathSelect,
+    /// A [`TreeSelect`](crate::TreeSelect) prompt, with the nesting of its items in
+    /// [`RenderContext::depth`].
+    TreeSelect,
+    /// A [`ReviewEach`](crate::ReviewEach) prompt, with the keys in
+    /// [`RenderContext::values`] and the reviewed item in [`RenderContext::position`].
+    ReviewEach,
+    /// A [`TreeMultiSelect`](crate::TreeMultiSelect) prompt, with the nesting of its items
+    /// in [`RenderContext::depth`].
+    TreeMultiSelect,
+}
+
+impl RenderKind {
+    /// Returns whether the elements of the prompt have dedicated `format_*` methods.
+    pub(crate) fn has_methods(self) -> bool {
+        matches!(
+            self,
+            RenderKind::Error
+                | RenderKind::Hint
+                | RenderKind::Confirm
+                | RenderKind::Input
+                | RenderKind::Password
+                | RenderKind::Select
+                | RenderKind::MultiSelect
+                | RenderKind::Sort
+                | RenderKind::FuzzySelect
+        )
+    }
+}
+
+/// The stage of the interaction an element is rendered in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RenderState<'a> {
+    /// The prompt waits for the user.
+    Active {
+        /// The text typed so far, or the value of prompts adjusting it with keys.
+        input: &'a str,
+        /// The position of the cursor in `input`.
+        cursor: usize,
+        /// The value taken if nothing is entered.
+        default: Option<&'a str>,
+    },
+    /// The prompt was answered.
+    Done {
+        /// The selected values, empty if nothing was selected.
+        selections: &'a [&'a str],
+    },
+}
+
+/// Flags modifying how an element is rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RenderFlags {
+    /// The item is highlighted by the cursor, or the pane a title belongs to has the focus.
+    pub active: bool,
+    /// The item is checked in a multi select, picked in a sort or within the range of a
+    /// range select prompt.
+    pub checked: bool,
+    /// The item can't be selected or toggled, checked items are locked in.
+    pub disabled: bool,
+    /// Matches of the input should be highlighted in the item.
+    pub highlight_matches: bool,
+    /// Some but not all leaves below a tree node are checked.
+    pub partial: bool,
+    /// The item leads to further items, like a submenu, a directory or a tree node with
+    /// children.
+    pub branch: bool,
+    /// The children of a tree node are shown.
+    pub expanded: bool,
+    /// The item is the title of a pane or the header of a table.
+    pub header: bool,
+    /// The input is masked, like the code of a PIN input.
+    pub masked: bool,
+    /// The input doesn't parse yet.
+    pub invalid: bool,
+    /// A color is being entered as hex code.
+    pub editing: bool,
+}
+
+/// Describes an element of a prompt to [`Theme::render`].
+///
+/// Fields are added here as prompts gain capabilities, so themes implementing
+/// [`Theme::render`] keep compiling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct RenderContext<'a> {
+    /// The prompt the element belongs to.
+    pub kind: RenderKind,
+    /// The prompt text, or the message of an error.
+    pub prompt: &'a str,
+    /// The stage of the interaction.
+    pub state: RenderState<'a>,
+    /// The text of the rendered item, `None` when rendering the prompt line itself.
+    pub item: Option<&'a str>,
+    /// The icon of the rendered item, padded to the width of the other icons.
+    pub icon: Option<&'a str>,
+    /// Flags of the rendered item.
+    pub flags: RenderFlags,
+    /// The step of the [`Wizard`](crate::Wizard) the prompt belongs to, as `(step, steps)`.
+    pub step: Option<(usize, usize)>,
+    /// The time left until the [timeout](crate::Select::timeout) of the prompt, rounded up
+    /// to whole seconds.
+    pub countdown: Option<Duration>,
+    /// The default of a [`Confirm`](crate::Confirm) prompt while it waits, and its answer
+    /// once it was answered, or the picked choice of a [`Toggle`](crate::Toggle) prompt.
+    pub choice: Option<bool>,
+    /// Further texts of the element, like the labels of a toggle, the keys accepted by a
+    /// key prompt, the tags entered, the opened submenus or the cells of a table row.
+    pub values: &'a [&'a str],
+    /// A note following the input, like the parsed duration or the directory listed.
+    pub detail: Option<&'a str>,
+    /// The nesting level of a tree node, roots are at 0.
+    pub depth: usize,
+    /// The number of cells of a PIN input or the width of a slider bar.
+    pub length: Option<usize>,
+    /// The position of a slider value between its bounds, in the range `0.0..=1.0`.
+    pub ratio: Option<f64>,
+    /// The color of a color select prompt or swatch, with its index in the terminal
+    /// palette if it has one.
+    pub color: Option<(Rgb, Option<u8>)>,
+    /// The position of the item under review counting from 1, with the number of items if
+    /// it is known upfront.
+    pub position: Option<(usize, Option<usize>)>,
+}
+
+impl<'a> RenderContext<'a> {
+    /// Creates a context for the prompt line of `kind`.
+    pub fn new(kind: RenderKind, prompt: &'a str, state: RenderState<'a>) -> Self {
+        Self {
+            kind,
+            prompt,
+            state,
+            item: None,
+            icon: None,
+            flags: RenderFlags::default(),
+            step: None,
+            countdown: None,
+            choice: None,
+            values: &[],
+            detail: None,
+            depth: 0,
+            length: None,
+            ratio: None,
+            color: None,
+            position: None,
+        }
+    }
+
+    /// Turns the context into one for an item of the prompt.
+    pub fn item(mut self, text: &'a str, flags: RenderFlags) -> Self {
+        self.item = Some(text);
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the icon of the rendered item.
+    pub fn icon(mut self, icon: Option<&'a str>) -> Self {
+        self.icon = icon;
+        self
+    }
+
+    /// Sets the wizard step of the prompt.
+    pub fn step(mut self, step: Option<(usize, usize)>) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Sets the time left until the timeout of the prompt.
+    pub fn countdown(mut self, countdown: Option<Duration>) -> Self {
+        self.countdown = countdown;
+        self
+    }
+
+    /// Sets the default or answer of a confirm prompt.
+    pub fn choice(mut self, choice: Option<bool>) -> Self {
+        self.choice = choice;
+        self
+    }
+
+    /// Sets the flags of the prompt line.
+    pub fn flags(mut self, flags: RenderFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the further texts of the element.
+    pub fn values(mut self, values: &'a [&'a str]) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Sets the note following the input.
+    pub fn detail(mut self, detail: Option<&'a str>) -> Self {
+        self.detail = detail;
+        self
+    }
+
+    /// Sets the nesting level of a tree node.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the number of cells of a PIN input or the width of a slider bar.
+    pub fn length(mut self, length: Option<usize>) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Sets the position of a slider value between its bounds.
+    pub fn ratio(mut self, ratio: Option<f64>) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Sets the color of a color select prompt or swatch.
+    pub fn color(mut self, color: Option<(Rgb, Option<u8>)>) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the position of the item under review.
+    pub fn position(mut self, position: Option<(usize, Option<usize>)>) -> Self {
+        self.position = position;
+        self
+    }
+}
+
+// Shared by all items, building a matcher allocates.
+#[cfg(feature = "fuzzy-select")]
+static MATCHER: Lazy<SkimMatcherV2> = Lazy::new(SkimMatcherV2::default);
+
+/// Renders `ctx` with the dedicated `format_*` methods of `theme`.
+///
+/// This is the default of [`Theme::render`]. Themes overriding it can fall back to this
+/// for the elements they don't handle themselves. Prompts without dedicated methods, like
+/// [`Toggle`](crate::Toggle) or [`Menu`](crate::Menu), are rendered by the `render` of the
+/// [inner theme](Theme::inner_theme) if there is one, and with the generic methods like
+/// [`format_prompt`](Theme::format_prompt) otherwise.
+pub fn render_with_methods<T: Theme + ?Sized>(
+    theme: &T,
+    f: &mut dyn fmt::Write,
+    ctx: &RenderContext,
+) -> fmt::Result {
+    if !ctx.kind.has_methods() {
+        if let Some(inner) = theme.inner_theme() {
+            return inner.render(f, ctx);
+        }
+    }
+
+    let prompt = ctx.prompt;
+    let flags = ctx.flags;
+    // The dedicated methods know nothing about icons, they get them as part of the text.
+    let labeled;
+    let item = match (ctx.item, ctx.icon) {
+        (Some(text), Some(icon)) => {
+            labeled = format!("{} {}", icon, text);
+            Some(labeled.as_str())
+        }
+        (item, _) => item,
+    };
+
+    write_progress(theme, f, ctx)?;
+
+    match (ctx.kind, item, ctx.state) {
+        (RenderKind::Error, _, _) => theme.format_error(f, prompt),
+        (RenderKind::Hint, _, _) => theme.format_hint(f, prompt),
+        (RenderKind::Confirm, _, RenderState::Active { .. }) => {
+            theme.format_confirm_prompt(f, prompt, ctx.choice)
+        }
+        (RenderKind::Confirm, _, RenderState::Done { .. }) => {
+            theme.format_confirm_prompt_selection(f, prompt, ctx.choice)
+        }
+        (RenderKind::Input, _, RenderState::Active { default, .. }) => {
+            theme.format_input_prompt(f, prompt, default)
+        }
+        (RenderKind::Input, _, RenderState::Done { selections }) => {
+            theme.format_input_prompt_selection(f, prompt, selections.first().unwrap_or(&""))
+        }
+        #[cfg(feature = "password")]
+        (RenderKind::Password, _, RenderState::Active { .. }) => {
+            theme.format_password_prompt(f, prompt)
+        }
+        #[cfg(feature = "password")]
+        (RenderKind::Password, _, RenderState::Done { .. }) => {
+            theme.format_password_prompt_selection(f, prompt)
+        }
+        (RenderKind::Select, Some(text), _) if flags.disabled => {
+            theme.format_select_prompt_disabled_item(f, text, flags.active)
+        }
+        (RenderKind::Select, Some(text), _) => {
+            theme.format_select_prompt_item(f, text, flags.active)
+        }
+        (RenderKind::Select, None, RenderState::Active { .. }) => {
+            theme.format_select_prompt(f, prompt)
+        }
+        (RenderKind::Select, None, RenderState::Done { selections })
+        | (RenderKind::FuzzySelect, None, RenderState::Done { selections }) => {
+            theme.format_select_prompt_selection(f, prompt, selections.first().unwrap_or(&""))
+        }
+        (RenderKind::MultiSelect, Some(text), _) if flags.disabled && flags.checked => {
+            theme.format_multi_select_prompt_locked_item(f, text, flags.active)
+        }
+        (RenderKind::MultiSelect, Some(text), _) if flags.disabled => {
+            theme.format_multi_select_prompt_disabled_item(f, text, flags.active)
+        }
+        (RenderKind::MultiSelect, Some(text), _) => {
+            theme.format_multi_select_prompt_item(f, text, flags.checked, flags.active)
+        }
+        (RenderKind::MultiSelect, None, RenderState::Active { .. }) => {
+            theme.format_multi_select_prompt(f, prompt)
+        }
+        (RenderKind::MultiSelect, None, RenderState::Done { selections }) => {
+            theme.format_multi_select_prompt_selection(f, prompt, selections)
+        }
+        (RenderKind::Sort, Some(text), _) => {
+            theme.format_sort_prompt_item(f, text, flags.checked, flags.active)
+        }
+        (RenderKind::Sort, None, RenderState::Active { .. }) => theme.format_sort_prompt(f, prompt),
+        (RenderKind::Sort, None, RenderState::Done { selections }) => {
+            theme.format_sort_prompt_selection(f, prompt, selections)
+        }
+        #[cfg(feature = "fuzzy-select")]
+        (RenderKind::FuzzySelect, Some(text), RenderState::Active { input, .. }) => theme
+            .format_fuzzy_select_prompt_item(
+                f,
+                text,
+                flags.active,
+                flags.highlight_matches,
+                &MATCHER,
+                input,
+            ),
+        #[cfg(feature = "fuzzy-select")]
+        (RenderKind::FuzzySelect, None, RenderState::Active { input, cursor, .. }) => {
+            theme.format_fuzzy_select_prompt(f, prompt, input, cursor)
+        }
+        _ => render_plain(theme, f, ctx),
+    }
+}
+
+/// Writes the wizard step and the countdown in front of the prompt line of `ctx`.
+fn write_progress<T: Theme + ?Sized>(
+    theme: &T,
+    f: &mut dyn fmt::Write,
+    ctx: &RenderContext,
+) -> fmt::Result {
+    if ctx.item.is_some() || matches!(ctx.kind, RenderKind::Error | RenderKind::Hint) {
+        return Ok(());
+    }
+
+    if let RenderState::Active { .. } = ctx.state {
+        if let Some((step, steps)) = ctx.step {
+            theme.format_step(f, step, steps)?;
+        }
+        if let Some(remaining) = ctx.countdown {
+            theme.format_countdown(f, remaining)?;
+        }
+    }
+    Ok(())
+}
+
+/// Renders the elements of prompts without dedicated methods with the generic ones.
+fn render_plain<T: Theme + ?Sized>(
+    theme: &T,
+    f: &mut dyn fmt::Write,
+    ctx: &RenderContext,
+) -> fmt::Result {
+    let prompt = ctx.prompt;
+    let flags = ctx.flags;
+    let cursor = if flags.active { ">" } else { " " };
+    let labeled = |f: &mut dyn fmt::Write| {
+        if prompt.is_empty() {
+            Ok(())
+        } else {
+            write!(f, "{}: ", prompt)
+        }
+    };
+
+    match (ctx.kind, ctx.item, ctx.state) {
+        // Reports of single values.
+        (
+            RenderKind::Toggle
+            | RenderKind::ColorSelect
+            | RenderKind::KeyPrompt
+            | RenderKind::NumberInput
+            | RenderKind::DurationInput
+            | RenderKind::Slider
+            | RenderKind::ReviewEach,
+            None,
+            RenderState::Done { selections },
+        ) => theme.format_input_prompt_selection(f, prompt, selections.first().unwrap_or(&"")),
+        // Items rendered like select items.
+        (
+            RenderKind::Autocomplete
+            | RenderKind::EditList
+            | RenderKind::TransferSelect
+            | RenderKind::TableSelect,
+            Some(text),
+            _,
+        ) if !flags.disabled && !flags.header => {
+            theme.format_select_prompt_item(f, text, flags.active)
+        }
+        (RenderKind::Toggle, None, RenderState::Active { .. }) => {
+            labeled(f)?;
+            let disabled = ctx.values.first().unwrap_or(&"");
+            let enabled = ctx.values.get(1).unwrap_or(&"");
+            if ctx.choice == Some(true) {
+                write!(f, " {}  / [{}]", disabled, enabled)
+            } else {
+                write!(f, "[{}] /  {} ", disabled, enabled)
+            }
+        }
+        (RenderKind::ColorSelect, Some(_), _) => match ctx.color {
+            Some((color, ansi)) => write!(
+                f,
+                "{}",
+                swatch(color, ansi, if flags.active { "[]" } else { "  " }, "··")
+            ),
+            None => Ok(()),
+        },
+        (RenderKind::ColorSelect, None, RenderState::Active { input, .. }) => {
+            labeled(f)?;
+            match ctx.color {
+                _ if flags.editing => write!(f, "{}_", input),
+                Some((color, _)) => write!(f, "{}", color),
+                None => Ok(()),
+            }
+        }
+        (RenderKind::Autocomplete, None, RenderState::Active { input, cursor, .. }) => {
+            labeled(f)?;
+            let head: String = input.chars().take(cursor).collect();
+            let tail: String = input.chars().skip(cursor).collect();
+            write!(f, "{}|{}", head, tail)
+        }
+        (RenderKind::GridSelect, Some(text), _) | (RenderKind::TransferSelect, Some(text), _) => {
+            if flags.active {
+                write!(f, "[{}]", text)
+            } else {
+                write!(f, " {} ", text)
+            }
+        }
+        (RenderKind::EditList, Some(text), _) => write!(f, "  ({})", text),
+        (RenderKind::EditList, None, RenderState::Active { .. }) => {
+            theme.format_select_prompt(f, prompt)
+        }
+        (RenderKind::EditList, None, RenderState::Done { selections })
+        | (RenderKind::TagInput, None, RenderState::Done { selections }) => {
+            theme.format_multi_select_prompt_selection(f, prompt, selections)
+        }
+        (RenderKind::KeyPrompt, None, RenderState::Active { .. }) => {
+            write!(f, "{}", prompt)?;
+            if !ctx.values.is_empty() {
+                write!(f, " [{}]", ctx.values.join("/"))?;
+            }
+            Ok(())
+        }
+        (RenderKind::NumberInput, None, RenderState::Active { input, .. }) => {
+            labeled(f)?;
+            write!(f, "{}", input)
+        }
+        (RenderKind::PinInput, None, RenderState::Active { input, .. }) => {
+            labeled(f)?;
+            let mut chars = input.chars();
+            for _ in 0..ctx.length.unwrap_or(0) {
+                match chars.next() {
+                    Some(_) if flags.masked => write!(f, "[*]")?,
+                    Some(chr) => write!(f, "[{}]", chr)?,
+                    None => write!(f, "[ ]")?,
+                }
+            }
+            Ok(())
+        }
+        (RenderKind::PinInput, None, RenderState::Done { selections }) => {
+            let code = if flags.masked {
+                "[hidden]"
+            } else {
+                selections.first().unwrap_or(&"")
+            };
+            theme.format_input_prompt_selection(f, prompt, code)
+        }
+        (RenderKind::IpInput, None, RenderState::Active { input, .. }) => {
+            labeled(f)?;
+            write!(f, "{}", input)?;
+            if let Some(completion) = ctx.detail {
+                write!(f, "[{}]", completion)?;
+            }
+            if flags.invalid && !input.is_empty() {
+                write!(f, " (incomplete)")?;
+            }
+            Ok(())
+        }
+        (RenderKind::DurationInput, None, RenderState::Active { input, .. }) => {
+            labeled(f)?;
+            write!(f, "{}", input)?;
+            if let Some(description) = ctx.detail {
+                write!(f, " ({})", description)?;
+            }
+            Ok(())
+        }
+        (RenderKind::Slider, None, RenderState::Active { input, .. }) => {
+            labeled(f)?;
+            let width = ctx.length.unwrap_or(0);
+            let filled = slider_filled_width(ctx.ratio.unwrap_or(0.0), width);
+            write!(
+                f,
+                "[{}{}] {}",
+                "#".repeat(filled),
+                "-".repeat(width - filled),
+                input
+            )
+        }
+        (RenderKind::TagInput, None, RenderState::Active { input, .. }) => {
+            labeled(f)?;
+            for tag in ctx.values {
+                write!(f, "[{}] ", tag)?;
+            }
+            write!(f, "{}", input)?;
+            if let Some(suggestion) = ctx.detail {
+                write!(f, " ({})", suggestion)?;
+            }
+            Ok(())
+        }
+        (RenderKind::RangeSelect, Some(text), _) => {
+            let marker = if flags.checked { "|" } else { " " };
+            write!(f, "{}{} {}", cursor, marker, text)
+        }
+        (RenderKind::RangeSelect, None, RenderState::Done { selections }) => match selections {
+            [first, last] if first != last => {
+                theme.format_select_prompt_selection(f, prompt, &format!("{} .. {}", first, last))
+            }
+            _ => theme.format_select_prompt_selection(f, prompt, selections.first().unwrap_or(&"")),
+        },
+        (RenderKind::Menu, Some(text), _) => {
+            theme.format_select_prompt_item(f, text, flags.active)?;
+            if flags.branch {
+                write!(f, " >")?;
+            }
+            Ok(())
+        }
+        (RenderKind::Menu, None, RenderState::Active { .. }) => {
+            let mut parts = vec![prompt];
+            parts.extend(ctx.values);
+            parts.retain(|part| !part.is_empty());
+            theme.format_select_prompt(f, &parts.join(" > "))
+        }
+        (RenderKind::Menu, None, RenderState::Done { selections }) => {
+            theme.format_select_prompt_selection(f, prompt, &selections.join(" > "))
+        }
+        (RenderKind::TableSelect, Some(text), _) => write!(f, "  {}", text),
+        (RenderKind::PathSelect, Some(name), _) => {
+            let slash = if flags.branch { "/" } else { "" };
+            write!(f, "{} {}{}", cursor, name, slash)
+        }
+        (RenderKind::PathSelect, None, RenderState::Active { .. }) => {
+            if !prompt.is_empty() {
+                theme.format_prompt(f, prompt)?;
+                write!(f, " ")?;
+            }
+            write!(f, "{}", ctx.detail.unwrap_or(""))
+        }
+        (RenderKind::TreeSelect, Some(text), _) => {
+            write!(
+                f,
+                "{} {}{} {}",
+                cursor,
+                "  ".repeat(ctx.depth),
+                tree_marker(flags),
+                text
+            )
+        }
+        (RenderKind::TreeMultiSelect, Some(text), _) => {
+            let check = match (flags.checked, flags.partial) {
+                (true, _) => "[x]",
+                (false, true) => "[~]",
+                (false, false) => "[ ]",
+            };
+            write!(
+                f,
+                "{} {}{} {} {}",
+                cursor,
+                "  ".repeat(ctx.depth),
+                tree_marker(flags),
+                check,
+                text
+            )
+        }
+        (RenderKind::ReviewEach, Some(text), _) => write!(f, "{}", text),
+        (RenderKind::ReviewEach, None, RenderState::Active { .. }) => {
+            match ctx.position {
+                Some((position, Some(total))) => write!(f, "{} ({}/{}) ", prompt, position, total)?,
+                Some((position, None)) => write!(f, "{} ({}) ", prompt, position)?,
+                None => write!(f, "{} ", prompt)?,
+            }
+            write!(f, "[{}] ", ctx.values.join(","))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the expansion marker of a tree node in the default themes.
+fn tree_marker(flags: RenderFlags) -> &'static str {
+    match (flags.branch, flags.expanded) {
+        (true, true) => "-",
+        (true, false) => "+",
+        (false, _) => " ",
+    }
+}
+
+/// Where paged prompts render the page indicator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "theme-file",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum PagingPosition {
+    /// Appended to the prompt line, the indicator isn't shown for prompts without a prompt.
+    Prompt,
+    /// On a line of its own below the items.
+    Footer,
+    /// Not at all.
+    Hidden,
+}
+
+/// How prompts signal rejected keys, values out of bounds and failed validations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "theme-file",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Bell {
+    /// Not at all, rejections are silent.
+    Off,
+    /// By writing the BEL character, which the terminal turns into a beep or its own flash.
+    Audible,
+    /// By briefly flashing the screen in reverse video.
+    Visual,
+}
+
+/// The brightness of the terminal background, which [`ColorfulTheme`] picks its colors for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "theme-file",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Background {
+    /// A dark background, with light text.
+    Dark,
+    /// A light background, with dark text.
+    Light,
+}
+
+impl Background {
+    /// Detects the background of the terminal.
+    ///
+    /// The `COLORFGBG` variable set by some terminals is consulted first. Otherwise the
+    /// terminal is asked for its background color (OSC 11) on Unix, if stderr is attended.
+    /// Returns `None` if the background can't be told. The result is cached for the
+    /// lifetime of the process.
+    ///
+    /// Asking the terminal may wait up to 100 ms for a reply, so the default theme only
+    /// consults `COLORFGBG`. Use [`ColorfulTheme::detect_background`] to opt in.
+    pub fn detect() -> Option<Background> {
+        static DETECTED: Lazy<Option<Background>> = Lazy::new(crate::background::detect);
+        *DETECTED
+    }
+}
+
+/// The state of a list item, passed to the [`ColorfulTheme::item_style`] closure.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ItemState {
+    /// The cursor is on the item.
+    pub active: bool,
+    /// The item is checked in a multi select prompt, or picked in a sort prompt.
+    pub checked: bool,
+    /// The item can't be selected, or its check can't be changed.
+    pub disabled: bool,
+    /// The item matches the search term of a fuzzy select prompt.
+    pub matched: bool,
+}
+
+/// Picks the style of list items from their state, see [`ColorfulTheme::item_style`].
+pub type ItemStyle = Arc<dyn Fn(ItemState) -> Style + Send + Sync>;
+
+/// The theme held by a prompt.
+///
+/// Prompts accept anything converting into this in `with_theme`. A borrowed theme ties
+/// the prompt to its lifetime, while a theme shared through an [`Arc`] (or moved in as a
+/// [`Box`]) lets the prompt be built in one function and kept or used elsewhere:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use dialoguer::{theme::ColorfulTheme, Confirm};
+///
+/// fn confirm(prompt: &str) -> Confirm<'static> {
+///     let mut confirm = Confirm::with_theme(Arc::new(ColorfulTheme::default()));
+///     confirm.with_prompt(prompt);
+///     confirm
+/// }
+/// ```
+#[derive(Clone)]
+pub enum ThemeRef<'a> {
+    /// A theme borrowed from the caller.
+    Borrowed(&'a dyn Theme),
+    /// A theme shared between prompts and threads.
+    Shared(Arc<dyn Theme + Send + Sync>),
+}
+
+impl<'a> Deref for ThemeRef<'a> {
+    type Target = dyn Theme + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            ThemeRef::Borrowed(theme) => *theme,
+            ThemeRef::Shared(theme) => &**theme,
+        }
+    }
+}
+
+impl<'a, T: Theme + 'a> From<&'a T> for ThemeRef<'a> {
+    fn from(theme: &'a T) -> Self {
+        ThemeRef::Borrowed(theme)
+    }
+}
+
+impl<'a> From<&'a dyn Theme> for ThemeRef<'a> {
+    fn from(theme: &'a dyn Theme) -> Self {
+        ThemeRef::Borrowed(theme)
+    }
+}
+
+impl<'a> From<&'a (dyn Theme + Sync)> for ThemeRef<'a> {
+    fn from(theme: &'a (dyn Theme + Sync)) -> Self {
+        ThemeRef::Borrowed(theme)
+    }
+}
+
+impl<T: Theme + Send + Sync + 'static> From<Arc<T>> for ThemeRef<'_> {
+    fn from(theme: Arc<T>) -> Self {
+        ThemeRef::Shared(theme)
+    }
+}
+
+impl From<Arc<dyn Theme + Send + Sync>> for ThemeRef<'_> {
+    fn from(theme: Arc<dyn Theme + Send + Sync>) -> Self {
+        ThemeRef::Shared(theme)
+    }
+}
+
+impl<T: Theme + Send + Sync + 'static> From<Box<T>> for ThemeRef<'_> {
+    fn from(theme: Box<T>) -> Self {
+        ThemeRef::Shared(Arc::from(theme as Box<dyn Theme + Send + Sync>))
+    }
+}
+
+impl From<Box<dyn Theme + Send + Sync>> for ThemeRef<'_> {
+    fn from(theme: Box<dyn Theme + Send + Sync>) -> Self {
+        ThemeRef::Shared(Arc::from(theme))
+    }
+}
+
+/// Returns early with the result of the inner theme, see [`Theme::inner_theme`].
+macro_rules! delegate {
+    ($self:ident . $method:ident($($arg:expr),*)) => {
+        if let Some(inner) = $self.inner_theme() {
+            return inner.$method($($arg),*);
+        }
+    };
+}
+
 /// Implements a theme for dialoguer.
-pub trait Theme {
+///
+/// Themes are `Sync`, so prompts borrowing them can be sent to other threads.
+pub trait Theme: Sync {
+    /// Returns the theme this one decorates, if any.
+    ///
+    /// Every method the theme doesn't implement itself delegates to the inner theme, so a
+    /// theme can wrap another one and override selectively. Only [`render`](Self::render)
+    /// keeps rendering through the methods of the wrapping theme, and hands prompts without
+    /// dedicated methods to the `render` of the inner theme. The default is `None`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::fmt;
+    ///
+    /// use dialoguer::theme::{ColorfulTheme, Theme};
+    ///
+    /// struct LoudErrors<T>(T);
+    ///
+    /// impl<T: Theme> Theme for LoudErrors<T> {
+    ///     fn inner_theme(&self) -> Option<&dyn Theme> {
+    ///         Some(&self.0)
+    ///     }
+    ///
+    ///     fn format_error(&self, f: &mut dyn fmt::Write, err: &str) -> fmt::Result {
+    ///         write!(f, "!! {}", err.to_uppercase())
+    ///     }
+    /// }
+    ///
+    /// let theme = LoudErrors(ColorfulTheme::default());
+    /// ```
+    #[inline]
+    fn inner_theme(&self) -> Option<&dyn Theme> {
+        None
+    }
+
+    /// Renders an element of a prompt.
+    ///
+    /// This is the entry point for errors, hints and the elements of all prompts. Unlike the
+    /// dedicated `format_*` methods it receives everything as a [`RenderContext`], which
+    /// grows new fields instead of new arguments as prompts gain capabilities, and prompts
+    /// like [`Toggle`](crate::Toggle) or [`Menu`](crate::Menu) are rendered only through it.
+    /// The default renders through the `format_*` methods, see [`render_with_methods`].
+    fn render(&self, f: &mut dyn fmt::Write, ctx: &RenderContext) -> fmt::Result {
+        render_with_methods(self, f, ctx)
+    }
+
+    /// Returns when the theme renders colors.
+    ///
+    /// Without colors all text styles are stripped from the rendered output, so every
+    /// prompt degrades consistently. The default is [`ColorMode::Auto`].
+    #[inline]
+    fn color_mode(&self) -> ColorMode {
+        delegate!(self.color_mode());
+        ColorMode::Auto
+    }
+
+    /// Returns the marker replacing the end of truncated text.
+    #[inline]
+    fn ellipsis(&self) -> &str {
+        delegate!(self.ellipsis());
+        "…"
+    }
+
+    /// Returns where paged prompts render the page indicator.
+    ///
+    /// The default is [`PagingPosition::Prompt`].
+    #[inline]
+    fn paging_position(&self) -> PagingPosition {
+        delegate!(self.paging_position());
+        PagingPosition::Prompt
+    }
+
+    /// Returns whether select and confirm prompts render on a single line.
+    ///
+    /// Compact select prompts show only the current item, which the arrow keys change,
+    /// see [`format_select_prompt_compact`](Self::format_select_prompt_compact). Compact
+    /// confirm prompts wait for 'Enter' and the arrow keys toggle the answer. This suits
+    /// prompts embedded in other output like build logs. The default is `false`.
+    #[inline]
+    fn compact(&self) -> bool {
+        delegate!(self.compact());
+        false
+    }
+
+    /// Returns how prompts signal keys they don't handle, values beyond their bounds and
+    /// input failing validation.
+    ///
+    /// The default is [`Bell::Off`].
+    #[inline]
+    fn bell(&self) -> Bell {
+        delegate!(self.bell());
+        Bell::Off
+    }
+
+    /// Formats the page indicator of paged prompts, `page` counts from 1.
+    #[inline]
+    fn format_paging_info(&self, f: &mut dyn fmt::Write, page: usize, pages: usize) -> fmt::Result {
+        delegate!(self.format_paging_info(f, page, pages));
+        write!(f, " [Page {}/{}] ", page, pages)
+    }
+
+    /// Formats the page indicator of paged prompts whose items are loaded on demand and
+    /// whose number of pages isn't known yet, `page` counts from 1.
+    #[inline]
+    fn format_paging_info_unknown(&self, f: &mut dyn fmt::Write, page: usize) -> fmt::Result {
+        delegate!(self.format_paging_info_unknown(f, page));
+        write!(f, " [Page {}/?] ", page)
+    }
+
+    /// Formats the cell of the scrollbar next to an item of a paged prompt.
+    ///
+    /// `thumb` is whether the row belongs to the thumb, which marks where the visible items
+    /// are in the list. Writing nothing, like the default, renders no scrollbar.
+    #[inline]
+    fn format_scrollbar(&self, f: &mut dyn fmt::Write, thumb: bool) -> fmt::Result {
+        delegate!(self.format_scrollbar(f, thumb));
+        Ok(())
+    }
+
+    /// Formats the number in front of an item of a numbered list prompt.
+    ///
+    /// `width` is the number of digits of the highest number, for right-aligning them.
+    #[inline]
+    fn format_item_number(
+        &self,
+        f: &mut dyn fmt::Write,
+        number: usize,
+        width: usize,
+    ) -> fmt::Result {
+        delegate!(self.format_item_number(f, number, width));
+        write!(f, "{:>width$}.", number, width = width)
+    }
+
+    /// Formats the digits typed to jump to an item of a numbered list prompt.
+    #[inline]
+    fn format_jump_input(&self, f: &mut dyn fmt::Write, digits: &str) -> fmt::Result {
+        delegate!(self.format_jump_input(f, digits));
+        write!(f, " [Go to {}] ", digits)
+    }
+
     /// Formats a prompt.
     #[inline]
     fn format_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        delegate!(self.format_prompt(f, prompt));
         write!(f, "{}:", prompt)
     }
 
     /// Formats out an error.
+    ///
+    /// All prompts report rejected input through this, like failed validations of
+    /// [`Input`](crate::Input), mismatching `Password` confirmations, unparsable numbers,
+    /// durations and addresses or duplicate tags, so styling it once covers all of them.
     #[inline]
     fn format_error(&self, f: &mut dyn fmt::Write, err: &str) -> fmt::Result {
+        delegate!(self.format_error(f, err));
         write!(f, "error: {}", err)
     }
 
+    /// Formats a hint line shown below a prompt, like `↑↓ move · enter confirm`.
+    ///
+    /// Writing nothing suppresses the line.
+    #[inline]
+    fn format_hint(&self, f: &mut dyn fmt::Write, hint: &str) -> fmt::Result {
+        delegate!(self.format_hint(f, hint));
+        write!(f, "{}", hint)
+    }
+
+    /// Formats the segment aligned to the right edge of a prompt line.
+    ///
+    /// Prompts use it for things like counters or key maps, the renderer pads the line
+    /// to the terminal width on every redraw. Writing nothing hides the segment.
+    fn format_right_segment(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        delegate!(self.format_right_segment(f, text));
+        write!(f, "{}", text)
+    }
+
+    /// Formats the report of any answered prompt, in place of the `*_selection` methods.
+    ///
+    /// `values` are the reported values as text, empty if nothing was selected or the
+    /// value is masked. Returning `None` leaves reports to the dedicated methods, which is
+    /// the default. Themes can return `Some` to render all reports alike, like as
+    /// `key=value` lines for logs, and suppress reports by writing nothing.
+    ///
+    /// ```rust
+    /// use std::fmt;
+    ///
+    /// use dialoguer::theme::Theme;
+    ///
+    /// struct LogReports;
+    ///
+    /// impl Theme for LogReports {
+    ///     fn format_report(
+    ///         &self,
+    ///         f: &mut dyn fmt::Write,
+    ///         prompt: &str,
+    ///         values: &[&str],
+    ///     ) -> Option<fmt::Result> {
+    ///         Some(write!(f, "{}={:?}", prompt, values.join(",")))
+    ///     }
+    /// }
+    /// ```
+    fn format_report(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        values: &[&str],
+    ) -> Option<fmt::Result> {
+        delegate!(self.format_report(f, prompt, values));
+        None
+    }
+
+    /// Formats the progress of a [`Wizard`](crate::Wizard) in front of a prompt.
+    ///
+    /// `step` counts from 1. Writing nothing hides the progress.
+    fn format_step(&self, f: &mut dyn fmt::Write, step: usize, steps: usize) -> fmt::Result {
+        delegate!(self.format_step(f, step, steps));
+        write!(f, "[{}/{}] ", step, steps)
+    }
+
+    /// Formats the time left until the timeout of a prompt in front of it.
+    ///
+    /// `remaining` is rounded up to whole seconds. Writing nothing hides the countdown.
+    fn format_countdown(&self, f: &mut dyn fmt::Write, remaining: Duration) -> fmt::Result {
+        delegate!(self.format_countdown(f, remaining));
+        write!(f, "({}s) ", remaining.as_secs())
+    }
+
     /// Formats a confirm prompt.
     fn format_confirm_prompt(
         &self,
@@ -26,6 +1149,7 @@ pub trait Theme {
         prompt: &str,
         default: Option<bool>,
     ) -> fmt::Result {
+        delegate!(self.format_confirm_prompt(f, prompt, default));
         if !prompt.is_empty() {
             write!(f, "{} ", &prompt)?;
         }
@@ -44,6 +1168,7 @@ pub trait Theme {
         prompt: &str,
         selection: Option<bool>,
     ) -> fmt::Result {
+        delegate!(self.format_confirm_prompt_selection(f, prompt, selection));
         let selection = selection.map(|b| if b { "yes" } else { "no" });
 
         match selection {
@@ -60,6 +1185,15 @@ pub trait Theme {
         }
     }
 
+    /// Formats the spinner shown while suggestions or completions are loading.
+    ///
+    /// `frame` counts up while loading, to animate the spinner.
+    fn format_loading(&self, f: &mut dyn fmt::Write, frame: usize) -> fmt::Result {
+        delegate!(self.format_loading(f, frame));
+        let spinner = ['|', '/', '-', '\\'][frame % 4];
+        write!(f, "{} loading", spinner)
+    }
+
     /// Formats an input prompt.
     fn format_input_prompt(
         &self,
@@ -67,6 +1201,7 @@ pub trait Theme {
         prompt: &str,
         default: Option<&str>,
     ) -> fmt::Result {
+        delegate!(self.format_input_prompt(f, prompt, default));
         match default {
             Some(default) if prompt.is_empty() => write!(f, "[{}]: ", default),
             Some(default) => write!(f, "{} [{}]: ", prompt, default),
@@ -82,13 +1217,33 @@ pub trait Theme {
         prompt: &str,
         sel: &str,
     ) -> fmt::Result {
+        delegate!(self.format_input_prompt_selection(f, prompt, sel));
         write!(f, "{}: {}", prompt, sel)
     }
 
+    /// Formats a sensitive value reported after interaction.
+    fn format_redacted_value(
+        &self,
+        f: &mut dyn fmt::Write,
+        value: &str,
+        redaction: Redaction,
+    ) -> fmt::Result {
+        delegate!(self.format_redacted_value(f, value, redaction));
+        let len = value.chars().count();
+        match redaction {
+            Redaction::LastChars(n) if n < len / 2 => {
+                let tail: String = value.chars().skip(len - n).collect();
+                write!(f, "****{}", tail)
+            }
+            _ => write!(f, "********"),
+        }
+    }
+
     /// Formats a password prompt.
     #[inline]
     #[cfg(feature = "password")]
     fn format_password_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        delegate!(self.format_password_prompt(f, prompt));
         self.format_input_prompt(f, prompt, None)
     }
 
@@ -100,12 +1255,14 @@ pub trait Theme {
         f: &mut dyn fmt::Write,
         prompt: &str,
     ) -> fmt::Result {
+        delegate!(self.format_password_prompt_selection(f, prompt));
         self.format_input_prompt_selection(f, prompt, "[hidden]")
     }
 
     /// Formats a select prompt.
     #[inline]
     fn format_select_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        delegate!(self.format_select_prompt(f, prompt));
         self.format_prompt(f, prompt)
     }
 
@@ -117,18 +1274,21 @@ pub trait Theme {
         prompt: &str,
         sel: &str,
     ) -> fmt::Result {
+        delegate!(self.format_select_prompt_selection(f, prompt, sel));
         self.format_input_prompt_selection(f, prompt, sel)
     }
 
     /// Formats a multi select prompt.
     #[inline]
     fn format_multi_select_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        delegate!(self.format_multi_select_prompt(f, prompt));
         self.format_prompt(f, prompt)
     }
 
     /// Formats a sort prompt.
     #[inline]
     fn format_sort_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        delegate!(self.format_sort_prompt(f, prompt));
         self.format_prompt(f, prompt)
     }
 
@@ -139,6 +1299,7 @@ pub trait Theme {
         prompt: &str,
         selections: &[&str],
     ) -> fmt::Result {
+        delegate!(self.format_multi_select_prompt_selection(f, prompt, selections));
         write!(f, "{}: ", prompt)?;
         for (idx, sel) in selections.iter().enumerate() {
             write!(f, "{}{}", if idx == 0 { "" } else { ", " }, sel)?;
@@ -154,6 +1315,7 @@ pub trait Theme {
         prompt: &str,
         selections: &[&str],
     ) -> fmt::Result {
+        delegate!(self.format_sort_prompt_selection(f, prompt, selections));
         self.format_multi_select_prompt_selection(f, prompt, selections)
     }
 
@@ -164,9 +1326,102 @@ pub trait Theme {
         text: &str,
         active: bool,
     ) -> fmt::Result {
+        delegate!(self.format_select_prompt_item(f, text, active));
         write!(f, "{} {}", if active { ">" } else { " " }, text)
     }
 
+    /// Formats a select prompt item which can't be selected.
+    #[inline]
+    fn format_select_prompt_disabled_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        delegate!(self.format_select_prompt_disabled_item(f, text, active));
+        write!(f, "{} {} (disabled)", if active { ">" } else { " " }, text)
+    }
+
+    /// Formats a select prompt of a [`compact`](Self::compact) theme.
+    ///
+    /// `text` is the current item, the `index` of which counts from 1.
+    fn format_select_prompt_compact(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        text: &str,
+        disabled: bool,
+        index: usize,
+        total: usize,
+    ) -> fmt::Result {
+        delegate!(self.format_select_prompt_compact(f, prompt, text, disabled, index, total));
+        if !prompt.is_empty() {
+            write!(f, "{} ", prompt)?;
+        }
+        write!(f, "< {} > ({}/{})", text, index, total)?;
+        if disabled {
+            write!(f, " (disabled)")?;
+        }
+        Ok(())
+    }
+
+    /// Formats a multi select prompt item which can't be checked.
+    #[inline]
+    fn format_multi_select_prompt_disabled_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        delegate!(self.format_multi_select_prompt_disabled_item(f, text, active));
+        write!(
+            f,
+            "{} [ ] {} (disabled)",
+            if active { ">" } else { " " },
+            text
+        )
+    }
+
+    /// Formats a multi select prompt item which is checked and can't be unchecked.
+    #[inline]
+    fn format_multi_select_prompt_locked_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        delegate!(self.format_multi_select_prompt_locked_item(f, text, active));
+        write!(
+            f,
+            "{} [x] {} (locked)",
+            if active { ">" } else { " " },
+            text
+        )
+    }
+
+    /// Formats the counter of checked items shown below a multi select prompt.
+    ///
+    /// `min` and `max` are the constraints on the number of checked items, if any. The
+    /// default renders like `2/5 selected, need at least 3`. Writing nothing hides the
+    /// counter.
+    fn format_multi_select_counter(
+        &self,
+        f: &mut dyn fmt::Write,
+        checked: usize,
+        total: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) -> fmt::Result {
+        delegate!(self.format_multi_select_counter(f, checked, total, min, max));
+        write!(f, "{}/{} selected", checked, total)?;
+
+        match (min, max) {
+            (Some(min), _) if checked < min => write!(f, ", need at least {}", min),
+            (_, Some(max)) if checked >= max => write!(f, ", at most {}", max),
+            _ => Ok(()),
+        }
+    }
+
     /// Formats a multi select prompt item.
     fn format_multi_select_prompt_item(
         &self,
@@ -175,6 +1430,7 @@ pub trait Theme {
         checked: bool,
         active: bool,
     ) -> fmt::Result {
+        delegate!(self.format_multi_select_prompt_item(f, text, checked, active));
         write!(
             f,
             "{} {}",
@@ -196,6 +1452,7 @@ pub trait Theme {
         picked: bool,
         active: bool,
     ) -> fmt::Result {
+        delegate!(self.format_sort_prompt_item(f, text, picked, active));
         write!(
             f,
             "{} {}",
@@ -219,25 +1476,41 @@ pub trait Theme {
         matcher: &SkimMatcherV2,
         search_term: &str,
     ) -> fmt::Result {
+        delegate!(self.format_fuzzy_select_prompt_item(
+            f,
+            text,
+            active,
+            highlight_matches,
+            matcher,
+            search_term
+        ));
         write!(f, "{} ", if active { ">" } else { " " })?;
 
         if highlight_matches {
-            if let Some((_score, indices)) = matcher.fuzzy_indices(text, &search_term) {
-                for (idx, c) in text.chars().into_iter().enumerate() {
-                    if indices.contains(&idx) {
-                        write!(f, "{}", style(c).for_stderr().bold())?;
-                    } else {
-                        write!(f, "{}", c)?;
-                    }
-                }
-
-                return Ok(());
+            if let Some((_score, indices)) = matcher.fuzzy_indices(text, search_term) {
+                return self.format_fuzzy_match(f, text, &fuzzy_match_ranges(text, &indices));
             }
         }
 
         write!(f, "{}", text)
     }
 
+    /// Formats the text of a fuzzy select item with the parts matching the search term.
+    ///
+    /// `matches` are the sorted byte ranges of `text` matching the search term, so themes
+    /// can style every run of matching characters at once. The default writes `text`
+    /// unstyled.
+    #[cfg(feature = "fuzzy-select")]
+    fn format_fuzzy_match(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[Range<usize>],
+    ) -> fmt::Result {
+        delegate!(self.format_fuzzy_match(f, text, matches));
+        write!(f, "{}", text)
+    }
+
     /// Formats a fuzzy select prompt.
     #[cfg(feature = "fuzzy-select")]
     fn format_fuzzy_select_prompt(
@@ -247,28 +1520,144 @@ pub trait Theme {
         search_term: &str,
         cursor_pos: usize,
     ) -> fmt::Result {
+        delegate!(self.format_fuzzy_select_prompt(f, prompt, search_term, cursor_pos));
         if !prompt.is_empty() {
             write!(f, "{} ", prompt,)?;
         }
 
-        if cursor_pos < search_term.len() {
-            let st_head = search_term[0..cursor_pos].to_string();
-            let st_tail = search_term[cursor_pos..search_term.len()].to_string();
-            let st_cursor = "|".to_string();
-            write!(f, "{}{}{}", st_head, st_cursor, st_tail)
+        let (st_head, st_tail) = search_term.split_at(byte_offset(search_term, cursor_pos));
+        write!(f, "{}|{}", st_head, st_tail)
+    }
+}
+
+/// Pads `text` to exactly `width` columns, truncating it with `ellipsis` if it is wider.
+fn fit_cell(text: &str, width: usize, ellipsis: &str) -> String {
+    if text_width(text) > width {
+        truncate_text(text, width, ellipsis).into_owned()
+    } else {
+        console::pad_str(text, width, console::Alignment::Left, None).into_owned()
+    }
+}
+
+/// Paints `text` on the background of a color swatch.
+///
+/// Palette colors use their index so they match the terminal's own palette. Without color
+/// support the swatch falls back to the dotted `placeholder`.
+fn swatch(color: Rgb, ansi: Option<u8>, text: &str, placeholder: &str) -> String {
+    if !console::colors_enabled_stderr() {
+        return if text.trim().is_empty() {
+            placeholder.into()
         } else {
-            let cursor = "|".to_string();
-            write!(f, "{}{}", search_term.to_string(), cursor)
+            text.into()
+        };
+    }
+
+    let background = match ansi {
+        Some(idx) => format!("48;5;{}", idx),
+        None => format!("48;2;{};{};{}", color.r, color.g, color.b),
+    };
+    // Keep the marker readable on light and dark swatches alike.
+    let luma = 299 * color.r as u32 + 587 * color.g as u32 + 114 * color.b as u32;
+    let foreground = if luma > 128_000 { "30" } else { "97" };
+
+    format!("\x1b[{};{}m{}\x1b[0m", background, foreground, text)
+}
+
+/// Returns how many of the `width` slider cells are filled at the given `ratio`.
+fn slider_filled_width(ratio: f64, width: usize) -> usize {
+    ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width)
+}
+
+/// Merges the char `indices` of a fuzzy match into sorted byte ranges of `text`.
+#[cfg(feature = "fuzzy-select")]
+fn fuzzy_match_ranges(text: &str, indices: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+
+    for (idx, (start, c)) in text.char_indices().enumerate() {
+        if !indices.contains(&idx) {
+            continue;
+        }
+
+        let end = start + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == start => last.end = end,
+            _ => ranges.push(start..end),
         }
     }
+
+    ranges
+}
+
+/// Writes `text` in `style` with the byte ranges in `matches` painted in `highlight`.
+#[cfg(feature = "fuzzy-select")]
+fn write_fuzzy_match(
+    f: &mut dyn fmt::Write,
+    text: &str,
+    matches: &[Range<usize>],
+    style: &Style,
+    highlight: &Style,
+) -> fmt::Result {
+    let mut pos = 0;
+
+    for range in matches {
+        write!(f, "{}", style.apply_to(&text[pos..range.start]))?;
+        write!(f, "{}", highlight.apply_to(&text[range.clone()]))?;
+        pos = range.end;
+    }
+
+    write!(f, "{}", style.apply_to(&text[pos..]))
+}
+
+/// The default theme.
+pub struct SimpleTheme;
+
+impl Theme for SimpleTheme {
+    #[cfg(feature = "fuzzy-select")]
+    fn format_fuzzy_match(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[Range<usize>],
+    ) -> fmt::Result {
+        write_fuzzy_match(
+            f,
+            text,
+            matches,
+            &Style::new(),
+            &Style::new().for_stderr().bold(),
+        )
+    }
 }
 
-/// The default theme.
-pub struct SimpleTheme;
+/// A theme like [`SimpleTheme`] rendering only ASCII characters.
+///
+/// Truncated text ends with `...` instead of `…`. This suits serial consoles, legacy
+/// code pages and logs, see [`ColorfulTheme::ascii`] for a colorful variant.
+pub struct AsciiTheme;
+
+impl Theme for AsciiTheme {
+    fn inner_theme(&self) -> Option<&dyn Theme> {
+        Some(&SimpleTheme)
+    }
+
+    fn ellipsis(&self) -> &str {
+        "..."
+    }
 
-impl Theme for SimpleTheme {}
+    fn render(&self, f: &mut dyn fmt::Write, ctx: &RenderContext) -> fmt::Result {
+        match (ctx.kind, ctx.item, ctx.color) {
+            (RenderKind::ColorSelect, Some(_), Some((color, ansi))) => {
+                let text = if ctx.flags.active { "[]" } else { "  " };
+                write!(f, "{}", swatch(color, ansi, text, ".."))
+            }
+            _ => render_with_methods(self, f, ctx),
+        }
+    }
+}
 
 /// A colorful theme
+///
+/// Use [`ColorfulTheme::ascii`] for a variant restricted to ASCII characters.
 pub struct ColorfulTheme {
     /// The style for default values
     pub defaults_style: Style,
@@ -294,6 +1683,10 @@ pub struct ColorfulTheme {
     pub active_item_style: Style,
     /// The style for inactive items
     pub inactive_item_style: Style,
+    /// The style for inactive items which can't be selected
+    pub disabled_item_style: Style,
+    /// The style for inactive checked items which can't be unchecked
+    pub locked_item_style: Style,
     /// Active item in select prefix value and style
     pub active_item_prefix: StyledObject<String>,
     /// Inctive item in select prefix value and style
@@ -314,41 +1707,978 @@ pub struct ColorfulTheme {
     pub fuzzy_match_highlight_style: Style,
     /// Show the selections from certain prompts inline
     pub inline_selections: bool,
+    /// When to render colors
+    pub color_mode: ColorMode,
+    /// Where to render the page indicator of paged prompts
+    pub paging_position: PagingPosition,
+    /// The background the default colors were picked for
+    pub background: Background,
+    /// Render select and confirm prompts on a single line
+    pub compact: bool,
+    /// Render a scrollbar next to the items of paged prompts
+    pub scrollbar: bool,
+    /// Layout of prompts, replacing the built-in one
+    pub prompt_template: Option<Template>,
+    /// Layout of select, multi select and sort items, replacing the built-in one
+    pub item_template: Option<Template>,
+    /// Layout of prompts after selection, replacing the built-in one
+    pub report_template: Option<Template>,
+    /// Picks the style of list items from their state, replacing the item styles
+    pub item_style: Option<ItemStyle>,
+    /// Render only ASCII characters, also in place of the built-in markers and bars
+    pub ascii: bool,
+    /// How rejected keys and input are signaled
+    pub bell: Bell,
+}
+
+impl Default for ColorfulTheme {
+    fn default() -> ColorfulTheme {
+        ColorfulThemeBuilder::default().build()
+    }
+}
+
+impl ColorfulTheme {
+    /// Creates a builder starting out with the default symbols and styles.
+    ///
+    /// Unlike a struct literal this keeps compiling when fields are added to the theme.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dialoguer::{console::Style, theme::ColorfulTheme};
+    ///
+    /// let theme = ColorfulTheme::builder()
+    ///     .prompt_prefix("»")
+    ///     .prompt_prefix_style(Style::new().for_stderr().magenta())
+    ///     .active_item_style(Style::new().for_stderr().bold())
+    ///     .build();
+    /// ```
+    pub fn builder() -> ColorfulThemeBuilder {
+        ColorfulThemeBuilder::default()
+    }
+
+    /// Creates the default theme restricted to ASCII characters.
+    ///
+    /// See [`ColorfulThemeBuilder::ascii`].
+    pub fn ascii() -> ColorfulTheme {
+        ColorfulTheme::builder().ascii(true).build()
+    }
+
+    /// Creates the default theme with colors for the background reported by the terminal.
+    ///
+    /// See [`ColorfulThemeBuilder::detect_background`].
+    pub fn detect_background() -> ColorfulTheme {
+        ColorfulTheme::builder().detect_background().build()
+    }
+
+    /// Returns `unicode`, or `ascii` if the theme is restricted to ASCII.
+    fn glyph(&self, unicode: &'static str, ascii: &'static str) -> &'static str {
+        if self.ascii {
+            ascii
+        } else {
+            unicode
+        }
+    }
+
+    /// Returns the style of an item in `state`, which is `default` without an item style
+    /// closure.
+    fn style_item(&self, state: ItemState, default: &Style) -> Style {
+        match &self.item_style {
+            Some(item_style) => item_style(state),
+            None => default.clone(),
+        }
+    }
+
+    /// Writes an item after its marker, laid out by the item template if there is one.
+    fn write_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        cursor: &dyn fmt::Display,
+        item: &dyn fmt::Display,
+    ) -> fmt::Result {
+        match &self.item_template {
+            Some(template) => template.render(f, &[("cursor", cursor), ("item", item)]),
+            None => write!(f, "{} {}", cursor, item),
+        }
+    }
+
+    /// Writes the prefix and the prompt in front of the suffix, unless the prompt is empty.
+    fn write_prompt_start(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        if prompt.is_empty() {
+            return Ok(());
+        }
+
+        write!(
+            f,
+            "{} {} ",
+            &self.prompt_prefix,
+            self.prompt_style.apply_to(prompt)
+        )
+    }
+
+    /// Writes a node of a tree prompt, with a check mark in tree multi select prompts.
+    fn write_tree_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        ctx: &RenderContext,
+        text: &str,
+    ) -> fmt::Result {
+        let flags = ctx.flags;
+        let (prefix, text) = if flags.active {
+            (
+                &self.active_item_prefix,
+                self.active_item_style.apply_to(text),
+            )
+        } else {
+            (
+                &self.inactive_item_prefix,
+                self.inactive_item_style.apply_to(text),
+            )
+        };
+
+        let marker = match (flags.branch, flags.expanded) {
+            (true, true) => self.glyph("▾", "v"),
+            (true, false) => self.glyph("▸", ">"),
+            (false, _) => " ",
+        };
+
+        write!(
+            f,
+            "{} {}{} ",
+            prefix,
+            "  ".repeat(ctx.depth),
+            self.hint_style.apply_to(marker)
+        )?;
+
+        if ctx.kind == RenderKind::TreeMultiSelect {
+            match (flags.checked, flags.partial) {
+                (true, _) => write!(f, "{} ", &self.checked_item_prefix)?,
+                (false, true) => write!(f, "{} ", self.values_style.apply_to("~"))?,
+                (false, false) => write!(f, "{} ", &self.unchecked_item_prefix)?,
+            }
+        }
+
+        write!(f, "{}", text)
+    }
+
+    /// Writes a prompt after selection with the report template, which must be set.
+    fn write_report(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        value: &dyn fmt::Display,
+    ) -> fmt::Result {
+        match &self.report_template {
+            Some(template) => template.render(
+                f,
+                &[
+                    ("prefix", &self.success_prefix),
+                    ("prompt", &self.prompt_style.apply_to(prompt)),
+                    ("separator", &self.success_suffix),
+                    ("value", value),
+                ],
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+macro_rules! style_setters {
+    ($($(#[$attr:meta])* $name:ident;)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name(mut self, style: Style) -> Self {
+                self.$name = Some(style);
+                self
+            }
+        )*
+    };
+}
+
+macro_rules! symbol_setters {
+    ($($(#[$attr:meta])* $name:ident, $style:ident;)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name<S: Into<String>>(mut self, symbol: S) -> Self {
+                self.$name.0 = Some(symbol.into());
+                self
+            }
+
+            /// Sets the style of the symbol.
+            pub fn $style(mut self, style: Style) -> Self {
+                self.$name.1 = Some(style);
+                self
+            }
+        )*
+    };
+}
+
+/// Builds a [`ColorfulTheme`] with customized symbols and styles.
+///
+/// Created by [`ColorfulTheme::builder`]. Every symbol has a setter for its text and one
+/// for its style, like [`prompt_prefix`](Self::prompt_prefix) and
+/// [`prompt_prefix_style`](Self::prompt_prefix_style).
+#[derive(Clone)]
+pub struct ColorfulThemeBuilder {
+    // Symbols and styles which weren't customized are `None`, their defaults depend on
+    // the background and whether the theme is restricted to ASCII.
+    defaults_style: Option<Style>,
+    prompt_style: Option<Style>,
+    prompt_prefix: (Option<String>, Option<Style>),
+    prompt_suffix: (Option<String>, Option<Style>),
+    success_prefix: (Option<String>, Option<Style>),
+    success_suffix: (Option<String>, Option<Style>),
+    error_prefix: (Option<String>, Option<Style>),
+    error_style: Option<Style>,
+    hint_style: Option<Style>,
+    values_style: Option<Style>,
+    active_item_style: Option<Style>,
+    inactive_item_style: Option<Style>,
+    disabled_item_style: Option<Style>,
+    locked_item_style: Option<Style>,
+    active_item_prefix: (Option<String>, Option<Style>),
+    inactive_item_prefix: (Option<String>, Option<Style>),
+    checked_item_prefix: (Option<String>, Option<Style>),
+    unchecked_item_prefix: (Option<String>, Option<Style>),
+    picked_item_prefix: (Option<String>, Option<Style>),
+    unpicked_item_prefix: (Option<String>, Option<Style>),
+    #[cfg(feature = "fuzzy-select")]
+    fuzzy_cursor_style: Option<Style>,
+    #[cfg(feature = "fuzzy-select")]
+    fuzzy_match_highlight_style: Option<Style>,
+    inline_selections: bool,
+    color_mode: ColorMode,
+    paging_position: PagingPosition,
+    background: Background,
+    compact: bool,
+    scrollbar: bool,
+    prompt_template: Option<Template>,
+    item_template: Option<Template>,
+    report_template: Option<Template>,
+    item_style: Option<ItemStyle>,
+    ascii: bool,
+    bell: Bell,
+}
+
+impl Default for ColorfulThemeBuilder {
+    fn default() -> Self {
+        ColorfulThemeBuilder {
+            defaults_style: None,
+            prompt_style: None,
+            prompt_prefix: (None, None),
+            prompt_suffix: (None, None),
+            success_prefix: (None, None),
+            success_suffix: (None, None),
+            error_prefix: (None, None),
+            error_style: None,
+            hint_style: None,
+            values_style: None,
+            active_item_style: None,
+            inactive_item_style: None,
+            disabled_item_style: None,
+            locked_item_style: None,
+            active_item_prefix: (None, None),
+            inactive_item_prefix: (None, None),
+            checked_item_prefix: (None, None),
+            unchecked_item_prefix: (None, None),
+            picked_item_prefix: (None, None),
+            unpicked_item_prefix: (None, None),
+            #[cfg(feature = "fuzzy-select")]
+            fuzzy_cursor_style: None,
+            #[cfg(feature = "fuzzy-select")]
+            fuzzy_match_highlight_style: None,
+            inline_selections: true,
+            color_mode: ColorMode::Auto,
+            paging_position: PagingPosition::Prompt,
+            background: crate::background::from_env().unwrap_or(Background::Dark),
+            compact: false,
+            scrollbar: false,
+            prompt_template: None,
+            item_template: None,
+            report_template: None,
+            item_style: None,
+            ascii: crate::is_legacy_console(),
+            bell: Bell::Off,
+        }
+    }
+}
+
+impl ColorfulThemeBuilder {
+    style_setters! {
+        /// Sets the style for default values.
+        defaults_style;
+        /// Sets the style for prompts.
+        prompt_style;
+        /// Sets the style for error messages.
+        error_style;
+        /// Sets the style for hints.
+        hint_style;
+        /// Sets the style for values on prompt success.
+        values_style;
+        /// Sets the style for active items.
+        active_item_style;
+        /// Sets the style for inactive items.
+        inactive_item_style;
+        /// Sets the style for items which can't be selected.
+        disabled_item_style;
+        /// Sets the style for checked items which can't be unchecked.
+        locked_item_style;
+    }
+
+    #[cfg(feature = "fuzzy-select")]
+    style_setters! {
+        /// Sets the style of the cursor in a fuzzy select prompt.
+        fuzzy_cursor_style;
+        /// Sets the style for highlighting matched characters in a fuzzy select prompt.
+        fuzzy_match_highlight_style;
+    }
+
+    symbol_setters! {
+        /// Sets the symbol in front of prompts.
+        prompt_prefix, prompt_prefix_style;
+        /// Sets the symbol behind prompts.
+        prompt_suffix, prompt_suffix_style;
+        /// Sets the symbol in front of prompts on success.
+        success_prefix, success_prefix_style;
+        /// Sets the symbol behind prompts on success.
+        success_suffix, success_suffix_style;
+        /// Sets the symbol in front of error messages.
+        error_prefix, error_prefix_style;
+        /// Sets the symbol in front of the active item in selections.
+        active_item_prefix, active_item_prefix_style;
+        /// Sets the symbol in front of inactive items in selections.
+        inactive_item_prefix, inactive_item_prefix_style;
+        /// Sets the symbol in front of checked items in multi selections.
+        checked_item_prefix, checked_item_prefix_style;
+        /// Sets the symbol in front of unchecked items in multi selections.
+        unchecked_item_prefix, unchecked_item_prefix_style;
+        /// Sets the symbol in front of the picked item in sort prompts.
+        picked_item_prefix, picked_item_prefix_style;
+        /// Sets the symbol in front of items not picked in sort prompts.
+        unpicked_item_prefix, unpicked_item_prefix_style;
+    }
+
+    /// Indicates whether the selections of certain prompts are shown inline.
+    ///
+    /// The default is to show them inline.
+    pub fn inline_selections(mut self, val: bool) -> Self {
+        self.inline_selections = val;
+        self
+    }
+
+    /// Sets when the theme renders colors.
+    ///
+    /// The default is [`ColorMode::Auto`].
+    pub fn color_mode(mut self, val: ColorMode) -> Self {
+        self.color_mode = val;
+        self
+    }
+
+    /// Sets where paged prompts render the page indicator.
+    ///
+    /// The default is [`PagingPosition::Prompt`].
+    pub fn paging_position(mut self, val: PagingPosition) -> Self {
+        self.paging_position = val;
+        self
+    }
+
+    /// Indicates whether select and confirm prompts render on a single line.
+    ///
+    /// See [`Theme::compact`]. The default is to render them regularly.
+    pub fn compact(mut self, val: bool) -> Self {
+        self.compact = val;
+        self
+    }
+
+    /// Indicates whether prompts ring the bell on keys they don't handle, values beyond
+    /// their bounds and input failing validation.
+    ///
+    /// See [`Theme::bell`]. The default is to reject silently.
+    pub fn bell(mut self, val: bool) -> Self {
+        self.bell = if val { Bell::Audible } else { Bell::Off };
+        self
+    }
+
+    /// Indicates whether prompts flash the screen instead of ringing the bell, see
+    /// [`bell`](Self::bell).
+    pub fn visual_bell(mut self, val: bool) -> Self {
+        self.bell = if val { Bell::Visual } else { Bell::Off };
+        self
+    }
+
+    /// Indicates whether paged prompts render a scrollbar at the right edge of the items.
+    ///
+    /// The thumb is styled like active items and the track like hints. The default is to
+    /// render no scrollbar.
+    pub fn scrollbar(mut self, val: bool) -> Self {
+        self.scrollbar = val;
+        self
+    }
+
+    /// Sets the layout of prompts.
+    ///
+    /// The fields are `{prefix}`, `{prompt}` and `{suffix}`, like in
+    /// `"{prefix} {prompt} {suffix}"`. The default is the built-in layout, which also
+    /// leaves out the prefix for empty prompts.
+    pub fn prompt_template(mut self, val: Template) -> Self {
+        self.prompt_template = Some(val);
+        self
+    }
+
+    /// Sets the layout of select, multi select and sort items.
+    ///
+    /// The fields are `{cursor}`, the active, checked or picked marker, and `{item}`, like
+    /// in `"{cursor} {item}"`. The default is the built-in layout.
+    pub fn item_template(mut self, val: Template) -> Self {
+        self.item_template = Some(val);
+        self
+    }
+
+    /// Sets the layout of prompts after selection.
+    ///
+    /// The fields are `{prefix}`, `{prompt}`, `{separator}` and `{value}`, like in
+    /// `"{prefix} {prompt} {separator} {value}"`. The default is the built-in layout,
+    /// which also leaves out the prefix for empty prompts.
+    pub fn report_template(mut self, val: Template) -> Self {
+        self.report_template = Some(val);
+        self
+    }
+
+    /// Sets a closure picking the style of list items from their [`ItemState`].
+    ///
+    /// It replaces the active, inactive, disabled and locked item styles for the items of
+    /// select, multi select, sort and fuzzy select prompts, so conditional colors don't
+    /// need a custom [`Theme`]:
+    ///
+    /// ```rust
+    /// use dialoguer::{console::Style, theme::ColorfulTheme};
+    ///
+    /// let theme = ColorfulTheme::builder()
+    ///     .item_style(|state| match (state.active, state.checked) {
+    ///         (true, _) => Style::new().for_stderr().cyan().bold(),
+    ///         (false, true) => Style::new().for_stderr().green(),
+    ///         (false, false) => Style::new().for_stderr(),
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn item_style<F>(mut self, val: F) -> Self
+    where
+        F: Fn(ItemState) -> Style + Send + Sync + 'static,
+    {
+        self.item_style = Some(Arc::new(val));
+        self
+    }
+
+    /// Sets the background the default colors are picked for.
+    ///
+    /// On light backgrounds yellow and cyan are replaced with darker colors which stay
+    /// readable. Only the styles which weren't customized are affected, whether they
+    /// were customized before or after. The default is the background reported by the
+    /// `COLORFGBG` variable, or [`Background::Dark`] without it.
+    pub fn background(mut self, val: Background) -> Self {
+        self.background = val;
+        self
+    }
+
+    /// Sets the background to the one reported by [`Background::detect`], keeping the
+    /// current one if it can't be detected.
+    ///
+    /// Unlike the default, this asks the terminal for its background color, which may
+    /// take up to 100 ms on terminals not answering.
+    pub fn detect_background(self) -> Self {
+        match Background::detect() {
+            Some(val) => self.background(val),
+            None => self,
+        }
+    }
+
+    /// Indicates whether the theme renders only ASCII characters.
+    ///
+    /// Enabling this replaces the symbols which weren't customized with ASCII
+    /// equivalents, whether they were customized before or after. This suits serial
+    /// consoles, legacy code pages and logs. The default is to render Unicode symbols,
+    /// unless the [legacy console mode](crate::set_legacy_console) is on.
+    pub fn ascii(mut self, val: bool) -> Self {
+        self.ascii = val;
+        self
+    }
+
+    /// Builds the theme.
+    pub fn build(self) -> ColorfulTheme {
+        let light = self.background == Background::Light;
+        let ascii = self.ascii;
+        let glyph = |unicode: &str, fallback: &str| if ascii { fallback } else { unicode }.into();
+        let plain = || Style::new().for_stderr();
+        let accent = || {
+            if light {
+                plain().blue()
+            } else {
+                plain().cyan()
+            }
+        };
+        let style = |style: Option<Style>, default: Style| style.unwrap_or(default);
+        let symbol = |(symbol, style): (Option<String>, Option<Style>),
+                      default: String,
+                      default_style: Style| {
+            style
+                .unwrap_or(default_style)
+                .apply_to(symbol.unwrap_or(default))
+        };
+
+        ColorfulTheme {
+            defaults_style: style(self.defaults_style, accent()),
+            prompt_style: style(self.prompt_style, plain().bold()),
+            prompt_prefix: symbol(
+                self.prompt_prefix,
+                "?".into(),
+                if light {
+                    plain().magenta()
+                } else {
+                    plain().yellow()
+                },
+            ),
+            prompt_suffix: symbol(
+                self.prompt_suffix,
+                glyph("›", ">"),
+                plain().black().bright(),
+            ),
+            success_prefix: symbol(self.success_prefix, glyph("✔", "v"), plain().green()),
+            success_suffix: symbol(
+                self.success_suffix,
+                glyph("·", "-"),
+                plain().black().bright(),
+            ),
+            error_prefix: symbol(self.error_prefix, glyph("✘", "x"), plain().red()),
+            error_style: style(self.error_style, plain().red()),
+            hint_style: style(self.hint_style, plain().black().bright()),
+            values_style: style(self.values_style, plain().green()),
+            active_item_style: style(self.active_item_style, accent()),
+            inactive_item_style: style(self.inactive_item_style, plain()),
+            disabled_item_style: style(
+                self.disabled_item_style,
+                plain().black().bright().strikethrough(),
+            ),
+            locked_item_style: style(self.locked_item_style, plain().black().bright()),
+            active_item_prefix: symbol(self.active_item_prefix, glyph("❯", ">"), plain().green()),
+            inactive_item_prefix: symbol(self.inactive_item_prefix, " ".into(), plain()),
+            checked_item_prefix: symbol(
+                self.checked_item_prefix,
+                glyph("✔", "[x]"),
+                plain().green(),
+            ),
+            unchecked_item_prefix: symbol(
+                self.unchecked_item_prefix,
+                glyph("✔", "[ ]"),
+                plain().black(),
+            ),
+            picked_item_prefix: symbol(self.picked_item_prefix, glyph("❯", ">"), plain().green()),
+            unpicked_item_prefix: symbol(self.unpicked_item_prefix, " ".into(), plain()),
+            #[cfg(feature = "fuzzy-select")]
+            fuzzy_cursor_style: style(
+                self.fuzzy_cursor_style,
+                if light {
+                    plain().white().on_black()
+                } else {
+                    plain().black().on_white()
+                },
+            ),
+            #[cfg(feature = "fuzzy-select")]
+            fuzzy_match_highlight_style: style(
+                self.fuzzy_match_highlight_style,
+                if light {
+                    plain().bold().magenta()
+                } else {
+                    plain().bold().yellow()
+                },
+            ),
+            inline_selections: self.inline_selections,
+            color_mode: self.color_mode,
+            paging_position: self.paging_position,
+            background: self.background,
+            compact: self.compact,
+            scrollbar: self.scrollbar,
+            prompt_template: self.prompt_template,
+            item_template: self.item_template,
+            report_template: self.report_template,
+            item_style: self.item_style,
+            ascii: self.ascii,
+            bell: self.bell,
+        }
+    }
 }
 
-impl Default for ColorfulTheme {
-    fn default() -> ColorfulTheme {
-        ColorfulTheme {
-            defaults_style: Style::new().for_stderr().cyan(),
-            prompt_style: Style::new().for_stderr().bold(),
-            prompt_prefix: style("?".to_string()).for_stderr().yellow(),
-            prompt_suffix: style("›".to_string()).for_stderr().black().bright(),
-            success_prefix: style("✔".to_string()).for_stderr().green(),
-            success_suffix: style("·".to_string()).for_stderr().black().bright(),
-            error_prefix: style("✘".to_string()).for_stderr().red(),
-            error_style: Style::new().for_stderr().red(),
-            hint_style: Style::new().for_stderr().black().bright(),
-            values_style: Style::new().for_stderr().green(),
-            active_item_style: Style::new().for_stderr().cyan(),
-            inactive_item_style: Style::new().for_stderr(),
-            active_item_prefix: style("❯".to_string()).for_stderr().green(),
-            inactive_item_prefix: style(" ".to_string()).for_stderr(),
-            checked_item_prefix: style("✔".to_string()).for_stderr().green(),
-            unchecked_item_prefix: style("✔".to_string()).for_stderr().black(),
-            picked_item_prefix: style("❯".to_string()).for_stderr().green(),
-            unpicked_item_prefix: style(" ".to_string()).for_stderr(),
-            #[cfg(feature = "fuzzy-select")]
-            fuzzy_cursor_style: Style::new().for_stderr().black().on_white(),
-            #[cfg(feature = "fuzzy-select")]
-            fuzzy_match_highlight_style: Style::new().for_stderr().bold().yellow(),
-            inline_selections: true,
+impl Theme for ColorfulTheme {
+    fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    fn ellipsis(&self) -> &str {
+        self.glyph("…", "...")
+    }
+
+    fn paging_position(&self) -> PagingPosition {
+        self.paging_position
+    }
+
+    fn compact(&self) -> bool {
+        self.compact
+    }
+
+    fn bell(&self) -> Bell {
+        self.bell
+    }
+
+    /// Renders the prompts without dedicated methods in the styles of the theme.
+    fn render(&self, f: &mut dyn fmt::Write, ctx: &RenderContext) -> fmt::Result {
+        if ctx.kind.has_methods() {
+            return render_with_methods(self, f, ctx);
+        }
+
+        write_progress(self, f, ctx)?;
+        let prompt = ctx.prompt;
+        let flags = ctx.flags;
+        match (ctx.kind, ctx.item, ctx.state) {
+            (RenderKind::Toggle, None, RenderState::Active { .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                let picked = self.active_item_style.clone().underlined();
+                let disabled = ctx.values.first().unwrap_or(&"");
+                let enabled = ctx.values.get(1).unwrap_or(&"");
+                let (disabled, enabled) = if ctx.choice == Some(true) {
+                    (self.hint_style.apply_to(disabled), picked.apply_to(enabled))
+                } else {
+                    (picked.apply_to(disabled), self.hint_style.apply_to(enabled))
+                };
+
+                write!(
+                    f,
+                    "{} {} {} {}",
+                    &self.prompt_suffix,
+                    disabled,
+                    self.hint_style.apply_to("/"),
+                    enabled
+                )
+            }
+            (RenderKind::ColorSelect, Some(_), _) => match ctx.color {
+                Some((color, ansi)) => {
+                    let text = if flags.active { "[]" } else { "  " };
+                    write!(f, "{}", swatch(color, ansi, text, self.glyph("··", "..")))
+                }
+                None => Ok(()),
+            },
+            (RenderKind::ColorSelect, None, RenderState::Active { input, .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                match ctx.color {
+                    _ if flags.editing => write!(
+                        f,
+                        "{} {}{}",
+                        &self.prompt_suffix,
+                        input,
+                        self.hint_style.apply_to("_")
+                    ),
+                    Some((color, _)) => write!(
+                        f,
+                        "{} {} {}",
+                        &self.prompt_suffix,
+                        self.values_style.apply_to(color),
+                        self.hint_style.apply_to("(tab for hex)")
+                    ),
+                    None => write!(f, "{}", &self.prompt_suffix),
+                }
+            }
+            (RenderKind::Autocomplete, Some(text), _) => {
+                if flags.active {
+                    write!(
+                        f,
+                        "{} {}",
+                        self.active_item_prefix,
+                        self.active_item_style.apply_to(text)
+                    )
+                } else {
+                    write!(
+                        f,
+                        "{} {}",
+                        self.inactive_item_prefix,
+                        self.hint_style.apply_to(text)
+                    )
+                }
+            }
+            (RenderKind::Autocomplete, None, RenderState::Active { input, cursor, .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                let head: String = input.chars().take(cursor).collect();
+                let under = input.chars().nth(cursor).unwrap_or(' ');
+                let tail: String = input.chars().skip(cursor + 1).collect();
+
+                write!(
+                    f,
+                    "{} {}{}{}",
+                    &self.prompt_suffix,
+                    head,
+                    style(under).for_stderr().reverse(),
+                    tail
+                )
+            }
+            (RenderKind::GridSelect, Some(text), _) => {
+                if flags.active {
+                    write!(
+                        f,
+                        "{}",
+                        self.active_item_style
+                            .clone()
+                            .reverse()
+                            .apply_to(format!(" {} ", text))
+                    )
+                } else {
+                    write!(f, " {} ", self.inactive_item_style.apply_to(text))
+                }
+            }
+            (RenderKind::EditList, Some(text), _) if flags.disabled => write!(
+                f,
+                "{} {}",
+                self.inactive_item_prefix,
+                self.hint_style.apply_to(text)
+            ),
+            (RenderKind::KeyPrompt, None, RenderState::Active { .. }) => {
+                write!(
+                    f,
+                    "{} {}",
+                    &self.prompt_prefix,
+                    self.prompt_style.apply_to(prompt)
+                )?;
+
+                if !ctx.values.is_empty() {
+                    write!(
+                        f,
+                        " {}",
+                        self.hint_style
+                            .apply_to(format!("({})", ctx.values.join("/")))
+                    )?;
+                }
+
+                Ok(())
+            }
+            (RenderKind::NumberInput, None, RenderState::Active { input, .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                write!(
+                    f,
+                    "{} {}",
+                    &self.prompt_suffix,
+                    self.active_item_style.apply_to(input)
+                )
+            }
+            (RenderKind::PinInput, None, RenderState::Active { input, .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                write!(f, "{}", &self.prompt_suffix)?;
+
+                let entered = input.chars().count();
+                let cells = input.chars().map(Some).chain(std::iter::repeat(None));
+                for (idx, chr) in cells.take(ctx.length.unwrap_or(0)).enumerate() {
+                    let cell = match chr {
+                        Some(_) if flags.masked => self.glyph("•", "*").to_string(),
+                        Some(chr) => chr.to_string(),
+                        None => "_".to_string(),
+                    };
+
+                    if idx == entered {
+                        write!(f, " {}", self.active_item_style.apply_to(cell))?;
+                    } else if chr.is_some() {
+                        write!(f, " {}", self.values_style.apply_to(cell))?;
+                    } else {
+                        write!(f, " {}", self.hint_style.apply_to(cell))?;
+                    }
+                }
+
+                Ok(())
+            }
+            (RenderKind::IpInput, None, RenderState::Active { input, .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                write!(f, "{} ", &self.prompt_suffix)?;
+
+                if flags.invalid {
+                    write!(f, "{}", self.error_style.apply_to(input))?;
+                } else {
+                    write!(f, "{}", self.values_style.apply_to(input))?;
+                }
+
+                if let Some(completion) = ctx.detail {
+                    write!(f, "{}", self.hint_style.apply_to(completion))?;
+                }
+
+                Ok(())
+            }
+            (RenderKind::DurationInput, None, RenderState::Active { input, .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                write!(f, "{} {}", &self.prompt_suffix, input)?;
+
+                if let Some(description) = ctx.detail {
+                    write!(f, " {}", self.hint_style.apply_to(description))?;
+                }
+
+                Ok(())
+            }
+            (RenderKind::Slider, None, RenderState::Active { input, .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                let width = ctx.length.unwrap_or(0);
+                let filled = slider_filled_width(ctx.ratio.unwrap_or(0.0), width);
+                write!(
+                    f,
+                    "{} {}{} {}",
+                    &self.prompt_suffix,
+                    self.active_item_style
+                        .apply_to(self.glyph("█", "#").repeat(filled)),
+                    self.hint_style
+                        .apply_to(self.glyph("░", "-").repeat(width - filled)),
+                    self.values_style.apply_to(input)
+                )
+            }
+            (RenderKind::TagInput, None, RenderState::Active { input, .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                write!(f, "{} ", &self.prompt_suffix)?;
+
+                for tag in ctx.values {
+                    write!(
+                        f,
+                        "{} ",
+                        self.values_style
+                            .clone()
+                            .reverse()
+                            .apply_to(format!(" {} ", tag))
+                    )?;
+                }
+
+                write!(f, "{}", input)?;
+
+                if let Some(suggestion) = ctx.detail {
+                    // Only hint the part that would be completed.
+                    let rest = suggestion.get(input.len()..).unwrap_or("");
+                    write!(f, "{}", self.hint_style.apply_to(rest))?;
+                }
+
+                Ok(())
+            }
+            (RenderKind::RangeSelect, Some(text), _) => {
+                let prefix = if flags.active {
+                    &self.active_item_prefix
+                } else {
+                    &self.inactive_item_prefix
+                };
+
+                let text = match (flags.checked, flags.active) {
+                    (true, _) => self.values_style.apply_to(text),
+                    (false, true) => self.active_item_style.apply_to(text),
+                    (false, false) => self.inactive_item_style.apply_to(text),
+                };
+
+                let marker = if flags.checked {
+                    self.values_style.apply_to(self.glyph("┃", "|"))
+                } else {
+                    self.inactive_item_style.apply_to(" ")
+                };
+
+                write!(f, "{}{} {}", prefix, marker, text)
+            }
+            (RenderKind::TransferSelect, Some(title), _) if flags.header => {
+                if flags.active {
+                    write!(
+                        f,
+                        "{} {}",
+                        self.active_item_prefix,
+                        self.prompt_style.clone().underlined().apply_to(title)
+                    )
+                } else {
+                    write!(
+                        f,
+                        "{} {}",
+                        self.inactive_item_prefix,
+                        self.hint_style.apply_to(title)
+                    )
+                }
+            }
+            (RenderKind::Menu, Some(text), _) => {
+                self.format_select_prompt_item(f, text, flags.active)?;
+
+                if flags.branch {
+                    write!(f, " {}", self.hint_style.apply_to(self.glyph("›", ">")))?;
+                }
+
+                Ok(())
+            }
+            (RenderKind::TableSelect, Some(text), _) if flags.header => write!(
+                f,
+                "{} {}",
+                &self.inactive_item_prefix,
+                self.prompt_style.apply_to(text)
+            ),
+            (RenderKind::PathSelect, Some(name), _) => {
+                let name = if flags.branch {
+                    format!("{}/", name)
+                } else {
+                    name.to_string()
+                };
+
+                self.format_select_prompt_item(f, &name, flags.active)
+            }
+            (RenderKind::PathSelect, None, RenderState::Active { .. }) => {
+                self.write_prompt_start(f, prompt)?;
+                write!(
+                    f,
+                    "{} {}",
+                    &self.prompt_suffix,
+                    self.hint_style.apply_to(ctx.detail.unwrap_or(""))
+                )
+            }
+            (RenderKind::TreeSelect, Some(text), _)
+            | (RenderKind::TreeMultiSelect, Some(text), _) => self.write_tree_item(f, ctx, text),
+            (RenderKind::ReviewEach, None, RenderState::Active { .. }) => {
+                let position = match ctx.position {
+                    Some((position, Some(total))) => format!("({}/{})", position, total),
+                    Some((position, None)) => format!("({})", position),
+                    None => String::new(),
+                };
+
+                write!(
+                    f,
+                    "{} {} {} {} {} ",
+                    &self.prompt_prefix,
+                    self.prompt_style.apply_to(prompt),
+                    self.hint_style.apply_to(position),
+                    self.hint_style
+                        .apply_to(format!("[{}]", ctx.values.join(","))),
+                    &self.prompt_suffix
+                )
+            }
+            _ => render_plain(self, f, ctx),
+        }
+    }
+
+    fn format_scrollbar(&self, f: &mut dyn fmt::Write, thumb: bool) -> fmt::Result {
+        if !self.scrollbar {
+            return Ok(());
+        }
+
+        if thumb {
+            write!(
+                f,
+                "{}",
+                self.active_item_style.apply_to(self.glyph("┃", "#"))
+            )
+        } else {
+            write!(f, "{}", self.hint_style.apply_to(self.glyph("│", "|")))
         }
     }
-}
 
-impl Theme for ColorfulTheme {
     /// Formats a prompt.
     fn format_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        if let Some(template) = &self.prompt_template {
+            return template.render(
+                f,
+                &[
+                    ("prefix", &self.prompt_prefix),
+                    ("prompt", &self.prompt_style.apply_to(prompt)),
+                    ("suffix", &self.prompt_suffix),
+                ],
+            );
+        }
+
         if !prompt.is_empty() {
             write!(
                 f,
@@ -371,6 +2701,60 @@ impl Theme for ColorfulTheme {
         )
     }
 
+    /// Formats a hint line
+    fn format_hint(&self, f: &mut dyn fmt::Write, hint: &str) -> fmt::Result {
+        write!(f, "{}", self.hint_style.apply_to(hint))
+    }
+
+    /// Formats the segment aligned to the right edge of a prompt line.
+    fn format_right_segment(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "{}", self.hint_style.apply_to(text))
+    }
+
+    /// Formats the number in front of an item of a numbered list prompt.
+    fn format_item_number(
+        &self,
+        f: &mut dyn fmt::Write,
+        number: usize,
+        width: usize,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.hint_style
+                .apply_to(format!("{:>width$}.", number, width = width))
+        )
+    }
+
+    /// Formats the digits typed to jump to an item of a numbered list prompt.
+    fn format_jump_input(&self, f: &mut dyn fmt::Write, digits: &str) -> fmt::Result {
+        write!(
+            f,
+            " {} {}",
+            self.hint_style.apply_to(self.glyph("→", "->")),
+            self.values_style.apply_to(digits)
+        )
+    }
+
+    /// Formats the progress of a wizard in front of a prompt.
+    fn format_step(&self, f: &mut dyn fmt::Write, step: usize, steps: usize) -> fmt::Result {
+        write!(
+            f,
+            "{} ",
+            self.hint_style.apply_to(format!("[{}/{}]", step, steps))
+        )
+    }
+
+    /// Formats the time left until the timeout in front of a prompt.
+    fn format_countdown(&self, f: &mut dyn fmt::Write, remaining: Duration) -> fmt::Result {
+        write!(
+            f,
+            "{} ",
+            self.hint_style
+                .apply_to(format!("({}s)", remaining.as_secs()))
+        )
+    }
+
     /// Formats an input prompt.
     fn format_input_prompt(
         &self,
@@ -445,6 +2829,15 @@ impl Theme for ColorfulTheme {
         prompt: &str,
         selection: Option<bool>,
     ) -> fmt::Result {
+        let selection = selection.map(|b| if b { "yes" } else { "no" });
+        if self.report_template.is_some() {
+            return self.write_report(
+                f,
+                prompt,
+                &self.values_style.apply_to(selection.unwrap_or("")),
+            );
+        }
+
         if !prompt.is_empty() {
             write!(
                 f,
@@ -453,7 +2846,6 @@ impl Theme for ColorfulTheme {
                 self.prompt_style.apply_to(prompt)
             )?;
         }
-        let selection = selection.map(|b| if b { "yes" } else { "no" });
 
         match selection {
             Some(selection) => {
@@ -477,6 +2869,10 @@ impl Theme for ColorfulTheme {
         prompt: &str,
         sel: &str,
     ) -> fmt::Result {
+        if self.report_template.is_some() {
+            return self.write_report(f, prompt, &self.values_style.apply_to(sel));
+        }
+
         if !prompt.is_empty() {
             write!(
                 f,
@@ -504,6 +2900,17 @@ impl Theme for ColorfulTheme {
         self.format_input_prompt_selection(f, prompt, "********")
     }
 
+    /// Formats the spinner shown while suggestions or completions are loading.
+    fn format_loading(&self, f: &mut dyn fmt::Write, frame: usize) -> fmt::Result {
+        let frames: Vec<char> = self.glyph("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏", "|/-\\").chars().collect();
+        write!(
+            f,
+            "{}",
+            self.hint_style
+                .apply_to(format!("{} loading", frames[frame % frames.len()]))
+        )
+    }
+
     /// Formats a multi select prompt after selection.
     fn format_multi_select_prompt_selection(
         &self,
@@ -511,6 +2918,19 @@ impl Theme for ColorfulTheme {
         prompt: &str,
         selections: &[&str],
     ) -> fmt::Result {
+        if self.report_template.is_some() {
+            let values = if self.inline_selections {
+                selections
+                    .iter()
+                    .map(|sel| self.values_style.apply_to(sel).to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            } else {
+                String::new()
+            };
+            return self.write_report(f, prompt, &values);
+        }
+
         if !prompt.is_empty() {
             write!(
                 f,
@@ -544,18 +2964,173 @@ impl Theme for ColorfulTheme {
         active: bool,
     ) -> fmt::Result {
         let details = if active {
-            (
-                &self.active_item_prefix,
-                self.active_item_style.apply_to(text),
-            )
+            (&self.active_item_prefix, &self.active_item_style)
         } else {
-            (
-                &self.inactive_item_prefix,
-                self.inactive_item_style.apply_to(text),
-            )
+            (&self.inactive_item_prefix, &self.inactive_item_style)
+        };
+        let state = ItemState {
+            active,
+            ..ItemState::default()
+        };
+
+        self.write_item(
+            f,
+            details.0,
+            &self.style_item(state, details.1).apply_to(text),
+        )
+    }
+
+    /// Formats a select prompt item which can't be selected.
+    fn format_select_prompt_disabled_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        let prefix = if active {
+            &self.active_item_prefix
+        } else {
+            &self.inactive_item_prefix
         };
+        let state = ItemState {
+            active,
+            disabled: true,
+            ..ItemState::default()
+        };
+
+        write!(
+            f,
+            "{} {}",
+            prefix,
+            self.style_item(state, &self.disabled_item_style)
+                .apply_to(text)
+        )
+    }
+
+    /// Formats a select prompt of a compact theme.
+    fn format_select_prompt_compact(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        text: &str,
+        disabled: bool,
+        index: usize,
+        total: usize,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(
+                f,
+                "{} {} {} ",
+                &self.prompt_prefix,
+                self.prompt_style.apply_to(prompt),
+                &self.prompt_suffix
+            )?;
+        }
+
+        let style = self.style_item(
+            ItemState {
+                active: true,
+                disabled,
+                ..ItemState::default()
+            },
+            if disabled {
+                &self.disabled_item_style
+            } else {
+                &self.active_item_style
+            },
+        );
+        write!(
+            f,
+            "{} {} {} {}",
+            self.hint_style.apply_to(self.glyph("‹", "<")),
+            style.apply_to(text),
+            self.hint_style.apply_to(self.glyph("›", ">")),
+            self.hint_style.apply_to(format!("{}/{}", index, total))
+        )
+    }
+
+    /// Formats a multi select prompt item which can't be checked.
+    fn format_multi_select_prompt_disabled_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        let style = self.style_item(
+            ItemState {
+                active,
+                disabled: true,
+                ..ItemState::default()
+            },
+            &if active {
+                self.active_item_style.clone().strikethrough()
+            } else {
+                self.disabled_item_style.clone()
+            },
+        );
+
+        write!(
+            f,
+            "{} {}",
+            &self.unchecked_item_prefix,
+            style.apply_to(text)
+        )
+    }
+
+    /// Formats a multi select prompt item which is checked and can't be unchecked.
+    fn format_multi_select_prompt_locked_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        let style = self.style_item(
+            ItemState {
+                active,
+                checked: true,
+                disabled: true,
+                ..ItemState::default()
+            },
+            if active {
+                &self.active_item_style
+            } else {
+                &self.locked_item_style
+            },
+        );
+
+        write!(f, "{} {}", &self.checked_item_prefix, style.apply_to(text))
+    }
+
+    /// Formats the counter of checked items, with unmet constraints styled as errors.
+    fn format_multi_select_counter(
+        &self,
+        f: &mut dyn fmt::Write,
+        checked: usize,
+        total: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.hint_style
+                .apply_to(format!("{}/{} selected", checked, total))
+        )?;
 
-        write!(f, "{} {}", details.0, details.1)
+        match (min, max) {
+            (Some(min), _) if checked < min => write!(
+                f,
+                "{}",
+                self.error_style
+                    .apply_to(format!(", need at least {}", min))
+            ),
+            (_, Some(max)) if checked >= max => write!(
+                f,
+                "{}",
+                self.hint_style.apply_to(format!(", at most {}", max))
+            ),
+            _ => Ok(()),
+        }
     }
 
     /// Formats a multi select prompt item.
@@ -566,26 +3141,23 @@ impl Theme for ColorfulTheme {
         checked: bool,
         active: bool,
     ) -> fmt::Result {
-        let details = match (checked, active) {
-            (true, true) => (
-                &self.checked_item_prefix,
-                self.active_item_style.apply_to(text),
-            ),
-            (true, false) => (
-                &self.checked_item_prefix,
-                self.inactive_item_style.apply_to(text),
-            ),
-            (false, true) => (
-                &self.unchecked_item_prefix,
-                self.active_item_style.apply_to(text),
-            ),
-            (false, false) => (
-                &self.unchecked_item_prefix,
-                self.inactive_item_style.apply_to(text),
-            ),
+        let prefix = if checked {
+            &self.checked_item_prefix
+        } else {
+            &self.unchecked_item_prefix
+        };
+        let style = if active {
+            &self.active_item_style
+        } else {
+            &self.inactive_item_style
+        };
+        let state = ItemState {
+            active,
+            checked,
+            ..ItemState::default()
         };
 
-        write!(f, "{} {}", details.0, details.1)
+        self.write_item(f, prefix, &self.style_item(state, style).apply_to(text))
     }
 
     /// Formats a sort prompt item.
@@ -597,24 +3169,41 @@ impl Theme for ColorfulTheme {
         active: bool,
     ) -> fmt::Result {
         let details = match (picked, active) {
-            (true, true) => (
-                &self.picked_item_prefix,
-                self.active_item_style.apply_to(text),
-            ),
-            (false, true) => (
-                &self.unpicked_item_prefix,
-                self.active_item_style.apply_to(text),
-            ),
-            (_, false) => (
-                &self.unpicked_item_prefix,
-                self.inactive_item_style.apply_to(text),
-            ),
+            (true, true) => (&self.picked_item_prefix, &self.active_item_style),
+            (false, true) => (&self.unpicked_item_prefix, &self.active_item_style),
+            (_, false) => (&self.unpicked_item_prefix, &self.inactive_item_style),
+        };
+        let state = ItemState {
+            active,
+            checked: picked,
+            ..ItemState::default()
         };
 
-        write!(f, "{} {}", details.0, details.1)
+        self.write_item(
+            f,
+            details.0,
+            &self.style_item(state, details.1).apply_to(text),
+        )
     }
 
-    /// Formats a fuzzy select prompt item.
+    /// Formats the text of a fuzzy select item, highlighting the matching parts.
+    #[cfg(feature = "fuzzy-select")]
+    fn format_fuzzy_match(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[Range<usize>],
+    ) -> fmt::Result {
+        write_fuzzy_match(
+            f,
+            text,
+            matches,
+            &Style::new(),
+            &self.fuzzy_match_highlight_style,
+        )
+    }
+
+    /// Formats a fuzzy select prompt item, styled by the item style closure if there is one.
     #[cfg(feature = "fuzzy-select")]
     fn format_fuzzy_select_prompt_item(
         &self,
@@ -627,21 +3216,33 @@ impl Theme for ColorfulTheme {
     ) -> fmt::Result {
         write!(f, "{} ", if active { ">" } else { " " })?;
 
-        if highlight_matches {
-            if let Some((_score, indices)) = matcher.fuzzy_indices(text, &search_term) {
-                for (idx, c) in text.chars().into_iter().enumerate() {
-                    if indices.contains(&idx) {
-                        write!(f, "{}", self.fuzzy_match_highlight_style.apply_to(c))?;
-                    } else {
-                        write!(f, "{}", c)?;
-                    }
-                }
+        let indices = if search_term.is_empty() {
+            None
+        } else {
+            matcher
+                .fuzzy_indices(text, search_term)
+                .map(|(_score, indices)| indices)
+        };
+        let state = ItemState {
+            active,
+            matched: indices.is_some(),
+            ..ItemState::default()
+        };
+        let matches = match indices {
+            Some(indices) if highlight_matches => fuzzy_match_ranges(text, &indices),
+            _ => vec![],
+        };
 
-                return Ok(());
-            }
+        match &self.item_style {
+            Some(item_style) => write_fuzzy_match(
+                f,
+                text,
+                &matches,
+                &item_style(state),
+                &self.fuzzy_match_highlight_style,
+            ),
+            None => self.format_fuzzy_match(f, text, &matches),
         }
-
-        write!(f, "{}", text)
     }
 
     /// Formats a fuzzy-selectprompt after selection.
@@ -662,62 +3263,319 @@ impl Theme for ColorfulTheme {
             )?;
         }
 
-        if cursor_pos < search_term.len() {
-            let st_head = search_term[0..cursor_pos].to_string();
-            let st_tail = search_term[cursor_pos + 1..search_term.len()].to_string();
-            let st_cursor = self
-                .fuzzy_cursor_style
-                .apply_to(search_term.to_string().chars().nth(cursor_pos).unwrap());
+        let (st_head, st_tail) = search_term.split_at(byte_offset(search_term, cursor_pos));
+        let mut st_tail = st_tail.chars();
+        if let Some(cursor) = st_tail.next() {
             write!(
                 f,
                 "{} {}{}{}",
-                &self.prompt_suffix, st_head, st_cursor, st_tail
+                &self.prompt_suffix,
+                st_head,
+                self.fuzzy_cursor_style.apply_to(cursor),
+                st_tail.as_str()
             )
         } else {
             let cursor = self.fuzzy_cursor_style.apply_to(" ");
-            write!(
-                f,
-                "{} {}{}",
-                &self.prompt_suffix,
-                search_term.to_string(),
-                cursor
-            )
+            write!(f, "{} {}{}", &self.prompt_suffix, search_term, cursor)
         }
     }
 }
 
+/// The state of a prompt waiting for input, with an optional default.
+fn waiting(default: Option<&str>) -> RenderState<'_> {
+    RenderState::Active {
+        input: "",
+        cursor: 0,
+        default,
+    }
+}
+
+/// The state of a prompt showing `input`, with the cursor at its end.
+fn typed(input: &str) -> RenderState<'_> {
+    RenderState::Active {
+        input,
+        cursor: input.chars().count(),
+        default: None,
+    }
+}
+
+/// The state of a prompt answered with `selections`.
+fn done<'a>(selections: &'a [&'a str]) -> RenderState<'a> {
+    RenderState::Done { selections }
+}
+
+/// The choice of a confirm prompt as passed to [`Theme::format_report`].
+fn choice(val: bool) -> &'static str {
+    if val {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
 /// Helper struct to conveniently render a theme ot a term.
 pub(crate) struct TermThemeRenderer<'a> {
-    term: &'a Term,
+    term: &'a dyn Backend,
     theme: &'a dyn Theme,
-    height: usize,
-    prompt_height: usize,
+    // The lines rendered since the last clear, kept to count the rows they take up at the
+    // terminal width when clearing them, which may have changed since they were rendered.
+    lines: Vec<String>,
+    prompt_lines: Vec<String>,
+    // The terminal width the lines were last rendered at, and whether rows are counted at
+    // the smaller of it and the current width.
+    drawn_width: usize,
+    strict: bool,
     prompts_reset_height: bool,
+    colors: bool,
+    // What the terminal can render, probed when the prompt starts.
+    capabilities: Capabilities,
+    step: Option<(usize, usize)>,
+    // The countdown to the timeout of the prompt when it was last checked for ticks.
+    countdown: Option<Duration>,
+    right_segment: Option<String>,
+    jump_input: Option<String>,
+    // The rows of the scrollbar thumb and the number of lines rendered next to the bar.
+    scrollbar: Option<(Range<usize>, usize)>,
+    // The columns the text of the active item is scrolled to the left by.
+    horizontal_offset: usize,
+    // Whether the lines of the previous frame are overwritten instead of cleared up front.
+    overwriting: bool,
+    // Whether the cursor was hidden, it's shown again when dropped on errors and panics.
+    cursor_hidden: bool,
+    // Restores the primary screen when dropped while the prompt renders in the alternate one.
+    alternate_screen: Option<AlternateScreen<'a>>,
+    // Whether frames are pinned to the bottom of the terminal, and the rows of the last
+    // pinned frame.
+    anchored_bottom: bool,
+    bottom_rows: Option<usize>,
+    // How often the process continued after being stopped, when the lines were rendered.
+    resumes: usize,
+    // Keep the `SIGWINCH` and `SIGTSTP` handlers installed while the prompt reads keys.
+    #[cfg(unix)]
+    _resize_watch: crate::backend::ResizeWatch,
+    #[cfg(unix)]
+    _stop_watch: crate::job_control::StopWatch,
+    // Keeps other renderers (like progress bars) off the terminal during the prompt.
+    _pause: PauseGuard,
+    // Lets the backend restore the terminal once the prompt ends, however it ends.
+    _prompt: crate::backend::PromptGuard<'a>,
 }
 
 impl<'a> TermThemeRenderer<'a> {
-    pub fn new(term: &'a Term, theme: &'a dyn Theme) -> TermThemeRenderer<'a> {
+    pub fn new(term: &'a dyn Backend, theme: &'a dyn Theme) -> TermThemeRenderer<'a> {
         TermThemeRenderer {
             term,
             theme,
-            height: 0,
-            prompt_height: 0,
+            lines: vec![],
+            prompt_lines: vec![],
+            drawn_width: term.size().1 as usize,
+            strict: crate::backend::strict_inline(),
             prompts_reset_height: true,
+            colors: theme.color_mode().colors_enabled(term),
+            capabilities: term.capabilities(),
+            step: crate::wizard::current_step(),
+            countdown: crate::timeout::countdown(),
+            right_segment: None,
+            jump_input: None,
+            scrollbar: None,
+            horizontal_offset: 0,
+            overwriting: false,
+            cursor_hidden: false,
+            alternate_screen: None,
+            anchored_bottom: false,
+            bottom_rows: None,
+            resumes: crate::job_control::resumes(),
+            #[cfg(unix)]
+            _resize_watch: crate::backend::ResizeWatch::start(),
+            #[cfg(unix)]
+            _stop_watch: crate::job_control::StopWatch::start(),
+            _pause: crate::pause_renderer(),
+            _prompt: crate::backend::PromptGuard::new(term),
+        }
+    }
+
+    #[cfg(feature = "password")]
+    pub fn set_prompts_reset_height(&mut self, val: bool) {
+        self.prompts_reset_height = val;
+    }
+
+    #[cfg(feature = "password")]
+    pub fn term(&self) -> &dyn Backend {
+        self.term
+    }
+
+    pub fn add_line(&mut self) {
+        self.lines.push(String::new());
+    }
+
+    fn format<F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result>(
+        &mut self,
+        f: F,
+    ) -> io::Result<String> {
+        let mut buf = String::new();
+        f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+        if !self.capabilities.hyperlinks {
+            buf = strip_osc(&buf).into_owned();
+        }
+        if self.colors {
+            Ok(buf)
+        } else {
+            Ok(strip_ansi_codes(&buf).into_owned())
+        }
+    }
+
+    /// Truncates every line of `text` to the width of the terminal.
+    ///
+    /// Lines wrapped by the terminal would shift the items below them on every redraw.
+    fn truncate_lines(&self, text: &str) -> String {
+        let width = self.term.size().1 as usize;
+
+        text.split('\n')
+            .map(|line| {
+                if text_width(line) > width {
+                    truncate_text(line, width, self.theme.ellipsis())
+                } else {
+                    line.into()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Writes the lines of `text`, clearing each row of the previous frame right before
+    /// overwriting it.
+    fn write_lines(&mut self, text: &str) -> io::Result<()> {
+        self.drawn_width = self.term.size().1 as usize;
+        if !self.overwriting {
+            return self.term.write_line(text);
+        }
+
+        for line in text.split('\n') {
+            self.term.clear_line()?;
+            self.term.write_line(line)?;
+        }
+
+        Ok(())
+    }
+
+    /// Truncates every line of `text` to leave room for `cell` at the right edge of the
+    /// terminal, and appends it there.
+    fn append_to_lines(&self, text: &str, cell: &str) -> String {
+        let width = (self.term.size().1 as usize).saturating_sub(text_width(cell));
+
+        text.split('\n')
+            .map(|line| {
+                let line = if text_width(line) >= width {
+                    truncate_text(line, width.saturating_sub(1), self.theme.ellipsis())
+                } else {
+                    line.into()
+                };
+                let padding = width.saturating_sub(text_width(&line));
+                format!("{}{:padding$}{}", line, "", cell, padding = padding)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns the scrollbar cell for the next line, if the scrollbar is shown.
+    fn scrollbar_cell(&mut self) -> io::Result<Option<String>> {
+        let thumb = match self.scrollbar {
+            Some((ref thumb, ref mut row)) => {
+                *row += 1;
+                thumb.contains(&(*row - 1))
+            }
+            None => return Ok(None),
+        };
+
+        let cell = self.format(|this, buf| this.theme.format_scrollbar(buf, thumb))?;
+        Ok(if cell.is_empty() { None } else { Some(cell) })
+    }
+
+    /// Shows a scrollbar next to the lines rendered until it is set to `None`, with the
+    /// thumb on the rows `thumb`.
+    pub fn set_scrollbar(&mut self, thumb: Option<Range<usize>>) {
+        self.scrollbar = thumb.map(|thumb| (thumb, 0));
+    }
+
+    /// Returns the columns the text of the active item is scrolled to the left by.
+    pub fn horizontal_offset(&self) -> usize {
+        self.horizontal_offset
+    }
+
+    /// Scrolls the text of the active item `offset` columns to the left, to reveal the end
+    /// of items wider than the terminal.
+    ///
+    /// The offset is reduced when rendering so the end of the item doesn't scroll further
+    /// than into view.
+    pub fn set_horizontal_offset(&mut self, offset: usize) {
+        self.horizontal_offset = offset;
+    }
+
+    /// Returns the text of the active item scrolled by the horizontal offset, which is
+    /// first limited to how far the rendered `line` overflows the terminal.
+    fn scroll_item(&mut self, text: &str, line: &str) -> String {
+        let scrollbar = if self.scrollbar.is_some() { 2 } else { 0 };
+        let width = (self.term.size().1 as usize).saturating_sub(scrollbar);
+        self.horizontal_offset = self
+            .horizontal_offset
+            .min(text_width(line).saturating_sub(width));
+
+        if self.horizontal_offset == 0 {
+            return text.to_string();
         }
-    }
 
-    #[cfg(feature = "password")]
-    pub fn set_prompts_reset_height(&mut self, val: bool) {
-        self.prompts_reset_height = val;
+        let ellipsis = self.theme.ellipsis();
+        let skipped = self.horizontal_offset + text_width(ellipsis);
+        format!("{}{}", ellipsis, skip_columns(text, skipped))
     }
 
-    #[cfg(feature = "password")]
-    pub fn term(&self) -> &Term {
-        self.term
+    /// Writes an item of a select or multi select prompt, scrolling the active one
+    /// horizontally.
+    fn write_list_item(
+        &mut self,
+        kind: RenderKind,
+        text: &str,
+        icon: Option<&str>,
+        flags: RenderFlags,
+    ) -> io::Result<()> {
+        let scrolled;
+        let text = if flags.active && self.horizontal_offset > 0 {
+            let ctx = self
+                .context(kind, "", waiting(None))
+                .item(text, flags)
+                .icon(icon);
+            let line = self.format(|this, buf| this.theme.render(buf, &ctx))?;
+            scrolled = self.scroll_item(text, &line);
+            &scrolled
+        } else {
+            text
+        };
+
+        let ctx = self
+            .context(kind, "", waiting(None))
+            .item(text, flags)
+            .icon(icon);
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
     }
 
-    pub fn add_line(&mut self) {
-        self.height += 1;
+    /// Returns the number of terminal rows `lines` take up at the current terminal width,
+    /// including the wrapped rows.
+    fn rows(&self, lines: &[String]) -> usize {
+        let width = self.term.size().1 as usize;
+
+        lines
+            .iter()
+            .map(|line| {
+                let rows = wrapped_rows(line, width);
+                if self.strict {
+                    // Terminals which don't rewrap keep the rows the line was drawn in.
+                    rows.min(wrapped_rows(line, self.drawn_width))
+                } else {
+                    rows
+                }
+            })
+            .sum()
     }
 
     fn write_formatted_str<
@@ -726,9 +3584,13 @@ impl<'a> TermThemeRenderer<'a> {
         &mut self,
         f: F,
     ) -> io::Result<()> {
-        let mut buf = String::new();
-        f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
-        self.height += buf.chars().filter(|&x| x == '\n').count();
+        self.start_over_if_resumed()?;
+        let buf = self.format(f)?;
+        // The line after the last newline is left open for the input.
+        if let Some(end) = buf.rfind('\n') {
+            self.lines.extend(buf[..end].split('\n').map(String::from));
+        }
+        self.drawn_width = self.term.size().1 as usize;
         self.term.write_str(&buf)
     }
 
@@ -738,10 +3600,14 @@ impl<'a> TermThemeRenderer<'a> {
         &mut self,
         f: F,
     ) -> io::Result<()> {
-        let mut buf = String::new();
-        f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
-        self.height += buf.chars().filter(|&x| x == '\n').count() + 1;
-        self.term.write_line(&buf)
+        self.start_over_if_resumed()?;
+        let buf = self.format(f)?;
+        let buf = match self.scrollbar_cell()? {
+            Some(cell) => self.append_to_lines(&buf, &cell),
+            None => self.truncate_lines(&buf),
+        };
+        self.lines.extend(buf.split('\n').map(String::from));
+        self.write_lines(&buf)
     }
 
     fn write_formatted_prompt<
@@ -750,29 +3616,185 @@ impl<'a> TermThemeRenderer<'a> {
         &mut self,
         f: F,
     ) -> io::Result<()> {
-        self.write_formatted_line(f)?;
+        self.start_over_if_resumed()?;
+        // Prompts and reports are not truncated so no part of the selection gets lost,
+        // the wrapped rows are counted instead.
+        let buf = self.format(f)?;
+        self.lines.extend(buf.split('\n').map(String::from));
+        self.write_lines(&buf)?;
+
         if self.prompts_reset_height {
-            self.prompt_height = self.height;
-            self.height = 0;
+            self.prompt_lines = std::mem::take(&mut self.lines);
         }
         Ok(())
     }
 
-    fn write_paging_info(buf: &mut dyn fmt::Write, paging_info: (usize, usize)) -> fmt::Result {
-        write!(buf, " [Page {}/{}] ", paging_info.0, paging_info.1)
+    /// Writes the report of an answered prompt, unless the theme takes over reports.
+    fn write_report<F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result>(
+        &mut self,
+        prompt: &str,
+        values: &[&str],
+        f: F,
+    ) -> io::Result<()> {
+        let mut report = String::new();
+        match self.theme.format_report(&mut report, prompt, values) {
+            None => self.write_formatted_prompt(f),
+            Some(result) => {
+                result.map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+                if report.is_empty() {
+                    return Ok(());
+                }
+                self.write_formatted_prompt(|_, buf| buf.write_str(&report))
+            }
+        }
+    }
+
+    fn write_paging_info(
+        &self,
+        buf: &mut dyn fmt::Write,
+        paging_info: Option<PagingInfo>,
+    ) -> fmt::Result {
+        match paging_info {
+            Some(paging_info) if self.theme.paging_position() == PagingPosition::Prompt => {
+                self.format_paging_info(buf, paging_info)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn format_paging_info(&self, buf: &mut dyn fmt::Write, paging_info: PagingInfo) -> fmt::Result {
+        match paging_info {
+            (page, Some(pages)) => self.theme.format_paging_info(buf, page, pages),
+            (page, None) => self.theme.format_paging_info_unknown(buf, page),
+        }
+    }
+
+    /// Returns whether the theme renders the page indicator below the items.
+    pub fn paging_footer(&self) -> bool {
+        self.theme.paging_position() == PagingPosition::Footer
+    }
+
+    fn context<'b>(
+        &self,
+        kind: RenderKind,
+        prompt: &'b str,
+        state: RenderState<'b>,
+    ) -> RenderContext<'b> {
+        RenderContext::new(kind, prompt, state)
+            .step(self.step)
+            .countdown(crate::timeout::countdown())
+    }
+
+    /// Writes the line of a single item of a prompt of `kind`.
+    fn write_item(&mut self, kind: RenderKind, text: &str, flags: RenderFlags) -> io::Result<()> {
+        let ctx = self.context(kind, "", waiting(None)).item(text, flags);
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    /// Returns whether the countdown to the timeout of the prompt changed since the last
+    /// call, so prompts which keep their prompt line render it again.
+    pub fn countdown_ticked(&mut self) -> bool {
+        let countdown = crate::timeout::countdown();
+        std::mem::replace(&mut self.countdown, countdown) != countdown
+    }
+
+    /// Sets the segment aligned to the right edge of the prompt line of list prompts.
+    pub fn set_right_segment(&mut self, segment: Option<&str>) {
+        self.right_segment = segment.map(str::to_string);
+    }
+
+    /// Sets the digits typed to jump to an item, shown in the prompt line of list prompts.
+    pub fn set_jump_input(&mut self, digits: Option<&str>) {
+        self.jump_input = digits.map(str::to_string);
+    }
+
+    fn write_jump_input(&self, buf: &mut dyn fmt::Write) -> fmt::Result {
+        match self.jump_input {
+            Some(ref digits) => self.theme.format_jump_input(buf, digits),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the number in front of an item of a numbered list prompt.
+    pub fn item_number(&mut self, number: usize, width: usize) -> io::Result<String> {
+        self.format(|this, buf| this.theme.format_item_number(buf, number, width))
+    }
+
+    /// Writes the prompt `line` followed by the right segment, padded to the terminal width.
+    fn write_with_right_segment(&self, buf: &mut dyn fmt::Write, line: &str) -> fmt::Result {
+        write!(buf, "{}", line)?;
+
+        let segment = match self.right_segment {
+            Some(ref text) => {
+                let mut segment = String::new();
+                self.theme.format_right_segment(&mut segment, text)?;
+                segment
+            }
+            None => return Ok(()),
+        };
+        if segment.is_empty() {
+            return Ok(());
+        }
+
+        // The last column stays free so the line never wraps on its own.
+        let width = (self.term.size().1 as usize).saturating_sub(1);
+        let used = text_width(line) + text_width(&segment);
+        let padding = if line.contains('\n') || used >= width {
+            1
+        } else {
+            width - used
+        };
+
+        write!(buf, "{:padding$}{}", "", segment, padding = padding)
+    }
+
+    /// Returns the number of lines rendered below the items of paged prompts.
+    pub fn footer_rows(&self, hint: bool) -> usize {
+        self.paging_footer() as usize + hint as usize
+    }
+
+    pub fn hint(&mut self, hint: &str) -> io::Result<()> {
+        let ctx = self.context(RenderKind::Hint, hint, waiting(None));
+        let mut buf = String::new();
+        self.theme
+            .render(&mut buf, &ctx)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn paging_info_footer(&mut self, paging_info: PagingInfo) -> io::Result<()> {
+        if !self.paging_footer() {
+            return Ok(());
+        }
+
+        self.write_formatted_line(|this, buf| this.format_paging_info(buf, paging_info))
     }
 
     pub fn error(&mut self, err: &str) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| this.theme.format_error(buf, err))
+        self.bell()?;
+        let ctx = self.context(RenderKind::Error, err, waiting(None));
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
     }
 
     pub fn confirm_prompt(&mut self, prompt: &str, default: Option<bool>) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| this.theme.format_confirm_prompt(buf, prompt, default))
+        let ctx = self
+            .context(RenderKind::Confirm, prompt, waiting(None))
+            .choice(default);
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
     }
 
     pub fn confirm_prompt_selection(&mut self, prompt: &str, sel: Option<bool>) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_confirm_prompt_selection(buf, prompt, sel)
+        let selections: Vec<&str> = sel.map(choice).into_iter().collect();
+        let ctx = self
+            .context(RenderKind::Confirm, prompt, done(&[]))
+            .choice(sel);
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
         })
     }
 
@@ -783,63 +3805,636 @@ impl<'a> TermThemeRenderer<'a> {
         search_term: &str,
         cursor_pos: usize,
     ) -> io::Result<()> {
+        let state = RenderState::Active {
+            input: search_term,
+            cursor: cursor_pos,
+            default: None,
+        };
+        let ctx = self.context(RenderKind::FuzzySelect, prompt, state);
+        self.write_formatted_prompt(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn toggle_prompt(
+        &mut self,
+        prompt: &str,
+        disabled: &str,
+        enabled: &str,
+        value: bool,
+    ) -> io::Result<()> {
+        let labels = [disabled, enabled];
+        let ctx = self
+            .context(RenderKind::Toggle, prompt, waiting(None))
+            .values(&labels)
+            .choice(Some(value));
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn toggle_prompt_selection(&mut self, prompt: &str, selection: &str) -> io::Result<()> {
+        let selections = [selection];
+        let ctx = self.context(RenderKind::Toggle, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
+        })
+    }
+
+    pub fn input_prompt(&mut self, prompt: &str, default: Option<&str>) -> io::Result<()> {
+        let ctx = self.context(RenderKind::Input, prompt, waiting(default));
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    /// Renders the prompt line of an input prompt again, followed by the `input` typed so
+    /// far, and returns whether it did.
+    ///
+    /// Only the row of the cursor is cleared, so lines which would wrap are left alone.
+    pub fn input_prompt_again(
+        &mut self,
+        prompt: &str,
+        default: Option<&str>,
+        input: &str,
+    ) -> io::Result<bool> {
+        let ctx = self.context(RenderKind::Input, prompt, waiting(default));
+        let buf = self.format(|this, buf| this.theme.render(buf, &ctx))?;
+        if buf.contains('\n') || text_width(&buf) + text_width(input) >= self.term.size().1 as usize
+        {
+            return Ok(false);
+        }
+
+        self.term.clear_line()?;
+        self.term.write_str(&buf)?;
+        self.term.write_str(input)?;
+        Ok(true)
+    }
+
+    pub fn input_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
+        let selections = [sel];
+        let ctx = self.context(RenderKind::Input, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
+        })
+    }
+
+    pub fn redacted_input_prompt_selection(
+        &mut self,
+        prompt: &str,
+        sel: &str,
+        redaction: Redaction,
+    ) -> io::Result<()> {
+        let mut masked = String::new();
+        self.theme
+            .format_redacted_value(&mut masked, sel, redaction)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let selections = [masked.as_str()];
+        let ctx = self.context(RenderKind::Input, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
+        })
+    }
+
+    pub fn number_input_prompt(&mut self, prompt: &str, value: &str) -> io::Result<()> {
+        let ctx = self.context(RenderKind::NumberInput, prompt, typed(value));
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn number_input_prompt_selection(&mut self, prompt: &str, value: &str) -> io::Result<()> {
+        let selections = [value];
+        let ctx = self.context(RenderKind::NumberInput, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
+        })
+    }
+
+    pub fn pin_input_prompt(
+        &mut self,
+        prompt: &str,
+        code: &str,
+        length: usize,
+        masked: bool,
+    ) -> io::Result<()> {
+        let flags = RenderFlags {
+            masked,
+            ..RenderFlags::default()
+        };
+        let ctx = self
+            .context(RenderKind::PinInput, prompt, typed(code))
+            .length(Some(length))
+            .flags(flags);
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn pin_input_prompt_selection(
+        &mut self,
+        prompt: &str,
+        code: &str,
+        masked: bool,
+    ) -> io::Result<()> {
+        // Masked codes are not revealed to custom reports either.
+        let selections = if masked {
+            &[][..]
+        } else {
+            std::slice::from_ref(&code)
+        };
+        let flags = RenderFlags {
+            masked,
+            ..RenderFlags::default()
+        };
+        let ctx = self
+            .context(RenderKind::PinInput, prompt, done(selections))
+            .flags(flags);
+        self.write_report(prompt, selections, |this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn ip_input_prompt(
+        &mut self,
+        prompt: &str,
+        input: &str,
+        completion: Option<&str>,
+        valid: bool,
+    ) -> io::Result<()> {
+        let flags = RenderFlags {
+            invalid: !valid,
+            ..RenderFlags::default()
+        };
+        let ctx = self
+            .context(RenderKind::IpInput, prompt, typed(input))
+            .detail(completion)
+            .flags(flags);
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn duration_input_prompt(
+        &mut self,
+        prompt: &str,
+        input: &str,
+        description: Option<&str>,
+    ) -> io::Result<()> {
+        let ctx = self
+            .context(RenderKind::DurationInput, prompt, typed(input))
+            .detail(description);
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn duration_input_prompt_selection(
+        &mut self,
+        prompt: &str,
+        duration: &str,
+    ) -> io::Result<()> {
+        let selections = [duration];
+        let ctx = self.context(RenderKind::DurationInput, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
+        })
+    }
+
+    pub fn slider_prompt(
+        &mut self,
+        prompt: &str,
+        value: &str,
+        ratio: f64,
+        width: usize,
+    ) -> io::Result<()> {
+        let ctx = self
+            .context(RenderKind::Slider, prompt, typed(value))
+            .ratio(Some(ratio))
+            .length(Some(width));
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn slider_prompt_selection(&mut self, prompt: &str, value: &str) -> io::Result<()> {
+        let selections = [value];
+        let ctx = self.context(RenderKind::Slider, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
+        })
+    }
+
+    pub fn color_select_prompt(
+        &mut self,
+        prompt: &str,
+        color: Rgb,
+        hex_input: Option<&str>,
+    ) -> io::Result<()> {
+        let flags = RenderFlags {
+            editing: hex_input.is_some(),
+            ..RenderFlags::default()
+        };
+        let ctx = self
+            .context(
+                RenderKind::ColorSelect,
+                prompt,
+                typed(hex_input.unwrap_or("")),
+            )
+            .color(Some((color, None)))
+            .flags(flags);
+        self.write_formatted_prompt(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn color_select_prompt_selection(&mut self, prompt: &str, color: Rgb) -> io::Result<()> {
+        let hex = color.to_string();
+        let selections = [hex.as_str()];
+        let ctx = self
+            .context(RenderKind::ColorSelect, prompt, done(&selections))
+            .color(Some((color, None)));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
+        })
+    }
+
+    pub fn color_select_row(
+        &mut self,
+        swatches: &[(Rgb, Option<u8>)],
+        active: Option<usize>,
+    ) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            for (idx, &(color, ansi)) in swatches.iter().enumerate() {
+                let hex = color.to_string();
+                let flags = RenderFlags {
+                    active: active == Some(idx),
+                    ..RenderFlags::default()
+                };
+                let ctx = this
+                    .context(RenderKind::ColorSelect, "", waiting(None))
+                    .item(&hex, flags)
+                    .color(Some((color, ansi)));
+                this.theme.render(buf, &ctx)?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn tag_input_prompt(
+        &mut self,
+        prompt: &str,
+        tags: &[String],
+        input: &str,
+        suggestion: Option<&str>,
+    ) -> io::Result<()> {
+        let tags: Vec<_> = tags.iter().map(String::as_str).collect();
+        let ctx = self
+            .context(RenderKind::TagInput, prompt, typed(input))
+            .values(&tags)
+            .detail(suggestion);
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn tag_input_prompt_selection(&mut self, prompt: &str, tags: &[String]) -> io::Result<()> {
+        let tags: Vec<_> = tags.iter().map(String::as_str).collect();
+        let ctx = self.context(RenderKind::TagInput, prompt, done(&tags));
+        self.write_report(prompt, &tags, |this, buf| this.theme.render(buf, &ctx))
+    }
+
+    #[cfg(feature = "password")]
+    pub fn password_prompt(&mut self, prompt: &str) -> io::Result<()> {
+        let ctx = self.context(RenderKind::Password, prompt, waiting(None));
+        self.write_formatted_str(|this, buf| {
+            write!(buf, "\r")?;
+            this.theme.render(buf, &ctx)
+        })
+    }
+
+    #[cfg(feature = "password")]
+    pub fn password_prompt_selection(&mut self, prompt: &str) -> io::Result<()> {
+        let ctx = self.context(RenderKind::Password, prompt, done(&[]));
+        self.write_report(prompt, &[], |this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn select_prompt(
+        &mut self,
+        prompt: &str,
+        paging_info: Option<PagingInfo>,
+    ) -> io::Result<()> {
+        let ctx = self.context(RenderKind::Select, prompt, waiting(None));
         self.write_formatted_prompt(|this, buf| {
+            let mut line = String::new();
+            this.theme.render(&mut line, &ctx)?;
+            this.write_paging_info(&mut line, paging_info)?;
+            this.write_jump_input(&mut line)?;
+            this.write_with_right_segment(buf, &line)
+        })
+    }
+
+    pub fn select_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
+        let selections = [sel];
+        let ctx = self.context(RenderKind::Select, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
+        })
+    }
+
+    pub fn select_prompt_item(
+        &mut self,
+        text: &str,
+        icon: Option<&str>,
+        disabled: bool,
+        active: bool,
+    ) -> io::Result<()> {
+        let flags = RenderFlags {
+            active,
+            disabled,
+            ..RenderFlags::default()
+        };
+        self.write_list_item(RenderKind::Select, text, icon, flags)
+    }
+
+    pub fn select_prompt_compact(
+        &mut self,
+        prompt: &str,
+        text: &str,
+        icon: Option<&str>,
+        disabled: bool,
+        index: usize,
+        total: usize,
+    ) -> io::Result<()> {
+        let text = match icon {
+            Some(icon) => format!("{} {}", icon, text),
+            None => text.to_string(),
+        };
+        self.write_formatted_line(|this, buf| {
             this.theme
-                .format_fuzzy_select_prompt(buf, prompt, search_term, cursor_pos)
+                .format_select_prompt_compact(buf, prompt, &text, disabled, index + 1, total)
+        })
+    }
+
+    pub fn table_select_header(&mut self, cells: &[String]) -> io::Result<()> {
+        let cells: Vec<_> = cells.iter().map(String::as_str).collect();
+        let text = cells.join("  ");
+        let flags = RenderFlags {
+            header: true,
+            ..RenderFlags::default()
+        };
+        let ctx = self
+            .context(RenderKind::TableSelect, "", waiting(None))
+            .item(&text, flags)
+            .values(&cells);
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn table_select_row(&mut self, cells: &[String], active: bool) -> io::Result<()> {
+        let cells: Vec<_> = cells.iter().map(String::as_str).collect();
+        let text = cells.join("  ");
+        let flags = RenderFlags {
+            active,
+            ..RenderFlags::default()
+        };
+        let ctx = self
+            .context(RenderKind::TableSelect, "", waiting(None))
+            .item(&text, flags)
+            .values(&cells);
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn path_select_prompt(&mut self, prompt: &str, dir: &str) -> io::Result<()> {
+        let ctx = self
+            .context(RenderKind::PathSelect, prompt, waiting(None))
+            .detail(Some(dir));
+        self.write_formatted_prompt(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn path_select_prompt_item(
+        &mut self,
+        name: &str,
+        is_dir: bool,
+        active: bool,
+    ) -> io::Result<()> {
+        let flags = RenderFlags {
+            active,
+            branch: is_dir,
+            ..RenderFlags::default()
+        };
+        self.write_item(RenderKind::PathSelect, name, flags)
+    }
+
+    pub fn autocomplete_prompt(
+        &mut self,
+        prompt: &str,
+        input: &str,
+        cursor_pos: usize,
+    ) -> io::Result<()> {
+        let state = RenderState::Active {
+            input,
+            cursor: cursor_pos,
+            default: None,
+        };
+        let ctx = self.context(RenderKind::Autocomplete, prompt, state);
+        self.write_formatted_prompt(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn autocomplete_suggestion(&mut self, text: &str, active: bool) -> io::Result<()> {
+        let flags = RenderFlags {
+            active,
+            ..RenderFlags::default()
+        };
+        self.write_item(RenderKind::Autocomplete, text, flags)
+    }
+
+    pub fn loading(&mut self, frame: usize) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_loading(buf, frame))
+    }
+
+    /// Returns the spinner of `frame` for rendering it inline.
+    #[cfg(feature = "completion")]
+    pub fn loading_inline(&mut self, frame: usize) -> io::Result<String> {
+        self.format(|this, buf| this.theme.format_loading(buf, frame))
+    }
+
+    pub fn grid_select_row(&mut self, cells: &[String], active: Option<usize>) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            for (idx, cell) in cells.iter().enumerate() {
+                if idx > 0 {
+                    write!(buf, " ")?;
+                }
+                let flags = RenderFlags {
+                    active: active == Some(idx),
+                    ..RenderFlags::default()
+                };
+                let ctx = this
+                    .context(RenderKind::GridSelect, "", waiting(None))
+                    .item(cell, flags);
+                this.theme.render(buf, &ctx)?;
+            }
+            Ok(())
         })
     }
 
-    pub fn input_prompt(&mut self, prompt: &str, default: Option<&str>) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| this.theme.format_input_prompt(buf, prompt, default))
+    pub fn edit_list_prompt(&mut self, prompt: &str) -> io::Result<()> {
+        let ctx = self.context(RenderKind::EditList, prompt, waiting(None));
+        self.write_formatted_prompt(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn edit_list_prompt_selection(&mut self, prompt: &str, items: &[String]) -> io::Result<()> {
+        let items: Vec<_> = items.iter().map(String::as_str).collect();
+        let ctx = self.context(RenderKind::EditList, prompt, done(&items));
+        self.write_report(prompt, &items, |this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn edit_list_item(&mut self, text: &str, active: bool) -> io::Result<()> {
+        let flags = RenderFlags {
+            active,
+            ..RenderFlags::default()
+        };
+        self.write_item(RenderKind::EditList, text, flags)
+    }
+
+    pub fn edit_list_empty(&mut self) -> io::Result<()> {
+        let flags = RenderFlags {
+            disabled: true,
+            ..RenderFlags::default()
+        };
+        self.write_item(
+            RenderKind::EditList,
+            "no entries, press 'a' to add one",
+            flags,
+        )
     }
 
-    pub fn input_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_input_prompt_selection(buf, prompt, sel)
+    pub fn key_prompt(&mut self, prompt: &str, keys: &[String]) -> io::Result<()> {
+        let keys: Vec<_> = keys.iter().map(String::as_str).collect();
+        let ctx = self
+            .context(RenderKind::KeyPrompt, prompt, waiting(None))
+            .values(&keys);
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn key_prompt_selection(&mut self, prompt: &str, key: &str) -> io::Result<()> {
+        let selections = [key];
+        let ctx = self.context(RenderKind::KeyPrompt, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
         })
     }
 
-    #[cfg(feature = "password")]
-    pub fn password_prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| {
-            write!(buf, "\r")?;
-            this.theme.format_password_prompt(buf, prompt)
+    pub fn range_select_prompt_item(
+        &mut self,
+        text: &str,
+        in_range: bool,
+        active: bool,
+    ) -> io::Result<()> {
+        let flags = RenderFlags {
+            active,
+            checked: in_range,
+            ..RenderFlags::default()
+        };
+        self.write_item(RenderKind::RangeSelect, text, flags)
+    }
+
+    pub fn range_select_prompt_selection(
+        &mut self,
+        prompt: &str,
+        first: &str,
+        last: &str,
+    ) -> io::Result<()> {
+        let selections = [first, last];
+        let ctx = self.context(RenderKind::RangeSelect, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
         })
     }
 
-    #[cfg(feature = "password")]
-    pub fn password_prompt_selection(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_password_prompt_selection(buf, prompt)
+    pub fn transfer_select_titles(
+        &mut self,
+        available: &str,
+        chosen: &str,
+        chosen_focused: bool,
+        width: usize,
+        gap: usize,
+    ) -> io::Result<()> {
+        let title = |this: &Self, text, active| {
+            let flags = RenderFlags {
+                active,
+                header: true,
+                ..RenderFlags::default()
+            };
+            this.context(RenderKind::TransferSelect, "", waiting(None))
+                .item(text, flags)
+        };
+        let left = title(self, available, !chosen_focused);
+        let right = title(self, chosen, chosen_focused);
+
+        self.write_formatted_line(|this, buf| {
+            let mut cell = String::new();
+            this.theme.render(&mut cell, &left)?;
+            write!(
+                buf,
+                "{}{}",
+                fit_cell(&cell, width, this.theme.ellipsis()),
+                " ".repeat(gap)
+            )?;
+            this.theme.render(buf, &right)
         })
     }
 
-    pub fn select_prompt(
+    pub fn transfer_select_row(
         &mut self,
-        prompt: &str,
-        paging_info: Option<(usize, usize)>,
+        available: Option<(&str, bool)>,
+        chosen: Option<(&str, bool)>,
+        width: usize,
+        gap: usize,
     ) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_select_prompt(buf, prompt)?;
+        let item = |this: &Self, (text, active)| {
+            let flags = RenderFlags {
+                active,
+                ..RenderFlags::default()
+            };
+            this.context(RenderKind::TransferSelect, "", waiting(None))
+                .item(text, flags)
+        };
+        let left = available.map(|entry| item(self, entry));
+        let right = chosen.map(|entry| item(self, entry));
 
-            if let Some(paging_info) = paging_info {
-                TermThemeRenderer::write_paging_info(buf, paging_info)?;
+        self.write_formatted_line(|this, buf| {
+            let mut cell = String::new();
+            if let Some(ctx) = left {
+                this.theme.render(&mut cell, &ctx)?;
+            }
+            write!(buf, "{}", fit_cell(&cell, width, this.theme.ellipsis()))?;
+
+            if let Some(ctx) = right {
+                write!(buf, "{}", " ".repeat(gap))?;
+                this.theme.render(buf, &ctx)?;
             }
 
             Ok(())
         })
     }
 
-    pub fn select_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_select_prompt_selection(buf, prompt, sel)
-        })
+    pub fn menu_prompt(&mut self, prompt: &str, breadcrumb: &[&str]) -> io::Result<()> {
+        let ctx = self
+            .context(RenderKind::Menu, prompt, waiting(None))
+            .values(breadcrumb);
+        self.write_formatted_prompt(|this, buf| this.theme.render(buf, &ctx))
     }
 
-    pub fn select_prompt_item(&mut self, text: &str, active: bool) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| {
-            this.theme.format_select_prompt_item(buf, text, active)
-        })
+    pub fn menu_prompt_selection(&mut self, prompt: &str, path: &[&str]) -> io::Result<()> {
+        let ctx = self.context(RenderKind::Menu, prompt, done(path));
+        self.write_report(prompt, path, |this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn menu_item(&mut self, text: &str, has_submenu: bool, active: bool) -> io::Result<()> {
+        let flags = RenderFlags {
+            active,
+            branch: has_submenu,
+            ..RenderFlags::default()
+        };
+        self.write_item(RenderKind::Menu, text, flags)
+    }
+
+    pub fn tree_select_prompt_item(
+        &mut self,
+        text: &str,
+        depth: usize,
+        expanded: Option<bool>,
+        active: bool,
+    ) -> io::Result<()> {
+        let flags = RenderFlags {
+            active,
+            branch: expanded.is_some(),
+            expanded: expanded == Some(true),
+            ..RenderFlags::default()
+        };
+        let ctx = self
+            .context(RenderKind::TreeSelect, "", waiting(None))
+            .item(text, flags)
+            .depth(depth);
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
     }
 
     #[cfg(feature = "fuzzy-select")]
@@ -848,103 +4443,667 @@ impl<'a> TermThemeRenderer<'a> {
         text: &str,
         active: bool,
         highlight: bool,
-        matcher: &SkimMatcherV2,
         search_term: &str,
     ) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| {
-            this.theme.format_fuzzy_select_prompt_item(
-                buf,
-                text,
-                active,
-                highlight,
-                matcher,
-                search_term,
-            )
+        let flags = RenderFlags {
+            active,
+            highlight_matches: highlight,
+            ..RenderFlags::default()
+        };
+        let state = RenderState::Active {
+            input: search_term,
+            cursor: search_term.len(),
+            default: None,
+        };
+        let ctx = self
+            .context(RenderKind::FuzzySelect, "", state)
+            .item(text, flags);
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn review_each_item(&mut self, text: &str) -> io::Result<()> {
+        self.write_item(RenderKind::ReviewEach, text, RenderFlags::default())
+    }
+
+    pub fn review_each_prompt(
+        &mut self,
+        prompt: &str,
+        position: usize,
+        total: Option<usize>,
+        keys: &[&str],
+    ) -> io::Result<()> {
+        let ctx = self
+            .context(RenderKind::ReviewEach, prompt, waiting(None))
+            .position(Some((position, total)))
+            .values(keys);
+        self.write_formatted_str(|this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn review_each_prompt_selection(
+        &mut self,
+        prompt: &str,
+        decision: ReviewDecision,
+    ) -> io::Result<()> {
+        let value = match decision {
+            ReviewDecision::Yes => "yes",
+            ReviewDecision::No => "no",
+            ReviewDecision::Edit => "edit",
+        };
+        let selections = [value];
+        let ctx = self.context(RenderKind::ReviewEach, prompt, done(&selections));
+        self.write_report(prompt, &selections, |this, buf| {
+            this.theme.render(buf, &ctx)
         })
     }
 
+    pub fn tree_multi_select_prompt_item(
+        &mut self,
+        text: &str,
+        depth: usize,
+        expanded: Option<bool>,
+        checked: CheckState,
+        active: bool,
+    ) -> io::Result<()> {
+        let flags = RenderFlags {
+            active,
+            checked: checked == CheckState::Checked,
+            partial: checked == CheckState::Partial,
+            branch: expanded.is_some(),
+            expanded: expanded == Some(true),
+            ..RenderFlags::default()
+        };
+        let ctx = self
+            .context(RenderKind::TreeMultiSelect, "", waiting(None))
+            .item(text, flags)
+            .depth(depth);
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
+    }
+
     pub fn multi_select_prompt(
         &mut self,
         prompt: &str,
-        paging_info: Option<(usize, usize)>,
+        paging_info: Option<PagingInfo>,
     ) -> io::Result<()> {
+        let ctx = self.context(RenderKind::MultiSelect, prompt, waiting(None));
         self.write_formatted_prompt(|this, buf| {
-            this.theme.format_multi_select_prompt(buf, prompt)?;
-
-            if let Some(paging_info) = paging_info {
-                TermThemeRenderer::write_paging_info(buf, paging_info)?;
-            }
-
-            Ok(())
+            let mut line = String::new();
+            this.theme.render(&mut line, &ctx)?;
+            this.write_paging_info(&mut line, paging_info)?;
+            this.write_jump_input(&mut line)?;
+            this.write_with_right_segment(buf, &line)
         })
     }
 
     pub fn multi_select_prompt_selection(&mut self, prompt: &str, sel: &[&str]) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
+        let ctx = self.context(RenderKind::MultiSelect, prompt, done(sel));
+        self.write_report(prompt, sel, |this, buf| this.theme.render(buf, &ctx))
+    }
+
+    pub fn multi_select_counter(
+        &mut self,
+        checked: usize,
+        total: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) -> io::Result<()> {
+        let mut buf = String::new();
+        self.theme
+            .format_multi_select_counter(&mut buf, checked, total, min, max)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        self.write_formatted_line(|this, buf| {
             this.theme
-                .format_multi_select_prompt_selection(buf, prompt, sel)
+                .format_multi_select_counter(buf, checked, total, min, max)
         })
     }
 
     pub fn multi_select_prompt_item(
         &mut self,
         text: &str,
+        icon: Option<&str>,
         checked: bool,
+        disabled: bool,
         active: bool,
     ) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| {
-            this.theme
-                .format_multi_select_prompt_item(buf, text, checked, active)
-        })
+        let flags = RenderFlags {
+            active,
+            checked,
+            disabled,
+            ..RenderFlags::default()
+        };
+        self.write_list_item(RenderKind::MultiSelect, text, icon, flags)
     }
 
-    pub fn sort_prompt(
-        &mut self,
-        prompt: &str,
-        paging_info: Option<(usize, usize)>,
-    ) -> io::Result<()> {
+    pub fn sort_prompt(&mut self, prompt: &str, paging_info: Option<PagingInfo>) -> io::Result<()> {
+        let ctx = self.context(RenderKind::Sort, prompt, waiting(None));
         self.write_formatted_prompt(|this, buf| {
-            this.theme.format_sort_prompt(buf, prompt)?;
-
-            if let Some(paging_info) = paging_info {
-                TermThemeRenderer::write_paging_info(buf, paging_info)?;
-            }
-
-            Ok(())
+            let mut line = String::new();
+            this.theme.render(&mut line, &ctx)?;
+            this.write_paging_info(&mut line, paging_info)?;
+            this.write_jump_input(&mut line)?;
+            this.write_with_right_segment(buf, &line)
         })
     }
 
     pub fn sort_prompt_selection(&mut self, prompt: &str, sel: &[&str]) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_sort_prompt_selection(buf, prompt, sel)
-        })
+        let ctx = self.context(RenderKind::Sort, prompt, done(sel));
+        self.write_report(prompt, sel, |this, buf| this.theme.render(buf, &ctx))
     }
 
     pub fn sort_prompt_item(&mut self, text: &str, picked: bool, active: bool) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| {
-            this.theme
-                .format_sort_prompt_item(buf, text, picked, active)
-        })
+        let flags = RenderFlags {
+            active,
+            checked: picked,
+            ..RenderFlags::default()
+        };
+        let ctx = self
+            .context(RenderKind::Sort, "", waiting(None))
+            .item(text, flags);
+        self.write_formatted_line(|this, buf| this.theme.render(buf, &ctx))
     }
 
     pub fn clear(&mut self) -> io::Result<()> {
+        self.start_over_if_resumed()?;
         self.term
-            .clear_last_lines(self.height + self.prompt_height)?;
-        self.height = 0;
+            .clear_last_lines(self.rows(&self.lines) + self.rows(&self.prompt_lines))?;
+        self.lines.clear();
+        Ok(())
+    }
+
+    /// Clears the lines rendered since the prompt, leaving the prompt on the screen.
+    pub fn clear_items(&mut self) -> io::Result<()> {
+        self.start_over_if_resumed()?;
+        self.term.clear_last_lines(self.rows(&self.lines))?;
+        self.lines.clear();
+        self.reprint_prompt()
+    }
+
+    /// Moves the cursor back to the first line rendered since the prompt, leaving the lines
+    /// on the screen.
+    ///
+    /// The next frame overwrites them row by row instead of rendering into a blank region,
+    /// which flickers over slow connections. [`finish_frame`](Self::finish_frame) erases
+    /// the rows the new frame didn't reach.
+    pub fn rewind_preserve_prompt(&mut self) -> io::Result<()> {
+        self.start_over_if_resumed()?;
+        self.term.move_cursor_up(self.rows(&self.lines))?;
+        self.lines.clear();
+        self.overwriting = true;
+        self.reprint_prompt()
+    }
+
+    /// Forgets the lines rendered before the process was stopped with Ctrl-Z, which are
+    /// above the output of the shell once it continues, so the next frame is rendered below
+    /// it from scratch.
+    fn start_over_if_resumed(&mut self) -> io::Result<()> {
+        let resumes = crate::job_control::resumes();
+        if resumes == self.resumes {
+            return Ok(());
+        }
+
+        self.resumes = resumes;
+        self.lines.clear();
+        self.prompt_lines.clear();
+        self.overwriting = false;
+        self.bottom_rows = None;
+        if self.cursor_hidden {
+            self.term.hide_cursor()?;
+        }
+        Ok(())
+    }
+
+    /// Renders the kept prompt again on terminals which can't move the cursor back up to
+    /// it, so every frame they print below the previous one is complete.
+    fn reprint_prompt(&mut self) -> io::Result<()> {
+        if self.capabilities.cursor_movement {
+            return Ok(());
+        }
+        for line in &self.prompt_lines {
+            self.term.write_line(line)?;
+        }
+        Ok(())
+    }
+
+    /// Erases what is left of the previous frame after
+    /// [`rewind_preserve_prompt`](Self::rewind_preserve_prompt).
+    pub fn finish_frame(&mut self) -> io::Result<()> {
+        if self.overwriting {
+            self.overwriting = false;
+            self.term.clear_to_end_of_screen()?;
+        }
+
+        if !self.anchored_bottom {
+            return Ok(());
+        }
+
+        let rows = self.rows(&self.prompt_lines) + self.rows(&self.lines);
+        match self.bottom_rows {
+            // The first frame is rendered below the earlier output and moved down from
+            // there, which scrolls the earlier output only as far as the frame needs.
+            None => {
+                self.term.move_cursor_up(rows)?;
+                self.term.clear_to_end_of_screen()?;
+                self.term.move_cursor_down(self.term.size().0 as usize)?;
+                self.term.move_cursor_up(rows)?;
+                for line in self.prompt_lines.iter().chain(&self.lines) {
+                    self.term.write_line(line)?;
+                }
+            }
+            // A frame shorter than the previous one is pushed down to the bottom again by
+            // blank rows above it, taller frames scroll the terminal by themselves.
+            Some(previous) if rows < previous => {
+                self.term.move_cursor_up(rows)?;
+                self.term.insert_lines(previous - rows)?;
+                self.term.move_cursor_down(previous)?;
+            }
+            Some(_) => {}
+        }
+        self.bottom_rows = Some(rows);
         Ok(())
     }
 
-    pub fn clear_preserve_prompt(&mut self, size_vec: &[usize]) -> io::Result<()> {
-        let mut new_height = self.height;
-        //Check each item size, increment on finding an overflow
-        for size in size_vec {
-            if *size > self.term.size().1 as usize {
-                new_height += 1;
+    /// Pins the following frames to the bottom of the terminal, with the output rendered
+    /// before left above them.
+    ///
+    /// Frames have to be completed with [`finish_frame`](Self::finish_frame).
+    pub fn anchor_to_bottom(&mut self) {
+        self.anchored_bottom = true;
+        self.bottom_rows = None;
+    }
+
+    /// Hides the cursor until [`show_cursor`](Self::show_cursor) or until the renderer is
+    /// dropped.
+    pub fn hide_cursor(&mut self) -> io::Result<()> {
+        self.cursor_hidden = true;
+        self.term.hide_cursor()
+    }
+
+    /// Shows the cursor hidden with [`hide_cursor`](Self::hide_cursor).
+    pub fn show_cursor(&mut self) -> io::Result<()> {
+        self.cursor_hidden = false;
+        self.term.show_cursor()
+    }
+
+    /// Signals a rejected key, a value beyond its bounds or failed validation as the theme
+    /// configures.
+    pub fn bell(&mut self) -> io::Result<()> {
+        match self.theme.bell() {
+            Bell::Off => return Ok(()),
+            Bell::Audible => self.term.write_str("\x07")?,
+            // Terminals without cursor movement would print the codes flashing the screen.
+            Bell::Visual if !self.capabilities.cursor_movement => self.term.write_str("\x07")?,
+            Bell::Visual => {
+                self.term.write_str("\x1b[?5h")?;
+                self.term.flush()?;
+                if !crate::deterministic::is_deterministic() {
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                }
+                self.term.write_str("\x1b[?5l")?;
             }
         }
+        self.term.flush()
+    }
+
+    /// Rings the [`bell`](Self::bell) for a key the prompt doesn't handle, except for the
+    /// [`Key::Unknown`] reads return after the terminal was resized.
+    pub fn reject_key(&mut self, key: &Key) -> io::Result<()> {
+        if *key == Key::Unknown {
+            return Ok(());
+        }
+        self.bell()
+    }
+
+    /// Renders the following lines in the alternate screen, leaving the shell history alone.
+    pub fn enter_alternate_screen(&mut self) -> io::Result<()> {
+        if self.alternate_screen.is_none() {
+            self.alternate_screen = Some(AlternateScreen::enter(self.term)?);
+        }
+        Ok(())
+    }
+
+    /// Returns to the primary screen, which shows none of the lines rendered in the
+    /// alternate one, so only lines rendered afterwards (like the selection) remain.
+    pub fn leave_alternate_screen(&mut self) -> io::Result<()> {
+        if let Some(screen) = self.alternate_screen.take() {
+            screen.leave()?;
+            self.lines.clear();
+            self.prompt_lines.clear();
+            self.overwriting = false;
+        }
+        Ok(())
+    }
 
-        self.term.clear_last_lines(new_height)?;
-        self.height = 0;
+    /// Clears the prompt kept by [`rewind_preserve_prompt`](Self::rewind_preserve_prompt), so
+    /// it can be rendered again in place.
+    pub fn clear_prompt(&mut self) -> io::Result<()> {
+        self.start_over_if_resumed()?;
+        self.term.clear_last_lines(self.rows(&self.prompt_lines))?;
+        self.prompt_lines.clear();
         Ok(())
     }
+
+    /// Makes the lines rendered since the prompt part of it, so they are kept by
+    /// [`rewind_preserve_prompt`](Self::rewind_preserve_prompt) too.
+    pub fn pin_to_prompt(&mut self) {
+        self.prompt_lines.append(&mut self.lines);
+    }
+}
+
+impl Drop for TermThemeRenderer<'_> {
+    fn drop(&mut self) {
+        // Prompts interrupted by errors, Ctrl-C or panics don't leave the cursor hidden.
+        if self.cursor_hidden {
+            let _ = self.term.show_cursor();
+        }
+        let _ = self.term.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redacted(value: &str, redaction: Redaction) -> String {
+        let mut buf = String::new();
+        SimpleTheme
+            .format_redacted_value(&mut buf, value, redaction)
+            .unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_redacted_value() {
+        assert_eq!(redacted("hunter2", Redaction::Full), "********");
+        assert_eq!(
+            redacted("ghp_abcdefgh1234", Redaction::LastChars(4)),
+            "****1234"
+        );
+        assert_eq!(redacted("12345678", Redaction::LastChars(4)), "********");
+    }
+
+    struct UpperItems;
+
+    impl Theme for UpperItems {
+        fn render(&self, f: &mut dyn fmt::Write, ctx: &RenderContext) -> fmt::Result {
+            match ctx.item {
+                Some(text) => write!(f, "{}", text.to_uppercase()),
+                None => render_with_methods(self, f, ctx),
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_context() {
+        let render = |theme: &dyn Theme, ctx: RenderContext| {
+            let mut buf = String::new();
+            theme.render(&mut buf, &ctx).unwrap();
+            buf
+        };
+        let flags = RenderFlags {
+            active: true,
+            checked: true,
+            ..RenderFlags::default()
+        };
+        let item =
+            RenderContext::new(RenderKind::MultiSelect, "", waiting(None)).item("tea", flags);
+
+        assert_eq!(render(&SimpleTheme, item), "> [x] tea");
+        assert_eq!(render(&UpperItems, item), "TEA");
+
+        let locked = RenderFlags {
+            checked: true,
+            disabled: true,
+            ..RenderFlags::default()
+        };
+        let item =
+            RenderContext::new(RenderKind::MultiSelect, "", waiting(None)).item("tea", locked);
+        assert_eq!(render(&SimpleTheme, item), "  [x] tea (locked)");
+        assert_eq!(
+            render(
+                &UpperItems,
+                RenderContext::new(RenderKind::Confirm, "Sure?", done(&[])).choice(Some(true))
+            ),
+            "Sure? yes"
+        );
+
+        let labels = ["HTTP", "HTTPS"];
+        let toggle = RenderContext::new(RenderKind::Toggle, "Protocol", waiting(None))
+            .values(&labels)
+            .choice(Some(true));
+        assert_eq!(render(&SimpleTheme, toggle), "Protocol:  HTTP  / [HTTPS]");
+        // Prompts without dedicated methods are handed to the inner theme.
+        assert_eq!(
+            render(&Bracketed(SimpleTheme), toggle),
+            render(&SimpleTheme, toggle)
+        );
+
+        let node = RenderFlags {
+            active: true,
+            partial: true,
+            branch: true,
+            ..RenderFlags::default()
+        };
+        let node = RenderContext::new(RenderKind::TreeMultiSelect, "", waiting(None))
+            .item("src", node)
+            .depth(1);
+        assert_eq!(render(&SimpleTheme, node), ">   + [~] src");
+        assert_eq!(render(&UpperItems, node), "SRC");
+    }
+
+    struct Bracketed<T>(T);
+
+    impl<T: Theme> Theme for Bracketed<T> {
+        fn inner_theme(&self) -> Option<&dyn Theme> {
+            Some(&self.0)
+        }
+
+        fn format_select_prompt_item(
+            &self,
+            f: &mut dyn fmt::Write,
+            text: &str,
+            active: bool,
+        ) -> fmt::Result {
+            write!(f, "[{}]", text)?;
+            self.0.format_select_prompt_item(f, "", active)
+        }
+    }
+
+    #[test]
+    fn test_inner_theme() {
+        let theme = Bracketed(ColorfulTheme::default());
+        assert_eq!(theme.ellipsis(), "…");
+
+        let mut buf = String::new();
+        theme
+            .format_select_prompt_item(&mut buf, "tea", true)
+            .unwrap();
+        let mut expected = String::from("[tea]");
+        theme
+            .0
+            .format_select_prompt_item(&mut expected, "", true)
+            .unwrap();
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_builder_keeps_customizations() {
+        let plain = |symbol: &StyledObject<String>| {
+            console::strip_ansi_codes(&symbol.to_string()).into_owned()
+        };
+        let bold = Style::new().for_stderr().bold();
+
+        for theme in [
+            ColorfulTheme::builder()
+                .prompt_suffix(":")
+                .active_item_style(bold.clone())
+                .ascii(true)
+                .background(Background::Light)
+                .build(),
+            ColorfulTheme::builder()
+                .ascii(true)
+                .background(Background::Light)
+                .prompt_suffix(":")
+                .active_item_style(bold.clone())
+                .build(),
+        ] {
+            assert_eq!(plain(&theme.prompt_suffix), ":");
+            assert_eq!(plain(&theme.checked_item_prefix), "[x]");
+            assert_eq!(theme.active_item_style, bold);
+            assert_eq!(theme.defaults_style, Style::new().for_stderr().blue());
+        }
+    }
+
+    #[test]
+    fn test_ascii_theme() {
+        assert_eq!(AsciiTheme.ellipsis(), "...");
+        assert_eq!(SimpleTheme.ellipsis(), "…");
+
+        let mut buf = String::new();
+        AsciiTheme
+            .format_select_prompt_item(&mut buf, "tea", true)
+            .unwrap();
+        assert_eq!(buf, "> tea");
+    }
+
+    #[test]
+    fn test_multi_select_counter() {
+        let counter = |checked, min, max| {
+            let mut buf = String::new();
+            SimpleTheme
+                .format_multi_select_counter(&mut buf, checked, 5, min, max)
+                .unwrap();
+            buf
+        };
+
+        assert_eq!(counter(2, Some(3), None), "2/5 selected, need at least 3");
+        assert_eq!(counter(3, Some(3), Some(3)), "3/5 selected, at most 3");
+        assert_eq!(counter(1, None, Some(3)), "1/5 selected");
+    }
+
+    #[test]
+    fn test_item_style() {
+        let red = Style::new().force_styling(true).red();
+        let theme = ColorfulTheme::builder()
+            .item_style({
+                let red = red.clone();
+                move |state| {
+                    if state.checked {
+                        red.clone()
+                    } else {
+                        Style::new()
+                    }
+                }
+            })
+            .build();
+
+        let mut buf = String::new();
+        theme
+            .format_multi_select_prompt_item(&mut buf, "tea", true, false)
+            .unwrap();
+        assert!(buf.ends_with(&red.apply_to("tea").to_string()));
+    }
+
+    #[cfg(feature = "fuzzy-select")]
+    #[test]
+    fn test_fuzzy_match_ranges() {
+        assert_eq!(fuzzy_match_ranges("config", &[0, 1, 2, 5]), [0..3, 5..6]);
+        assert_eq!(fuzzy_match_ranges("äbc", &[0, 1, 2]), vec![0..4]);
+        assert!(fuzzy_match_ranges("abc", &[]).is_empty());
+
+        let mut buf = String::new();
+        write_fuzzy_match(
+            &mut buf,
+            "config",
+            &[0..3, 5..6],
+            &Style::new(),
+            &Style::new(),
+        )
+        .unwrap();
+        assert_eq!(buf, "config");
+    }
+
+    /// A terminal which was resized to 20 columns.
+    struct Narrowed;
+
+    impl Backend for Narrowed {
+        fn read_key(&self) -> io::Result<Key> {
+            Ok(Key::Enter)
+        }
+
+        fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+            Ok(initial.into())
+        }
+
+        fn read_secure_line(&self) -> io::Result<String> {
+            Ok(String::new())
+        }
+
+        fn write_str(&self, _: &str) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn flush(&self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn size(&self) -> (u16, u16) {
+            (24, 20)
+        }
+    }
+
+    #[test]
+    fn test_strict_rows() {
+        let mut render = TermThemeRenderer::new(&Narrowed, &SimpleTheme);
+        render.drawn_width = 40;
+        let lines = vec!["x".repeat(30)];
+
+        assert_eq!(render.rows(&lines), 2);
+        render.strict = true;
+        assert_eq!(render.rows(&lines), 1);
+    }
+
+    fn render_select(render: &mut TermThemeRenderer) -> io::Result<()> {
+        render.select_prompt("Pick", None)?;
+        render.select_prompt_item("a", None, false, true)?;
+        render.select_prompt_item("b", None, false, false)
+    }
+
+    #[test]
+    fn test_start_over_after_resume() {
+        let term = crate::Headless::new(10, 20).colors(false);
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        render_select(&mut render).unwrap();
+        render.clear().unwrap();
+        render_select(&mut render).unwrap();
+        let redrawn = term.screen();
+
+        // The frame before stopping is above the output of the shell, so the next one is
+        // rendered from scratch below it.
+        render.resumes = render.resumes.wrapping_sub(1);
+        render.clear().unwrap();
+        render_select(&mut render).unwrap();
+        assert_eq!(term.screen(), format!("{}\n{}", redrawn, redrawn));
+    }
+
+    #[test]
+    fn test_cursor_shown_after_errors() {
+        let prompt = |input: &'static [u8]| {
+            let term = crate::StreamBackend::new(input, Vec::new());
+            let err = crate::Select::new()
+                .items(&["a", "b"])
+                .interact_on(&term)
+                .unwrap_err();
+            let output = String::from_utf8(term.into_inner().1).unwrap();
+            assert!(output.contains("\x1b[?25l"));
+            assert!(output.ends_with("\x1b[?25h"));
+            err.kind()
+        };
+
+        assert_eq!(prompt(b"\x1b[B\x03"), io::ErrorKind::Interrupted);
+        assert_eq!(prompt(b"\x1b[B"), io::ErrorKind::UnexpectedEof);
+    }
 }
@@ -0,0 +1,285 @@
+//! Loads [`ColorfulTheme`]s from TOML or JSON files.
+use std::{fs, io, path::Path};
+
+use console::Style;
+use serde::Deserialize;
+
+use crate::{
+    theme::{Background, Bell, ColorMode, ColorfulTheme, ColorfulThemeBuilder, PagingPosition},
+    Rgb,
+};
+
+/// The style attributes understood in theme files, besides 256 color numbers and hex colors.
+const STYLE_NAMES: &[&str] = &[
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright",
+    "on_black",
+    "on_red",
+    "on_green",
+    "on_yellow",
+    "on_blue",
+    "on_magenta",
+    "on_cyan",
+    "on_white",
+    "on_bright",
+    "bold",
+    "dim",
+    "underlined",
+    "blink",
+    "blink_fast",
+    "reverse",
+    "hidden",
+    "strikethrough",
+];
+
+/// Parses a dotted style like `green.bold`, `on_236.15` or `#ff7800.on_#202020`.
+///
+/// Hex colors are approximated with the palette the terminal supports.
+fn parse_style(style: &str) -> Result<Style, String> {
+    let mut named = vec![];
+    let mut rgb = vec![];
+
+    for part in style.split('.').filter(|part| !part.is_empty()) {
+        let color = part.strip_prefix("on_").unwrap_or(part);
+        if color.starts_with('#') {
+            rgb.push((color.len() < part.len(), color.parse::<Rgb>()?));
+        } else if STYLE_NAMES.contains(&part) || color.parse::<u8>().is_ok() {
+            named.push(part);
+        } else {
+            return Err(format!("Unknown style '{}' in '{}'", part, style));
+        }
+    }
+
+    let mut style = Style::from_dotted_str(&named.join(".")).for_stderr();
+    for (background, color) in rgb {
+        style = if background {
+            color.bg(style)
+        } else {
+            color.fg(style)
+        };
+    }
+
+    Ok(style)
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SymbolDef {
+    symbol: Option<String>,
+    style: Option<String>,
+}
+
+macro_rules! theme_file {
+    (
+        styles { $($style:ident,)* }
+        symbols { $($symbol:ident => $symbol_style:ident,)* }
+    ) => {
+        #[derive(Default, Deserialize)]
+        #[serde(default, deny_unknown_fields)]
+        struct ThemeFile {
+            $($style: Option<String>,)*
+            $($symbol: SymbolDef,)*
+            inline_selections: Option<bool>,
+            color_mode: Option<ColorMode>,
+            paging_position: Option<PagingPosition>,
+            background: Option<Background>,
+            compact: Option<bool>,
+            scrollbar: Option<bool>,
+            prompt_template: Option<String>,
+            item_template: Option<String>,
+            report_template: Option<String>,
+            ascii: Option<bool>,
+            bell: Option<Bell>,
+        }
+
+        impl ThemeFile {
+            fn apply(self, mut builder: ColorfulThemeBuilder) -> Result<ColorfulThemeBuilder, String> {
+                // Replaces the symbols, so it has to come before them.
+                if let Some(ascii) = self.ascii {
+                    builder = builder.ascii(ascii);
+                }
+                // Replaces some styles, so it has to come before them.
+                if let Some(background) = self.background {
+                    builder = builder.background(background);
+                }
+                $(
+                    if let Some(style) = self.$style {
+                        builder = builder.$style(parse_style(&style)?);
+                    }
+                )*
+                $(
+                    if let Some(symbol) = self.$symbol.symbol {
+                        builder = builder.$symbol(symbol);
+                    }
+                    if let Some(style) = self.$symbol.style {
+                        builder = builder.$symbol_style(parse_style(&style)?);
+                    }
+                )*
+                if let Some(inline_selections) = self.inline_selections {
+                    builder = builder.inline_selections(inline_selections);
+                }
+                if let Some(color_mode) = self.color_mode {
+                    builder = builder.color_mode(color_mode);
+                }
+                if let Some(paging_position) = self.paging_position {
+                    builder = builder.paging_position(paging_position);
+                }
+                if let Some(compact) = self.compact {
+                    builder = builder.compact(compact);
+                }
+                if let Some(scrollbar) = self.scrollbar {
+                    builder = builder.scrollbar(scrollbar);
+                }
+                match self.bell {
+                    Some(Bell::Off) => builder = builder.bell(false),
+                    Some(Bell::Audible) => builder = builder.bell(true),
+                    Some(Bell::Visual) => builder = builder.visual_bell(true),
+                    None => {}
+                }
+                if let Some(template) = self.prompt_template {
+                    builder = builder.prompt_template(template.parse()?);
+                }
+                if let Some(template) = self.item_template {
+                    builder = builder.item_template(template.parse()?);
+                }
+                if let Some(template) = self.report_template {
+                    builder = builder.report_template(template.parse()?);
+                }
+
+                Ok(builder)
+            }
+        }
+    };
+}
+
+theme_file! {
+    styles {
+        defaults_style,
+        prompt_style,
+        error_style,
+        hint_style,
+        values_style,
+        active_item_style,
+        inactive_item_style,
+        disabled_item_style,
+        locked_item_style,
+    }
+    symbols {
+        prompt_prefix => prompt_prefix_style,
+        prompt_suffix => prompt_suffix_style,
+        success_prefix => success_prefix_style,
+        success_suffix => success_suffix_style,
+        error_prefix => error_prefix_style,
+        active_item_prefix => active_item_prefix_style,
+        inactive_item_prefix => inactive_item_prefix_style,
+        checked_item_prefix => checked_item_prefix_style,
+        unchecked_item_prefix => unchecked_item_prefix_style,
+        picked_item_prefix => picked_item_prefix_style,
+        unpicked_item_prefix => unpicked_item_prefix_style,
+    }
+}
+
+fn invalid_data<E: ToString>(err: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+impl ColorfulTheme {
+    /// Loads a theme from a TOML file, or a JSON file if the extension is `.json`.
+    ///
+    /// Settings missing from the file keep their defaults. Styles are given as dotted
+    /// attribute lists like `"green.bold"`, `"on_236.15"` or `"#ff7800.on_#202020"`, hex
+    /// colors being approximated with the palette the terminal supports. Symbols are
+    /// given as tables with a `symbol` and a `style`. `color_mode` is one of `"auto"`,
+    /// `"always"` or `"never"`, `paging_position` one of `"prompt"`, `"footer"` or
+    /// `"hidden"`, `background` one of `"dark"` or `"light"` and `bell` one of `"off"`,
+    /// `"audible"` or `"visual"`. Layouts are given as
+    /// [`Template`](crate::theme::Template)s in `prompt_template`, `item_template` and
+    /// `report_template`:
+    ///
+    /// ```toml
+    /// color_mode = "auto"
+    /// item_template = "{cursor} {item}"
+    /// prompt_style = "bold"
+    /// hint_style = "black.bright"
+    ///
+    /// [prompt_prefix]
+    /// symbol = "»"
+    /// style = "magenta"
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<ColorfulTheme> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+
+        match path.extension() {
+            Some(ext) if ext == "json" => ColorfulTheme::from_json_str(&contents),
+            _ => ColorfulTheme::from_toml_str(&contents),
+        }
+    }
+
+    /// Parses a theme from TOML, see [`from_file`](Self::from_file) for the format.
+    pub fn from_toml_str(toml: &str) -> io::Result<ColorfulTheme> {
+        let file: ThemeFile = toml::from_str(toml).map_err(invalid_data)?;
+        Ok(file
+            .apply(ColorfulTheme::builder())
+            .map_err(invalid_data)?
+            .build())
+    }
+
+    /// Parses a theme from JSON, see [`from_file`](Self::from_file) for the format.
+    pub fn from_json_str(json: &str) -> io::Result<ColorfulTheme> {
+        let file: ThemeFile = serde_json::from_str(json).map_err(invalid_data)?;
+        Ok(file
+            .apply(ColorfulTheme::builder())
+            .map_err(invalid_data)?
+            .build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_style() {
+        assert_eq!(
+            parse_style("green.bold"),
+            Ok(Style::new().for_stderr().green().bold())
+        );
+        assert_eq!(
+            parse_style("on_236.15"),
+            Ok(Style::new().for_stderr().on_color256(236).color256(15))
+        );
+        assert!(parse_style("grene").is_err());
+        assert!(parse_style("#ff7800.on_#202020.bold").is_ok());
+        assert!(parse_style("#ff78").is_err());
+    }
+
+    #[test]
+    fn test_toml_and_json_agree() {
+        let toml = ColorfulTheme::from_toml_str(
+            "hint_style = \"blue\"\n[prompt_prefix]\nsymbol = \">\"\nstyle = \"red\"\n",
+        )
+        .unwrap();
+        let json = ColorfulTheme::from_json_str(
+            r#"{"hint_style": "blue", "prompt_prefix": {"symbol": ">", "style": "red"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(toml.hint_style, Style::new().for_stderr().blue());
+        assert_eq!(
+            toml.prompt_prefix.to_string(),
+            json.prompt_prefix.to_string()
+        );
+        assert!(ColorfulTheme::from_toml_str("hint = \"blue\"").is_err());
+
+        let never = ColorfulTheme::from_toml_str("color_mode = \"never\"").unwrap();
+        assert_eq!(never.color_mode, ColorMode::Never);
+    }
+}
@@ -0,0 +1,428 @@
+//! Gives up waiting for the answer of a prompt after a while.
+//!
+//! The deadline is kept for the thread while the prompt runs, so renderers pass the
+//! [`countdown`] to the theme. Reading keys returns [`Key::Unknown`] whenever it ticks,
+//! which prompts render again on.
+use std::{
+    cell::Cell,
+    io,
+    time::{Duration, Instant},
+};
+
+use console::Key;
+
+use crate::{backend::read_line_keys, fallback, Backend, Capabilities};
+
+/// How long a prompt with a timeout waits for a key before checking the size again.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Returns how long the prompt of the current thread has left until its timeout, rounded
+/// up to whole seconds.
+pub(crate) fn countdown() -> Option<Duration> {
+    DEADLINE
+        .with(Cell::get)
+        .map(|deadline| Duration::from_secs(seconds(deadline)))
+}
+
+/// Returns the whole seconds left until `deadline`, rounded up.
+fn seconds(deadline: Instant) -> u64 {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    remaining.as_secs() + (remaining.subsec_nanos() > 0) as u64
+}
+
+/// Restores the deadline of an enclosing prompt, also when the prompt panics.
+struct DeadlineGuard(Option<Instant>);
+
+impl Drop for DeadlineGuard {
+    fn drop(&mut self) {
+        DEADLINE.with(|deadline| deadline.set(self.0));
+    }
+}
+
+/// Returns the error prompts fail with when their [timeout](crate::Select::timeout) ran
+/// out and they have no default to resolve to.
+///
+/// Its kind is [`TimedOut`](io::ErrorKind::TimedOut), which tells it apart from other
+/// errors.
+pub fn timed_out() -> io::Error {
+    io::Error::new(io::ErrorKind::TimedOut, "Timed out waiting for an answer")
+}
+
+/// Runs `ask` on `term` until `timeout` runs out, then resolves the prompt to its default
+/// like in [defaults mode](crate::set_defaults_mode), or fails with [`timed_out`] if it
+/// has none.
+///
+/// The timeout only interrupts waiting on backends implementing
+/// [`Backend::poll_key`], others time out with the next key.
+pub(crate) fn ask<T>(
+    term: &dyn Backend,
+    timeout: Option<Duration>,
+    mut ask: impl FnMut(&dyn Backend) -> io::Result<T>,
+) -> io::Result<T> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return ask(term),
+    };
+
+    let deadline = Deadline {
+        term,
+        deadline: Instant::now() + timeout,
+    };
+    let guard = DeadlineGuard(DEADLINE.with(Cell::get));
+    DEADLINE.with(|current| current.set(Some(deadline.deadline)));
+    let rv = ask(&deadline);
+    drop(guard);
+
+    match rv {
+        Err(err) if err.kind() == io::ErrorKind::TimedOut => fallback::with_defaults(|| ask(term))
+            .map_err(|err| {
+                if err.kind() == io::ErrorKind::InvalidInput {
+                    timed_out()
+                } else {
+                    err
+                }
+            }),
+        rv => rv,
+    }
+}
+
+/// A backend whose keys stop coming at a deadline.
+struct Deadline<'a> {
+    term: &'a dyn Backend,
+    deadline: Instant,
+}
+
+impl Deadline<'_> {
+    /// Returns how long is left until the deadline.
+    fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+}
+
+impl Backend for Deadline<'_> {
+    fn read_key(&self) -> io::Result<Key> {
+        let size = self.term.size();
+        let shown = seconds(self.deadline);
+        loop {
+            let remaining = self.remaining();
+            if remaining == Duration::from_millis(0) {
+                // The row of the cursor is where the answer gets reported instead.
+                self.term.clear_line()?;
+                return Err(timed_out());
+            }
+            if self.term.poll_key(remaining.min(POLL_INTERVAL))? {
+                break;
+            }
+            // The countdown isn't rendered again for the last tick, the prompt times out.
+            let seconds = seconds(self.deadline);
+            if self.term.size() != size || (seconds != shown && seconds > 0) {
+                return Ok(Key::Unknown);
+            }
+        }
+
+        self.term.read_key()
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        read_line_keys(self, initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        read_line_keys(self, "", false)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.term.write_str(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.term.flush()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        self.term.size()
+    }
+
+    fn key_pending(&self) -> bool {
+        self.term.key_pending()
+    }
+
+    fn poll_key(&self, timeout: Duration) -> io::Result<bool> {
+        self.term.poll_key(timeout.min(self.remaining()))
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.term.write_line(s)
+    }
+
+    fn is_attended(&self) -> bool {
+        self.term.is_attended()
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.term.colors_supported()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.term.capabilities()
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_up(n)
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_down(n)
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_left(n)
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_right(n)
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        self.term.clear_chars(n)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        self.term.clear_line()
+    }
+
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        self.term.clear_last_lines(n)
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        self.term.clear_to_end_of_screen()
+    }
+
+    fn insert_lines(&self, n: usize) -> io::Result<()> {
+        self.term.insert_lines(n)
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        self.term.hide_cursor()
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        self.term.show_cursor()
+    }
+
+    fn enter_alternate_screen(&self) -> io::Result<()> {
+        self.term.enter_alternate_screen()
+    }
+
+    fn leave_alternate_screen(&self) -> io::Result<()> {
+        self.term.leave_alternate_screen()
+    }
+
+    fn begin_prompt(&self) {
+        self.term.begin_prompt()
+    }
+
+    fn end_prompt(&self) {
+        self.term.end_prompt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        theme::SimpleTheme, Confirm, Headless, Input, KeyPrompt, Menu, MenuItem, MultiSelect,
+        NumberInput, PinInput, RangeSelect, ReviewEach, Select, Slider, TagInput, Toggle,
+        TreeMultiSelect, TreeNode, TreeSelect,
+    };
+
+    /// A headless terminal whose keys run out by never arriving.
+    struct Idle(Headless);
+
+    impl Backend for Idle {
+        fn read_key(&self) -> io::Result<Key> {
+            self.0.read_key()
+        }
+
+        fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+            read_line_keys(self, initial, true)
+        }
+
+        fn read_secure_line(&self) -> io::Result<String> {
+            read_line_keys(self, "", false)
+        }
+
+        fn write_str(&self, s: &str) -> io::Result<()> {
+            self.0.write_str(s)
+        }
+
+        fn flush(&self) -> io::Result<()> {
+            self.0.flush()
+        }
+
+        fn size(&self) -> (u16, u16) {
+            self.0.size()
+        }
+
+        fn poll_key(&self, timeout: Duration) -> io::Result<bool> {
+            if self.0.key_pending() {
+                return Ok(true);
+            }
+            std::thread::sleep(timeout);
+            Ok(false)
+        }
+    }
+
+    const TIMEOUT: Duration = Duration::from_millis(20);
+
+    #[test]
+    fn test_timeout_resolves_to_default() {
+        let term = Idle(Headless::new(10, 40));
+
+        let proceed = Confirm::new()
+            .with_prompt("Continue?")
+            .default(true)
+            .timeout(TIMEOUT)
+            .interact_on(&term)
+            .unwrap();
+        assert!(proceed);
+
+        let checked = MultiSelect::new()
+            .items(&["a", "b", "c"])
+            .defaults(&[false, true, true])
+            .timeout(TIMEOUT)
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(checked, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_timeout_without_default() {
+        let term = Idle(Headless::new(10, 40).keys(vec![Key::Char('h')]));
+
+        let err = Input::<String>::new()
+            .with_prompt("Name")
+            .timeout(TIMEOUT)
+            .interact_text_on(&term)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_countdown() {
+        let frame = Headless::new(10, 40)
+            .colors(false)
+            .render(|term| {
+                Select::with_theme(&SimpleTheme)
+                    .with_prompt("Pick")
+                    .items(&["a", "b"])
+                    .default(0)
+                    .timeout(Duration::from_millis(59_500))
+                    .interact_on(term)
+            })
+            .unwrap();
+        assert_eq!(frame, "(60s) Pick:\n> a\n  b");
+        assert_eq!(countdown(), None);
+    }
+
+    #[test]
+    fn test_timeout_resolves_prompts_to_default() {
+        let term = Idle(Headless::new(10, 40));
+
+        let toggle = Toggle::new()
+            .default(true)
+            .timeout(TIMEOUT)
+            .interact_on(&term);
+        assert!(toggle.unwrap());
+
+        let number = NumberInput::<u32>::new()
+            .default(3)
+            .timeout(TIMEOUT)
+            .interact_on(&term);
+        assert_eq!(number.unwrap(), 3);
+
+        let slider = Slider::<u32>::new()
+            .min(0)
+            .max(10)
+            .default(7)
+            .timeout(TIMEOUT)
+            .interact_on(&term);
+        assert_eq!(slider.unwrap(), 7);
+
+        let tags = TagInput::new()
+            .defaults(&["a", "b"])
+            .timeout(TIMEOUT)
+            .interact_on(&term);
+        assert_eq!(tags.unwrap(), vec!["a", "b"]);
+
+        let range = RangeSelect::new()
+            .items(&["a", "b", "c"])
+            .default(1)
+            .timeout(TIMEOUT)
+            .interact_on(&term);
+        assert_eq!(range.unwrap(), (1, 1));
+
+        let tree = TreeMultiSelect::new()
+            .item(TreeNode::new("a").checked(true))
+            .item(TreeNode::new("b"))
+            .timeout(TIMEOUT)
+            .interact_on(&term);
+        assert_eq!(tree.unwrap(), vec![vec![0]]);
+    }
+
+    #[test]
+    fn test_timeout_without_default_fails_prompts() {
+        let term = Idle(Headless::new(10, 40));
+        let timed_out = |err: io::Error| assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+
+        timed_out(
+            KeyPrompt::new()
+                .timeout(TIMEOUT)
+                .interact_on(&term)
+                .unwrap_err(),
+        );
+        timed_out(
+            PinInput::new()
+                .timeout(TIMEOUT)
+                .interact_on(&term)
+                .unwrap_err(),
+        );
+        timed_out(
+            Menu::new()
+                .item(MenuItem::new("a"))
+                .timeout(TIMEOUT)
+                .interact_on(&term)
+                .unwrap_err(),
+        );
+        timed_out(
+            TreeSelect::new()
+                .item(TreeNode::new("a"))
+                .timeout(TIMEOUT)
+                .interact_on(&term)
+                .unwrap_err(),
+        );
+        timed_out(
+            ReviewEach::new()
+                .timeout(TIMEOUT)
+                .interact_on(&term, vec!["a"], |item| item.to_string())
+                .unwrap_err(),
+        );
+    }
+
+    #[test]
+    fn test_answered_in_time() {
+        let term = Idle(Headless::new(10, 40).keys(vec![Key::Char('n')]));
+
+        let proceed = Confirm::new()
+            .default(true)
+            .timeout(Duration::from_secs(60))
+            .interact_on(&term)
+            .unwrap();
+        assert!(!proceed);
+    }
+}
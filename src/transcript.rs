@@ -0,0 +1,594 @@
+//! Records the keys and output of sessions and replays them.
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    sync::{Mutex, MutexGuard},
+    thread,
+    time::{Duration, Instant},
+};
+
+use console::Key;
+
+use crate::{
+    backend::read_line_keys,
+    key_script::{key_name, parse_keys},
+    Backend, Capabilities,
+};
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// A backend capturing the escape codes the default methods of [`Backend`] write.
+struct Codes {
+    capabilities: Capabilities,
+    written: Mutex<String>,
+}
+
+impl Backend for Codes {
+    fn read_key(&self) -> io::Result<Key> {
+        Err(io::Error::new(io::ErrorKind::Other, "no keys"))
+    }
+
+    fn read_line_initial_text(&self, _initial: &str) -> io::Result<String> {
+        Err(io::Error::new(io::ErrorKind::Other, "no lines"))
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        Err(io::Error::new(io::ErrorKind::Other, "no lines"))
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        lock(&self.written).push_str(s);
+        Ok(())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> (u16, u16) {
+        (0, 0)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+}
+
+/// Returns the escape codes `op` writes on a terminal with `capabilities`.
+fn escape_codes<F: Fn(&dyn Backend) -> io::Result<()>>(
+    capabilities: Capabilities,
+    op: &F,
+) -> io::Result<String> {
+    let codes = Codes {
+        capabilities,
+        written: Mutex::new(String::new()),
+    };
+    op(&codes)?;
+    Ok(codes
+        .written
+        .into_inner()
+        .unwrap_or_else(|err| err.into_inner()))
+}
+
+/// Implements the cursor movement and clearing methods of [`Backend`] with a method
+/// `escape` taking the operation, which applies it to the inner backend and keeps its
+/// escape codes as output.
+macro_rules! escape_methods {
+    () => {
+        fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+            self.escape(|term| term.move_cursor_up(n))
+        }
+
+        fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+            self.escape(|term| term.move_cursor_down(n))
+        }
+
+        fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+            self.escape(|term| term.move_cursor_left(n))
+        }
+
+        fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+            self.escape(|term| term.move_cursor_right(n))
+        }
+
+        fn clear_chars(&self, n: usize) -> io::Result<()> {
+            self.escape(|term| term.clear_chars(n))
+        }
+
+        fn clear_line(&self) -> io::Result<()> {
+            self.escape(|term| term.clear_line())
+        }
+
+        fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+            self.escape(|term| term.clear_last_lines(n))
+        }
+
+        fn clear_to_end_of_screen(&self) -> io::Result<()> {
+            self.escape(|term| term.clear_to_end_of_screen())
+        }
+
+        fn insert_lines(&self, n: usize) -> io::Result<()> {
+            self.escape(|term| term.insert_lines(n))
+        }
+
+        fn hide_cursor(&self) -> io::Result<()> {
+            self.escape(|term| term.hide_cursor())
+        }
+
+        fn show_cursor(&self) -> io::Result<()> {
+            self.escape(|term| term.show_cursor())
+        }
+
+        fn enter_alternate_screen(&self) -> io::Result<()> {
+            self.escape(|term| term.enter_alternate_screen())
+        }
+
+        fn leave_alternate_screen(&self) -> io::Result<()> {
+            self.escape(|term| term.leave_alternate_screen())
+        }
+    };
+}
+
+/// A [`Backend`] recording the keys read from another backend and the output written to
+/// it into a transcript file, which [`Replay`] plays back.
+///
+/// A transcript captures a session exactly, so a rendering glitch a user runs into can be
+/// reproduced from the transcript they attach to the report. Every line holds the
+/// milliseconds since the recording started, the kind of the entry and its data, separated
+/// by tabs:
+///
+/// * `size` entries hold the size of the terminal as rows and columns like `24x80`, written
+///   at the start and whenever it changes.
+/// * `key` entries hold a key read, a char as it is and other keys by names like
+///   `<enter>`, `<down>` or `<ctrl-c>`.
+/// * `out` entries hold text written, including the escape codes which move the cursor.
+///
+/// Backslashes, tabs, newlines and other control characters in the data are escaped as
+/// `\\`, `\t`, `\n` and `\xNN`. Cursor movements are recorded as the ANSI escape codes
+/// of the default [`Backend`] methods. Keys are read one at a time, so keys held down
+/// aren't merged while recording.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use console::Term;
+/// use dialoguer::{Recorder, Select};
+///
+/// let term = Recorder::new(Term::stderr(), "session.txt")?;
+/// let selection = Select::new()
+///     .items(&["Red", "Green", "Blue"])
+///     .interact_on(&term)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Recorder<B> {
+    term: B,
+    start: Instant,
+    file: Mutex<File>,
+    size: Mutex<Option<(u16, u16)>>,
+}
+
+impl<B: Backend> Recorder<B> {
+    /// Creates a recorder of the session on `term`, writing the transcript to the file at
+    /// `path` and replacing it.
+    pub fn new<P: AsRef<Path>>(term: B, path: P) -> io::Result<Self> {
+        let recorder = Recorder {
+            term,
+            start: Instant::now(),
+            file: Mutex::new(File::create(path)?),
+            size: Mutex::new(None),
+        };
+        recorder.size();
+        Ok(recorder)
+    }
+
+    /// Returns the backend the session is recorded on.
+    pub fn into_inner(self) -> B {
+        self.term
+    }
+
+    fn escape<F: Fn(&dyn Backend) -> io::Result<()>>(&self, op: F) -> io::Result<()> {
+        let codes = escape_codes(self.term.capabilities(), &op)?;
+        if !codes.is_empty() {
+            self.log("out", &codes)?;
+        }
+        op(&self.term)
+    }
+
+    fn log(&self, kind: &str, data: &str) -> io::Result<()> {
+        let mut file = lock(&self.file);
+        writeln!(
+            file,
+            "{}\t{}\t{}",
+            self.start.elapsed().as_millis(),
+            kind,
+            escape(data)
+        )?;
+        file.flush()
+    }
+}
+
+impl<B: Backend> Backend for Recorder<B> {
+    fn read_key(&self) -> io::Result<Key> {
+        match self.term.read_key() {
+            Ok(key) => {
+                self.log("key", &key_name(&key))?;
+                Ok(key)
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+                self.log("key", &key_name(&Key::CtrlC))?;
+                Err(err)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        read_line_keys(self, initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        read_line_keys(self, "", false)
+    }
+
+    fn poll_key(&self, timeout: Duration) -> io::Result<bool> {
+        self.term.poll_key(timeout)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.log("out", s)?;
+        self.term.write_str(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.term.flush()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        let size = self.term.size();
+        let mut logged = lock(&self.size);
+        if *logged != Some(size) {
+            *logged = Some(size);
+            // The size is logged as a side effect, a failing file shows on the next key.
+            let _ = self.log("size", &format!("{}x{}", size.0, size.1));
+        }
+        size
+    }
+
+    fn is_attended(&self) -> bool {
+        self.term.is_attended()
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.term.colors_supported()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.term.capabilities()
+    }
+
+    fn begin_prompt(&self) {
+        self.term.begin_prompt()
+    }
+
+    fn end_prompt(&self) {
+        self.term.end_prompt()
+    }
+
+    escape_methods!();
+}
+
+/// A recorded entry of a transcript.
+enum Entry {
+    Size(u16, u16),
+    Key(Duration, Key),
+}
+
+/// A [`Backend`] playing back the keys of a transcript written by [`Recorder`] and
+/// rendering to another backend at the recorded size.
+///
+/// The output of the replay can be compared to the recorded one for golden tests: prompts
+/// given the same keys at the same size render the same output. Once the keys run out
+/// reading another one fails with an [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) error.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Headless, Replay, Select};
+///
+/// let replay = Replay::open(Headless::new(24, 80), "session.txt")?;
+/// let selection = Select::new()
+///     .items(&["Red", "Green", "Blue"])
+///     .interact_on(&replay)?;
+///
+/// assert_eq!(replay.output(), replay.recorded_output());
+/// # Ok(())
+/// # }
+/// ```
+pub struct Replay<B> {
+    term: B,
+    entries: Mutex<VecDeque<Entry>>,
+    size: Mutex<(u16, u16)>,
+    recorded_output: String,
+    output: Mutex<String>,
+    realtime: bool,
+    start: Instant,
+}
+
+impl<B: Backend> Replay<B> {
+    /// Creates a replay of the transcript in the file at `path`, rendering to `term`.
+    ///
+    /// Fails with an [`InvalidData`](io::ErrorKind::InvalidData) error if a line isn't a
+    /// transcript entry.
+    pub fn open<P: AsRef<Path>>(term: B, path: P) -> io::Result<Self> {
+        Self::from_reader(term, BufReader::new(File::open(path)?))
+    }
+
+    /// Creates a replay of the transcript read from `reader`, rendering to `term`.
+    pub fn from_reader<R: BufRead>(term: B, reader: R) -> io::Result<Self> {
+        let mut entries = VecDeque::new();
+        let mut recorded_output = String::new();
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |what: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} on line {} of the transcript", what, idx + 1),
+                )
+            };
+            let mut fields = line.splitn(3, '\t');
+            let (millis, kind, data) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(millis), Some(kind), Some(data)) => (millis, kind, unescape(data)),
+                _ => return Err(invalid("Missing fields")),
+            };
+            let millis: u64 = millis.parse().map_err(|_| invalid("Invalid time"))?;
+
+            match kind {
+                "size" => {
+                    let size = data
+                        .find('x')
+                        .and_then(|x| Some((data[..x].parse().ok()?, data[x + 1..].parse().ok()?)))
+                        .ok_or_else(|| invalid("Invalid size"))?;
+                    entries.push_back(Entry::Size(size.0, size.1));
+                }
+                "key" => {
+                    let keys = parse_keys(&data).map_err(|err| invalid(&err))?;
+                    for key in keys {
+                        entries.push_back(Entry::Key(Duration::from_millis(millis), key));
+                    }
+                }
+                "out" => recorded_output.push_str(&data),
+                _ => return Err(invalid("Unknown entry")),
+            }
+        }
+
+        let size = term.size();
+        let replay = Replay {
+            term,
+            entries: Mutex::new(entries),
+            size: Mutex::new(size),
+            recorded_output,
+            output: Mutex::new(String::new()),
+            realtime: false,
+            start: Instant::now(),
+        };
+        replay.apply_sizes();
+        Ok(replay)
+    }
+
+    /// Sets whether keys are played back with the recorded delays between them, rather
+    /// than as fast as prompts read them.
+    ///
+    /// The default is false.
+    pub fn realtime(mut self, val: bool) -> Self {
+        self.realtime = val;
+        self.start = Instant::now();
+        self
+    }
+
+    /// Returns the output written during the recording.
+    pub fn recorded_output(&self) -> &str {
+        &self.recorded_output
+    }
+
+    /// Returns the output written during the replay so far.
+    pub fn output(&self) -> String {
+        lock(&self.output).clone()
+    }
+
+    /// Returns the backend the replay renders to.
+    pub fn into_inner(self) -> B {
+        self.term
+    }
+
+    fn escape<F: Fn(&dyn Backend) -> io::Result<()>>(&self, op: F) -> io::Result<()> {
+        let codes = escape_codes(self.term.capabilities(), &op)?;
+        lock(&self.output).push_str(&codes);
+        op(&self.term)
+    }
+
+    /// Takes the size entries up to the next key.
+    fn apply_sizes(&self) {
+        let mut entries = lock(&self.entries);
+        while let Some(Entry::Size(rows, cols)) = entries.front() {
+            *lock(&self.size) = (*rows, *cols);
+            entries.pop_front();
+        }
+    }
+}
+
+impl<B: Backend> Backend for Replay<B> {
+    fn read_key(&self) -> io::Result<Key> {
+        let entry = lock(&self.entries).pop_front();
+        let (at, key) = match entry {
+            Some(Entry::Key(at, key)) => (at, key),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "no more keys in the transcript",
+                ))
+            }
+        };
+
+        if self.realtime {
+            if let Some(wait) = at.checked_sub(self.start.elapsed()) {
+                thread::sleep(wait);
+            }
+        }
+        self.apply_sizes();
+
+        match key {
+            Key::CtrlC => Err(crate::interrupted()),
+            key => Ok(key),
+        }
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        read_line_keys(self, initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        read_line_keys(self, "", false)
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        lock(&self.output).push_str(s);
+        self.term.write_str(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.term.flush()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        *lock(&self.size)
+    }
+
+    fn colors_supported(&self) -> bool {
+        self.term.colors_supported()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.term.capabilities()
+    }
+
+    fn begin_prompt(&self) {
+        self.term.begin_prompt()
+    }
+
+    fn end_prompt(&self) {
+        self.term.end_prompt()
+    }
+
+    escape_methods!();
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() && (c as u32) < 0x100 => {
+                escaped.push_str(&format!("\\x{:02x}", c as u32))
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                match u8::from_str_radix(&hex, 16) {
+                    Ok(byte) => unescaped.push(char::from(byte)),
+                    Err(_) => {
+                        unescaped.push_str("\\x");
+                        unescaped.push_str(&hex);
+                    }
+                }
+            }
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{theme::SimpleTheme, Headless, Select};
+
+    #[test]
+    fn test_escape() {
+        let text = "a\tb\\c\nd\x1b[1m\x07";
+        assert_eq!(escape(text), "a\\tb\\\\c\\nd\\x1b[1m\\x07");
+        assert_eq!(unescape(&escape(text)), text);
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let path =
+            std::env::temp_dir().join(format!("dialoguer-transcript-{}", std::process::id()));
+        let select = |term: &dyn Backend| {
+            Select::with_theme(&SimpleTheme)
+                .with_prompt("Pick")
+                .items(&["a", "b", "c"])
+                .default(0)
+                .interact_on(term)
+        };
+
+        let screen = Headless::new(10, 40).keys(vec![Key::ArrowDown, Key::Enter]);
+        let recorder = Recorder::new(screen, &path).unwrap();
+        assert_eq!(select(&recorder).unwrap(), 1);
+        drop(recorder);
+
+        let replay = Replay::open(Headless::new(30, 100), &path).unwrap();
+        assert_eq!(replay.size(), (10, 40));
+        assert_eq!(select(&replay).unwrap(), 1);
+        assert_eq!(replay.output(), replay.recorded_output());
+        assert!(!replay.output().is_empty());
+        assert_eq!(
+            replay.read_key().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_transcript() {
+        let err = Replay::from_reader(Headless::new(1, 1), &b"0\tkey\t<nope>\n"[..])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 1"));
+    }
+}
@@ -0,0 +1,205 @@
+//! Changes the items of list prompts while they are open.
+use std::{
+    io,
+    sync::mpsc::{Receiver, TryRecvError},
+    time::Duration,
+};
+
+use crate::Backend;
+
+/// How long a prompt receiving item updates waits for a key before checking for updates.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A change to the items of an open [`Select`](crate::Select),
+/// [`MultiSelect`](crate::MultiSelect) or [`FuzzySelect`](crate::FuzzySelect), sent through
+/// the channel given to their `item_updates`.
+///
+/// Indices refer to the items with the updates sent before applied, and ones past the end
+/// are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ItemUpdate {
+    /// Adds an item after the others.
+    Push(String),
+    /// Inserts an item before the one at the index.
+    Insert(usize, String),
+    /// Changes the text of the item at the index.
+    Set(usize, String),
+    /// Removes the item at the index.
+    Remove(usize),
+    /// Replaces all items. The ones with the same text as before count as the same item,
+    /// so the cursor and checks stay on them.
+    Replace(Vec<String>),
+}
+
+/// Waits for a key on `term`, returning the updates received from `updates` in the
+/// meantime instead as soon as there are any.
+///
+/// Returns no updates once a key can be read, right away without a channel or when the
+/// prompt has `pending` keys already. Backends which can't
+/// [wait for keys](Backend::poll_key) only get the updates between keys.
+pub(crate) fn wait(
+    term: &dyn Backend,
+    updates: Option<&Receiver<ItemUpdate>>,
+    pending: bool,
+) -> io::Result<Vec<ItemUpdate>> {
+    let updates = match updates {
+        Some(updates) if !pending => updates,
+        _ => return Ok(vec![]),
+    };
+
+    loop {
+        let mut received = vec![];
+        loop {
+            match updates.try_recv() {
+                Ok(update) => received.push(update),
+                Err(TryRecvError::Empty) => break,
+                // Nothing changes anymore, keys are read as usual.
+                Err(TryRecvError::Disconnected) if received.is_empty() => return Ok(vec![]),
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+        if !received.is_empty() {
+            return Ok(received);
+        }
+
+        if term.poll_key(POLL_INTERVAL)? {
+            return Ok(vec![]);
+        }
+    }
+}
+
+/// Applies `updates` to `items`, returning where each of the previous items ended up.
+pub(crate) fn apply(items: &mut Vec<String>, updates: Vec<ItemUpdate>) -> Vec<Option<usize>> {
+    let mut moved: Vec<_> = (0..items.len()).map(Some).collect();
+
+    for update in updates {
+        match update {
+            ItemUpdate::Push(item) => items.push(item),
+            ItemUpdate::Insert(idx, item) if idx <= items.len() => {
+                items.insert(idx, item);
+                for pos in moved.iter_mut().flatten() {
+                    if *pos >= idx {
+                        *pos += 1;
+                    }
+                }
+            }
+            ItemUpdate::Set(idx, item) if idx < items.len() => items[idx] = item,
+            ItemUpdate::Remove(idx) if idx < items.len() => {
+                items.remove(idx);
+                for pos in moved.iter_mut() {
+                    *pos = match *pos {
+                        Some(pos) if pos == idx => None,
+                        Some(pos) if pos > idx => Some(pos - 1),
+                        pos => pos,
+                    };
+                }
+            }
+            ItemUpdate::Replace(new_items) => {
+                let mut taken = vec![false; new_items.len()];
+                let positions: Vec<_> = items
+                    .iter()
+                    .map(|item| {
+                        let found = (0..new_items.len())
+                            .find(|&new| !taken[new] && new_items[new] == *item)?;
+                        taken[found] = true;
+                        Some(found)
+                    })
+                    .collect();
+                for pos in moved.iter_mut() {
+                    *pos = pos.and_then(|pos| positions[pos]);
+                }
+                *items = new_items;
+            }
+            _ => {}
+        }
+    }
+
+    moved
+}
+
+/// Returns where the cursor on `sel` goes after the items `moved`, staying at the same
+/// index among the `len` items if its item was removed.
+pub(crate) fn follow(sel: Option<usize>, moved: &[Option<usize>], len: usize) -> Option<usize> {
+    let sel = sel?;
+    if len == 0 {
+        return None;
+    }
+    Some(
+        moved
+            .get(sel)
+            .copied()
+            .flatten()
+            .unwrap_or(sel)
+            .min(len - 1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Headless, MultiSelect, Select};
+
+    use console::Key;
+    use std::sync::mpsc;
+
+    fn items(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut list = items(&["a", "b", "c"]);
+        let moved = apply(
+            &mut list,
+            vec![
+                ItemUpdate::Insert(0, "z".into()),
+                ItemUpdate::Remove(2),
+                ItemUpdate::Set(2, "C".into()),
+                ItemUpdate::Push("d".into()),
+                ItemUpdate::Remove(10),
+            ],
+        );
+        assert_eq!(list, items(&["z", "a", "C", "d"]));
+        assert_eq!(moved, vec![Some(1), None, Some(2)]);
+        assert_eq!(follow(Some(1), &moved, list.len()), Some(1));
+        assert_eq!(follow(Some(2), &moved, list.len()), Some(2));
+    }
+
+    #[test]
+    fn test_replace_keeps_items_with_the_same_text() {
+        let mut list = items(&["usb0", "usb1", "usb2"]);
+        let moved = apply(
+            &mut list,
+            vec![ItemUpdate::Replace(items(&["usb2", "usb0"]))],
+        );
+        assert_eq!(moved, vec![Some(1), None, Some(0)]);
+        assert_eq!(follow(Some(2), &moved, list.len()), Some(0));
+        assert_eq!(follow(Some(1), &moved, list.len()), Some(1));
+    }
+
+    #[test]
+    fn test_prompts_follow_updates() {
+        let (sender, updates) = mpsc::channel();
+        sender.send(ItemUpdate::Insert(0, "z".into())).unwrap();
+        let term = Headless::new(10, 40).keys(vec![Key::Enter]);
+        let sel = Select::new()
+            .items(&["a", "b"])
+            .default(1)
+            .item_updates(updates)
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(sel, 2);
+
+        let (sender, updates) = mpsc::channel();
+        sender.send(ItemUpdate::Remove(0)).unwrap();
+        sender.send(ItemUpdate::Push("d".into())).unwrap();
+        let term = Headless::new(10, 40).keys(vec![Key::ArrowDown, Key::Char(' '), Key::Enter]);
+        let checked = MultiSelect::new()
+            .items_checked(&[("a", false), ("b", true), ("c", false)])
+            .item_updates(updates)
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(checked, vec![0, 1]);
+    }
+}
@@ -24,3 +24,63 @@ where
         self(input)
     }
 }
+
+/// Trait for validators which also turn the input into the value of the prompt.
+///
+/// Parsing and validating happen in one place, and the error is the message shown to
+/// the user. A generic implementation for `Fn(&str) -> Result<T, E>` is provided, with
+/// `E` converted by [`ToString`].
+pub trait TransformingValidator<T> {
+    /// Invoked with the text entered.
+    ///
+    /// If this produces `Ok` then that value is the answer, if an error is returned
+    /// validation fails with that error.
+    fn validate(&self, input: &str) -> Result<T, String>;
+}
+
+impl<T, F, E> TransformingValidator<T> for F
+where
+    F: Fn(&str) -> Result<T, E>,
+    E: ToString,
+{
+    fn validate(&self, input: &str) -> Result<T, String> {
+        self(input).map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use console::Key;
+
+    use crate::{Headless, Input};
+
+    fn keys(text: &str) -> Vec<Key> {
+        text.chars()
+            .map(Key::Char)
+            .chain(Some(Key::Enter))
+            .collect()
+    }
+
+    #[test]
+    fn test_transforming_validator() {
+        let ask = |term: &Headless| {
+            Input::<u16>::new()
+                .with_prompt("Port")
+                .validate_into(|input: &str| match input.trim().parse::<u16>() {
+                    Ok(port) if port >= 1024 => Ok(port),
+                    _ => Err("Pick an unprivileged port"),
+                })
+                .interact_text_on(term)
+        };
+
+        let term = Headless::new(10, 40).colors(false).keys(keys(" 8080"));
+        assert_eq!(ask(&term).unwrap(), 8080);
+
+        let frame = Headless::new(10, 40)
+            .colors(false)
+            .keys(keys("80"))
+            .render(ask)
+            .unwrap();
+        assert_eq!(frame, "error: Pick an unprivileged port\nPort:");
+    }
+}
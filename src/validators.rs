@@ -0,0 +1,295 @@
+//! Ready-made validators for common inputs.
+//!
+//! They implement [`Validator`] with messages meant for the user, so several can be
+//! registered on an [`Input`](crate::Input) with
+//! [`validate_with`](crate::Input::validate_with):
+//!
+//! ```rust,no_run
+//! # fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! use dialoguer::{
+//!     validators::{Email, InRange, NonEmpty},
+//!     Input,
+//! };
+//!
+//! let email: String = Input::new()
+//!     .with_prompt("Email")
+//!     .validate_with(NonEmpty)
+//!     .validate_with(Email)
+//!     .interact_text()?;
+//!
+//! let workers: u8 = Input::new()
+//!     .with_prompt("Workers")
+//!     .validate_with(InRange::new(1..=16))
+//!     .interact_text()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Validators checking text work with every input whose value is `AsRef<str>`, the path
+//! validators with ones whose value is `AsRef<Path>`, like [`String`].
+use std::{fmt::Display, io, ops::RangeInclusive, path::Path};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::Validator;
+
+/// Rejects input which is empty or only whitespace.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NonEmpty;
+
+impl<T: AsRef<str>> Validator<T> for NonEmpty {
+    type Err = String;
+
+    fn validate(&mut self, input: &T) -> Result<(), String> {
+        if input.as_ref().trim().is_empty() {
+            return Err("Enter a value".into());
+        }
+        Ok(())
+    }
+}
+
+/// Rejects numbers outside of a range, for integers as well as floats.
+#[derive(Clone, Debug)]
+pub struct InRange<T> {
+    range: RangeInclusive<T>,
+}
+
+impl<T> InRange<T> {
+    /// Creates a validator accepting numbers in `range`.
+    pub fn new(range: RangeInclusive<T>) -> Self {
+        InRange { range }
+    }
+}
+
+impl<T: PartialOrd + Display> Validator<T> for InRange<T> {
+    type Err = String;
+
+    fn validate(&mut self, input: &T) -> Result<(), String> {
+        if !self.range.contains(input) {
+            return Err(format!(
+                "Enter a number from {} to {}",
+                self.range.start(),
+                self.range.end()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects input which doesn't match a regular expression.
+///
+/// The pattern isn't anchored, start it with `^` and end it with `$` to match the whole
+/// input.
+#[derive(Clone, Debug)]
+pub struct Matches {
+    regex: Regex,
+    message: String,
+}
+
+impl Matches {
+    /// Creates a validator rejecting input which doesn't match `pattern` with `message`.
+    ///
+    /// Fails if `pattern` isn't a valid regular expression.
+    pub fn new<S: Into<String>>(pattern: &str, message: S) -> Result<Self, regex::Error> {
+        Ok(Matches {
+            regex: Regex::new(pattern)?,
+            message: message.into(),
+        })
+    }
+}
+
+impl<T: AsRef<str>> Validator<T> for Matches {
+    type Err = String;
+
+    fn validate(&mut self, input: &T) -> Result<(), String> {
+        if !self.regex.is_match(input.as_ref()) {
+            return Err(self.message.clone());
+        }
+        Ok(())
+    }
+}
+
+static EMAIL: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[^\s@]+@([^\s@.]+\.)+[^\s@.]+$").unwrap());
+
+/// Rejects input which doesn't look like an email address.
+///
+/// Only the shape is checked, like `name@example.com`, whether mails can be delivered
+/// there is up to the mail server.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Email;
+
+impl<T: AsRef<str>> Validator<T> for Email {
+    type Err = String;
+
+    fn validate(&mut self, input: &T) -> Result<(), String> {
+        if !EMAIL.is_match(input.as_ref()) {
+            return Err("Enter an email address like name@example.com".into());
+        }
+        Ok(())
+    }
+}
+
+static URL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-zA-Z][a-zA-Z0-9+.-]*://[^\s/?#]+([/?#]\S*)?$").unwrap());
+
+/// Rejects input which isn't an absolute URL with a scheme and a host, like
+/// `https://example.com/path`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Url;
+
+impl<T: AsRef<str>> Validator<T> for Url {
+    type Err = String;
+
+    fn validate(&mut self, input: &T) -> Result<(), String> {
+        if !URL.is_match(input.as_ref()) {
+            return Err("Enter a URL like https://example.com".into());
+        }
+        Ok(())
+    }
+}
+
+// The expression recommended by the specification at https://semver.org.
+static SEMVER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)",
+        r"(?:-((?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)",
+        r"(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?",
+        r"(?:\+([0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?$",
+    ))
+    .unwrap()
+});
+
+/// Rejects input which isn't a semantic version, like `1.2.3` or `2.0.0-rc.1`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Semver;
+
+impl<T: AsRef<str>> Validator<T> for Semver {
+    type Err = String;
+
+    fn validate(&mut self, input: &T) -> Result<(), String> {
+        if !SEMVER.is_match(input.as_ref()) {
+            return Err("Enter a version like 1.2.3".into());
+        }
+        Ok(())
+    }
+}
+
+/// Rejects paths which don't exist.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExistingPath;
+
+impl<T: AsRef<Path>> Validator<T> for ExistingPath {
+    type Err = String;
+
+    fn validate(&mut self, input: &T) -> Result<(), String> {
+        let path = input.as_ref();
+        if !path.exists() {
+            return Err(format!("{} doesn't exist", path.display()));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects paths which can't be written: existing files and directories the process may
+/// not write to, and new ones in directories it may not create them in.
+///
+/// Nothing is written to check this.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WritablePath;
+
+impl<T: AsRef<Path>> Validator<T> for WritablePath {
+    type Err = String;
+
+    fn validate(&mut self, input: &T) -> Result<(), String> {
+        let path = input.as_ref();
+        let target = if path.exists() {
+            path
+        } else {
+            match path.parent() {
+                Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+                Some(parent) if parent.is_dir() => parent,
+                _ => return Err(format!("{} isn't in a directory", path.display())),
+            }
+        };
+
+        match writable(target) {
+            Ok(true) => Ok(()),
+            _ => Err(format!("{} isn't writable", path.display())),
+        }
+    }
+}
+
+/// Returns whether the process may write to `path`.
+#[cfg(unix)]
+fn writable(path: &Path) -> io::Result<bool> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    Ok(unsafe { libc::access(path.as_ptr(), libc::W_OK) } == 0)
+}
+
+/// Returns whether the process may write to `path`.
+#[cfg(not(unix))]
+fn writable(path: &Path) -> io::Result<bool> {
+    Ok(!path.metadata()?.permissions().readonly())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<T, V: Validator<T, Err = String>>(mut validator: V, input: T) -> bool {
+        validator.validate(&input).is_ok()
+    }
+
+    #[test]
+    fn test_text_validators() {
+        assert!(check(NonEmpty, "a"));
+        assert!(!check(NonEmpty, " \t"));
+
+        assert!(check(Email, "name@example.com"));
+        assert!(!check(Email, "name@localhost"));
+        assert!(!check(Email, "first last@example.com"));
+
+        assert!(check(Url, "https://example.com/path?query#top"));
+        assert!(!check(Url, "example.com"));
+
+        assert!(check(Semver, "1.2.3"));
+        assert!(check(Semver, "2.0.0-rc.1+build.5"));
+        assert!(!check(Semver, "1.2"));
+        assert!(!check(Semver, "01.2.3"));
+
+        let digits = Matches::new(r"^\d+$", "Enter digits").unwrap();
+        assert!(check(digits.clone(), "123"));
+        assert_eq!(
+            digits.clone().validate(&"12a"),
+            Err("Enter digits".to_string())
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        assert!(check(InRange::new(1..=16), 16));
+        assert_eq!(
+            InRange::new(1..=16).validate(&0),
+            Err("Enter a number from 1 to 16".to_string())
+        );
+        assert!(check(InRange::new(0.0..=1.0), 0.5));
+        assert!(!check(InRange::new(0.0..=1.0), f64::NAN));
+    }
+
+    #[test]
+    fn test_path_validators() {
+        let dir = std::env::temp_dir();
+        let file = dir.join("dialoguer-validators-missing.toml");
+
+        assert!(check(ExistingPath, &dir));
+        assert!(!check(ExistingPath, &file));
+        assert!(check(WritablePath, &file));
+        assert!(!check(
+            WritablePath,
+            dir.join("dialoguer-missing/config.toml")
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+//! Measures text in terminal columns.
+use std::borrow::Cow;
+
+use console::{strip_ansi_codes, AnsiCodeIterator};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::link::{split_osc, strip_osc};
+
+/// Returns the number of terminal columns `text` takes up.
+///
+/// ANSI escape codes and hyperlinks take up no columns, while wide characters like CJK
+/// ideographs and most emoji take up two. Use this rather than `str::len` or counting chars
+/// when aligning text in custom themes.
+pub fn text_width(text: &str) -> usize {
+    strip_ansi_codes(&strip_osc(text)).width()
+}
+
+/// Returns the number of terminal columns `c` takes up, zero for control characters.
+pub fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Splits `text` into the clusters of chars a terminal draws as one, with their byte
+/// offsets.
+///
+/// Zero-width chars like combining marks and variation selectors belong to the char before
+/// them, as does the char after a zero-width joiner, so that emoji sequences are measured
+/// as a whole like [`text_width`] does.
+pub(crate) fn clusters(text: &str) -> Vec<(usize, &str)> {
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    let mut joined = false;
+
+    for (offset, c) in text.char_indices() {
+        if offset > start && !joined && c.width() != Some(0) {
+            clusters.push((start, &text[start..offset]));
+            start = offset;
+        }
+        joined = c == '\u{200d}';
+    }
+
+    if start < text.len() {
+        clusters.push((start, &text[start..]));
+    }
+
+    clusters
+}
+
+/// Splits `text` into escape codes, including the OSC sequences of hyperlinks, and the
+/// text between them.
+pub(crate) fn escape_parts(text: &str) -> Vec<(&str, bool)> {
+    let mut parts = Vec::new();
+    for (part, is_osc) in split_osc(text) {
+        if is_osc {
+            parts.push((part, true));
+        } else {
+            parts.extend(AnsiCodeIterator::new(part));
+        }
+    }
+    parts
+}
+
+/// Returns the byte offset of the char at `position` in `text`.
+pub(crate) fn byte_offset(text: &str, position: usize) -> usize {
+    text.char_indices()
+        .nth(position)
+        .map(|(offset, _)| offset)
+        .unwrap_or_else(|| text.len())
+}
+
+/// Returns the rest of `text` after skipping its first `columns` columns.
+///
+/// A wide character only partly within the skipped columns is skipped as a whole. Escape
+/// codes within the skipped columns are kept, so styles and hyperlinks still apply to the
+/// rest.
+pub(crate) fn skip_columns(text: &str, columns: usize) -> Cow<'_, str> {
+    let mut skipped = 0;
+    let mut rest = String::new();
+
+    for (part, is_ansi) in escape_parts(text) {
+        if is_ansi || skipped >= columns {
+            rest.push_str(part);
+            continue;
+        }
+
+        for (offset, cluster) in clusters(part) {
+            if skipped >= columns {
+                rest.push_str(&part[offset..]);
+                break;
+            }
+            skipped += cluster.width();
+        }
+    }
+
+    if rest.len() == text.len() {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(rest)
+    }
+}
+
+/// Truncates `text` to at most `width` columns, ending it with `tail` if it is cut.
+///
+/// Unlike [`console::truncate_str`] emoji sequences are measured and cut as a whole, so the
+/// result never overflows `width`. ANSI escape codes and hyperlinks are kept, also past the
+/// cut.
+pub(crate) fn truncate_text<'a>(text: &'a str, width: usize, tail: &str) -> Cow<'a, str> {
+    if text_width(text) <= width {
+        return Cow::Borrowed(text);
+    }
+
+    let mut remaining = width.saturating_sub(text_width(tail));
+    let mut truncated = String::with_capacity(text.len());
+    let mut cut = false;
+
+    for (part, is_ansi) in escape_parts(text) {
+        if is_ansi {
+            truncated.push_str(part);
+            continue;
+        }
+        if cut {
+            continue;
+        }
+
+        for (_, cluster) in clusters(part) {
+            let cluster_width = cluster.width();
+            if cluster_width > remaining {
+                truncated.push_str(tail);
+                cut = true;
+                break;
+            }
+            remaining -= cluster_width;
+            truncated.push_str(cluster);
+        }
+    }
+
+    Cow::Owned(truncated)
+}
+
+/// Returns the number of rows the single line `line` takes up in a terminal of `width`
+/// columns.
+///
+/// Wide characters which don't fit in the last column of a row wrap as a whole, leaving
+/// the column empty.
+pub(crate) fn wrapped_rows(line: &str, width: usize) -> usize {
+    let width = width.max(1);
+    let mut rows = 1;
+    let mut column = 0;
+
+    let line = strip_ansi_codes(&strip_osc(line)).into_owned();
+    for (_, cluster) in clusters(&line) {
+        let cluster_width = cluster.width();
+        if column + cluster_width > width && column > 0 {
+            rows += 1;
+            column = 0;
+        }
+        column += cluster_width;
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_width() {
+        assert_eq!(text_width("abc"), 3);
+        assert_eq!(text_width("日本"), 4);
+        assert_eq!(text_width("\x1b[32m✔\x1b[0m 🚀"), 4);
+        assert_eq!(text_width(&crate::hyperlink("docs", "https://docs.rs")), 4);
+    }
+
+    #[test]
+    fn test_clusters() {
+        assert_eq!(clusters("ab"), [(0, "a"), (1, "b")]);
+        assert_eq!(clusters("e\u{301}x"), [(0, "e\u{301}"), (3, "x")]);
+        assert_eq!(clusters("❤\u{fe0f}!"), [(0, "❤\u{fe0f}"), (6, "!")]);
+        assert_eq!(
+            clusters("👨\u{200d}👩\u{200d}👧a"),
+            [(0, "👨\u{200d}👩\u{200d}👧"), (18, "a")]
+        );
+        assert!(clusters("").is_empty());
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("abc", 3, "…"), "abc");
+        assert_eq!(truncate_text("abcdef", 4, "…"), "abc…");
+        // A wide char which doesn't fit is dropped as a whole.
+        assert_eq!(truncate_text("日本語", 4, "…"), "日…");
+        assert_eq!(truncate_text("a日本語", 4, "…"), "a日…");
+        assert_eq!(truncate_text("❤\u{fe0f}❤\u{fe0f}x", 4, "…"), "❤\u{fe0f}…");
+        assert_eq!(
+            truncate_text("\x1b[1m日本\x1b[0m語", 5, "…"),
+            "\x1b[1m日本\x1b[0m…"
+        );
+        // The end of a hyperlink past the cut is kept.
+        assert_eq!(
+            truncate_text(&crate::hyperlink("abcdef", "u"), 4, "…"),
+            "\x1b]8;;u\x1b\\abc…\x1b]8;;\x1b\\"
+        );
+        for text in [
+            "日本語 items",
+            "🚀 ❤\u{fe0f} 👨\u{200d}👩\u{200d}👧 ok",
+            "mixed ｶﾀｶﾅ 한국어",
+        ] {
+            for width in 1..text_width(text) {
+                assert!(text_width(&truncate_text(text, width, "…")) <= width);
+            }
+        }
+    }
+
+    #[test]
+    fn test_skip_columns() {
+        assert_eq!(skip_columns("abcdef", 0), "abcdef");
+        assert_eq!(skip_columns("abcdef", 2), "cdef");
+        assert_eq!(skip_columns("日本語", 2), "本語");
+        assert_eq!(skip_columns("日本語", 3), "語");
+        assert_eq!(skip_columns("abc", 5), "");
+        assert_eq!(skip_columns("❤\u{fe0f}ab", 2), "ab");
+        assert_eq!(skip_columns("👨\u{200d}👩\u{200d}👧ab", 2), "ab");
+        assert_eq!(
+            skip_columns(&crate::hyperlink("abcd", "u"), 2),
+            "\x1b]8;;u\x1b\\cd\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_wrapped_rows() {
+        assert_eq!(wrapped_rows("", 4), 1);
+        assert_eq!(wrapped_rows("abcd", 4), 1);
+        assert_eq!(wrapped_rows("abcde", 4), 2);
+        // The second ideograph doesn't fit in the last column and wraps.
+        assert_eq!(wrapped_rows("a日本x", 4), 2);
+        assert_eq!(wrapped_rows("a日本語x", 4), 3);
+        // Emoji sequences take up their full width and wrap as a whole.
+        assert_eq!(wrapped_rows("abc❤\u{fe0f}", 4), 2);
+        assert_eq!(wrapped_rows("ab👨\u{200d}👩\u{200d}👧", 4), 1);
+        assert_eq!(wrapped_rows("\x1b[32m日本\x1b[0m", 4), 1);
+    }
+}
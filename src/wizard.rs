@@ -0,0 +1,87 @@
+//! Groups prompts into the numbered steps of a wizard.
+use std::cell::Cell;
+
+thread_local! {
+    static STEP: Cell<Option<(usize, usize)>> = const { Cell::new(None) };
+}
+
+/// Returns the wizard step the current thread is in, as `(step, steps)`.
+pub(crate) fn current_step() -> Option<(usize, usize)> {
+    STEP.with(Cell::get)
+}
+
+/// Restores the step of an enclosing wizard, also when a step panics.
+struct StepGuard(Option<(usize, usize)>);
+
+impl Drop for StepGuard {
+    fn drop(&mut self) {
+        STEP.with(|step| step.set(self.0));
+    }
+}
+
+/// Runs a sequence of prompts as the steps of a wizard.
+///
+/// Prompts started within [`step`](Self::step) pass the step to the theme, which renders
+/// the progress like `[2/5]` in front of the prompt with
+/// [`Theme::format_step`](crate::theme::Theme::format_step). Themes implementing
+/// [`Theme::render`](crate::theme::Theme::render) find it in
+/// [`RenderContext::step`](crate::theme::RenderContext::step).
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Confirm, Input, Wizard};
+///
+/// let mut wizard = Wizard::new(2);
+/// let name: String = wizard.step(|| Input::new().with_prompt("Name").interact_text())?;
+/// let admin = wizard.step(|| Confirm::new().with_prompt("Admin?").interact())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Wizard {
+    step: usize,
+    steps: usize,
+}
+
+impl Wizard {
+    /// Creates a wizard with the given number of steps.
+    pub fn new(steps: usize) -> Self {
+        Self { step: 0, steps }
+    }
+
+    /// Runs `prompt` as the next step of the wizard and returns its result.
+    pub fn step<T, F: FnOnce() -> T>(&mut self, prompt: F) -> T {
+        self.step += 1;
+        self.steps = self.steps.max(self.step);
+
+        let _guard = StepGuard(current_step());
+        STEP.with(|step| step.set(Some((self.step, self.steps))));
+        prompt()
+    }
+
+    /// Returns the number of steps started so far.
+    pub fn current_step(&self) -> usize {
+        self.step
+    }
+
+    /// Returns the total number of steps.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_are_scoped() {
+        let mut wizard = Wizard::new(2);
+        assert_eq!(wizard.step(current_step), Some((1, 2)));
+        assert_eq!(wizard.step(current_step), Some((2, 2)));
+        assert_eq!(current_step(), None);
+        assert_eq!(wizard.step(current_step), Some((3, 3)));
+    }
+}